@@ -126,19 +126,18 @@ impl ScalePage {
             )
         };
         let track = ProtoTrack::new("notes".to_owned(), TrackKind::Vocal, entries);
-        let bars = vec![
-            new_bar(0),
-            new_bar(4),
-            new_bar(8),
-            new_bar(12),
-        ];
+        // Up and down the scale, with the root an octave higher twice in the middle.
+        let note_num = syllables.len() * 2 + 2;
+        let bars = (0..note_num.div_ceil(4))
+            .map(|x| new_bar(x * 4))
+            .collect();
         let section = ProtoSection::new("notes".to_owned(), SectionKind::Verse, bars);
         ProtoTab::new(
             ProtoTab::new_uuid().as_str(),
             meta,
             vec![track],
             vec![section],
-            ProtoForm{ sections: vec!["notes".to_owned()]},
+            ProtoForm::from(vec!["notes"]),
         )
     }
     pub fn check_reload(&self, tab: &tab_viewer::prelude::Tab) -> bool {
@@ -44,7 +44,7 @@ pub fn new_tab() -> Tab {
                 "i:1,2" Pick [ (6 3) 4 2 3 2@1 2, 3, 3@2, 2, 3@2 ] |
                 "i:3" Pick [ (5 3) 4 2 3 2@1 2, 3, 3@2, 2, 3@2 ] |
                 "i:4" Pick [ 3 4 2 3 2@1 2, 3, 3@2, 2, 3@2 ] |
-                "v:1,2" Pick [ (6 3) 4 2 2, 3, (6 3) 4 2@3* ] |
+                "v:1,2" Pick [ (6 3) 4 2 2, 3, (6 3) 4 &Mordent 2@3* ] |
                 "v:3" Pick [ (5 3) 4 2 2, 3, (5 3) 4 2@1* ] |
                 $duration = _1
                 "v:4" Pick [ (4 3 2 1) ] |
@@ -122,7 +122,7 @@ pub fn new_tab() -> Tab {
             ]}
             {vocal Vocal [
                 $duration = _1_4
-                "v:1,2" Tone [ .5 2 2, 1, 3 ] |
+                "v:1,2" Tone [ .5 &Grace -2 2 2, 1, 3 ] |
                 "v:3" Tone [ .6 3 3, 2, 4 ] |
                 "v:4_1" Tone [ .6b 3,+ 4,, 3, 2, 1, .6b,, .5,, ] |
                 "v:4_2" Tone [ .6b 3,+ 4,, 3, 2, 1, 5,, 6,, ] |
@@ -145,7 +145,7 @@ pub fn new_tab() -> Tab {
                     $duration = _1_16
                     Tone [ 3 4, 3, 2 3, 4, 4 5, 4, 3 4, 5, ] |
                 "c:4@2" $duration = _1_8
-                    Tone [ _ 1, 2, _ 1, 2, 3 4 5 ^1 @ ] |
+                    Tone [ _ 1, 2, _ 1, 2, 3 4 5 &Turn ^1 @ ] |
                 "c:5" $duration = _1
                     Tone [ @ ^1 ] |
                 "b" $duration = _1_8
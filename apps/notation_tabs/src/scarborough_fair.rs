@@ -155,12 +155,19 @@ pub fn new_tab() -> Tab {
                 "v4:15" Tone [ 4 3 2 ] |
                 "v4:16" Tone [ 3, 1, @ 1* ] |
             ]}
+            {meta Meta [
+                "rit" Rit 3 |
+                "fermata" Fermata 3 |
+                "p" Dynamic p |
+                "cresc" Cresc 6 mf |
+            ]}
         ]
         Sections: [
             {intro Intro [
                 {
                     chord [ "2sus4_7" 1 ]
                     guitar [ "Asus4" 1 ; "b:5" | ; "i" | ]
+                    meta [ "p" 1 ]
                 } {
                     chord [ "2sus4_7/4" 1 ]
                     guitar [ "Asus4/D" 1 ; "b:5" | ; "i" | ]
@@ -179,6 +186,7 @@ pub fn new_tab() -> Tab {
                 {
                     chord [ "2sus4_7" 1 ]
                     guitar [ "Asus4" 1 ; "b:5" | ; "i" | ]
+                    meta [ "cresc" 1 ]
                 }
                 {
                     chord [ "2sus2" 1 ]
@@ -331,13 +339,15 @@ pub fn new_tab() -> Tab {
                 {
                     chord [ "2-" 1 ]
                     guitar [ "o:1" | ]
+                    meta [ "rit" 1 ]
                 }
                 {
                     chord [ "2-" 1 ]
                     guitar [ "o:2" | ]
+                    meta [ "fermata" 1 ]
                 }
             ]}
         ]
-        Form: intro verse verse verse verse verse outro
+        Form: intro |: verse :| 5 outro
     }
 }
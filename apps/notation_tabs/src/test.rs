@@ -11,13 +11,13 @@ pub fn new_tab() -> Tab {
         Tracks: [
             {chord Chord [
                 $duration = _1
-                "1" Chord ( 1: 3 5 )
-                "6-" Chord ( 6: 3- 5 )
+                "1" Chord ( "G" )
+                "6-" Chord ( "Em" )
             ]}
             {guitar Guitar [
                 Fretboard
                 $duration = _1
-                "Em" Shape ( 0 2 2 0 0 0 )
+                "Em" Shape ( "Em" )
                 "G" Shape ( 3 2 0 0 0 3 )
                 $duration = T_1_8
                 "picks" Pick [ _ 3 2 1 2 3 ]
@@ -25,24 +25,43 @@ pub fn new_tab() -> Tab {
                 $duration = _1_2
                 "bass" Pick [ 6 6 ] |
             ]}
+            {drums Drums [
+                $duration = _1_8
+                "beat" Drum [ (Kick HiHat) HiHat > (Snare HiHat) HiHat (Kick HiHat) Kick > (Snare OpenHiHat) HiHat ] |
+                "fill" Drum [ Snare Snare HighTom HighTom MidTom MidTom FloorTom (Kick Crash) ] |
+            ]}
+            {bass Bass [
+                Fretboard
+                $duration = _1_2
+                "E" Pick [ 4 3@2 ] |
+                "G" Pick [ 4@3 3@5 ] |
+            ]}
         ]
         Sections: [
             {"A" Verse [
                 {
                     chord [ "6-" 1 ]
                     guitar [ "Em" 1 ; "picks" | ; "bass" | ]
+                    drums [ "beat" | ]
+                    bass [ "E" | ]
                 } {
                     chord [ "6-" 1 ]
                     guitar [ "Em" 1 ; "picks" | ; "bass" | ]
+                    drums [ "beat" | ]
+                    bass [ "E" | ]
                 } {
                     chord [ "1" 1 ]
                     guitar [ "G" 1 ; "picks" | ; "bass" | ]
+                    drums [ "beat" | ]
+                    bass [ "G" | ]
                 } {
                     chord [ "1" 1 ]
                     guitar [ "G" 1 ; "picks" | ; "bass" | ]
+                    drums [ "fill" | ]
+                    bass [ "G" | ]
                 }
             ]}
         ]
-        Form: "A" "A"
+        Form: |: "A" :|
     }
 }
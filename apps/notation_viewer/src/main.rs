@@ -5,5 +5,10 @@ use notation_viewer::assets::NotationViewerAssets;
 #[bevy_main]
 fn main() {
     let args = NotationArgs::parse_args();
+    #[cfg(feature = "native")]
+    if let Some(folder) = args.export.as_ref() {
+        args.export_tabs(folder);
+        return;
+    }
     notation_viewer::viewer::NotationViewer::run(args);
 }
@@ -1,4 +1,7 @@
+pub mod midi_accompaniment;
+pub mod midi_export;
 pub mod midi_hub;
+pub mod midi_import;
 pub mod midi_message;
 pub mod midi_plugin;
 pub mod midi_settings;
@@ -19,8 +22,14 @@ pub mod prelude {
     #[doc(hidden)]
     pub use notation_audio::prelude::*;
     #[doc(hidden)]
+    pub use crate::midi_accompaniment::{AccompanimentPattern, MidiAccompaniment};
+    #[doc(hidden)]
+    pub use crate::midi_export::MidiExport;
+    #[doc(hidden)]
     pub use crate::midi_hub::MidiHub;
     #[doc(hidden)]
+    pub use crate::midi_import::{MidiImport, MidiImportError, MidiImportOptions};
+    #[doc(hidden)]
     pub use crate::midi_message::MidiMessage;
     #[doc(hidden)]
     pub use crate::midi_plugin::MidiPlugin;
@@ -0,0 +1,178 @@
+use std::fmt::Display;
+
+use notation_model::prelude::{
+    Chord, Entry, Interval, LaneEntry, Note, Octave, Semitones, Syllable, TabBar, TabMeta, Units,
+};
+
+use crate::prelude::{MidiChannel, MidiMessage, MidiSettings, MidiUtil};
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum AccompanimentPattern {
+    /// All notes held for the whole chord.
+    Block,
+    /// One note every half beat, from the bass up.
+    ArpeggioUp,
+    /// One note every half beat, from the top down.
+    ArpeggioDown,
+    /// Bass, top, middle, top, one note every half beat.
+    Alberti,
+    /// Bass on the strong beats, the other notes on the rest of the beats.
+    BassChord,
+}
+impl Display for AccompanimentPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                AccompanimentPattern::Block => "Block",
+                AccompanimentPattern::ArpeggioUp => "Arpeggio Up",
+                AccompanimentPattern::ArpeggioDown => "Arpeggio Down",
+                AccompanimentPattern::Alberti => "Alberti",
+                AccompanimentPattern::BassChord => "Bass + Chord",
+            }
+        )
+    }
+}
+impl AccompanimentPattern {
+    pub const ALL: [AccompanimentPattern; 5] = [
+        AccompanimentPattern::Block,
+        AccompanimentPattern::ArpeggioUp,
+        AccompanimentPattern::ArpeggioDown,
+        AccompanimentPattern::Alberti,
+        AccompanimentPattern::BassChord,
+    ];
+}
+
+/// Backing notes for the entries of the chord track.
+pub struct MidiAccompaniment();
+
+impl MidiAccompaniment {
+    /// The bass note one octave below the root (or the chord's bass), and the chord notes
+    /// from the root up, in the tab's key.
+    pub fn voice_chord(meta: &TabMeta, chord: &Chord, octave: &Octave) -> (Note, Vec<Note>) {
+        let root = meta
+            .scale
+            .calc_note_from_syllable(&meta.key, &chord.root, octave);
+        let root_semitones = Semitones::from(root);
+        let note_above = |interval: Interval, min: Semitones| {
+            let syllable = Syllable::from((chord.root, interval));
+            let mut octave = octave.get_lower();
+            let mut note = meta
+                .scale
+                .calc_note_from_syllable(&meta.key, &syllable, &octave);
+            while Semitones::from(note) < min && octave < Octave::P10 {
+                octave = octave.get_higher();
+                note = meta
+                    .scale
+                    .calc_note_from_syllable(&meta.key, &syllable, &octave);
+            }
+            note
+        };
+        let bass_min = Semitones(root_semitones.0 - 12);
+        let bass = note_above(chord.bass.unwrap_or(Interval::Unison), bass_min);
+        let mut notes = vec![root];
+        for interval in chord.intervals.get_intervals() {
+            let min = Semitones(root_semitones.0 + Semitones::from(interval).0);
+            notes.push(note_above(interval, min));
+        }
+        notes.sort_by_key(|x| Semitones::from(*x).0);
+        (bass, notes)
+    }
+    /// Notes of the pattern within the chord, as (offset, length, notes).
+    pub fn pattern_hits(
+        pattern: &AccompanimentPattern,
+        bar: &TabBar,
+        entry: &LaneEntry,
+        bass: Note,
+        notes: &[Note],
+    ) -> Vec<(Units, Units, Vec<Note>)> {
+        let units = entry.tied_units();
+        let signature = bar.signature();
+        let beat_units = Units::from(signature.beat_unit);
+        let mut all = vec![bass];
+        all.extend_from_slice(notes);
+        let sequence = match pattern {
+            AccompanimentPattern::Block => return vec![(Units::ZERO, units, all)],
+            AccompanimentPattern::ArpeggioUp => all,
+            AccompanimentPattern::ArpeggioDown => all.into_iter().rev().collect(),
+            AccompanimentPattern::Alberti => {
+                let top = notes[notes.len() - 1];
+                let middle = notes[if notes.len() > 2 { 1 } else { 0 }];
+                vec![bass, top, middle, top]
+            }
+            AccompanimentPattern::BassChord => {
+                let start = entry.props.in_bar_pos;
+                let end = start + units;
+                let mut hits = Vec::new();
+                let mut pos = start;
+                while pos < end {
+                    let next = beat_units * ((pos / beat_units).floor() + 1);
+                    let beat = (pos / beat_units).as_f32().round() as usize
+                        % signature.bar_beats as usize;
+                    let strong = beat == 0
+                        || (signature.bar_beats.is_multiple_of(2)
+                            && signature.bar_beats >= 4
+                            && beat == signature.bar_beats as usize / 2);
+                    let hit_notes = if hits.is_empty() || strong {
+                        vec![bass]
+                    } else {
+                        notes.to_vec()
+                    };
+                    let length = next.min(end) - pos;
+                    hits.push((pos - start, length, hit_notes));
+                    pos = next;
+                }
+                return hits;
+            }
+        };
+        let step = beat_units / 2;
+        let mut hits = Vec::new();
+        let mut index = 0;
+        while step * (index as i64) < units {
+            let offset = step * index as i64;
+            let length = step.min(units - offset);
+            hits.push((offset, length, vec![sequence[index % sequence.len()]]));
+            index += 1;
+        }
+        hits
+    }
+    pub fn get_chord_midi_msgs(
+        settings: &MidiSettings,
+        channel: &MidiChannel,
+        bar: &TabBar,
+        entry: &LaneEntry,
+        chord: &Chord,
+    ) -> Option<Vec<MidiMessage>> {
+        if entry.prev_is_tie() {
+            return None;
+        }
+        let meta = bar.tab_meta();
+        let (bass, notes) = Self::voice_chord(&meta, chord, &settings.chord_octave);
+        let mut msgs = Vec::new();
+        for (offset, length, hit_notes) in
+            Self::pattern_hits(&settings.chord_pattern, bar, entry, bass, &notes)
+        {
+            for note in hit_notes.iter() {
+                if let Some(msg) =
+                    MidiUtil::note_midi_on_msg(note, channel.channel, channel.velocity)
+                {
+                    msgs.push(MidiMessage::of_entry(entry, false, msg).with_offset(offset));
+                }
+                if let Some(msg) =
+                    MidiUtil::note_midi_off_msg(note, channel.channel, channel.velocity)
+                {
+                    msgs.push(
+                        MidiMessage::of_entry(entry, false, msg)
+                            .with_offset(offset + length - MidiMessage::DELAY_GAP),
+                    );
+                }
+            }
+        }
+        if !msgs.is_empty() {
+            Some(msgs)
+        } else {
+            None
+        }
+    }
+}
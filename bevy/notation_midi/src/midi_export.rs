@@ -0,0 +1,310 @@
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+
+use helgoboss_midi::{StructuredShortMessage, U7};
+use midly::num::{u14, u15, u24, u28, u4, u7};
+use midly::{Format, Header, MetaMessage, Smf, Timing, TrackEvent, TrackEventKind};
+use notation_model::prelude::{
+    BarPosition, DynamicMap, Signature, Tab, TempoChange, TrackKind, Unit, Units,
+};
+
+use crate::prelude::{MidiChannel, MidiMessage, MidiSettings, MidiState, MidiUtil};
+
+/// Standard MIDI File (type 1) export, one conductor track for tempo, signature and
+/// section markers, then one track per tab track that got a midi channel.
+pub struct MidiExport();
+
+impl MidiExport {
+    pub const TICKS_PER_QUARTER: u16 = 480;
+
+    pub fn units_to_ticks(units: Units) -> u32 {
+        let ticks = (units / Units::from(Unit::Quarter)).as_f32() * Self::TICKS_PER_QUARTER as f32;
+        if ticks > 0.0 {
+            ticks.round() as u32
+        } else {
+            0
+        }
+    }
+    pub fn tempo_micros_per_quarter(change: &TempoChange, factor: f32) -> u32 {
+        let bpm = change.calc_bpm(factor).max(1.0);
+        let quarters_per_beat =
+            (Units::from(change.beat_unit) / Units::from(Unit::Quarter)).as_f32();
+        (60_000_000.0 / bpm * quarters_per_beat).round() as u32
+    }
+    fn time_signature_kind(signature: &Signature) -> TrackEventKind<'static> {
+        TrackEventKind::Meta(MetaMessage::TimeSignature(
+            signature.bar_beats,
+            Self::beat_unit_power(signature.beat_unit),
+            24,
+            8,
+        ))
+    }
+    pub fn beat_unit_power(unit: Unit) -> u8 {
+        match unit {
+            Unit::Whole => 0,
+            Unit::Half => 1,
+            Unit::Quarter => 2,
+            Unit::Eighth => 3,
+            Unit::Sixteenth => 4,
+            Unit::ThirtySecondth => 5,
+            Unit::SixtyFourth => 6,
+        }
+    }
+    fn smf_midi_kind(msg: &StructuredShortMessage) -> Option<(u8, TrackEventKind<'static>)> {
+        let (order, channel, message) = match *msg {
+            StructuredShortMessage::NoteOff {
+                channel,
+                key_number,
+                velocity,
+            } => (
+                0,
+                channel,
+                midly::MidiMessage::NoteOff {
+                    key: u7::new(Self::smf_key(channel.get(), key_number.get())),
+                    vel: u7::new(velocity.get()),
+                },
+            ),
+            StructuredShortMessage::ProgramChange {
+                channel,
+                program_number,
+            } => (
+                1,
+                channel,
+                midly::MidiMessage::ProgramChange {
+                    program: u7::new(program_number.get()),
+                },
+            ),
+            StructuredShortMessage::ControlChange {
+                channel,
+                controller_number,
+                control_value,
+            } => (
+                1,
+                channel,
+                midly::MidiMessage::Controller {
+                    controller: u7::new(controller_number.get()),
+                    value: u7::new(control_value.get()),
+                },
+            ),
+            StructuredShortMessage::PitchBendChange {
+                channel,
+                pitch_bend_value,
+            } => (
+                1,
+                channel,
+                midly::MidiMessage::PitchBend {
+                    bend: midly::PitchBend(u14::new(pitch_bend_value.get())),
+                },
+            ),
+            StructuredShortMessage::NoteOn {
+                channel,
+                key_number,
+                velocity,
+            } => (
+                2,
+                channel,
+                midly::MidiMessage::NoteOn {
+                    key: u7::new(Self::smf_key(channel.get(), key_number.get())),
+                    vel: u7::new(velocity.get()),
+                },
+            ),
+            _ => return None,
+        };
+        Some((
+            order,
+            TrackEventKind::Midi {
+                channel: u4::new(channel.get()),
+                message,
+            },
+        ))
+    }
+    // The key numbers from MidiUtil are shifted for the internal synth, undo it for the file,
+    // drum keys are not shifted.
+    fn smf_key(channel: u8, key: u8) -> u8 {
+        if channel == MidiUtil::DRUMS_CHANNEL {
+            return key;
+        }
+        (key as i16 - MidiUtil::PLAYBACK_KEY_OFFSET as i16).clamp(0, 127) as u8
+    }
+    fn to_track_events<'a>(
+        mut events: Vec<(u32, u8, TrackEventKind<'a>)>,
+        end_tick: u32,
+    ) -> Vec<TrackEvent<'a>> {
+        events.sort_by_key(|(tick, order, _)| (*tick, *order));
+        let mut result = Vec::with_capacity(events.len() + 1);
+        let mut last_tick = 0;
+        for (tick, _, kind) in events {
+            result.push(TrackEvent {
+                delta: u28::new(tick - last_tick),
+                kind,
+            });
+            last_tick = tick;
+        }
+        result.push(TrackEvent {
+            delta: u28::new(end_tick.max(last_tick) - last_tick),
+            kind: TrackEventKind::Meta(MetaMessage::EndOfTrack),
+        });
+        result
+    }
+    /// Accents and dynamics are applied to the note velocity, the click is kept as is.
+    fn effect_midi(
+        channel: &MidiChannel,
+        dynamic_map: &DynamicMap,
+        msg: &MidiMessage,
+    ) -> StructuredShortMessage {
+        match msg.midi {
+            StructuredShortMessage::NoteOn {
+                channel: midi_channel,
+                key_number,
+                velocity,
+            } if channel.track.is_some() => StructuredShortMessage::NoteOn {
+                channel: midi_channel,
+                key_number,
+                velocity: U7::new(
+                    dynamic_map.calc_velocity(msg.pos.into(), msg.effect_velocity(velocity.get())),
+                ),
+            },
+            midi => midi,
+        }
+    }
+    fn channel_events(
+        channel: &MidiChannel,
+        dynamic_map: &DynamicMap,
+        begin_units: Units,
+    ) -> Vec<(u32, u8, TrackEventKind<'static>)> {
+        let mut events = Vec::new();
+        let program = StructuredShortMessage::ProgramChange {
+            channel: channel.channel,
+            program_number: channel.program,
+        };
+        if let Some((order, kind)) = Self::smf_midi_kind(&program) {
+            events.push((0, order, kind));
+        }
+        for msg in MidiUtil::pitch_bend_range_msgs(channel.channel) {
+            if let Some((order, kind)) = Self::smf_midi_kind(&msg) {
+                events.push((0, order, kind));
+            }
+        }
+        for msg in channel.messages.iter() {
+            if let Some((order, kind)) =
+                Self::smf_midi_kind(&Self::effect_midi(channel, dynamic_map, msg))
+            {
+                let tick = Self::units_to_ticks(msg.effect_units() - begin_units);
+                events.push((tick, order, kind));
+            }
+        }
+        events
+    }
+    pub fn export_tab(settings: &MidiSettings, tab: Arc<Tab>) -> io::Result<Vec<u8>> {
+        let mut state = MidiState::default();
+        state.load_tab(settings, tab.clone());
+        let begin_units = tab
+            .bars
+            .first()
+            .map(|x| Units::from(BarPosition::from(x.props)))
+            .unwrap_or_default();
+        let end_tick = Self::units_to_ticks(
+            tab.bars
+                .last()
+                .map(|x| BarPosition::from(x.props).next_bar_pos())
+                .unwrap_or_default()
+                - begin_units,
+        );
+        let section_names: Vec<(u32, String)> = tab
+            .bars
+            .iter()
+            .filter(|x| x.props.bar_index == 0)
+            .map(|x| {
+                let pos = Units::from(BarPosition::from(x.props)) - begin_units;
+                (Self::units_to_ticks(pos), x.section.id.clone())
+            })
+            .collect();
+        let channels: Vec<(String, &MidiChannel)> = state
+            .channels
+            .iter()
+            .filter(|x| !x.messages.is_empty())
+            .filter_map(|x| match &x.track {
+                Some(track) if track.kind == TrackKind::Chord && settings.chord_mute => None,
+                Some(track) if track.kind == TrackKind::Drums && settings.drums_mute => None,
+                Some(track) => Some((track.id.clone(), x)),
+                None if !settings.click_mute => Some(("click".to_owned(), x)),
+                None => None,
+            })
+            .collect();
+
+        let mut smf = Smf::new(Header::new(
+            Format::Parallel,
+            Timing::Metrical(u15::new(Self::TICKS_PER_QUARTER)),
+        ));
+        let mut conductor = Vec::new();
+        let tempo_map = tab.tempo_map();
+        let dynamic_map = tab.dynamic_map();
+        for (index, change) in tempo_map.changes.iter().enumerate() {
+            // Rit. and accel. are written as a tempo per beat, at the speed of its middle.
+            let mut steps = vec![(change.in_tab_pos, change.factor)];
+            if let Some(next) = tempo_map.changes.get(index + 1) {
+                if !change.is_constant() {
+                    steps.clear();
+                    let beat_units = Units::from(change.beat_unit);
+                    let mut pos = change.in_tab_pos;
+                    while pos < next.in_tab_pos {
+                        let end = (pos + beat_units).min(next.in_tab_pos);
+                        let middle = pos + (end - pos) / 2;
+                        steps.push((pos, tempo_map.calc_factor(middle)));
+                        pos = end;
+                    }
+                }
+            }
+            for (pos, factor) in steps {
+                conductor.push((
+                    Self::units_to_ticks(pos - begin_units),
+                    0,
+                    TrackEventKind::Meta(MetaMessage::Tempo(u24::new(
+                        Self::tempo_micros_per_quarter(change, factor),
+                    ))),
+                ));
+            }
+        }
+        let mut signature = None;
+        for bar in tab.bars.iter() {
+            if signature != Some(bar.signature()) {
+                signature = Some(bar.signature());
+                let pos = Units::from(BarPosition::from(bar.props)) - begin_units;
+                conductor.push((
+                    Self::units_to_ticks(pos),
+                    0,
+                    Self::time_signature_kind(&bar.signature()),
+                ));
+            }
+        }
+        for (tick, name) in section_names.iter() {
+            conductor.push((
+                *tick,
+                1,
+                TrackEventKind::Meta(MetaMessage::Marker(name.as_bytes())),
+            ));
+        }
+        smf.tracks.push(Self::to_track_events(conductor, end_tick));
+        for (name, channel) in channels.iter() {
+            let mut events = Self::channel_events(channel, &dynamic_map, begin_units);
+            events.push((
+                0,
+                0,
+                TrackEventKind::Meta(MetaMessage::TrackName(name.as_bytes())),
+            ));
+            smf.tracks.push(Self::to_track_events(events, end_tick));
+        }
+        let mut bytes = Vec::new();
+        smf.write_std(&mut bytes)?;
+        Ok(bytes)
+    }
+    pub fn write_tab<P: AsRef<Path>>(
+        settings: &MidiSettings,
+        tab: Arc<Tab>,
+        path: P,
+    ) -> io::Result<()> {
+        let bytes = Self::export_tab(settings, tab)?;
+        std::fs::write(path, bytes)
+    }
+}
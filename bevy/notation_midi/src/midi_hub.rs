@@ -89,7 +89,7 @@ impl MidiHub {
         self.check_output(settings);
         if let Some(synth) = &self.output_synth {
             //println!("send to synth: {:?}", msg);
-            if let Err(err) = synth.send(speed, msg, velocity) {
+            if let Err(err) = synth.send(speed, msg, msg.effect_velocity(velocity)) {
                 if Self::PRINT_SYNTH_ERROR {
                     println!("send to synth failed: {:?} -> {:?}", msg, err);
                 }
@@ -0,0 +1,493 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use fehler::{throw, throws};
+use midly::{MetaMessage, Smf, Timing, TrackEventKind};
+use notation_model::notation_proto::prelude::{
+    Bar, BarLayer, CoreEntry, Duration, Form, Key, ProtoEntry, Scale, Section, SectionKind,
+    Semitones, Signature, Slice, SliceBegin, SliceEnd, Tab, TabMeta, Tempo, Tone, Track,
+    TrackKind, Unit, Units,
+};
+use thiserror::Error;
+
+use crate::prelude::MidiUtil;
+
+#[derive(Error, Debug)]
+pub enum MidiImportError {
+    #[error("read midi file failed")]
+    Io(#[from] std::io::Error),
+    #[error("parse midi file failed")]
+    Parse(#[from] midly::Error),
+    #[error("timecode timing not supported")]
+    TimecodeTiming,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct MidiImportOptions {
+    pub quantize: Unit,
+    pub bars_per_section: usize,
+}
+
+impl Default for MidiImportOptions {
+    fn default() -> Self {
+        Self {
+            quantize: Unit::Sixteenth,
+            bars_per_section: 8,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct ImportNote {
+    begin: u64,
+    end: u64,
+    key: u8,
+}
+
+#[derive(Clone, Debug, Default)]
+struct ImportTrack {
+    name: Option<String>,
+    channel: u8,
+    program: Option<u8>,
+    notes: Vec<ImportNote>,
+}
+
+#[derive(Clone, Debug)]
+struct ImportSegment {
+    begin: u64,
+    steps: u64,
+    keys: Vec<u8>,
+    tied: bool,
+}
+
+pub struct MidiImport();
+
+impl MidiImport {
+    #[throws(MidiImportError)]
+    pub fn read_tab<P: AsRef<Path>>(path: P, options: &MidiImportOptions) -> Tab {
+        let bytes = std::fs::read(path)?;
+        Self::import_tab(&bytes, options)?
+    }
+    #[throws(MidiImportError)]
+    pub fn import_tab(bytes: &[u8], options: &MidiImportOptions) -> Tab {
+        let smf = Smf::parse(bytes)?;
+        let ticks_per_quarter = match smf.header.timing {
+            Timing::Metrical(v) => v.as_int() as u64,
+            Timing::Timecode(_, _) => throw!(MidiImportError::TimecodeTiming),
+        };
+        let mut tempo = None;
+        let mut signature = None;
+        let mut key_signature = None;
+        let mut markers: Vec<(u64, String)> = Vec::new();
+        let mut tracks: Vec<ImportTrack> = Vec::new();
+        for events in smf.tracks.iter() {
+            let mut tick: u64 = 0;
+            let mut track_name = None;
+            let mut channels: HashMap<u8, ImportTrack> = HashMap::new();
+            let mut pending: HashMap<(u8, u8), Vec<u64>> = HashMap::new();
+            for event in events.iter() {
+                tick += event.delta.as_int() as u64;
+                match event.kind {
+                    TrackEventKind::Meta(MetaMessage::Tempo(v)) if tempo.is_none() => {
+                        tempo = Some(v.as_int());
+                    }
+                    TrackEventKind::Meta(MetaMessage::TimeSignature(beats, power, _, _))
+                        if signature.is_none() =>
+                    {
+                        signature = Some((beats, power));
+                    }
+                    TrackEventKind::Meta(MetaMessage::KeySignature(sharps, minor))
+                        if key_signature.is_none() =>
+                    {
+                        key_signature = Some((sharps, minor));
+                    }
+                    TrackEventKind::Meta(MetaMessage::Marker(text)) => {
+                        let text = String::from_utf8_lossy(text).trim().to_string();
+                        if !text.is_empty() {
+                            markers.push((tick, text));
+                        }
+                    }
+                    TrackEventKind::Meta(MetaMessage::TrackName(text)) => {
+                        let text = String::from_utf8_lossy(text).trim().to_string();
+                        if !text.is_empty() {
+                            track_name = Some(text);
+                        }
+                    }
+                    TrackEventKind::Midi { channel, message } => {
+                        let channel = channel.as_int();
+                        let track = channels.entry(channel).or_insert_with(|| ImportTrack {
+                            channel,
+                            ..Default::default()
+                        });
+                        match message {
+                            midly::MidiMessage::ProgramChange { program }
+                                if track.program.is_none() =>
+                            {
+                                track.program = Some(program.as_int());
+                            }
+                            midly::MidiMessage::NoteOn { key, vel } if vel.as_int() > 0 => {
+                                pending
+                                    .entry((channel, key.as_int()))
+                                    .or_default()
+                                    .push(tick);
+                            }
+                            midly::MidiMessage::NoteOn { key, .. }
+                            | midly::MidiMessage::NoteOff { key, .. } => {
+                                if let Some(begins) = pending.get_mut(&(channel, key.as_int())) {
+                                    if !begins.is_empty() {
+                                        let begin = begins.remove(0);
+                                        track.notes.push(ImportNote {
+                                            begin,
+                                            end: tick,
+                                            key: key.as_int(),
+                                        });
+                                    }
+                                }
+                            }
+                            _ => (),
+                        }
+                    }
+                    _ => (),
+                }
+            }
+            let mut channels: Vec<ImportTrack> = channels
+                .into_values()
+                .filter(|x| !x.notes.is_empty())
+                .collect();
+            channels.sort_by_key(|x| x.channel);
+            let multi_channels = channels.len() > 1;
+            for mut track in channels {
+                if track.channel == MidiUtil::DRUMS_CHANNEL {
+                    println!(
+                        "MidiImport::import_tab(), drums channel skipped: {} notes",
+                        track.notes.len()
+                    );
+                    continue;
+                }
+                track.name = match &track_name {
+                    Some(name) if multi_channels => Some(format!("{} {}", name, track.channel + 1)),
+                    Some(name) => Some(name.clone()),
+                    None => None,
+                };
+                tracks.push(track);
+            }
+        }
+        let meta = Self::new_meta(tempo, signature, key_signature);
+        let step_ticks = (ticks_per_quarter as f32
+            * (Units::from(options.quantize) / Units::from(Unit::Quarter)).as_f32())
+            .round()
+            .max(1.0) as u64;
+        let bar_steps = (meta.bar_units() / Units::from(options.quantize))
+            .as_f32()
+            .round()
+            .max(1.0) as u64;
+        let to_steps = |tick: u64| (tick as f32 / step_ticks as f32).round() as u64;
+        let last_step = tracks
+            .iter()
+            .flat_map(|x| x.notes.iter())
+            .map(|x| to_steps(x.end).max(to_steps(x.begin) + 1))
+            .max()
+            .unwrap_or(0);
+        let bar_count = last_step.div_ceil(bar_steps).max(1) as usize;
+
+        let mut proto_tracks = Vec::new();
+        let mut bar_layers: Vec<Vec<BarLayer>> = vec![Vec::new(); bar_count];
+        let mut used_ids: Vec<String> = Vec::new();
+        for (index, track) in tracks.iter().enumerate() {
+            let id = Self::unique_id(
+                &mut used_ids,
+                track.name.clone().unwrap_or(format!("track{}", index + 1)),
+            );
+            let segments = Self::calc_segments(track, bar_steps, bar_count, &to_steps);
+            let mut entries = Vec::new();
+            for (bar_index, layers) in bar_layers.iter_mut().enumerate() {
+                let begin = entries.len();
+                let bar_begin = bar_index as u64 * bar_steps;
+                for segment in segments
+                    .iter()
+                    .filter(|x| x.begin >= bar_begin && x.begin < bar_begin + bar_steps)
+                {
+                    Self::add_segment_entries(&meta, options.quantize, segment, &mut entries);
+                }
+                let slice = Slice::new(
+                    SliceBegin::Index(begin),
+                    SliceEnd::Count(entries.len() - begin),
+                    None,
+                );
+                layers.push(BarLayer::new(id.clone(), vec![slice]));
+            }
+            proto_tracks.push(Track::new(id, Self::track_kind(track), entries));
+        }
+        let bar_ticks = bar_steps * step_ticks;
+        let (sections, form) = Self::new_sections(
+            bar_layers,
+            &markers
+                .iter()
+                .map(|(tick, text)| ((*tick / bar_ticks) as usize, text.clone()))
+                .collect::<Vec<(usize, String)>>(),
+            options.bars_per_section.max(1),
+        );
+        Tab::new(&Tab::new_uuid(), meta, proto_tracks, sections, form)
+    }
+    fn new_meta(
+        tempo: Option<u32>,
+        signature: Option<(u8, u8)>,
+        key_signature: Option<(i8, bool)>,
+    ) -> TabMeta {
+        let signature = match signature {
+            Some((beats, power)) if beats > 0 => {
+                let beat_unit = match power {
+                    0 => Unit::Whole,
+                    1 => Unit::Half,
+                    2 => Unit::Quarter,
+                    3 => Unit::Eighth,
+                    4 => Unit::Sixteenth,
+                    5 => Unit::ThirtySecondth,
+                    _ => Unit::SixtyFourth,
+                };
+                Signature::new(beat_unit, beats)
+            }
+            _ => Signature::_4_4,
+        };
+        let tempo = match tempo {
+            Some(micros) if micros > 0 => {
+                let quarters_per_beat =
+                    (Units::from(signature.beat_unit) / Units::from(Unit::Quarter)).as_f32();
+                let bpm = 60_000_000.0 / micros as f32 / quarters_per_beat;
+                Tempo::Bpm(bpm.round().clamp(1.0, u16::MAX as f32) as u16)
+            }
+            _ => Tempo::Bpm(120),
+        };
+        let (key, scale) = match key_signature {
+            Some((sharps, minor)) => Self::key_of_signature(sharps, minor),
+            None => (Key::C, Scale::Major),
+        };
+        TabMeta::new(key, scale, signature, tempo)
+    }
+    pub fn key_of_signature(sharps: i8, minor: bool) -> (Key, Scale) {
+        let index = (sharps.clamp(-7, 7) + 7) as usize;
+        if minor {
+            let keys = [
+                Key::A_FLAT,
+                Key::E_FLAT,
+                Key::B_FLAT,
+                Key::F,
+                Key::C,
+                Key::G,
+                Key::D,
+                Key::A,
+                Key::E,
+                Key::B,
+                Key::F_SHARP,
+                Key::C_SHARP,
+                Key::G_SHARP,
+                Key::D_SHARP,
+                Key::A_SHARP,
+            ];
+            (keys[index], Scale::Minor)
+        } else {
+            let keys = [
+                Key::B,
+                Key::G_FLAT,
+                Key::D_FLAT,
+                Key::A_FLAT,
+                Key::E_FLAT,
+                Key::B_FLAT,
+                Key::F,
+                Key::C,
+                Key::G,
+                Key::D,
+                Key::A,
+                Key::E,
+                Key::B,
+                Key::F_SHARP,
+                Key::C_SHARP,
+            ];
+            (keys[index], Scale::Major)
+        }
+    }
+    fn track_kind(track: &ImportTrack) -> TrackKind {
+        if let Some(32..=39) = track.program {
+            return TrackKind::Bass;
+        }
+        let polyphonic = track.notes.iter().any(|a| {
+            track
+                .notes
+                .iter()
+                .any(|b| b.begin > a.begin && b.begin < a.end)
+        });
+        if polyphonic {
+            TrackKind::Piano
+        } else {
+            TrackKind::Vocal
+        }
+    }
+    fn unique_id(used_ids: &mut Vec<String>, id: String) -> String {
+        let mut result = id.clone();
+        let mut index = 2;
+        while used_ids.contains(&result) {
+            result = format!("{} {}", id, index);
+            index += 1;
+        }
+        used_ids.push(result.clone());
+        result
+    }
+    /*
+     * Cut the track into segments at every note begin/end and bar line, each segment has
+     * the keys sounding in it, continued segments with the same keys are marked as tied.
+     */
+    fn calc_segments<F: Fn(u64) -> u64>(
+        track: &ImportTrack,
+        bar_steps: u64,
+        bar_count: usize,
+        to_steps: &F,
+    ) -> Vec<ImportSegment> {
+        let notes: Vec<(u64, u64, u8)> = track
+            .notes
+            .iter()
+            .map(|x| {
+                let begin = to_steps(x.begin);
+                (begin, to_steps(x.end).max(begin + 1), x.key)
+            })
+            .collect();
+        let total_steps = bar_steps * bar_count as u64;
+        let mut bounds: Vec<u64> = (0..=bar_count as u64).map(|x| x * bar_steps).collect();
+        for (begin, end, _) in notes.iter() {
+            bounds.push(*begin);
+            bounds.push(*end);
+        }
+        bounds.sort();
+        bounds.dedup();
+        let mut segments: Vec<ImportSegment> = Vec::new();
+        for pair in bounds.windows(2) {
+            let (begin, end) = (pair[0], pair[1]);
+            if begin >= total_steps {
+                break;
+            }
+            let mut keys: Vec<u8> = notes
+                .iter()
+                .filter(|(b, e, _)| *b <= begin && *e > begin)
+                .map(|(_, _, k)| *k)
+                .collect();
+            keys.sort();
+            keys.dedup();
+            let continued = !keys.is_empty()
+                && notes
+                    .iter()
+                    .filter(|(b, e, _)| *b <= begin && *e > begin)
+                    .all(|(b, _, _)| *b < begin);
+            let tied = continued
+                && segments
+                    .last()
+                    .map(|x| x.keys == keys && x.begin + x.steps == begin)
+                    .unwrap_or(false);
+            let bar_line = begin % bar_steps == 0;
+            match segments.last_mut() {
+                Some(last) if !bar_line && last.keys == keys && (tied || keys.is_empty()) => {
+                    last.steps += end - begin;
+                }
+                _ => segments.push(ImportSegment {
+                    begin,
+                    steps: end - begin,
+                    keys,
+                    tied,
+                }),
+            }
+        }
+        segments
+    }
+    fn add_segment_entries(
+        meta: &TabMeta,
+        quantize: Unit,
+        segment: &ImportSegment,
+        entries: &mut Vec<ProtoEntry>,
+    ) {
+        let durations = Self::split_steps(quantize, segment.steps);
+        if segment.keys.is_empty() {
+            for duration in durations {
+                entries.push(ProtoEntry::from(CoreEntry::from(duration)));
+            }
+            return;
+        }
+        let notes = segment
+            .keys
+            .iter()
+            .map(|x| {
+                meta.scale
+                    .calc_note_from_semitones(&meta.key, Semitones(*x as i8 - 12))
+            })
+            .collect::<Vec<_>>();
+        let tone = Tone::from(notes);
+        for (index, duration) in durations.into_iter().enumerate() {
+            if index > 0 || segment.tied {
+                entries.push(ProtoEntry::from(CoreEntry::from(())));
+            }
+            entries.push(ProtoEntry::from(CoreEntry::from((tone, duration))));
+        }
+    }
+    /// Split the steps into durations that can be represented, longest first.
+    ///
+    /// The quantize unit itself is always one of the candidates, so all the steps are covered.
+    pub fn split_steps(quantize: Unit, steps: u64) -> Vec<Duration> {
+        let step_units = Units::from(quantize);
+        let mut candidates: Vec<(u64, Duration)> = Vec::new();
+        for unit in Unit::ALL {
+            for duration in [Duration::Dotted(unit), Duration::Simple(unit)] {
+                let value = Units::from(duration) / step_units;
+                if value.is_positive() && value.denominator() == 1 {
+                    candidates.push((value.numerator() as u64, duration));
+                }
+            }
+        }
+        let mut result = Vec::new();
+        let mut remain = steps;
+        while let Some((value, duration)) = candidates.iter().find(|(x, _)| *x <= remain) {
+            result.push(*duration);
+            remain -= value;
+        }
+        result
+    }
+    fn new_sections(
+        bar_layers: Vec<Vec<BarLayer>>,
+        markers: &[(usize, String)],
+        bars_per_section: usize,
+    ) -> (Vec<Section>, Form) {
+        let mut starts: Vec<(usize, String)> = Vec::new();
+        if markers.is_empty() {
+            for bar_index in (0..bar_layers.len()).step_by(bars_per_section) {
+                starts.push((bar_index, format!("{}", bar_index / bars_per_section + 1)));
+            }
+        } else {
+            for (bar_index, text) in markers.iter() {
+                if *bar_index >= bar_layers.len() {
+                    continue;
+                }
+                match starts.last_mut() {
+                    Some(last) if last.0 == *bar_index => last.1 = text.clone(),
+                    _ => starts.push((*bar_index, text.clone())),
+                }
+            }
+            if starts.first().map(|x| x.0 > 0).unwrap_or(true) {
+                starts.insert(0, (0, "start".to_string()));
+            }
+        }
+        let mut sections = Vec::new();
+        let mut form = Vec::new();
+        let mut used_ids = Vec::new();
+        for (index, (bar_index, text)) in starts.iter().enumerate() {
+            let end = starts
+                .get(index + 1)
+                .map(|x| x.0)
+                .unwrap_or(bar_layers.len());
+            let bars = bar_layers[*bar_index..end]
+                .iter()
+                .map(|x| Bar::from(x.clone()))
+                .collect();
+            let id = Self::unique_id(&mut used_ids, text.clone());
+            let kind = SectionKind::from_ident(text.as_str());
+            form.push(id.clone());
+            sections.push(Section::new(id, kind, bars));
+        }
+        (sections, form.into())
+    }
+}
@@ -7,10 +7,14 @@ pub struct MidiMessage {
     pub pos: BarPosition,
     pub duration: Units,
     pub delay: bool,
+    /// Shifts the note on from the entry position, e.g. for the strings of a strum.
+    pub offset: Units,
+    /// Applied to the channel velocity when playing, starts with the entry's accent.
+    pub velocity_factor: f32,
     pub midi: StructuredShortMessage,
 }
 impl MidiMessage {
-    pub const DELAY_GAP: Units = Units(Units::_MIN_ACCURACY * 2.0);
+    pub const DELAY_GAP: Units = Units::new(2, Units::F32_RESOLUTION);
     pub fn new(
         pass_mode: EntryPassMode,
         pos: BarPosition,
@@ -23,6 +27,8 @@ impl MidiMessage {
             pos,
             duration,
             delay,
+            offset: Units::ZERO,
+            velocity_factor: 1.0,
             midi,
         }
     }
@@ -32,19 +38,42 @@ impl MidiMessage {
             pos: entry.bar_position(),
             duration: entry.tied_units(),
             delay,
+            offset: Units::ZERO,
+            velocity_factor: entry
+                .model
+                .accent()
+                .map(|x| x.velocity_factor())
+                .unwrap_or(1.0),
             midi,
         }
     }
+    pub fn with_offset(mut self, offset: Units) -> Self {
+        self.offset = offset;
+        self
+    }
+    pub fn with_duration(mut self, duration: Units) -> Self {
+        self.duration = duration;
+        self
+    }
+    pub fn with_velocity_factor(mut self, velocity_factor: f32) -> Self {
+        self.velocity_factor *= velocity_factor;
+        self
+    }
     pub fn bar_ordinal(&self) -> usize {
         self.pos.bar_ordinal
     }
     pub fn effect_position(&self) -> BarPosition {
         if self.delay {
             self.pos.with_delay(self.duration - Self::DELAY_GAP)
+        } else if self.offset.is_positive() {
+            self.pos.with_delay(self.offset)
         } else {
             self.pos
         }
     }
+    pub fn effect_velocity(&self, velocity: u8) -> u8 {
+        (velocity as f32 * self.velocity_factor).round().clamp(0.0, 127.0) as u8
+    }
     pub fn effect_units(&self) -> Units {
         self.effect_position().into()
     }
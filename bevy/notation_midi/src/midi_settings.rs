@@ -2,6 +2,8 @@ use bevy::prelude::*;
 use midi_msg::GMSoundSet;
 use notation_model::prelude::{Octave, TrackKind};
 
+use crate::prelude::AccompanimentPattern;
+
 #[derive(Copy, Clone, PartialEq, Debug, Resource)]
 pub struct MidiSettings {
     pub bypass_hub: bool,
@@ -12,13 +14,29 @@ pub struct MidiSettings {
     pub vocal_velocity: u8,
     pub guitar_mute: bool,
     pub guitar_velocity: u8,
+    pub bass_mute: bool,
+    pub bass_velocity: u8,
     pub piano_mute: bool,
     pub piano_velocity: u8,
+    pub chord_mute: bool,
+    pub chord_velocity: u8,
+    pub chord_octave: Octave,
+    pub chord_pattern: AccompanimentPattern,
+    pub drums_mute: bool,
+    pub drums_velocity: u8,
+    /// Milliseconds from one string to the next in a strum.
+    pub strum_speed: u8,
+    /// Velocity of the last string in a strum, relative to the first one.
+    pub strum_velocity_curve: f32,
     pub use_internal_synth: bool,
     pub click_sound: u8,
     pub vocal_sound: u8,
     pub guitar_sound: u8,
+    pub bass_sound: u8,
     pub piano_sound: u8,
+    pub chord_sound: u8,
+    /// Drum kit program on the percussion channel, 0 is the standard kit.
+    pub drums_sound: u8,
     pub seeking_track: TrackKind,
     pub seeking_init_channel: bool,
 }
@@ -34,13 +52,26 @@ impl Default for MidiSettings {
             vocal_velocity: 110,
             guitar_mute: false,
             guitar_velocity: 120,
+            bass_mute: false,
+            bass_velocity: 110,
             piano_mute: false,
             piano_velocity: 110,
+            chord_mute: true,
+            chord_velocity: 90,
+            chord_octave: Octave::P3,
+            chord_pattern: AccompanimentPattern::BassChord,
+            drums_mute: false,
+            drums_velocity: 100,
+            strum_speed: 12,
+            strum_velocity_curve: 0.8,
             use_internal_synth: Self::default_use_internal_synth(),
             click_sound: GMSoundSet::Dulcimer as u8,
             vocal_sound: GMSoundSet::Cello as u8,
             guitar_sound: GMSoundSet::AcousticGuitarSteel as u8,
+            bass_sound: GMSoundSet::ElectricBassFinger as u8,
             piano_sound: GMSoundSet::AcousticGrandPiano as u8,
+            chord_sound: GMSoundSet::ElectricPiano1 as u8,
+            drums_sound: 0,
             seeking_track: TrackKind::Guitar,
             seeking_init_channel: true,
         }
@@ -60,7 +91,10 @@ impl MidiSettings {
         match kind {
             TrackKind::Vocal => Some((self.vocal_sound, self.vocal_velocity)),
             TrackKind::Guitar => Some((self.guitar_sound, self.guitar_velocity)),
+            TrackKind::Bass => Some((self.bass_sound, self.bass_velocity)),
             TrackKind::Piano => Some((self.piano_sound, self.piano_velocity)),
+            TrackKind::Chord => Some((self.chord_sound, self.chord_velocity)),
+            TrackKind::Drums => Some((self.drums_sound, self.drums_velocity)),
             _ => None,
         }
     }
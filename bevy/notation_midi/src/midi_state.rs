@@ -121,6 +121,13 @@ impl MidiChannel {
                                 settings.guitar_velocity
                             };
                         }
+                        TrackKind::Bass => {
+                            velocity = if !seeking && settings.bass_mute {
+                                0
+                            } else {
+                                settings.bass_velocity
+                            };
+                        }
                         TrackKind::Piano => {
                             velocity = if !seeking && settings.piano_mute {
                                 0
@@ -128,6 +135,20 @@ impl MidiChannel {
                                 settings.piano_velocity
                             };
                         }
+                        TrackKind::Chord => {
+                            velocity = if !seeking && settings.chord_mute {
+                                0
+                            } else {
+                                settings.chord_velocity
+                            };
+                        }
+                        TrackKind::Drums => {
+                            velocity = if !seeking && settings.drums_mute {
+                                0
+                            } else {
+                                settings.drums_velocity
+                            };
+                        }
                         _ => (),
                     }
                 }
@@ -152,6 +173,14 @@ impl MidiChannel {
                     count += 1;
                     if !bypass {
                         if !is_seeking || next.should_send_in_seeking() {
+                            // The click keeps its velocity, tracks follow the dynamics.
+                            let velocity = if self.track.is_some() {
+                                play_control
+                                    .dynamic_map
+                                    .calc_velocity(next.pos.into(), velocity)
+                            } else {
+                                velocity
+                            };
                             hub.send(settings, speed, next, velocity);
                         }
                     }
@@ -203,25 +232,28 @@ impl MidiChannel {
                 ),
                 self.velocity.into(),
             );
+            for msg in MidiUtil::pitch_bend_range_msgs(self.channel) {
+                hub.send(
+                    settings,
+                    speed,
+                    &MidiMessage::new(
+                        first_msg.pass_mode,
+                        first_msg.pos,
+                        first_msg.duration,
+                        false,
+                        msg,
+                    ),
+                    self.velocity.into(),
+                );
+            }
         }
     }
-    pub fn setup(
-        &mut self,
-        _settings: &MidiSettings,
-        _hub: &mut MidiHub,
-        params: (u8, u8),
-        track: &Arc<Track>,
-    ) {
+    pub fn setup(&mut self, _settings: &MidiSettings, params: (u8, u8), track: &Arc<Track>) {
         self.track = Some(track.clone());
         self.program = U7::new(params.0);
         self.velocity = U7::new(params.1);
     }
-    pub fn setup_no_track(
-        &mut self,
-        _settings: &MidiSettings,
-        _hub: &mut MidiHub,
-        params: (u8, u8),
-    ) {
+    pub fn setup_no_track(&mut self, _settings: &MidiSettings, params: (u8, u8)) {
         self.track = None;
         self.program = U7::new(params.0);
         self.velocity = U7::new(params.1);
@@ -285,28 +317,21 @@ impl MidiState {
         }
         None
     }
-    fn create_click_channel(
-        &mut self,
-        settings: &MidiSettings,
-        hub: &mut MidiHub,
-        tab: &Tab,
-        index: &mut usize,
-    ) {
+    fn create_click_channel(&mut self, settings: &MidiSettings, tab: &Tab, index: &mut usize) {
         let params = settings.get_click_channel_params();
         if let Some(channel) = self.channels.get_mut(*index) {
-            channel.setup_no_track(settings, hub, params);
+            channel.setup_no_track(settings, params);
             println!(
                 "switch_tab(), setup click channel: [{}] -> {}, {}",
                 index, params.0, params.1
             );
             *index += 1;
             let scale_root = tab.meta.scale.calc_root_syllable();
-            let signature = tab.signature();
-            let bar_units = tab.bar_units();
-            let beat_duration = Units::from(signature.beat_unit);
             for bar in tab.bars.iter() {
+                let signature = bar.signature();
+                let beat_duration = Units::from(signature.beat_unit);
                 for beat in 0..signature.bar_beats {
-                    let in_bar_pos = Units(beat as f32 * Units::from(signature.beat_unit).0);
+                    let in_bar_pos = beat_duration * beat as i64;
                     let root = bar
                         .get_chord(Some(in_bar_pos))
                         .map(|x| x.root)
@@ -316,7 +341,7 @@ impl MidiState {
                         &settings.click_octave,
                         &root,
                     );
-                    let pos = BarPosition::new(bar_units, bar.props.bar_ordinal, in_bar_pos);
+                    let pos = BarPosition::from(bar.props).with_in_bar_pos(in_bar_pos);
                     if let Some(midi_msg) =
                         MidiUtil::note_midi_on_msg(&note, channel.channel, channel.velocity)
                     {
@@ -344,22 +369,47 @@ impl MidiState {
         }
     }
     pub fn switch_tab(&mut self, settings: &MidiSettings, hub: &mut MidiHub, tab: Arc<Tab>) {
+        self.load_tab(settings, tab);
+        self.init_channels(settings, hub);
+    }
+    /// Assign channels and collect messages for the tab, without sending anything to the hub.
+    pub fn load_tab(&mut self, settings: &MidiSettings, tab: Arc<Tab>) {
         self.tab = Some(tab.clone());
         self.reset_channels();
         let mut index: usize = 0;
-        self.create_click_channel(settings, hub, &tab, &mut index);
+        self.create_click_channel(settings, &tab, &mut index);
+        let mut drums_assigned = false;
         for track in tab.tracks.iter() {
+            if index == MidiUtil::DRUMS_CHANNEL as usize {
+                index += 1;
+            }
             if index >= self.channels.len() {
                 return;
             }
             if let Some(params) = settings.get_track_channel_params(&track.kind) {
-                if let Some(channel) = self.channels.get_mut(index) {
-                    channel.setup(settings, hub, params, track);
+                // General MIDI only plays percussion on channel 10.
+                let channel_index = if track.kind == TrackKind::Drums {
+                    if drums_assigned {
+                        println!(
+                            "switch_tab(), only one drums track supported, skipped: {}",
+                            track
+                        );
+                        continue;
+                    }
+                    drums_assigned = true;
+                    MidiUtil::DRUMS_CHANNEL as usize
+                } else {
+                    index
+                };
+                if let Some(channel) = self.channels.get_mut(channel_index) {
+                    channel.setup(settings, params, track);
                     println!(
                         "switch_tab(), setup channel: [{}] -> {}, {} - {}",
-                        index, params.0, params.1, track
+                        channel_index, params.0, params.1, track
                     );
-                    index += 1;
+                    if channel_index == index {
+                        index += 1;
+                    }
                 }
             }
         }
@@ -367,9 +417,9 @@ impl MidiState {
             for ((_k, _i), lane) in bar.lanes.iter() {
                 if let Some(channel) = self.get_channel_mut(&lane.track.id, &lane.track.kind) {
                     for entry in lane.entries.iter() {
-                        if let Some(msgs) = MidiUtil::get_midi_msgs(channel, bar, &entry) {
+                        if let Some(msgs) = MidiUtil::get_midi_msgs(settings, channel, bar, entry) {
                             for msg in msgs {
-                                channel.add_message(MidiMessage::of_entry(entry, msg.0, msg.1));
+                                channel.add_message(msg);
                             }
                         }
                     }
@@ -377,7 +427,6 @@ impl MidiState {
             }
         }
         self.play_control = PlayControl::new(&tab);
-        self.init_channels(settings, hub);
     }
     pub fn jump_to_bar(
         &mut self,
@@ -386,8 +435,7 @@ impl MidiState {
         bar_props: TabBarProps,
     ) {
         self.play_control
-            .position
-            .set_in_bar(bar_props.bar_ordinal, Units(0.0));
+            .set_position_in_bar(bar_props.bar_ordinal, Units::ZERO);
         if self.play_control.is_bar_in_range(bar_props.bar_ordinal) {
             for channel in self.channels.iter_mut() {
                 channel.calc_next_index(&self.play_control.position.bar);
@@ -461,7 +509,8 @@ impl MidiState {
         if self.tab.is_some() {
             let pos = self.play_control.position.bar;
             if let Some(bar) = self.tab.as_ref().unwrap().get_bar(pos) {
-                if let Some(props) = bar.get_next_entry(pos.in_bar_pos, &|x| {
+                // Seeking stops a tick before the entry, which is skipped next time.
+                if let Some(props) = bar.get_next_entry(pos.in_bar_pos + Units::TICK, &|x| {
                     if x.track_kind() != settings.seeking_track {
                         None
                     } else if x.prev_is_tie() {
@@ -470,16 +519,10 @@ impl MidiState {
                         Some(x.props.clone())
                     }
                 }) {
-                    self.setup_seek(
-                        pos.with_in_bar_pos(props.in_bar_pos - Units::HALF_MIN_ACCURACY),
-                    );
+                    self.setup_seek(pos.with_in_bar_pos(props.in_bar_pos - Units::TICK));
                     return true;
                 } else {
-                    self.setup_seek(BarPosition::new(
-                        pos.bar_units,
-                        pos.bar_ordinal,
-                        pos.bar_units - Units::HALF_MIN_ACCURACY,
-                    ));
+                    self.setup_seek(pos.with_in_bar_pos(pos.bar_units - Units::TICK));
                     return true;
                 }
             }
@@ -495,8 +538,10 @@ impl MidiState {
     fn seek_passed(&mut self, settings: &MidiSettings) -> bool {
         if self.tab.is_some() {
             let mut pos = self.play_control.position.bar;
-            if pos.in_bar_pos >= pos.bar_units - Units::MIN_ACCURACY {
-                pos = BarPosition::new(pos.bar_units, pos.bar_ordinal + 1, Units::MIN_ACCURACY);
+            if pos.in_bar_pos >= pos.bar_units - Units::TICK {
+                pos = self
+                    .play_control
+                    .get_bar_position(pos.bar_ordinal + 1, Units::TICK);
                 if let Some(bar) = self.tab.as_ref().unwrap().get_bar(pos) {
                     if let Some(props) = bar.get_next_entry(pos.in_bar_pos, &|x| {
                         if x.track_kind() != settings.seeking_track {
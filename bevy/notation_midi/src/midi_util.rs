@@ -1,21 +1,110 @@
 use std::convert::TryFrom;
 
-use helgoboss_midi::{Channel, KeyNumber, StructuredShortMessage, U7};
+use std::sync::Arc;
+
+use helgoboss_midi::{controller_numbers, Channel, KeyNumber, StructuredShortMessage, U14, U7};
+use notation_model::notation_proto::notation_fretted::strum::{StrumDirection, StrumStrings};
 use notation_model::prelude::{
-    CoreEntry, Entry, FrettedEntry4, FrettedEntry6, LaneEntry, Note, Pick, Semitones, TabBar, Tone,
+    Bpm, CoreEntry, DrumEntry, Entry, FrettedEntry4, FrettedEntry6, LaneEntry, Note, Pick,
+    PickNote, PickTechnique, Semitones, Strum, TabBar, Tone, Unit, Units,
 };
 
-use crate::prelude::MidiChannel;
+use crate::prelude::{MidiAccompaniment, MidiChannel, MidiMessage, MidiSettings};
 
 pub struct MidiUtil();
 
 impl MidiUtil {
+    #[cfg(not(target_arch = "wasm32"))]
+    pub const PLAYBACK_KEY_OFFSET: i8 = -1; //Not sure why got a higher pitch when playing, temp fix for get it right in video
+    #[cfg(target_arch = "wasm32")]
+    pub const PLAYBACK_KEY_OFFSET: i8 = 0;
+    /// Channel 10 in General MIDI, the keys on it are drum pieces instead of pitches.
+    pub const DRUMS_CHANNEL: u8 = 9;
+    /// Set on every channel, wide enough for slides along the neck.
+    pub const PITCH_BEND_SEMITONES: u8 = 12;
+    pub const PITCH_BEND_STEPS: i64 = 8;
+    pub const VIBRATO_SEMITONES: f32 = 0.5;
+    pub const SLIDE_IN_SEMITONES: f32 = -2.0;
+    pub const SLIDE_OUT_SEMITONES: f32 = -5.0;
     pub fn note_midi_key_number(note: &Note) -> Option<KeyNumber> {
-        #[cfg(not(target_arch = "wasm32"))]
-        let midi_note = Semitones::from(*note).0 + 12 - 1; //Not sure why got a higher pitch when playing, temp fix for get it right in video
-        #[cfg(target_arch = "wasm32")]
-        let midi_note = Semitones::from(*note).0 + 12;
-        KeyNumber::try_from(midi_note as u8).ok()
+        Self::note_midi_key_number_with_offset(note, 0)
+    }
+    pub fn note_midi_key_number_with_offset(note: &Note, offset: u8) -> Option<KeyNumber> {
+        let midi_note =
+            Semitones::from(*note).0 as i16 + 12 + Self::PLAYBACK_KEY_OFFSET as i16 + offset as i16;
+        u8::try_from(midi_note)
+            .ok()
+            .and_then(|x| KeyNumber::try_from(x).ok())
+    }
+    /// Registered parameter 0 sets the pitch bend range.
+    pub fn pitch_bend_range_msgs(channel: Channel) -> Vec<StructuredShortMessage> {
+        [
+            (controller_numbers::REGISTERED_PARAMETER_NUMBER_MSB, 0),
+            (controller_numbers::REGISTERED_PARAMETER_NUMBER_LSB, 0),
+            (controller_numbers::DATA_ENTRY_MSB, Self::PITCH_BEND_SEMITONES),
+            (controller_numbers::DATA_ENTRY_MSB_LSB, 0),
+        ]
+        .into_iter()
+        .map(|(controller_number, value)| StructuredShortMessage::ControlChange {
+            channel,
+            controller_number,
+            control_value: U7::new(value),
+        })
+        .collect()
+    }
+    pub fn pitch_bend_msg(channel: Channel, semitones: f32) -> StructuredShortMessage {
+        let value = 8192.0 + semitones / Self::PITCH_BEND_SEMITONES as f32 * 8192.0;
+        StructuredShortMessage::PitchBendChange {
+            channel,
+            pitch_bend_value: U14::new(value.round().clamp(0.0, 16383.0) as u16),
+        }
+    }
+    /// Pitch bends moving between the values in steps, over the units after the offset.
+    pub fn pitch_bend_glide_msgs(
+        channel: &MidiChannel,
+        entry: &LaneEntry,
+        from: f32,
+        to: f32,
+        offset: Units,
+        units: Units,
+    ) -> Vec<MidiMessage> {
+        (0..=Self::PITCH_BEND_STEPS)
+            .map(|step| {
+                let factor = step as f32 / Self::PITCH_BEND_STEPS as f32;
+                let msg = Self::pitch_bend_msg(channel.channel, from + (to - from) * factor);
+                MidiMessage::of_entry(entry, false, msg)
+                    .with_offset(offset + units * step / Self::PITCH_BEND_STEPS)
+            })
+            .collect()
+    }
+    pub fn vibrato_msgs(channel: &MidiChannel, entry: &LaneEntry) -> Vec<MidiMessage> {
+        let step = Units::from(Unit::ThirtySecondth);
+        let steps = (entry.tied_units() / step).floor();
+        (0..steps)
+            .map(|index| {
+                let semitones = match index % 4 {
+                    1 => Self::VIBRATO_SEMITONES,
+                    3 => -Self::VIBRATO_SEMITONES,
+                    _ => 0.0,
+                };
+                let msg = Self::pitch_bend_msg(channel.channel, semitones);
+                MidiMessage::of_entry(entry, false, msg).with_offset(step * index)
+            })
+            .collect()
+    }
+    /// Palm mutes and dead notes are cut short and softer.
+    pub fn technique_duration_velocity(technique: Option<PickTechnique>, units: Units) -> (Units, f32) {
+        match technique {
+            Some(PickTechnique::PalmMute) => (units / 2, 0.8),
+            Some(PickTechnique::DeadNote) => (units.min(Units::from(Unit::ThirtySecondth)), 0.6),
+            Some(PickTechnique::NaturalHarmonic) | Some(PickTechnique::ArtificialHarmonic) => {
+                (units, 0.8)
+            }
+            _ => (units, 1.0),
+        }
+    }
+    fn key_semitones(from: KeyNumber, to: KeyNumber) -> f32 {
+        to.get() as f32 - from.get() as f32
     }
     pub fn note_midi_on_msg(
         note: &Note,
@@ -46,21 +135,21 @@ impl MidiUtil {
         _bar: &TabBar,
         entry: &LaneEntry,
         tone: &Tone,
-    ) -> Option<Vec<(bool, StructuredShortMessage)>> {
+    ) -> Option<Vec<MidiMessage>> {
         if tone.is_none() || entry.prev_is_tie() {
             return None;
         }
-        let mut play_msgs: Vec<(bool, StructuredShortMessage)> = tone
+        let mut play_msgs: Vec<MidiMessage> = tone
             .get_notes()
             .iter()
             .flat_map(|x| MidiUtil::note_midi_on_msg(x, channel.channel, channel.velocity))
-            .map(|x| (false, x))
+            .map(|x| MidiMessage::of_entry(entry, false, x))
             .collect();
-        let mut stop_msgs: Vec<(bool, StructuredShortMessage)> = tone
+        let mut stop_msgs: Vec<MidiMessage> = tone
             .get_notes()
             .iter()
             .flat_map(|x| MidiUtil::note_midi_off_msg(x, channel.channel, channel.velocity))
-            .map(|x| (true, x))
+            .map(|x| MidiMessage::of_entry(entry, true, x))
             .collect();
         play_msgs.append(&mut stop_msgs);
         if play_msgs.len() > 0 {
@@ -69,31 +158,210 @@ impl MidiUtil {
             None
         }
     }
+    pub fn get_drum_midi_msgs(
+        channel: &MidiChannel,
+        _bar: &TabBar,
+        entry: &LaneEntry,
+        drum_entry: &DrumEntry,
+    ) -> Option<Vec<MidiMessage>> {
+        let hit = drum_entry.as_hit()?;
+        let mut play_msgs: Vec<MidiMessage> = Vec::new();
+        let mut stop_msgs: Vec<MidiMessage> = Vec::new();
+        for piece in hit.pieces.iter() {
+            let key_number = KeyNumber::new(piece.midi_key());
+            play_msgs.push(MidiMessage::of_entry(
+                entry,
+                false,
+                StructuredShortMessage::NoteOn {
+                    channel: channel.channel,
+                    key_number,
+                    velocity: channel.velocity,
+                },
+            ));
+            stop_msgs.push(MidiMessage::of_entry(
+                entry,
+                true,
+                StructuredShortMessage::NoteOff {
+                    channel: channel.channel,
+                    key_number,
+                    velocity: channel.velocity,
+                },
+            ));
+        }
+        play_msgs.append(&mut stop_msgs);
+        if !play_msgs.is_empty() {
+            Some(play_msgs)
+        } else {
+            None
+        }
+    }
     pub fn get_core_midi_msgs(
+        settings: &MidiSettings,
         channel: &MidiChannel,
         bar: &TabBar,
         entry: &LaneEntry,
         core_entry: &CoreEntry,
-    ) -> Option<Vec<(bool, StructuredShortMessage)>> {
+    ) -> Option<Vec<MidiMessage>> {
         match core_entry {
             CoreEntry::Tone(tone, _) => Self::get_tone_midi_msgs(channel, bar, entry, tone),
+            CoreEntry::Chord(chord, _) => {
+                MidiAccompaniment::get_chord_midi_msgs(settings, channel, bar, entry, chord)
+            }
             _ => None,
         }
     }
+    /// Notes are played one by one from the first note of the strum, the gap between
+    /// them is capped so that the last note starts within the first half of the entry.
+    pub fn get_strum_notes_midi_msgs(
+        settings: &MidiSettings,
+        channel: &MidiChannel,
+        bar: &TabBar,
+        entry: &LaneEntry,
+        notes: &[Note],
+    ) -> Option<Vec<MidiMessage>> {
+        if notes.is_empty() || entry.prev_is_tie() {
+            return None;
+        }
+        let beat_units = Units::from(bar.beat_unit());
+        let units_per_ms = beat_units.as_f32() * Bpm::from(bar.props.tempo) as f32 / 60000.0;
+        let last = (notes.len() - 1).max(1) as f32;
+        let gap = (settings.strum_speed as f32 * units_per_ms)
+            .min(entry.tied_units().as_f32() / 2.0 / last);
+        let mut play_msgs: Vec<MidiMessage> = Vec::new();
+        let mut stop_msgs: Vec<MidiMessage> = Vec::new();
+        for (index, note) in notes.iter().enumerate() {
+            let factor = 1.0 - (1.0 - settings.strum_velocity_curve) * index as f32 / last;
+            if let Some(msg) = MidiUtil::note_midi_on_msg(note, channel.channel, channel.velocity) {
+                play_msgs.push(
+                    MidiMessage::of_entry(entry, false, msg)
+                        .with_offset(Units::from_f32(gap * index as f32))
+                        .with_velocity_factor(factor),
+                );
+            }
+            if let Some(msg) = MidiUtil::note_midi_off_msg(note, channel.channel, channel.velocity) {
+                stop_msgs.push(MidiMessage::of_entry(entry, true, msg));
+            }
+        }
+        play_msgs.append(&mut stop_msgs);
+        if !play_msgs.is_empty() {
+            Some(play_msgs)
+        } else {
+            None
+        }
+    }
+    /// Grace notes before the beat are played at the end of the previous note, which can't be
+    /// tied into the grace notes' entry.
+    fn is_grace_donor(entry: &LaneEntry) -> bool {
+        entry.tied_units().is_positive() && !entry.prev_is_tie() && !entry.next_is_tie()
+    }
+    /// Neighbour entry with duration, skipping the zero length ones like accents and ornaments.
+    fn neighbour_sounding_entry(entry: &LaneEntry, next: bool) -> Option<Arc<LaneEntry>> {
+        let mut current = if next { entry.next() } else { entry.prev() };
+        while let Some(x) = current {
+            if x.tied_units().is_positive() {
+                return Some(x);
+            }
+            current = if next { x.next() } else { x.prev() };
+        }
+        None
+    }
+    fn ornament_note_units(units: Units, count: usize) -> Units {
+        Units::from(Unit::ThirtySecondth).min(units / (count as i64 * 2))
+    }
+    fn ornament_note_msgs(
+        channel: &MidiChannel,
+        entry: &LaneEntry,
+        notes: &[Note],
+        start: Units,
+        note_units: Units,
+    ) -> Vec<MidiMessage> {
+        let mut msgs = Vec::new();
+        for (index, note) in notes.iter().enumerate() {
+            let offset = start + note_units * index as i64;
+            if let Some(x) = Self::note_midi_on_msg(note, channel.channel, channel.velocity) {
+                msgs.push(MidiMessage::of_entry(entry, false, x).with_offset(offset));
+            }
+            if let Some(x) = Self::note_midi_off_msg(note, channel.channel, channel.velocity) {
+                msgs.push(MidiMessage::of_entry(entry, true, x).with_duration(offset + note_units));
+            }
+        }
+        msgs
+    }
+    /// Ornaments on the beat take their time from the start of the entry, grace notes before
+    /// the beat from the end of the previous one.
+    fn add_ornament_msgs(
+        channel: &MidiChannel,
+        bar: &TabBar,
+        entry: &LaneEntry,
+        msgs: Vec<MidiMessage>,
+    ) -> Vec<MidiMessage> {
+        let mut msgs = msgs;
+        if msgs.is_empty() {
+            return msgs;
+        }
+        let units = entry.tied_units();
+        if Self::is_grace_donor(entry) {
+            if let Some(next) = Self::neighbour_sounding_entry(entry, true) {
+                let notes = bar.calc_ornament_notes(&next);
+                let before_beat = next.model.ornament().map(|x| x.is_before_beat()).unwrap_or(false);
+                if before_beat && !notes.is_empty() {
+                    let note_units = Self::ornament_note_units(units, notes.len());
+                    let start = units - note_units * notes.len() as i64;
+                    for msg in msgs.iter_mut().filter(|x| x.delay) {
+                        msg.duration = msg.duration.min(start);
+                    }
+                    msgs.append(&mut Self::ornament_note_msgs(channel, entry, &notes, start, note_units));
+                }
+            }
+        }
+        let before_beat = match entry.model.ornament() {
+            Some(ornament) => {
+                ornament.is_before_beat()
+                    && Self::neighbour_sounding_entry(entry, false)
+                        .map(|x| Self::is_grace_donor(&x))
+                        .unwrap_or(false)
+            }
+            None => return msgs,
+        };
+        let notes = bar.calc_ornament_notes(entry);
+        if before_beat || notes.is_empty() {
+            return msgs;
+        }
+        let note_units = Self::ornament_note_units(units, notes.len());
+        let shift = note_units * notes.len() as i64;
+        for msg in msgs.iter_mut().filter(|x| !x.delay) {
+            msg.offset += shift;
+        }
+        msgs.append(&mut Self::ornament_note_msgs(channel, entry, &notes, Units::ZERO, note_units));
+        msgs
+    }
     pub fn get_midi_msgs(
+        settings: &MidiSettings,
+        channel: &MidiChannel,
+        bar: &TabBar,
+        entry: &LaneEntry,
+    ) -> Option<Vec<MidiMessage>> {
+        Self::get_entry_midi_msgs(settings, channel, bar, entry)
+            .map(|msgs| Self::add_ornament_msgs(channel, bar, entry, msgs))
+    }
+    fn get_entry_midi_msgs(
+        settings: &MidiSettings,
         channel: &MidiChannel,
         bar: &TabBar,
         entry: &LaneEntry,
-    ) -> Option<Vec<(bool, StructuredShortMessage)>> {
+    ) -> Option<Vec<MidiMessage>> {
         match entry.proto() {
             notation_model::prelude::ProtoEntry::Core(core_entry) => {
-                Self::get_core_midi_msgs(channel, bar, entry, core_entry)
+                Self::get_core_midi_msgs(settings, channel, bar, entry, core_entry)
             }
             notation_model::prelude::ProtoEntry::Fretted6(fretted_entry) => {
-                Self::get_fretted_midi_msgs6(channel, bar, entry, fretted_entry)
+                Self::get_fretted_midi_msgs6(settings, channel, bar, entry, fretted_entry)
             }
             notation_model::prelude::ProtoEntry::Fretted4(fretted_entry) => {
-                Self::get_fretted_midi_msgs4(channel, bar, entry, fretted_entry)
+                Self::get_fretted_midi_msgs4(settings, channel, bar, entry, fretted_entry)
+            }
+            notation_model::prelude::ProtoEntry::Drum(drum_entry) => {
+                Self::get_drum_midi_msgs(channel, bar, entry, drum_entry)
             }
             _ => None,
         }
@@ -101,18 +369,185 @@ impl MidiUtil {
 }
 
 macro_rules! impl_get_pick_midi_msgs {
-    ($name:ident, $get_fretted_shape:ident) => {
+    ($name:ident, $get_fretted_shape:ident, $as_fretted:ident,
+        $pick_note_key:ident, $neighbour_pick_note:ident, $legato_keys:ident
+    ) => {
         impl MidiUtil {
+            /// Harmonics sound at the fret they ring like.
+            fn $pick_note_key(bar: &TabBar, entry: &LaneEntry, pick_note: PickNote) -> Option<KeyNumber> {
+                let (fretboard, shape) = bar.$get_fretted_shape(entry)?;
+                let meta = bar.tab_meta();
+                let (fret, note) =
+                    fretboard.shape_pick_fret_note(&meta.scale, &meta.key, &shape, pick_note)?;
+                let offset = pick_note
+                    .technique
+                    .map(|x| x.sounding_fret(fret) - fret)
+                    .unwrap_or(0);
+                Self::note_midi_key_number_with_offset(&note, offset)
+            }
+            /// Notes next to each other on the string, rests and other entries break them.
+            fn $neighbour_pick_note(
+                entry: &LaneEntry,
+                string: u8,
+                next: bool,
+            ) -> Option<(Arc<LaneEntry>, PickNote)> {
+                let mut current = if next { entry.next() } else { entry.prev() };
+                while let Some(x) = current {
+                    if let Some(pick) = x.model().$as_fretted().and_then(|y| y.as_pick()) {
+                        return pick.get_pick_note(string).map(|note| (x.clone(), note));
+                    }
+                    if x.tied_units().is_positive() {
+                        return None;
+                    }
+                    current = if next { x.next() } else { x.prev() };
+                }
+                None
+            }
+            /// The picked key a legato note is played from, and the key right before it.
+            fn $legato_keys(
+                bar: &TabBar,
+                entry: &LaneEntry,
+                pick_note: PickNote,
+            ) -> Option<(KeyNumber, KeyNumber)> {
+                if !pick_note.is_legato() {
+                    return None;
+                }
+                let (mut attack_entry, mut attack_note) =
+                    Self::$neighbour_pick_note(entry, pick_note.string, false)?;
+                let prev_key = Self::$pick_note_key(bar, &attack_entry, attack_note)?;
+                while attack_note.is_legato() {
+                    match Self::$neighbour_pick_note(&attack_entry, pick_note.string, false) {
+                        Some((x, note)) => {
+                            attack_entry = x;
+                            attack_note = note;
+                        }
+                        None => break,
+                    }
+                }
+                let attack_key = Self::$pick_note_key(bar, &attack_entry, attack_note)?;
+                Some((attack_key, prev_key))
+            }
+            /// Legato notes are not picked, the key picked before keeps sounding and is bent
+            /// to their pitch, and is only stopped at the end of the last one.
             pub fn $name(
                 channel: &MidiChannel,
                 bar: &TabBar,
                 entry: &LaneEntry,
                 pick: &Pick,
-            ) -> Option<Vec<(bool, StructuredShortMessage)>> {
-                if let Some((fretboard, shape)) = bar.$get_fretted_shape(entry) {
+            ) -> Option<Vec<MidiMessage>> {
+                let (fretboard, shape) = bar.$get_fretted_shape(entry)?;
+                let held = |pick_note: &PickNote| {
+                    Self::$neighbour_pick_note(entry, pick_note.string, true)
+                        .map(|(_, x)| x.is_legato())
+                        .unwrap_or(false)
+                };
+                let notes = pick.get_notes();
+                if notes.iter().all(|x| x.technique.is_none() && !held(x)) {
                     let meta = bar.tab_meta();
                     let tone = fretboard.pick_tone(&meta.scale, &meta.key, &shape, pick);
-                    Self::get_tone_midi_msgs(channel, bar, entry, &tone)
+                    return Self::get_tone_midi_msgs(channel, bar, entry, &tone);
+                }
+                if entry.prev_is_tie() {
+                    return None;
+                }
+                let units = entry.tied_units();
+                let mut play_msgs: Vec<MidiMessage> = Vec::new();
+                let mut stop_msgs: Vec<MidiMessage> = Vec::new();
+                for pick_note in notes.iter() {
+                    let key = match Self::$pick_note_key(bar, entry, *pick_note) {
+                        Some(x) => x,
+                        None => continue,
+                    };
+                    let (duration, velocity_factor) =
+                        Self::technique_duration_velocity(pick_note.technique, units);
+                    let (stop_key, bent) = match Self::$legato_keys(bar, entry, *pick_note) {
+                        Some((attack_key, prev_key)) => {
+                            let target = Self::key_semitones(attack_key, key);
+                            let from = Self::key_semitones(attack_key, prev_key);
+                            play_msgs.append(&mut match pick_note.technique {
+                                Some(PickTechnique::Slide) => Self::pitch_bend_glide_msgs(
+                                    channel, entry, from, target, Units::ZERO, units / 4,
+                                ),
+                                Some(PickTechnique::Release(x)) => Self::pitch_bend_glide_msgs(
+                                    channel, entry, target + x as f32, target, Units::ZERO, units / 2,
+                                ),
+                                _ => vec![MidiMessage::of_entry(
+                                    entry,
+                                    false,
+                                    Self::pitch_bend_msg(channel.channel, target),
+                                )],
+                            });
+                            (attack_key, true)
+                        }
+                        None => {
+                            if pick_note.technique == Some(PickTechnique::SlideIn) {
+                                play_msgs.append(&mut Self::pitch_bend_glide_msgs(
+                                    channel, entry, Self::SLIDE_IN_SEMITONES, 0.0, Units::ZERO, units / 4,
+                                ));
+                            } else if pick_note.is_legato() {
+                                play_msgs.push(MidiMessage::of_entry(
+                                    entry,
+                                    false,
+                                    Self::pitch_bend_msg(channel.channel, 0.0),
+                                ));
+                            }
+                            play_msgs.push(
+                                MidiMessage::of_entry(
+                                    entry,
+                                    false,
+                                    StructuredShortMessage::NoteOn {
+                                        channel: channel.channel,
+                                        key_number: key,
+                                        velocity: channel.velocity,
+                                    },
+                                )
+                                .with_duration(duration)
+                                .with_velocity_factor(velocity_factor),
+                            );
+                            let mut bends = match pick_note.technique {
+                                Some(PickTechnique::Bend(x)) => Self::pitch_bend_glide_msgs(
+                                    channel, entry, 0.0, x as f32, Units::ZERO, units / 2,
+                                ),
+                                Some(PickTechnique::SlideOut) => Self::pitch_bend_glide_msgs(
+                                    channel, entry, 0.0, Self::SLIDE_OUT_SEMITONES, units / 2, units / 2,
+                                ),
+                                Some(PickTechnique::Vibrato) => Self::vibrato_msgs(channel, entry),
+                                _ => vec![],
+                            };
+                            let bent = !bends.is_empty();
+                            play_msgs.append(&mut bends);
+                            (key, bent)
+                        }
+                    };
+                    if held(pick_note) {
+                        continue;
+                    }
+                    stop_msgs.push(
+                        MidiMessage::of_entry(
+                            entry,
+                            true,
+                            StructuredShortMessage::NoteOff {
+                                channel: channel.channel,
+                                key_number: stop_key,
+                                velocity: channel.velocity,
+                            },
+                        )
+                        .with_duration(duration),
+                    );
+                    if bent {
+                        stop_msgs.push(
+                            MidiMessage::of_entry(
+                                entry,
+                                true,
+                                Self::pitch_bend_msg(channel.channel, 0.0),
+                            )
+                            .with_duration(duration),
+                        );
+                    }
+                }
+                play_msgs.append(&mut stop_msgs);
+                if !play_msgs.is_empty() {
+                    Some(play_msgs)
                 } else {
                     None
                 }
@@ -121,19 +556,53 @@ macro_rules! impl_get_pick_midi_msgs {
     };
 }
 
+macro_rules! impl_get_strum_midi_msgs {
+    ($name:ident, $get_fretted_shape:ident, $strings:literal) => {
+        impl MidiUtil {
+            /// Down strums go from the low strings to the high ones, up strums the other way.
+            pub fn $name(
+                settings: &MidiSettings,
+                channel: &MidiChannel,
+                bar: &TabBar,
+                entry: &LaneEntry,
+                strum: &Strum,
+            ) -> Option<Vec<MidiMessage>> {
+                let (fretboard, shape) = bar.$get_fretted_shape(entry)?;
+                let meta = bar.tab_meta();
+                let (high, low) = match strum.strings {
+                    StrumStrings::All => (1, $strings),
+                    StrumStrings::Between(x, y) => (x.min(y).max(1), x.max(y).min($strings)),
+                };
+                let mut notes: Vec<Note> = (high..=low)
+                    .rev()
+                    .filter_map(|string| fretboard.shape_note(&meta.scale, &meta.key, &shape, string))
+                    .collect();
+                if strum.direction == Some(StrumDirection::Up) {
+                    notes.reverse();
+                }
+                Self::get_strum_notes_midi_msgs(settings, channel, bar, entry, &notes)
+            }
+        }
+    };
+}
+
 macro_rules! impl_get_fretted_midi_msgs {
-    ($name:ident, $get_pick_midi_msgs:ident, $fretted_entry:ident) => {
+    ($name:ident, $get_pick_midi_msgs:ident, $get_strum_midi_msgs:ident, $fretted_entry:ident) => {
         impl MidiUtil {
             pub fn $name(
+                settings: &MidiSettings,
                 channel: &MidiChannel,
                 bar: &TabBar,
                 entry: &LaneEntry,
                 fretted_entry: &$fretted_entry,
-            ) -> Option<Vec<(bool, StructuredShortMessage)>> {
+            ) -> Option<Vec<MidiMessage>> {
                 match fretted_entry {
                     $fretted_entry::Pick(pick, _) => {
                         Self::$get_pick_midi_msgs(channel, bar, entry, pick)
                     }
+                    $fretted_entry::Strum(strum, _) => {
+                        Self::$get_strum_midi_msgs(settings, channel, bar, entry, strum)
+                    }
                     _ => None,
                 }
             }
@@ -141,8 +610,35 @@ macro_rules! impl_get_fretted_midi_msgs {
     };
 }
 
-impl_get_pick_midi_msgs!(get_pick_midi_msgs6, get_fretted_shape6);
-impl_get_pick_midi_msgs!(get_pick_midi_msgs4, get_fretted_shape4);
+impl_get_pick_midi_msgs!(
+    get_pick_midi_msgs6,
+    get_fretted_shape6,
+    as_fretted6,
+    pick_note_key6,
+    neighbour_pick_note6,
+    legato_keys6
+);
+impl_get_pick_midi_msgs!(
+    get_pick_midi_msgs4,
+    get_fretted_shape4,
+    as_fretted4,
+    pick_note_key4,
+    neighbour_pick_note4,
+    legato_keys4
+);
+
+impl_get_strum_midi_msgs!(get_strum_midi_msgs6, get_fretted_shape6, 6);
+impl_get_strum_midi_msgs!(get_strum_midi_msgs4, get_fretted_shape4, 4);
 
-impl_get_fretted_midi_msgs!(get_fretted_midi_msgs6, get_pick_midi_msgs6, FrettedEntry6);
-impl_get_fretted_midi_msgs!(get_fretted_midi_msgs4, get_pick_midi_msgs4, FrettedEntry4);
+impl_get_fretted_midi_msgs!(
+    get_fretted_midi_msgs6,
+    get_pick_midi_msgs6,
+    get_strum_midi_msgs6,
+    FrettedEntry6
+);
+impl_get_fretted_midi_msgs!(
+    get_fretted_midi_msgs4,
+    get_pick_midi_msgs4,
+    get_strum_midi_msgs4,
+    FrettedEntry4
+);
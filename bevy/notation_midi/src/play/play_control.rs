@@ -1,23 +1,19 @@
-use notation_model::prelude::{TabPosition, BarPosition, Bpm, Position, Tab, TabMeta, Units};
+use notation_model::prelude::{TabPosition, BarPosition, DynamicMap, Position, Tab, TempoMap, Units};
 use crate::prelude::PlayState;
 
 #[derive(Debug)]
 pub struct TabPlayStateChanged();
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Clone, Debug)]
 pub struct PlaySpeed {
-    pub seconds_per_unit: f32,
-    pub units_per_second: f32,
+    pub tempo_map: TempoMap,
     factor: f32,
 }
 
 impl PlaySpeed {
-    pub fn new(tab_meta: &TabMeta) -> Self {
-        let units_per_second =
-            Bpm::from(tab_meta.tempo) as f32 / 60.0 * Units::from(tab_meta.signature.beat_unit).0;
+    pub fn new(tempo_map: TempoMap) -> Self {
         Self {
-            seconds_per_unit: 1.0 / units_per_second,
-            units_per_second,
+            tempo_map,
             factor: 1.0,
         }
     }
@@ -33,11 +29,12 @@ impl PlaySpeed {
             false
         }
     }
-    pub fn calc_units(&self, seconds: f32) -> Units {
-        Units(seconds * self.units_per_second * self.factor)
+    /// Tempo changes, rit., accel. and fermatas are applied from the position on.
+    pub fn calc_units(&self, from: Units, seconds: f32) -> Units {
+        self.tempo_map.calc_units(from, seconds * self.factor)
     }
-    pub fn calc_seconds(&self, units: Units) -> f32 {
-        units.0 * self.seconds_per_unit / self.factor
+    pub fn calc_seconds(&self, from: Units, units: Units) -> f32 {
+        self.tempo_map.calc_seconds(from, units) / self.factor
     }
 }
 
@@ -53,6 +50,7 @@ pub struct PlayControl {
     pub should_loop: bool,
     pub play_state: PlayState,
     pub play_speed: PlaySpeed,
+    pub dynamic_map: DynamicMap,
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -74,21 +72,21 @@ impl TickResult {
 }
 impl Default for PlayControl {
     fn default() -> Self {
-        let tab_meta = TabMeta::default();
-        Self::_new(&tab_meta, 0)
+        Self::_new(TempoMap::default(), DynamicMap::default(), 0)
     }
 }
 
 impl PlayControl {
-    fn _new(tab_meta: &TabMeta, bars: usize) -> Self {
+    fn _new(tempo_map: TempoMap, dynamic_map: DynamicMap, bars: usize) -> Self {
         Self {
-            position: Position::new(tab_meta.bar_units()),
+            position: tempo_map.calc_position(Units::ZERO),
             bars,
             begin_bar_ordinal: 0,
             end_bar_ordinal: if bars > 0 { bars - 1 } else { bars },
             should_loop: false,
             play_state: PlayState::default(),
-            play_speed: PlaySpeed::new(tab_meta),
+            play_speed: PlaySpeed::new(tempo_map),
+            dynamic_map,
         }
     }
     pub fn get_last_bar_ordinal(&self) -> usize {
@@ -99,7 +97,18 @@ impl PlayControl {
         }
     }
     pub fn new(tab: &Tab) -> Self {
-        Self::_new(&tab.meta, tab.bars.len())
+        Self::_new(tab.tempo_map(), tab.dynamic_map(), tab.bars.len())
+    }
+    pub fn get_bar_position(&self, bar_ordinal: usize, in_bar_pos: Units) -> BarPosition {
+        self.play_speed
+            .tempo_map
+            .get_bar_position(bar_ordinal, in_bar_pos)
+    }
+    pub fn set_position_in_tab(&mut self, in_tab_pos: Units) {
+        self.position = self.play_speed.tempo_map.calc_position(in_tab_pos);
+    }
+    pub fn set_position_in_bar(&mut self, bar_ordinal: usize, in_bar_pos: Units) {
+        self.position = Position::from(self.get_bar_position(bar_ordinal, in_bar_pos));
     }
     pub fn play(&mut self) -> bool {
         if self.play_state.is_playing() {
@@ -114,8 +123,7 @@ impl PlayControl {
             false
         } else {
             self.play_state = PlayState::Paused;
-            //self.position
-            //    .set_in_bar(self.position.bar.bar_ordinal, Units(0.0));
+            //self.set_position_in_bar(self.position.bar.bar_ordinal, Units::ZERO);
             true
         }
     }
@@ -123,16 +131,15 @@ impl PlayControl {
         if !self.play_state.is_stopped() {
             self.play_state = PlayState::Stopped;
         }
-        self.position.set_in_bar(self.begin_bar_ordinal, Units(0.0));
+        self.set_position_in_bar(self.begin_bar_ordinal, Units::ZERO);
         true
     }
     pub fn _tick_to_position(&mut self, jumped: bool, pos: TabPosition) -> TickResult {
-        self.position.set_in_tab(pos.in_tab_pos);
+        self.set_position_in_tab(pos.in_tab_pos);
         let end_passed = self.position.bar.bar_ordinal > self.end_bar_ordinal;
         let stopped = if end_passed {
             if self.should_loop {
-                self.position
-                    .set_in_bar(self.begin_bar_ordinal, self.position.bar.in_bar_pos);
+                self.set_position_in_bar(self.begin_bar_ordinal, self.position.bar.in_bar_pos);
                 if self.position.bar.bar_ordinal > self.end_bar_ordinal {
                     self.stop() //Corner case for too small range
                 } else {
@@ -152,11 +159,12 @@ impl PlayControl {
             let delta_units = if self.position.bar.bar_ordinal < self.begin_bar_ordinal
                 || self.position.bar.bar_ordinal > self.end_bar_ordinal
             {
-                self.position.set_in_bar(self.begin_bar_ordinal, Units(0.0));
+                self.set_position_in_bar(self.begin_bar_ordinal, Units::ZERO);
                 jumped = true;
-                Units(0.0)
+                Units::ZERO
             } else {
-                self.play_speed.calc_units(delta_seconds)
+                self.play_speed
+                    .calc_units(self.position.tab.in_tab_pos, delta_seconds)
             };
             self._tick_to_position(
                 jumped,
@@ -172,11 +180,7 @@ impl PlayControl {
             && bar_ordinal <= self.end_bar_ordinal
     }
     pub fn begin_bar_position(&self) -> BarPosition {
-        BarPosition::new(
-            self.position.bar.bar_units,
-            self.begin_bar_ordinal,
-            Units(0.0),
-        )
+        self.get_bar_position(self.begin_bar_ordinal, Units::ZERO)
     }
     pub fn has_selection(&self, add_ready_section: bool) -> bool {
         if add_ready_section {
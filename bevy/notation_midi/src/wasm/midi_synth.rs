@@ -41,7 +41,7 @@ impl MidiSynth {
                     Ok(play_note(
                         channel.into(),
                         key_number.into(),
-                        speed.calc_seconds(msg.duration),
+                        speed.calc_seconds(msg.effect_units(), msg.duration),
                         volume * Self::VOLUME_FACTOR,
                     ))
                 } else {
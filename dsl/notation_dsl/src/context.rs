@@ -4,7 +4,8 @@ use crate::core::duration::DurationTweakDsl;
 use crate::core::octave::OctaveTweakDsl;
 use fehler::{throw, throws};
 use notation_proto::prelude::{
-    Duration, Key, Note, Octave, Scale, Syllable, GUITAR_STRING_NUM, Pitch,
+    BassUtil, Duration, Fretboard4, Fretboard6, GuitarUtil, Key, Note, Octave, Scale, Syllable,
+    GUITAR_STRING_NUM, Pitch,
 };
 use notation_proto::proto_entry::ProtoEntry;
 use proc_macro2::TokenStream;
@@ -19,11 +20,15 @@ lazy_static! {
 #[derive(Copy, Clone, Debug)]
 pub struct FrettedContext {
     pub string_num: usize,
+    pub fretboard6: Fretboard6,
+    pub fretboard4: Fretboard4,
 }
 impl Default for FrettedContext {
     fn default() -> Self {
         Self {
             string_num: GUITAR_STRING_NUM,
+            fretboard6: GuitarUtil::new_default_fretboard(),
+            fretboard4: BassUtil::new_default_fretboard(),
         }
     }
 }
@@ -106,6 +111,19 @@ impl Context {
     pub fn set_octave(octave: Octave) {
         CONTEXT.write().unwrap().octave = octave;
     }
+    pub fn set_string_num(string_num: usize) {
+        CONTEXT.write().unwrap().fretted.string_num = string_num;
+    }
+    /// Also resets the fretboards, which are only set by fretboard entries.
+    pub fn set_fretted(fretted: FrettedContext) {
+        CONTEXT.write().unwrap().fretted = fretted;
+    }
+    pub fn set_fretboard6(fretboard: Fretboard6) {
+        CONTEXT.write().unwrap().fretted.fretboard6 = fretboard;
+    }
+    pub fn set_fretboard4(fretboard: Fretboard4) {
+        CONTEXT.write().unwrap().fretted.fretboard4 = fretboard;
+    }
 }
 
 impl Context {
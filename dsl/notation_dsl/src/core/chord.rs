@@ -1,10 +1,10 @@
 use fehler::{throw, throws};
-use notation_proto::prelude::{Chord, CoreEntry, Interval};
+use notation_proto::prelude::{Chord, ChordSymbol, CoreEntry, Interval};
 use notation_proto::proto_entry::ProtoEntry;
 use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
 use syn::parse::{Error, ParseStream};
-use syn::Token;
+use syn::{LitStr, Token};
 
 use crate::context::Context;
 
@@ -18,6 +18,7 @@ pub struct ChordDsl {
     pub root: Option<SyllableDsl>,
     pub intervals: Vec<IntervalDsl>,
     pub bass: Option<IntervalDsl>,
+    pub symbol: Option<ChordSymbol>,
     pub duration_tweak: Option<DurationTweakDsl>,
 }
 impl ChordDsl {
@@ -27,6 +28,7 @@ impl ChordDsl {
             root: None,
             intervals: vec![],
             bass: None,
+            symbol: None,
             duration_tweak: None,
         }
     }
@@ -41,14 +43,54 @@ impl ChordDsl {
             root: Some(root),
             intervals,
             bass,
+            symbol: None,
             duration_tweak,
         }
     }
+    pub fn symbol(symbol: ChordSymbol, duration_tweak: Option<DurationTweakDsl>) -> Self {
+        Self {
+            empty: None,
+            root: None,
+            intervals: vec![],
+            bass: None,
+            symbol: Some(symbol),
+            duration_tweak,
+        }
+    }
+    /// Letter symbols are converted with the key and scale from meta.
+    fn symbol_chord(&self) -> Option<Chord> {
+        self.symbol
+            .as_ref()
+            .map(|x| x.to_chord(&Context::key(), &Context::scale()))
+    }
+    pub fn to_chord(&self) -> Option<Chord> {
+        if let Some(chord) = self.symbol_chord() {
+            return Some(chord);
+        }
+        self.root.as_ref().map(|root| {
+            let intervals = self
+                .intervals
+                .iter()
+                .map(|x| x.to_proto())
+                .collect::<Vec<Interval>>();
+            let bass = self.bass.as_ref().map(|x| x.to_proto());
+            Chord::new(root.to_proto(), intervals.into(), bass)
+        })
+    }
 }
 
 impl ChordDsl {
     #[throws(Error)]
     pub fn parse_without_paren(input: ParseStream, multied: bool, with_paren: bool) -> Self {
+        if input.peek(LitStr) {
+            let text = input.parse::<LitStr>()?;
+            let symbol = match ChordSymbol::parse(&text.value()) {
+                Some(symbol) => symbol,
+                None => throw!(Error::new(text.span(), "Invalid chord symbol")),
+            };
+            let duration_tweak = DurationTweakDsl::try_parse(input);
+            return ChordDsl::symbol(symbol, duration_tweak);
+        }
         if multied && !with_paren {
             throw!(Error::new(input.span(), "paren required in multied mode"));
         }
@@ -61,6 +103,9 @@ impl ChordDsl {
         while IntervalDsl::peek(input) {
             intervals.push(input.parse()?);
         }
+        if intervals.len() > 6 {
+            throw!(Error::new(input.span(), "Too many intervals, at most 6 supported"));
+        }
         let base: Option<IntervalDsl> = if input.peek(Token![/]) {
             input.parse::<Token![/]>()?;
             Some(input.parse()?)
@@ -79,11 +124,34 @@ impl ToTokens for ChordDsl {
             root,
             intervals,
             bass,
+            symbol: _,
             duration_tweak,
         } = self;
         let duration_quote = Context::duration_quote(duration_tweak);
         if empty.is_some() {
             tokens.extend(empty.as_ref().unwrap().quote(duration_quote));
+        } else if let Some(chord) = self.symbol_chord() {
+            let root_text = chord.root.to_text();
+            let interval_texts: Vec<_> = chord
+                .intervals
+                .get_intervals()
+                .iter()
+                .map(|x| x.to_text())
+                .collect();
+            let bass_quote = match chord.bass {
+                Some(bass) => {
+                    let bass_text = bass.to_text();
+                    quote! { Some(Interval::from_text(#bass_text)) }
+                }
+                None => quote! { None },
+            };
+            tokens.extend(quote! {
+                ProtoEntry::from(CoreEntry::from(
+                    (Chord::new(Syllable::from_text(#root_text), vec![
+                        #(Interval::from_text(#interval_texts)),*
+                    ].into(), #bass_quote), #duration_quote)
+                ))
+            })
         } else if let Some(root) = root {
             let intervals_quote: Vec<_> = intervals.iter().map(|x| quote! { #x }).collect();
             tokens.extend(match bass {
@@ -110,24 +178,14 @@ impl ChordDsl {
     pub fn to_proto(&self) -> ProtoEntry {
         let ChordDsl {
             empty,
-            root,
-            intervals,
-            bass,
             duration_tweak,
+            ..
         } = self;
         let duration = Context::tweaked_duration(duration_tweak);
         if empty.is_some() {
             empty.as_ref().unwrap().to_proto(duration)
-        } else if let Some(root) = root {
-            let intervals = intervals
-                .iter()
-                .map(|x| x.to_proto())
-                .collect::<Vec<Interval>>();
-            let bass = bass.as_ref().map(|x| x.to_proto().clone());
-            ProtoEntry::from(CoreEntry::from((
-                Chord::new(root.to_proto(), intervals.into(), bass),
-                duration,
-            )))
+        } else if let Some(chord) = self.to_chord() {
+            ProtoEntry::from(CoreEntry::from((chord, duration)))
         } else {
             ProtoEntry::from(CoreEntry::from(duration))
         }
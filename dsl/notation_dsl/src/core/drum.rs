@@ -0,0 +1,92 @@
+use fehler::{throw, throws};
+use notation_proto::prelude::{DrumEntry, DrumPiece};
+use notation_proto::proto_entry::ProtoEntry;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, ToTokens};
+use syn::parse::{Error, ParseStream};
+use syn::Ident;
+
+use crate::context::Context;
+
+use super::duration::DurationTweakDsl;
+use super::empty::EmptyDsl;
+
+pub struct DrumDsl {
+    pub empty: Option<EmptyDsl>,
+    pub pieces: Vec<DrumPiece>,
+    pub duration_tweak: Option<DurationTweakDsl>,
+}
+
+impl DrumDsl {
+    #[throws(Error)]
+    pub fn parse_without_paren(input: ParseStream, multied: bool, with_paren: bool) -> Self {
+        let mut empty = None;
+        let mut pieces = vec![];
+        if EmptyDsl::peek(input) {
+            empty = Some(input.parse()?);
+        } else {
+            while input.peek(Ident) {
+                let ident = input.parse::<Ident>()?;
+                match DrumPiece::from_ident(ident.to_string().as_str()) {
+                    Some(piece) => pieces.push(piece),
+                    None => throw!(Error::new(ident.span(), "Invalid DrumPiece")),
+                }
+                if multied && !with_paren {
+                    break;
+                }
+            }
+            if pieces.is_empty() {
+                throw!(Error::new(input.span(), "Missing DrumPiece"));
+            }
+        }
+        let duration_tweak = DurationTweakDsl::try_parse(input);
+        DrumDsl {
+            empty,
+            pieces,
+            duration_tweak,
+        }
+    }
+}
+
+impl ToTokens for DrumDsl {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let DrumDsl {
+            empty,
+            pieces,
+            duration_tweak,
+        } = self;
+        let duration_quote = Context::duration_quote(duration_tweak);
+        if empty.is_some() {
+            tokens.extend(empty.as_ref().unwrap().quote(duration_quote));
+        } else {
+            let pieces_quote: Vec<_> = pieces
+                .iter()
+                .map(|x| {
+                    let ident = format_ident!("{}", x.to_ident());
+                    quote! { DrumPiece::#ident }
+                })
+                .collect();
+            tokens.extend(quote! {
+                ProtoEntry::from(DrumEntry::from(
+                    (vec![#(#pieces_quote),*], #duration_quote)
+                ))
+            });
+        }
+    }
+}
+
+impl DrumDsl {
+    pub fn to_proto(&self) -> ProtoEntry {
+        let DrumDsl {
+            empty,
+            pieces,
+            duration_tweak,
+        } = self;
+        let duration = Context::tweaked_duration(duration_tweak);
+        if empty.is_some() {
+            empty.as_ref().unwrap().to_proto(duration)
+        } else {
+            ProtoEntry::from(DrumEntry::from((pieces.clone(), duration)))
+        }
+    }
+}
@@ -1,15 +1,15 @@
 use std::cmp::{max, min};
 
 use fehler::throws;
-use notation_proto::prelude::Duration;
+use notation_proto::prelude::{Duration, Tuplet};
 use syn::parse::{Error, Parse, ParseStream};
-use syn::Token;
+use syn::{LitInt, Token};
 
 #[derive(Debug)]
 pub struct DurationTweakDsl {
     pub half_num: i8,
-    pub dotted: bool,
-    pub triplet: bool,
+    pub dots: u8,
+    pub tuplet: Tuplet,
 }
 
 impl Parse for DurationTweakDsl {
@@ -26,23 +26,35 @@ impl Parse for DurationTweakDsl {
             }
         }
         half_num = min(max(half_num, -4), 4);
-        let mut dotted = false;
-        let mut triplet = false;
-        if input.peek(Token![+]) {
+        let mut dots = 0;
+        let mut tuplet = Tuplet::NONE;
+        while input.peek(Token![+]) {
             input.parse::<Token![+]>()?;
-            dotted = true;
-        } else if input.peek(Token![=]) {
+            dots += 1;
+        }
+        if dots == 0 && input.peek(Token![=]) {
             input.parse::<Token![=]>()?;
-            dotted = true;
-            triplet = true;
+            dots = 1;
+            tuplet = Tuplet::TRIPLET;
         } else if input.peek(Token![-]) {
             input.parse::<Token![-]>()?;
-            triplet = true;
+            tuplet = Tuplet::TRIPLET;
+        } else if input.peek(Token![/]) {
+            // `/5` for the usual ratio, `/5:6` for others.
+            input.parse::<Token![/]>()?;
+            let actual = input.parse::<LitInt>()?.base10_parse::<u8>()?;
+            tuplet = if input.peek(Token![:]) {
+                input.parse::<Token![:]>()?;
+                let normal = input.parse::<LitInt>()?.base10_parse::<u8>()?;
+                Tuplet::new(actual, normal)
+            } else {
+                Tuplet::of_actual(actual)
+            };
         }
         DurationTweakDsl {
             half_num,
-            dotted,
-            triplet,
+            dots,
+            tuplet,
         }
     }
 }
@@ -54,6 +66,7 @@ impl DurationTweakDsl {
             || input.peek(Token![+])
             || input.peek(Token![=])
             || input.peek(Token![-])
+            || input.peek(Token![/])
     }
     pub fn try_parse(input: ParseStream) -> Option<Self> {
         if Self::peek(input) {
@@ -74,12 +87,7 @@ impl DurationTweakDsl {
                     unit = unit.doubled();
                 }
             }
-            match (self.dotted, self.triplet) {
-                (false, false) => Duration::Simple(unit),
-                (true, false) => Duration::Dotted(unit),
-                (false, true) => Duration::Triplet(unit),
-                (true, true) => Duration::DottedTriplet(unit),
-            }
+            Duration::new(unit, self.dots, self.tuplet)
         } else {
             println!("Can only tweak simple duration: {} - {:?}", base, self);
             return base.clone();
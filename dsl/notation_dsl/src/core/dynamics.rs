@@ -0,0 +1,146 @@
+use fehler::{throw, throws};
+
+use notation_proto::prelude::{Accent, CoreEntry, Dynamic, DynamicCurve, DynamicCurveKind};
+use notation_proto::proto_entry::ProtoEntry;
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::parse::{Error, Parse, ParseStream};
+use syn::{Ident, LitInt, Token};
+
+pub struct DynamicDsl {
+    pub dynamic: Dynamic,
+}
+
+impl Parse for DynamicDsl {
+    #[throws(Error)]
+    fn parse(input: ParseStream) -> Self {
+        let ident = input.parse::<Ident>()?;
+        match Dynamic::from_ident(ident.to_string().as_str()) {
+            Some(dynamic) => DynamicDsl { dynamic },
+            None => throw!(Error::new(ident.span(), "Invalid Dynamic")),
+        }
+    }
+}
+
+impl ToTokens for DynamicDsl {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let dynamic_ident = self.dynamic.to_ident();
+        tokens.extend(quote! {
+            Dynamic::from_ident(#dynamic_ident).unwrap_or_default()
+        });
+    }
+}
+
+impl DynamicDsl {
+    pub fn to_proto(&self) -> Dynamic {
+        self.dynamic
+    }
+}
+
+pub struct DynamicCurveDsl {
+    pub kind: DynamicCurveKind,
+    pub beats: u8,
+    pub target: Option<Dynamic>,
+}
+
+impl DynamicCurveDsl {
+    /// The kind ident is already parsed, the target dynamic is optional.
+    #[throws(Error)]
+    pub fn parse_with_kind(kind: DynamicCurveKind, input: ParseStream) -> Self {
+        let beats = input.parse::<LitInt>()?.base10_parse::<u8>()?;
+        let target = if input.peek(Ident) {
+            let fork = input.fork();
+            let ident = fork.parse::<Ident>()?.to_string();
+            let target = Dynamic::from_ident(ident.as_str());
+            if target.is_some() {
+                input.parse::<Ident>()?;
+            }
+            target
+        } else {
+            None
+        };
+        DynamicCurveDsl {
+            kind,
+            beats,
+            target,
+        }
+    }
+}
+
+impl ToTokens for DynamicCurveDsl {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let DynamicCurveDsl {
+            kind,
+            beats,
+            target,
+        } = self;
+        let kind_ident = kind.to_ident();
+        let target_ident = target.map(|x| x.to_ident()).unwrap_or("");
+        tokens.extend(quote! {
+            DynamicCurve::new(
+                DynamicCurveKind::from_ident(#kind_ident),
+                #beats,
+                Dynamic::from_ident(#target_ident),
+            )
+        });
+    }
+}
+
+impl DynamicCurveDsl {
+    pub fn to_proto(&self) -> DynamicCurve {
+        DynamicCurve::new(self.kind, self.beats, self.target)
+    }
+}
+
+/// `>` for accent, `!` for marcato and `?` for ghost, in front of the entry.
+pub struct AccentDsl {
+    pub accent: Accent,
+}
+
+impl Parse for AccentDsl {
+    #[throws(Error)]
+    fn parse(input: ParseStream) -> Self {
+        let accent = if input.peek(Token![>]) {
+            input.parse::<Token![>]>()?;
+            Accent::Accent
+        } else if input.peek(Token![!]) {
+            input.parse::<Token![!]>()?;
+            Accent::Marcato
+        } else if input.peek(Token![?]) {
+            input.parse::<Token![?]>()?;
+            Accent::Ghost
+        } else {
+            throw!(Error::new(input.span(), "Invalid Accent"))
+        };
+        AccentDsl { accent }
+    }
+}
+
+impl AccentDsl {
+    pub fn peek(input: ParseStream) -> bool {
+        input.peek(Token![>]) || input.peek(Token![!]) || input.peek(Token![?])
+    }
+    #[throws(Error)]
+    pub fn try_parse(input: ParseStream) -> Option<Self> {
+        if Self::peek(input) {
+            Some(Self::parse(input)?)
+        } else {
+            None
+        }
+    }
+}
+
+impl ToTokens for AccentDsl {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let accent_ident = self.accent.to_ident();
+        tokens.extend(quote! {
+            ProtoEntry::from(CoreEntry::from(Accent::from_ident(#accent_ident)))
+        });
+    }
+}
+
+impl AccentDsl {
+    pub fn to_proto(&self) -> ProtoEntry {
+        ProtoEntry::from(CoreEntry::from(self.accent))
+    }
+}
@@ -1,13 +1,17 @@
 pub mod chord;
+pub mod drum;
 pub mod duration;
+pub mod dynamics;
 pub mod empty;
 pub mod interval;
 pub mod note;
 pub mod octave;
+pub mod ornament;
 pub mod pitch_name;
 pub mod pitch_sign;
 pub mod signature;
 pub mod syllable;
 pub mod tempo;
+pub mod tempo_curve;
 pub mod tone;
 pub mod word;
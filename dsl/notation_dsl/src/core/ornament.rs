@@ -0,0 +1,76 @@
+use fehler::{throw, throws};
+
+use notation_proto::prelude::{CoreEntry, Ornament, ProtoEntry, Semitones};
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::parse::{Error, Parse, ParseStream};
+use syn::{Ident, LitInt, Token};
+
+/// `&Grace -2`, `&Appo 1`, `&Mordent`, `&LowerMordent` or `&Turn` in front of the entry,
+/// grace notes and appoggiaturas are in semitones from the main note.
+pub struct OrnamentDsl {
+    pub ornament: Ornament,
+}
+
+impl OrnamentDsl {
+    #[throws(Error)]
+    fn parse_semitones(input: ParseStream) -> Semitones {
+        let negative = input.peek(Token![-]);
+        if negative {
+            input.parse::<Token![-]>()?;
+        }
+        let value = input.parse::<LitInt>()?.base10_parse::<i8>()?;
+        Semitones(if negative { -value } else { value })
+    }
+}
+
+impl Parse for OrnamentDsl {
+    #[throws(Error)]
+    fn parse(input: ParseStream) -> Self {
+        input.parse::<Token![&]>()?;
+        let ornament = match input.parse::<Ident>()?.to_string().as_str() {
+            "Grace" => Ornament::Grace(Self::parse_semitones(input)?),
+            "Appo" | "Appoggiatura" => Ornament::Appoggiatura(Self::parse_semitones(input)?),
+            "Mordent" => Ornament::Mordent,
+            "LowerMordent" => Ornament::LowerMordent,
+            "Turn" => Ornament::Turn,
+            _ => throw!(Error::new(input.span(), "Invalid Ornament")),
+        };
+        OrnamentDsl { ornament }
+    }
+}
+
+impl OrnamentDsl {
+    pub fn peek(input: ParseStream) -> bool {
+        input.peek(Token![&])
+    }
+    #[throws(Error)]
+    pub fn try_parse(input: ParseStream) -> Option<Self> {
+        if Self::peek(input) {
+            Some(Self::parse(input)?)
+        } else {
+            None
+        }
+    }
+}
+
+impl ToTokens for OrnamentDsl {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let ornament_quote = match self.ornament {
+            Ornament::Grace(Semitones(x)) => quote! { Ornament::Grace(Semitones(#x)) },
+            Ornament::Appoggiatura(Semitones(x)) => quote! { Ornament::Appoggiatura(Semitones(#x)) },
+            Ornament::Mordent => quote! { Ornament::Mordent },
+            Ornament::LowerMordent => quote! { Ornament::LowerMordent },
+            Ornament::Turn => quote! { Ornament::Turn },
+        };
+        tokens.extend(quote! {
+            ProtoEntry::from(CoreEntry::from(#ornament_quote))
+        });
+    }
+}
+
+impl OrnamentDsl {
+    pub fn to_proto(&self) -> ProtoEntry {
+        ProtoEntry::from(CoreEntry::from(self.ornament))
+    }
+}
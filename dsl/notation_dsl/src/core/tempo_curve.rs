@@ -0,0 +1,51 @@
+use fehler::throws;
+
+use notation_proto::prelude::{TempoCurve, TempoCurveKind};
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::parse::{Error, ParseStream};
+use syn::LitInt;
+
+pub struct TempoCurveDsl {
+    pub kind: TempoCurveKind,
+    pub beats: u8,
+    pub percent: u8,
+}
+
+impl TempoCurveDsl {
+    /// The kind ident is already parsed, the percent is optional.
+    #[throws(Error)]
+    pub fn parse_with_kind(kind: TempoCurveKind, input: ParseStream) -> Self {
+        let beats = input.parse::<LitInt>()?.base10_parse::<u8>()?;
+        let percent = if input.peek(LitInt) {
+            input.parse::<LitInt>()?.base10_parse::<u8>()?
+        } else {
+            kind.default_percent()
+        };
+        TempoCurveDsl {
+            kind,
+            beats,
+            percent,
+        }
+    }
+}
+
+impl ToTokens for TempoCurveDsl {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let TempoCurveDsl {
+            kind,
+            beats,
+            percent,
+        } = self;
+        let kind_ident = kind.to_ident();
+        tokens.extend(quote! {
+            TempoCurve::new(TempoCurveKind::from_ident(#kind_ident), #beats, #percent)
+        });
+    }
+}
+
+impl TempoCurveDsl {
+    pub fn to_proto(&self) -> TempoCurve {
+        TempoCurve::new(self.kind, self.beats, self.percent)
+    }
+}
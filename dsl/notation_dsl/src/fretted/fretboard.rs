@@ -1,7 +1,7 @@
-use fehler::throws;
+use fehler::{throw, throws};
 use notation_proto::prelude::{
-    Fretboard4, Fretboard6, FrettedEntry4, FrettedEntry6, GuitarTuning,
-    GUITAR_FRET_NUM_ACOUSTIC, Pitch, Octave,
+    BassTuning, Fretboard4, Fretboard6, FrettedEntry4, FrettedEntry6, GuitarTuning,
+    BASS_FRET_NUM_STANDARD, GUITAR_FRET_NUM_ACOUSTIC,
 };
 use notation_proto::proto_entry::ProtoEntry;
 use proc_macro2::TokenStream;
@@ -33,7 +33,15 @@ impl Parse for FretboardDsl {
             if input.peek(kw::tuning) {
                 input.parse::<kw::tuning>()?;
                 input.parse::<Token![:]>()?;
-                tuning = Some(input.parse::<Ident>()?);
+                let ident = input.parse::<Ident>()?;
+                // Only 4-string bass fretboards exist, so the low B would be lost.
+                if BassTuning::from_ident(ident.to_string().as_str()).string_num() > 4 {
+                    throw!(Error::new(
+                        ident.span(),
+                        format!("{} tuning is not supported on a 4-string track", ident),
+                    ));
+                }
+                tuning = Some(ident);
             } else if input.peek(kw::fret_num) {
                 input.parse::<kw::fret_num>()?;
                 input.parse::<Token![:]>()?;
@@ -63,7 +71,7 @@ impl ToTokens for FretboardDsl {
         } = self;
         let string_num = Context::fretted().string_num;
         let fret_num = fret_num.unwrap_or(match string_num {
-            6 => GUITAR_FRET_NUM_ACOUSTIC,
+            4 => BASS_FRET_NUM_STANDARD,
             _ => GUITAR_FRET_NUM_ACOUSTIC,
         });
         let capo = capo.unwrap_or(0);
@@ -71,13 +79,17 @@ impl ToTokens for FretboardDsl {
             Some(tuning) => {
                 let tuning_quote = tuning.to_string();
                 match string_num {
+                    4 => quote! { BassTuning::from_ident(#tuning_quote)},
                     _ => quote! { GuitarTuning::from_ident(#tuning_quote)},
                 }
             }
             None => match string_num {
+                4 => quote! { BassTuning::Standard },
                 _ => quote! { GuitarTuning::Standard },
             },
         };
+        // Shapes written as chords need the fretboard in context.
+        self.set_context();
         let fretted_entry_quote = Context::fretted().fretted_entry_quote();
         let fretboard_quote = Context::fretted().fretboard_quote();
         tokens.extend(quote! {
@@ -89,7 +101,7 @@ impl ToTokens for FretboardDsl {
 }
 
 impl FretboardDsl {
-    pub fn to_proto(&self) -> ProtoEntry {
+    pub fn set_context(&self) {
         let FretboardDsl {
             tuning,
             fret_num,
@@ -97,26 +109,33 @@ impl FretboardDsl {
         } = self;
         let string_num = Context::fretted().string_num;
         let fret_num = fret_num.unwrap_or(match string_num {
-            6 => GUITAR_FRET_NUM_ACOUSTIC,
+            4 => BASS_FRET_NUM_STANDARD,
             _ => GUITAR_FRET_NUM_ACOUSTIC,
         });
         let capo = capo.unwrap_or(0);
         match string_num {
             4 => {
-                let tuning = [(Pitch::E, Octave::P2).into(); 4]; //todo;
-                ProtoEntry::from(FrettedEntry4::from(Fretboard4::new(fret_num, tuning, capo)))
+                let tuning = match tuning {
+                    Some(ident) => BassTuning::from_ident(ident.to_string().as_str()),
+                    None => BassTuning::Standard,
+                };
+                Context::set_fretboard4(Fretboard4::new(fret_num, tuning.into(), capo));
             }
             _ => {
                 let tuning = match tuning {
                     Some(ident) => GuitarTuning::from_ident(ident.to_string().as_str()),
                     None => GuitarTuning::Standard,
                 };
-                ProtoEntry::from(FrettedEntry6::from(Fretboard6::new(
-                    fret_num,
-                    tuning.into(),
-                    capo,
-                )))
+                Context::set_fretboard6(Fretboard6::new(fret_num, tuning.into(), capo));
             }
         }
     }
+    pub fn to_proto(&self) -> ProtoEntry {
+        self.set_context();
+        let fretted = Context::fretted();
+        match fretted.string_num {
+            4 => ProtoEntry::from(FrettedEntry4::from(fretted.fretboard4)),
+            _ => ProtoEntry::from(FrettedEntry6::from(fretted.fretboard6)),
+        }
+    }
 }
@@ -1,13 +1,14 @@
-use fehler::throws;
-use notation_proto::prelude::PickNote;
+use fehler::{throw, throws};
+use notation_proto::prelude::{PickNote, PickTechnique};
 use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
 use syn::parse::{Error, Parse, ParseStream};
-use syn::{LitInt, Token};
+use syn::{Ident, LitInt, Token};
 
 pub struct PickNoteDsl {
     pub string: u8,
     pub fret: Option<u8>,
+    pub technique: Option<PickTechnique>,
 }
 
 impl Parse for PickNoteDsl {
@@ -20,22 +21,46 @@ impl Parse for PickNoteDsl {
         } else {
             None
         };
-        PickNoteDsl { string, fret }
+        let technique = if input.peek(Token![~]) {
+            input.parse::<Token![~]>()?;
+            let ident = input.parse::<Ident>()?;
+            match PickTechnique::from_ident(ident.to_string().as_str()) {
+                Some(technique) => Some(technique),
+                None => throw!(Error::new(ident.span(), "Invalid PickTechnique")),
+            }
+        } else {
+            None
+        };
+        PickNoteDsl {
+            string,
+            fret,
+            technique,
+        }
     }
 }
 
 impl ToTokens for PickNoteDsl {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        let PickNoteDsl { string, fret } = self;
+        let PickNoteDsl {
+            string,
+            fret,
+            technique,
+        } = self;
         let fret_quote = fret.map(|f| quote! { Some(#f) }).unwrap_or(quote! {None});
+        let technique_quote = technique
+            .map(|x| {
+                let ident = x.to_ident();
+                quote! { PickTechnique::from_ident(#ident) }
+            })
+            .unwrap_or(quote! {None});
         tokens.extend(quote! {
-            PickNote::new(#string, #fret_quote, None, None, None)
+            PickNote::new(#string, #fret_quote, None, None, None, #technique_quote)
         });
     }
 }
 
 impl PickNoteDsl {
     pub fn to_proto(&self) -> PickNote {
-        PickNote::new(self.string, self.fret, None, None, None)
+        PickNote::new(self.string, self.fret, None, None, None, self.technique)
     }
 }
@@ -1,17 +1,23 @@
 use fehler::throws;
-use notation_proto::prelude::{FrettedEntry4, FrettedEntry6, HandShape4, HandShape6};
+use notation_proto::prelude::{Finger, FrettedEntry4, FrettedEntry6, HandShape4, HandShape6};
 use notation_proto::proto_entry::ProtoEntry;
-use proc_macro2::TokenStream;
+use proc_macro2::{Span, TokenStream};
 use quote::{quote, ToTokens};
 use syn::parse::{Error, Parse, ParseStream};
-use syn::{parenthesized, token, LitInt, Token};
+use syn::{parenthesized, token, Ident, LitInt, LitStr, Token};
 
 use crate::context::Context;
+use crate::core::chord::ChordDsl;
 use crate::core::duration::DurationTweakDsl;
 
+/// Barre, frets and fingers of a shape.
+type ShapeValue = (u8, Vec<Option<u8>>, Vec<Option<Finger>>);
+
 pub struct ShapeDsl {
     pub barre: Option<u8>,
     pub frets: Vec<Option<u8>>,
+    /// Shape written as a chord, the best voicing is picked on current fretboard.
+    pub chord: Option<ChordDsl>,
     pub duration_tweak: Option<DurationTweakDsl>,
 }
 
@@ -19,9 +25,13 @@ impl Parse for ShapeDsl {
     #[throws(Error)]
     fn parse(input: ParseStream) -> Self {
         let mut frets = vec![];
+        let mut chord = None;
         if input.peek(token::Paren) {
             let content;
             parenthesized!(content in input);
+            if content.peek(LitStr) || content.peek2(Token![:]) {
+                chord = Some(ChordDsl::parse_without_paren(&content, false, true)?);
+            }
             while content.peek(LitInt) || content.peek(Token![_]) {
                 if content.peek(LitInt) {
                     frets.push(Some(content.parse::<LitInt>()?.base10_parse::<u8>()?));
@@ -42,18 +52,55 @@ impl Parse for ShapeDsl {
         ShapeDsl {
             barre,
             frets,
+            chord,
             duration_tweak,
         }
     }
 }
 
+impl ShapeDsl {
+    fn calc_voicing(&self) -> Option<ShapeValue> {
+        let chord = self.chord.as_ref()?.to_chord()?;
+        let key = Context::key();
+        let scale = Context::scale();
+        let fretted = Context::fretted();
+        let voicing = match fretted.string_num {
+            4 => fretted
+                .fretboard4
+                .calc_best_voicing(&scale, &key, &chord)
+                .map(|x| (x.barre(), x.frets.to_vec(), x.fingers.to_vec())),
+            _ => fretted
+                .fretboard6
+                .calc_best_voicing(&scale, &key, &chord)
+                .map(|x| (x.barre(), x.frets.to_vec(), x.fingers.to_vec())),
+        };
+        if voicing.is_none() {
+            println!(
+                "ShapeDsl::calc_voicing(), no voicing found: {} x {}",
+                chord, fretted.string_num
+            );
+        }
+        voicing
+    }
+    /// Chords without voicing got all strings muted.
+    fn calc_shape(&self) -> ShapeValue {
+        let string_num = Context::fretted().string_num;
+        if self.chord.is_some() {
+            self.calc_voicing()
+                .unwrap_or_else(|| (0, vec![None; string_num], vec![None; string_num]))
+        } else {
+            (
+                self.barre.unwrap_or(0),
+                self.frets.clone(),
+                vec![None; self.frets.len()],
+            )
+        }
+    }
+}
+
 impl ToTokens for ShapeDsl {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        let ShapeDsl {
-            barre,
-            frets,
-            duration_tweak,
-        } = self;
+        let (barre, frets, fingers) = self.calc_shape();
         //let string_num = Context::fretted().string_num;
         let mut frets_quote: Vec<TokenStream> = vec![];
         let mut fingers_quote: Vec<TokenStream> = vec![];
@@ -62,12 +109,19 @@ impl ToTokens for ShapeDsl {
                 Some(fret) => quote! { Some(#fret) },
                 None => quote! { None },
             });
-            fingers_quote.push(quote! { None });
         }
-        let duration_quote = Context::duration_quote(duration_tweak);
+        for finger in fingers {
+            fingers_quote.push(match finger {
+                Some(finger) => {
+                    let finger_ident = Ident::new(&format!("{:?}", finger), Span::call_site());
+                    quote! { Some(Finger::#finger_ident) }
+                }
+                None => quote! { None },
+            });
+        }
+        let duration_quote = Context::duration_quote(&self.duration_tweak);
         let fretted_entry_quote = Context::fretted().fretted_entry_quote();
         let hand_shape_quote = Context::fretted().hand_shape_quote();
-        let barre = barre.unwrap_or(0);
         tokens.extend(quote! {
             ProtoEntry::from(#fretted_entry_quote::from(
                 (#hand_shape_quote::new_barre(
@@ -83,33 +137,26 @@ impl ToTokens for ShapeDsl {
 
 impl ShapeDsl {
     pub fn to_proto(&self) -> ProtoEntry {
-        let ShapeDsl {
-            barre,
-            frets: _,
-            duration_tweak,
-        } = self;
-        let barre = barre.unwrap_or(0);
-        let duration = Context::tweaked_duration(duration_tweak);
+        let (barre, frets, fingers) = self.calc_shape();
+        let duration = Context::tweaked_duration(&self.duration_tweak);
         match Context::fretted().string_num {
             4 => {
-                let mut frets = [None; 4];
-                for i in 0..4 {
-                    frets[i] = self.frets.get(i).unwrap().clone();
-                }
-                let fingers = [None; 4];
+                let mut shape_frets = [None; 4];
+                let mut shape_fingers = [None; 4];
+                shape_frets.copy_from_slice(&frets[0..4]);
+                shape_fingers.copy_from_slice(&fingers[0..4]);
                 ProtoEntry::from(FrettedEntry4::from((
-                    HandShape4::new_barre(barre, frets, fingers),
+                    HandShape4::new_barre(barre, shape_frets, shape_fingers),
                     duration,
                 )))
             }
             _ => {
-                let mut frets = [None; 6];
-                for i in 0..6 {
-                    frets[i] = self.frets.get(i).unwrap().clone();
-                }
-                let fingers = [None; 6];
+                let mut shape_frets = [None; 6];
+                let mut shape_fingers = [None; 6];
+                shape_frets.copy_from_slice(&frets[0..6]);
+                shape_fingers.copy_from_slice(&fingers[0..6]);
                 ProtoEntry::from(FrettedEntry6::from((
-                    HandShape6::new_barre(barre, frets, fingers),
+                    HandShape6::new_barre(barre, shape_frets, shape_fingers),
                     duration,
                 )))
             }
@@ -1,4 +1,5 @@
 use fehler::{throw, throws};
+use notation_proto::prelude::{DynamicCurveKind, MetaEntry, TempoCurveKind};
 use notation_proto::proto_entry::ProtoEntry;
 use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
@@ -8,6 +9,12 @@ use syn::Ident;
 use crate::context::ContextDsl;
 
 use crate::core::chord::ChordDsl;
+use crate::core::drum::DrumDsl;
+use crate::core::dynamics::{AccentDsl, DynamicCurveDsl, DynamicDsl};
+use crate::core::ornament::OrnamentDsl;
+use crate::core::signature::SignatureDsl;
+use crate::core::tempo::TempoDsl;
+use crate::core::tempo_curve::TempoCurveDsl;
 use crate::core::tone::ToneDsl;
 use crate::core::word::WordDsl;
 use crate::fretted::fretboard::FretboardDsl;
@@ -16,7 +23,7 @@ use crate::fretted::shape::ShapeDsl;
 use crate::proto::mark::MarkDsl;
 
 pub struct MultibleDsl<T> {
-    pub items: Vec<T>,
+    pub items: Vec<(Option<AccentDsl>, Option<OrnamentDsl>, T)>,
 }
 
 pub enum EntryDsl {
@@ -26,8 +33,14 @@ pub enum EntryDsl {
     Chord(MultibleDsl<ChordDsl>),
     Word(MultibleDsl<WordDsl>),
     Pick(MultibleDsl<PickDsl>),
+    Drum(MultibleDsl<DrumDsl>),
     Shape(ShapeDsl),
     Fretboard(FretboardDsl),
+    Signature(SignatureDsl),
+    Tempo(TempoDsl),
+    TempoCurve(TempoCurveDsl),
+    Dynamic(DynamicDsl),
+    DynamicCurve(DynamicCurveDsl),
 }
 
 impl EntryDsl {
@@ -38,13 +51,24 @@ impl EntryDsl {
         } else if MarkDsl::peek(input) {
             Self::Mark(input.parse()?)
         } else {
-            match input.parse::<Ident>()?.to_string().as_str() {
+            let ident = input.parse::<Ident>()?.to_string();
+            match ident.as_str() {
                 "Tone" => Self::Tone(input.parse()?),
                 "Chord" => Self::Chord(input.parse()?),
                 "Word" => Self::Word(input.parse()?),
                 "Pick" => Self::Pick(input.parse()?),
+                "Drum" => Self::Drum(input.parse()?),
                 "Shape" => Self::Shape(input.parse()?),
                 "Fretboard" => Self::Fretboard(input.parse()?),
+                "Signature" => Self::Signature(input.parse()?),
+                "Tempo" => Self::Tempo(input.parse()?),
+                "Rit" | "Ritardando" | "Accel" | "Accelerando" | "Fermata" => Self::TempoCurve(
+                    TempoCurveDsl::parse_with_kind(TempoCurveKind::from_ident(&ident), input)?,
+                ),
+                "Dynamic" => Self::Dynamic(input.parse()?),
+                "Cresc" | "Crescendo" | "Dim" | "Diminuendo" => Self::DynamicCurve(
+                    DynamicCurveDsl::parse_with_kind(DynamicCurveKind::from_ident(&ident), input)?,
+                ),
                 _ => throw!(Error::new(input.span(), "Invalid Entry")),
             }
         }
@@ -60,8 +84,14 @@ impl ToTokens for EntryDsl {
             Self::Chord(x) => quote! { #x },
             Self::Word(x) => quote! { #x },
             Self::Pick(x) => quote! { #x },
+            Self::Drum(x) => quote! { #x },
             Self::Shape(x) => quote! { #x },
             Self::Fretboard(x) => quote! { #x },
+            Self::Signature(x) => quote! { ProtoEntry::from(MetaEntry::from(#x)) },
+            Self::Tempo(x) => quote! { ProtoEntry::from(MetaEntry::from(#x)) },
+            Self::TempoCurve(x) => quote! { ProtoEntry::from(MetaEntry::from(#x)) },
+            Self::Dynamic(x) => quote! { ProtoEntry::from(MetaEntry::from(#x)) },
+            Self::DynamicCurve(x) => quote! { ProtoEntry::from(MetaEntry::from(#x)) },
         });
     }
 }
@@ -74,8 +104,14 @@ impl EntryDsl {
             EntryDsl::Chord(x) => x.add_proto(entries),
             EntryDsl::Word(x) => x.add_proto(entries),
             EntryDsl::Pick(x) => x.add_proto(entries),
+            EntryDsl::Drum(x) => x.add_proto(entries),
             EntryDsl::Shape(x) => entries.push(x.to_proto()),
             EntryDsl::Fretboard(x) => entries.push(x.to_proto()),
+            EntryDsl::Signature(x) => entries.push(MetaEntry::from(x.to_proto()).into()),
+            EntryDsl::Tempo(x) => entries.push(MetaEntry::from(x.to_proto()).into()),
+            EntryDsl::TempoCurve(x) => entries.push(MetaEntry::from(x.to_proto()).into()),
+            EntryDsl::Dynamic(x) => entries.push(MetaEntry::from(x.to_proto()).into()),
+            EntryDsl::DynamicCurve(x) => entries.push(MetaEntry::from(x.to_proto()).into()),
         }
     }
 }
@@ -1,42 +1,196 @@
-use fehler::throws;
+use fehler::{throw, throws};
 
-use notation_proto::prelude::Form;
+use notation_proto::prelude::{Form, FormJumpEnd, FormMark, FormRepeat, FormSign, FormVolta};
 use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
 use syn::parse::{Error, Parse, ParseStream};
+use syn::{bracketed, parenthesized, token, Ident, LitInt, Token};
 
 use super::id::IdDsl;
 
+/// `|: a [1] b [2] c :| 2` for repeats with endings, `@Segno` and `@Coda` in front of a section,
+/// `@ToCoda`, `@Fine`, `@DC` and `@DS` (with optional `(AlFine)` or `(AlCoda)`) after it.
 pub struct FormDsl {
     pub sections: Vec<IdDsl>,
+    pub repeats: Vec<FormRepeat>,
+    pub marks: Vec<FormMark>,
+}
+
+impl FormDsl {
+    #[throws(Error)]
+    fn parse_jump_end(input: ParseStream) -> FormJumpEnd {
+        if input.peek(token::Paren) {
+            let content;
+            parenthesized!(content in input);
+            match content.parse::<Ident>()?.to_string().as_str() {
+                "AlFine" => FormJumpEnd::Fine,
+                "AlCoda" => FormJumpEnd::Coda,
+                _ => throw!(Error::new(content.span(), "Invalid Jump End")),
+            }
+        } else {
+            FormJumpEnd::End
+        }
+    }
+    /// Signs are behind `@`, so sections can still be named like them.
+    #[throws(Error)]
+    fn parse_sign(input: ParseStream) -> FormSign {
+        input.parse::<Token![@]>()?;
+        let ident = input.parse::<Ident>()?;
+        match ident.to_string().as_str() {
+            "Segno" => FormSign::Segno,
+            "Coda" => FormSign::Coda,
+            "ToCoda" => FormSign::ToCoda,
+            "Fine" => FormSign::Fine,
+            "DC" => FormSign::DaCapo(Self::parse_jump_end(input)?),
+            "DS" => FormSign::DalSegno(Self::parse_jump_end(input)?),
+            _ => throw!(Error::new(ident.span(), "Invalid Sign")),
+        }
+    }
 }
 
 impl Parse for FormDsl {
     #[throws(Error)]
     fn parse(input: ParseStream) -> Self {
         let mut sections = Vec::new();
-        while IdDsl::peek(input) {
-            sections.push(input.parse()?);
+        let mut repeats = Vec::new();
+        let mut marks = Vec::new();
+        let mut open_repeats: Vec<(usize, Vec<FormVolta>)> = Vec::new();
+        let mut start_signs = Vec::new();
+        let mut volta_rounds: Option<Vec<usize>> = None;
+        loop {
+            if input.peek(Token![|]) && input.peek2(Token![:]) {
+                input.parse::<Token![|]>()?;
+                input.parse::<Token![:]>()?;
+                open_repeats.push((sections.len(), Vec::new()));
+            } else if input.peek(Token![:]) && input.peek2(Token![|]) {
+                input.parse::<Token![:]>()?;
+                input.parse::<Token![|]>()?;
+                let times = if input.peek(LitInt) {
+                    input.parse::<LitInt>()?.base10_parse::<usize>()?
+                } else {
+                    2
+                };
+                match open_repeats.pop() {
+                    Some((begin, voltas)) if begin < sections.len() => {
+                        repeats.push(FormRepeat::new(begin, sections.len() - 1, times, voltas));
+                    }
+                    _ => throw!(Error::new(input.span(), "Invalid Repeat")),
+                }
+            } else if input.peek(syn::token::Bracket) {
+                let content;
+                bracketed!(content in input);
+                let mut rounds = Vec::new();
+                while content.peek(LitInt) {
+                    rounds.push(content.parse::<LitInt>()?.base10_parse::<usize>()?);
+                }
+                volta_rounds = Some(rounds);
+            } else if input.peek(Token![@]) {
+                let sign = Self::parse_sign(input)?;
+                if sign.is_at_start() {
+                    start_signs.push(sign);
+                } else if sections.is_empty() {
+                    throw!(Error::new(input.span(), "Invalid Sign"));
+                } else {
+                    marks.push(FormMark::new(sections.len() - 1, sign));
+                }
+            } else if IdDsl::peek(input) {
+                let index = sections.len();
+                sections.push(input.parse::<IdDsl>()?);
+                for sign in start_signs.drain(..) {
+                    marks.push(FormMark::new(index, sign));
+                }
+                if let Some(rounds) = volta_rounds.take() {
+                    match open_repeats.last_mut() {
+                        Some((_, voltas)) => voltas.push(FormVolta::new(index, rounds)),
+                        None => throw!(Error::new(input.span(), "Volta Outside Repeat")),
+                    }
+                }
+            } else {
+                break;
+            }
+        }
+        if !open_repeats.is_empty() {
+            throw!(Error::new(input.span(), "Repeat Not Closed"));
+        }
+        FormDsl {
+            sections,
+            repeats,
+            marks,
         }
-        FormDsl { sections }
     }
 }
+
+fn quote_jump_end(end: &FormJumpEnd) -> TokenStream {
+    match end {
+        FormJumpEnd::End => quote! { FormJumpEnd::End },
+        FormJumpEnd::Fine => quote! { FormJumpEnd::Fine },
+        FormJumpEnd::Coda => quote! { FormJumpEnd::Coda },
+    }
+}
+
+fn quote_sign(sign: &FormSign) -> TokenStream {
+    match sign {
+        FormSign::Segno => quote! { FormSign::Segno },
+        FormSign::Coda => quote! { FormSign::Coda },
+        FormSign::ToCoda => quote! { FormSign::ToCoda },
+        FormSign::Fine => quote! { FormSign::Fine },
+        FormSign::DaCapo(end) => {
+            let end_quote = quote_jump_end(end);
+            quote! { FormSign::DaCapo(#end_quote) }
+        }
+        FormSign::DalSegno(end) => {
+            let end_quote = quote_jump_end(end);
+            quote! { FormSign::DalSegno(#end_quote) }
+        }
+    }
+}
+
 impl ToTokens for FormDsl {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        let FormDsl { sections } = self;
+        let FormDsl {
+            sections,
+            repeats,
+            marks,
+        } = self;
+        let repeats_quote = repeats.iter().map(|x| {
+            let FormRepeat {
+                begin,
+                end,
+                times,
+                voltas,
+            } = x;
+            let voltas_quote = voltas.iter().map(|v| {
+                let FormVolta { index, rounds } = v;
+                quote! {
+                    FormVolta::new(#index, vec![#(#rounds),*])
+                }
+            });
+            quote! {
+                FormRepeat::new(#begin, #end, #times, vec![#(#voltas_quote),*])
+            }
+        });
+        let marks_quote = marks.iter().map(|x| {
+            let index = x.index;
+            let sign_quote = quote_sign(&x.sign);
+            quote! {
+                FormMark::new(#index, #sign_quote)
+            }
+        });
         tokens.extend(quote! {
-            Form::from(vec![
-                #(#sections),*
-            ])
+            Form::new(
+                vec![#(#sections),*].into_iter().map(|x: &str| x.to_string()).collect(),
+                vec![#(#repeats_quote),*],
+                vec![#(#marks_quote),*],
+            )
         });
     }
 }
 impl FormDsl {
     pub fn to_proto(&self) -> Form {
-        self.sections
-            .iter()
-            .map(|x| x.id.clone())
-            .collect::<Vec<String>>()
-            .into()
+        Form::new(
+            self.sections.iter().map(|x| x.id.clone()).collect(),
+            self.repeats.clone(),
+            self.marks.clone(),
+        )
     }
 }
@@ -1,11 +1,12 @@
 use fehler::throws;
 
-use notation_proto::prelude::{Track, TrackKind};
+use notation_proto::prelude::{Track, TrackKind, BASS_STRING_NUM, GUITAR_STRING_NUM};
 use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
 use syn::parse::{Error, ParseStream};
 use syn::Ident;
 
+use crate::context::{Context, FrettedContext};
 use crate::proto::entry::EntryDsl;
 
 use super::id::IdDsl;
@@ -24,10 +25,25 @@ impl TrackDsl {
         let entries = EntryDsl::parse_vec(input)?;
         TrackDsl { id, kind, entries }
     }
+    /// Fretted tracks start with their instrument's string number and default fretboard.
+    fn init_context(&self) {
+        match TrackKind::from_ident(self.kind.to_string().as_str()) {
+            TrackKind::Guitar => {
+                Context::set_fretted(FrettedContext::default());
+                Context::set_string_num(GUITAR_STRING_NUM);
+            }
+            TrackKind::Bass => {
+                Context::set_fretted(FrettedContext::default());
+                Context::set_string_num(BASS_STRING_NUM);
+            }
+            _ => (),
+        }
+    }
 }
 
 impl ToTokens for TrackDsl {
     fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.init_context();
         let TrackDsl { id, kind, entries } = self;
         let kind_quote = kind.to_string();
         let entries_quote = EntryDsl::quote_vec(entries);
@@ -39,6 +55,7 @@ impl ToTokens for TrackDsl {
 
 impl TrackDsl {
     pub fn to_proto(&self) -> Track {
+        self.init_context();
         let mut entries = Vec::new();
         for entry in self.entries.iter() {
             entry.add_proto(&mut entries);
@@ -7,6 +7,9 @@ use syn::{braced, bracketed, parenthesized, token};
 use notation_proto::prelude::ProtoEntry;
 
 use crate::core::chord::ChordDsl;
+use crate::core::dynamics::AccentDsl;
+use crate::core::drum::DrumDsl;
+use crate::core::ornament::OrnamentDsl;
 use crate::core::tone::ToneDsl;
 use crate::core::word::WordDsl;
 use crate::fretted::pick::PickDsl;
@@ -83,21 +86,35 @@ macro_rules! impl_multible_dsl {
                     Self::parse_without_paren(input, multied, false)
                 }
             }
+            /// Each item can be marked with an accent and an ornament in front of it.
             #[throws(Error)]
-            pub fn parse_vec(input: ParseStream) -> Vec<$dsl_type> {
+            pub fn parse_vec(
+                input: ParseStream,
+            ) -> Vec<(Option<AccentDsl>, Option<OrnamentDsl>, $dsl_type)> {
                 let mut result = vec![];
                 if input.peek(token::Bracket) {
                     let content;
                     bracketed!(content in *input);
                     while !content.is_empty() {
-                        result.push(Self::parse_multible(&content, true)?);
+                        let accent = AccentDsl::try_parse(&content)?;
+                        let ornament = OrnamentDsl::try_parse(&content)?;
+                        result.push((accent, ornament, Self::parse_multible(&content, true)?));
                     }
                 }
                 result
             }
+            fn quote_item(
+                accent: &Option<AccentDsl>,
+                ornament: &Option<OrnamentDsl>,
+                item: &$dsl_type,
+            ) -> TokenStream {
+                let accent_quote = accent.as_ref().map(|x| quote! { #x, });
+                let ornament_quote = ornament.as_ref().map(|x| quote! { #x, });
+                quote! { #accent_quote #ornament_quote #item }
+            }
             pub fn quote_multible(v: &MultibleDsl<$dsl_type>) -> TokenStream {
                 let item_quotes: Vec<TokenStream> = v.items.iter().map(
-                    |x| quote! { #x }
+                    |(accent, ornament, x)| Self::quote_item(accent, ornament, x)
                 ).collect();
                 quote! {
                     vec![
@@ -112,8 +129,10 @@ macro_rules! impl_multible_dsl {
                     if input.peek(token::Bracket) {
                         $dsl_type::parse_vec(input)?
                     } else {
+                        let accent = AccentDsl::try_parse(input)?;
+                        let ornament = OrnamentDsl::try_parse(input)?;
                         vec![
-                            $dsl_type::parse(input)?
+                            (accent, ornament, $dsl_type::parse(input)?)
                         ]
                     };
                 Ok(Self { items } )
@@ -122,7 +141,7 @@ macro_rules! impl_multible_dsl {
         impl ToTokens for MultibleDsl<$dsl_type> {
             fn to_tokens(&self, tokens: &mut TokenStream) {
                 let item_quotes: Vec<TokenStream> = self.items.iter().map(
-                    |x| quote! { #x }
+                    |(accent, ornament, x)| $dsl_type::quote_item(accent, ornament, x)
                 ).collect();
                 tokens.extend(quote! {
                     #(#item_quotes),*
@@ -131,7 +150,13 @@ macro_rules! impl_multible_dsl {
         }
         impl MultibleDsl<$dsl_type> {
             pub fn add_proto(&self, entries: &mut Vec<ProtoEntry>) {
-                for item in self.items.iter() {
+                for (accent, ornament, item) in self.items.iter() {
+                    if let Some(accent) = accent {
+                        entries.push(accent.to_proto());
+                    }
+                    if let Some(ornament) = ornament {
+                        entries.push(ornament.to_proto());
+                    }
                     entries.push(item.to_proto());
                 }
             }
@@ -143,3 +168,4 @@ impl_multible_dsl!(ToneDsl);
 impl_multible_dsl!(ChordDsl);
 impl_multible_dsl!(WordDsl);
 impl_multible_dsl!(PickDsl);
+impl_multible_dsl!(DrumDsl);
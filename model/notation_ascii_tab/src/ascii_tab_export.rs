@@ -0,0 +1,149 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use notation_model::notation_proto::notation_fretted::strum::StrumStrings;
+use notation_model::prelude::{
+    FrettedEntry6, GuitarUtil, LaneEntry, LaneKind, Tab, TabBar, Unit, Units,
+};
+
+use crate::prelude::AsciiTabUtil;
+
+/// Plain text tab export of the strings lanes, one column per grid.
+///
+/// Tied notes are not repeated, strums are written as the frets of the current shape.
+pub struct AsciiTabExport();
+
+impl AsciiTabExport {
+    pub const BARS_PER_LINE: usize = 4;
+
+    /// Lines of the bar from string 1, without string names and bar lines.
+    pub fn export_bar(bar: &TabBar, track_index: Option<usize>, grid: Unit) -> Vec<String> {
+        Self::bar_lines(bar, track_index, grid, &mut BTreeMap::new())
+            .iter()
+            .map(|x| x.iter().collect())
+            .collect()
+    }
+    /// The strings lane of one track (the first guitar one by default), in blocks of 4 bars.
+    pub fn export_tab(tab: &Arc<Tab>, track_index: Option<usize>, grid: Unit) -> String {
+        let track_index = track_index.or_else(|| {
+            tab.tracks
+                .iter()
+                .find(|x| x.get_fretboard6().is_some())
+                .map(|x| x.props.index)
+        });
+        let fretboard = track_index
+            .and_then(|x| tab.tracks.get(x))
+            .and_then(|x| x.get_fretboard6())
+            .unwrap_or_else(GuitarUtil::new_default_fretboard);
+        let names = AsciiTabUtil::tuning_names(&fretboard.string_notes);
+        let name_width = names.iter().map(|x| x.len()).max().unwrap_or(0);
+        let mut warnings: BTreeMap<&'static str, usize> = BTreeMap::new();
+        let mut blocks = Vec::new();
+        for bars in tab.bars.chunks(Self::BARS_PER_LINE) {
+            let mut lines: Vec<String> = names
+                .iter()
+                .map(|x| format!("{:<width$}|", x, width = name_width))
+                .collect();
+            for bar in bars.iter() {
+                for (line, bar_line) in lines
+                    .iter_mut()
+                    .zip(Self::bar_lines(bar, track_index, grid, &mut warnings).iter())
+                {
+                    line.extend(bar_line.iter());
+                    line.push('|');
+                }
+            }
+            blocks.push(lines.join("\n"));
+        }
+        for (warning, count) in warnings.iter() {
+            println!("AsciiTabExport::export_tab(), {}: {}", warning, count);
+        }
+        let mut text = blocks.join("\n\n");
+        text.push('\n');
+        text
+    }
+    fn entry_frets(bar: &TabBar, entry: &LaneEntry) -> Vec<(u8, u8)> {
+        let fretted = match entry.model().as_fretted6() {
+            Some(x) => x,
+            None => return vec![],
+        };
+        let shape = bar
+            .get_fretted_shape6(entry)
+            .map(|x| x.1)
+            .unwrap_or_default();
+        match fretted {
+            FrettedEntry6::Pick(pick, _) => pick
+                .get_notes()
+                .iter()
+                .filter_map(|x| {
+                    x.fret
+                        .or_else(|| shape.string_fret_with_barre(x.string))
+                        .map(|fret| (x.string, fret))
+                })
+                .collect(),
+            FrettedEntry6::Strum(strum, _) => {
+                let (low, high) = match strum.strings {
+                    StrumStrings::All => (1, 6),
+                    StrumStrings::Between(x, y) => (x.min(y), x.max(y)),
+                };
+                (low..=high)
+                    .filter_map(|string| {
+                        shape
+                            .string_fret_with_barre(string)
+                            .map(|fret| (string, fret))
+                    })
+                    .collect()
+            }
+            _ => vec![],
+        }
+    }
+    fn bar_lines(
+        bar: &TabBar,
+        track_index: Option<usize>,
+        grid: Unit,
+        warnings: &mut BTreeMap<&'static str, usize>,
+    ) -> Vec<Vec<char>> {
+        let grid_units = Units::from(grid);
+        let columns = (bar.bar_units() / grid_units).as_f32().round().max(1.0) as usize;
+        let mut lines = vec![vec!['-'; columns]; 6];
+        let lane = match bar.get_lane_of_kind(LaneKind::Strings, track_index) {
+            Some(x) => x,
+            None => return lines,
+        };
+        for entry in lane.entries.iter() {
+            let tied = entry
+                .model()
+                .prev()
+                .map(|x| x.proto.is_core_tie())
+                .unwrap_or(false);
+            if tied {
+                continue;
+            }
+            let position = entry.props.in_bar_pos / grid_units;
+            let column = position.as_f32().round() as usize;
+            if position != Units::new(column as i64, 1) {
+                *warnings.entry("off grid, rounded").or_default() += 1;
+            }
+            for (string, fret) in Self::entry_frets(bar, entry) {
+                if string == 0 || string > 6 {
+                    continue;
+                }
+                let line = &mut lines[string as usize - 1];
+                for (offset, c) in fret.to_string().chars().enumerate() {
+                    match line.get_mut(column + offset) {
+                        Some(x) => {
+                            if *x != '-' {
+                                *warnings.entry("overlapping frets").or_default() += 1;
+                            }
+                            *x = c;
+                        }
+                        None => {
+                            *warnings.entry("fret past bar line").or_default() += 1;
+                        }
+                    }
+                }
+            }
+        }
+        lines
+    }
+}
@@ -0,0 +1,338 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use fehler::{throw, throws};
+use notation_model::prelude::{
+    CoreEntry, Duration, Fretboard6, FrettedEntry6, GuitarUtil, Pick, PickNote, ProtoBar,
+    ProtoBarLayer, ProtoEntry, ProtoSection, ProtoTab, ProtoTrack, SectionKind, Slice, SliceBegin,
+    SliceEnd, TabMeta, TrackKind, Unit, Units,
+};
+
+use crate::prelude::{AsciiTabError, AsciiTabUtil};
+
+#[derive(Copy, Clone, Debug)]
+pub struct AsciiTabOptions {
+    /// Length of one character column.
+    pub grid: Unit,
+    /// Used when the string names of the tab can't be read as a tuning.
+    pub fretboard: Fretboard6,
+}
+
+impl Default for AsciiTabOptions {
+    fn default() -> Self {
+        Self {
+            grid: Unit::Sixteenth,
+            fretboard: GuitarUtil::new_default_fretboard(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct AsciiNote {
+    column: usize,
+    string: u8,
+    fret: u8,
+    width: usize,
+}
+
+#[derive(Clone, Debug, Default)]
+struct AsciiBar {
+    columns: usize,
+    notes: Vec<AsciiNote>,
+    /// The first column is a dash on all strings.
+    padded: bool,
+}
+
+/// Plain text tab import, e.g. the "e|---0---" blocks copied from forums.
+///
+/// Every character column after a bar line lasts one grid, a note lasts until
+/// the next note in the bar (or the bar line), notes in the same column are
+/// picked together. The dash right after the bar lines is treated as padding when
+/// all bars have one. Techniques like hammer-ons and bends are reported and skipped.
+pub struct AsciiTabImport();
+
+impl AsciiTabImport {
+    pub const BARS_PER_SECTION: usize = 8;
+    pub const TRACK_ID: &'static str = "guitar";
+
+    #[throws(AsciiTabError)]
+    pub fn read_tab<P: AsRef<Path>>(path: P, meta: TabMeta, options: &AsciiTabOptions) -> ProtoTab {
+        let text = std::fs::read_to_string(path)?;
+        Self::import_tab(&text, meta, options)?
+    }
+    /// Guitar tab with one section every 8 bars.
+    #[throws(AsciiTabError)]
+    pub fn import_tab(text: &str, meta: TabMeta, options: &AsciiTabOptions) -> ProtoTab {
+        let mut warnings: BTreeMap<&'static str, usize> = BTreeMap::new();
+        let (names, bars) = Self::parse_bars(text, &mut warnings);
+        let grid_units = Units::from(options.grid);
+        let bar_units = meta.bar_units();
+        let mismatched = bars
+            .iter()
+            .filter(|x| grid_units * x.columns as i64 != bar_units)
+            .count();
+        if mismatched > 0 {
+            println!(
+                "AsciiTabImport::import_tab(), bar length not matching signature: {} x {}",
+                meta.signature, mismatched
+            );
+        }
+        let (track, bar_slices) =
+            Self::new_track(names, &bars, Self::TRACK_ID, options, &warnings)?;
+        let mut sections = Vec::new();
+        let mut form = Vec::new();
+        for (index, slices) in bar_slices.chunks(Self::BARS_PER_SECTION).enumerate() {
+            let id = format!("{}", index + 1);
+            let bars = slices
+                .iter()
+                .map(|x| {
+                    ProtoBar::from(vec![ProtoBarLayer::new(
+                        Self::TRACK_ID.to_string(),
+                        vec![x.clone()],
+                    )])
+                })
+                .collect();
+            sections.push(ProtoSection::new(id.clone(), SectionKind::Verse, bars));
+            form.push(id);
+        }
+        ProtoTab::new(
+            &ProtoTab::new_uuid(),
+            meta,
+            vec![track],
+            sections,
+            form.into(),
+        )
+    }
+    /// Guitar track of picks, with the slice of each bar.
+    ///
+    /// The tuning is read from the string names of the first block when possible.
+    #[throws(AsciiTabError)]
+    pub fn import_track(
+        text: &str,
+        id: &str,
+        options: &AsciiTabOptions,
+    ) -> (ProtoTrack, Vec<Slice>) {
+        let mut warnings: BTreeMap<&'static str, usize> = BTreeMap::new();
+        let (names, bars) = Self::parse_bars(text, &mut warnings);
+        Self::new_track(names, &bars, id, options, &warnings)?
+    }
+    #[throws(AsciiTabError)]
+    fn new_track(
+        names: Option<Vec<String>>,
+        bars: &[AsciiBar],
+        id: &str,
+        options: &AsciiTabOptions,
+        warnings: &BTreeMap<&'static str, usize>,
+    ) -> (ProtoTrack, Vec<Slice>) {
+        if bars.is_empty() {
+            throw!(AsciiTabError::NoTabBlock);
+        }
+        let mut fretboard = options.fretboard;
+        if let Some(string_notes) = names.and_then(|x| AsciiTabUtil::parse_tuning(&x)) {
+            fretboard.string_notes = string_notes;
+        }
+        let grid_units = Units::from(options.grid);
+        let mut entries = vec![ProtoEntry::from(FrettedEntry6::from(fretboard))];
+        let mut slices = Vec::new();
+        for bar in bars.iter() {
+            let begin = entries.len();
+            let groups = Self::group_notes(&bar.notes);
+            let first = groups.first().map(|x| x.0).unwrap_or(bar.columns);
+            if first > 0 {
+                for duration in Duration::split_units(grid_units * first as i64) {
+                    entries.push(ProtoEntry::from(CoreEntry::Rest(duration)));
+                }
+            }
+            for (index, (column, notes)) in groups.iter().enumerate() {
+                let end = groups.get(index + 1).map(|x| x.0).unwrap_or(bar.columns);
+                let pick = Pick::from(
+                    notes
+                        .iter()
+                        .map(|x| PickNote::new_string_fret(x.string, x.fret))
+                        .collect::<Vec<PickNote>>(),
+                );
+                let units = grid_units * (end - column) as i64;
+                for (index, duration) in Duration::split_units(units).iter().enumerate() {
+                    if index > 0 {
+                        entries.push(ProtoEntry::from(CoreEntry::from(())));
+                    }
+                    entries.push(ProtoEntry::from(FrettedEntry6::from((pick, *duration))));
+                }
+            }
+            slices.push(Slice::new(
+                SliceBegin::Index(begin),
+                SliceEnd::Count(entries.len() - begin),
+                None,
+            ));
+        }
+        for (technique, count) in warnings.iter() {
+            println!(
+                "AsciiTabImport::new_track(), not supported, skipped: {} x {}",
+                technique, count
+            );
+        }
+        (
+            ProtoTrack::new(id.to_string(), TrackKind::Guitar, entries),
+            slices,
+        )
+    }
+    /// Notes picked together, by starting column, a multi-digit fret can
+    /// overlap the column of a note on another string.
+    fn group_notes(notes: &[AsciiNote]) -> Vec<(usize, Vec<AsciiNote>)> {
+        let mut notes = notes.to_vec();
+        notes.sort_by_key(|x| (x.column, x.string));
+        let mut groups: Vec<(usize, Vec<AsciiNote>)> = Vec::new();
+        let mut group_end = 0;
+        for note in notes {
+            if let Some((_, group)) = groups.last_mut() {
+                if note.column < group_end && !group.iter().any(|x| x.string == note.string) {
+                    group_end = group_end.max(note.column + note.width);
+                    group.push(note);
+                    continue;
+                }
+            }
+            group_end = note.column + note.width;
+            groups.push((note.column, vec![note]));
+        }
+        groups
+    }
+    fn technique_name(c: char) -> Option<&'static str> {
+        match c {
+            'h' | 'H' => Some("hammer-on"),
+            'p' | 'P' => Some("pull-off"),
+            'b' | 'B' => Some("bend"),
+            'r' | 'R' => Some("release"),
+            '/' | '\\' | 's' | 'S' => Some("slide"),
+            '~' | 'v' | 'V' => Some("vibrato"),
+            'x' | 'X' => Some("dead note"),
+            't' | 'T' => Some("tap"),
+            _ => None,
+        }
+    }
+    /// The string name and the rest of the line from the first bar line.
+    fn split_tab_line(line: &str) -> Option<(String, Vec<char>)> {
+        let line = line.trim();
+        let bar_line = line.find('|')?;
+        let name = line[..bar_line].trim();
+        if name.chars().count() > 3 || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '#')
+        {
+            return None;
+        }
+        let body: Vec<char> = line[bar_line..].chars().collect();
+        if body.iter().filter(|x| **x == '-').count() < 2 {
+            return None;
+        }
+        Some((name.to_string(), body))
+    }
+    /// String names of the first block, and the bars of all blocks.
+    fn parse_bars(
+        text: &str,
+        warnings: &mut BTreeMap<&'static str, usize>,
+    ) -> (Option<Vec<String>>, Vec<AsciiBar>) {
+        let mut names = None;
+        let mut bars = Vec::new();
+        let mut block: Vec<(String, Vec<char>)> = Vec::new();
+        for line in text.lines().chain(std::iter::once("")) {
+            if let Some(tab_line) = Self::split_tab_line(line) {
+                block.push(tab_line);
+                continue;
+            }
+            if block.len() == 6 {
+                if names.is_none() {
+                    names = Some(block.iter().map(|x| x.0.clone()).collect());
+                }
+                Self::parse_block(&block, &mut bars, warnings);
+            } else if !block.is_empty() {
+                println!(
+                    "AsciiTabImport::parse_bars(), {} lines block skipped",
+                    block.len()
+                );
+            }
+            block.clear();
+        }
+        if bars.iter().all(|x| x.padded) {
+            for bar in bars.iter_mut() {
+                bar.columns -= 1;
+                for note in bar.notes.iter_mut() {
+                    note.column -= 1;
+                }
+            }
+        }
+        (names, bars)
+    }
+    fn parse_block(
+        block: &[(String, Vec<char>)],
+        bars: &mut Vec<AsciiBar>,
+        warnings: &mut BTreeMap<&'static str, usize>,
+    ) {
+        let lines: Vec<&Vec<char>> = block.iter().map(|x| &x.1).collect();
+        let char_at = |line: usize, column: usize| lines[line].get(column).copied().unwrap_or(' ');
+        let first = lines[0];
+        let mut bar_lines: Vec<usize> = first
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| **c == '|')
+            .map(|(i, _)| i)
+            .collect();
+        let tail = &first[bar_lines.last().map(|x| x + 1).unwrap_or(0)..];
+        if tail.iter().filter(|x| **x == '-').count() >= 2 {
+            bar_lines.push(first.len());
+        }
+        for window in bar_lines.windows(2) {
+            // Repeat signs and double bar lines at both ends are not part of the bar.
+            let is_time = |column: usize| {
+                let chars: Vec<char> = (0..6).map(|line| char_at(line, column)).collect();
+                chars.iter().any(|c| c.is_ascii_digit())
+                    || (chars.contains(&'-')
+                        && !chars.iter().any(|c| *c == '|' || *c == ':' || *c == '*'))
+            };
+            let mut begin = window[0] + 1;
+            let mut end = window[1];
+            while begin < end && !is_time(begin) {
+                begin += 1;
+            }
+            while end > begin && !is_time(end - 1) {
+                end -= 1;
+            }
+            if begin == end {
+                continue;
+            }
+            let mut bar = AsciiBar {
+                columns: end - begin,
+                notes: Vec::new(),
+                padded: end - begin > 1 && (0..6).all(|line| char_at(line, begin) == '-'),
+            };
+            for line in 0..6 {
+                let mut column = begin;
+                while column < end {
+                    let c = char_at(line, column);
+                    if let Some(technique) = Self::technique_name(c) {
+                        *warnings.entry(technique).or_default() += 1;
+                    }
+                    if !c.is_ascii_digit() {
+                        column += 1;
+                        continue;
+                    }
+                    let mut width = 1;
+                    while column + width < end && char_at(line, column + width).is_ascii_digit() {
+                        width += 1;
+                    }
+                    let digits: String = (column..column + width.min(2))
+                        .map(|x| char_at(line, x))
+                        .collect();
+                    if width > 2 {
+                        *warnings.entry("more than 2 digits fret").or_default() += 1;
+                    }
+                    bar.notes.push(AsciiNote {
+                        column: column - begin,
+                        string: line as u8 + 1,
+                        fret: digits.parse().unwrap_or(0),
+                        width,
+                    });
+                    column += width;
+                }
+            }
+            bars.push(bar);
+        }
+    }
+}
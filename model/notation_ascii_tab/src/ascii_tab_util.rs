@@ -0,0 +1,73 @@
+use notation_model::prelude::{GuitarTuning, Semitones};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum AsciiTabError {
+    #[error("read ascii tab file failed")]
+    Io(#[from] std::io::Error),
+    #[error("no 6 strings tab block found")]
+    NoTabBlock,
+}
+
+pub struct AsciiTabUtil();
+
+impl AsciiTabUtil {
+    pub const PITCH_NAMES: [&'static str; 12] = [
+        "C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B",
+    ];
+
+    /// Pitch class of a string name like "e", "Bb" or "F#3", C is 0.
+    pub fn string_name_pitch_class(name: &str) -> Option<i8> {
+        let mut chars = name.trim().chars();
+        let mut value: i8 = match chars.next()?.to_ascii_uppercase() {
+            'C' => 0,
+            'D' => 2,
+            'E' => 4,
+            'F' => 5,
+            'G' => 7,
+            'A' => 9,
+            'B' => 11,
+            _ => return None,
+        };
+        for c in chars {
+            match c {
+                '#' => value += 1,
+                'b' => value -= 1,
+                _ if c.is_ascii_digit() => {}
+                _ => return None,
+            }
+        }
+        Some(value.rem_euclid(12))
+    }
+    /// Open string notes from string names (string 1 first), picking the octave
+    /// closest to the standard tuning.
+    pub fn parse_tuning(names: &[String]) -> Option<[Semitones; 6]> {
+        if names.len() != 6 {
+            return None;
+        }
+        let standard: [Semitones; 6] = GuitarTuning::Standard.into();
+        let mut result = standard;
+        for (index, name) in names.iter().enumerate() {
+            let pitch_class = Self::string_name_pitch_class(name)?;
+            let base = standard[index].0;
+            let mut value = base + (pitch_class - base).rem_euclid(12);
+            if value - base > 5 {
+                value -= 12;
+            }
+            result[index] = Semitones(value);
+        }
+        Some(result)
+    }
+    /// String names for the open string notes (string 1 first), the highest one
+    /// is written in lower case if it's the same as the lowest one, e.g. "e B G D A E".
+    pub fn tuning_names(string_notes: &[Semitones; 6]) -> Vec<String> {
+        let mut names: Vec<String> = string_notes
+            .iter()
+            .map(|x| Self::PITCH_NAMES[x.0.rem_euclid(12) as usize].to_string())
+            .collect();
+        if names[0] == names[5] {
+            names[0] = names[0].to_lowercase();
+        }
+        names
+    }
+}
@@ -0,0 +1,14 @@
+pub use notation_model;
+
+pub mod ascii_tab_export;
+pub mod ascii_tab_import;
+pub mod ascii_tab_util;
+
+pub mod prelude {
+    #[doc(hidden)]
+    pub use crate::ascii_tab_export::AsciiTabExport;
+    #[doc(hidden)]
+    pub use crate::ascii_tab_import::{AsciiTabImport, AsciiTabOptions};
+    #[doc(hidden)]
+    pub use crate::ascii_tab_util::{AsciiTabError, AsciiTabUtil};
+}
@@ -0,0 +1,32 @@
+use crate::tuning::BassTuning;
+use notation_core::prelude::Semitones;
+use notation_fretted::prelude::Fretboard4;
+
+pub const BASS_STRING_NUM: usize = 4;
+
+pub const BASS_FRET_NUM_STANDARD: usize = 20;
+pub const BASS_FRET_NUM_EXTENDED: usize = 24;
+
+pub struct BassUtil();
+
+impl BassUtil {
+    pub fn new_bass_fretboard(total_fret_num: usize, strings: [Semitones; 4]) -> Fretboard4 {
+        Fretboard4 {
+            total_fret_num,
+            string_notes: strings,
+            capo: 0,
+        }
+    }
+
+    pub fn new_standard_bass_fretboard(tuning: Option<BassTuning>) -> Fretboard4 {
+        Self::new_bass_fretboard(BASS_FRET_NUM_STANDARD, tuning.unwrap_or_default().into())
+    }
+
+    pub fn new_extended_bass_fretboard(tuning: Option<BassTuning>) -> Fretboard4 {
+        Self::new_bass_fretboard(BASS_FRET_NUM_EXTENDED, tuning.unwrap_or_default().into())
+    }
+
+    pub fn new_default_fretboard() -> Fretboard4 {
+        BassUtil::new_standard_bass_fretboard(None)
+    }
+}
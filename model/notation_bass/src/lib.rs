@@ -0,0 +1,9 @@
+pub mod bass;
+pub mod tuning;
+
+pub mod prelude {
+    #[doc(hidden)]
+    pub use crate::bass::*;
+    #[doc(hidden)]
+    pub use crate::tuning::BassTuning;
+}
@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+
+use notation_core::prelude::{Octave, Pitch, Semitones};
+
+#[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug, Default)]
+pub enum BassTuning {
+    #[default]
+    Standard,
+    DropD,
+    FiveString,
+}
+
+impl BassTuning {
+    pub fn string_num(&self) -> usize {
+        match self {
+            BassTuning::FiveString => 5,
+            _ => 4,
+        }
+    }
+    /// Open string notes, from the highest string (string 1) down.
+    pub fn string_notes(&self) -> Vec<Semitones> {
+        match self {
+            BassTuning::Standard => vec![
+                (Pitch::G, Octave::P2).into(),
+                (Pitch::D, Octave::P2).into(),
+                (Pitch::A, Octave::P1).into(),
+                (Pitch::E, Octave::P1).into(),
+            ],
+            BassTuning::DropD => vec![
+                (Pitch::G, Octave::P2).into(),
+                (Pitch::D, Octave::P2).into(),
+                (Pitch::A, Octave::P1).into(),
+                (Pitch::D, Octave::P1).into(),
+            ],
+            BassTuning::FiveString => vec![
+                (Pitch::G, Octave::P2).into(),
+                (Pitch::D, Octave::P2).into(),
+                (Pitch::A, Octave::P1).into(),
+                (Pitch::E, Octave::P1).into(),
+                (Pitch::B, Octave::P0).into(),
+            ],
+        }
+    }
+}
+
+/// 4-string fretboards only get the highest four strings, the low B of
+/// the 5-string tuning is dropped.
+impl From<BassTuning> for [Semitones; 4] {
+    fn from(v: BassTuning) -> Self {
+        let notes = v.string_notes();
+        [notes[0], notes[1], notes[2], notes[3]]
+    }
+}
+
+impl BassTuning {
+    pub fn to_ident(&self) -> String {
+        format!("{:?}", self)
+    }
+    pub fn from_ident(ident: &str) -> Self {
+        match ident {
+            "DropD" => Self::DropD,
+            "FiveString" => Self::FiveString,
+            _ => Self::Standard,
+        }
+    }
+}
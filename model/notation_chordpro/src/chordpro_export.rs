@@ -0,0 +1,267 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use fehler::throws;
+use notation_model::prelude::{
+    Bpm, Chord, LaneKind, LyricEntry, ProtoEntry, ProtoTab, SectionKind, Tab, TabBar, TabMeta,
+    TrackKind, Units,
+};
+
+use crate::prelude::{ChordProError, ChordProSong, ChordProUtil};
+
+/// ChordPro lyric sheet export of the first chord and lyrics tracks.
+///
+/// Sections are written in the order of the form, with the lyrics of each round,
+/// chords are only written when changed.
+pub struct ChordProExport();
+
+impl ChordProExport {
+    pub const BARS_PER_LINE: usize = 4;
+
+    #[throws(ChordProError)]
+    pub fn export_tab(tab: &ProtoTab) -> String {
+        let tab = Tab::try_parse_arc(tab.clone(), false, None)?;
+        Self::export_model_tab(&tab, "", "")
+    }
+    #[throws(ChordProError)]
+    pub fn export_song(song: &ChordProSong) -> String {
+        let tab = Tab::try_parse_arc(song.tab.clone(), false, None)?;
+        Self::export_model_tab(&tab, &song.title, &song.artist)
+    }
+    #[throws(ChordProError)]
+    pub fn write_song<P: AsRef<Path>>(song: &ChordProSong, path: P) {
+        std::fs::write(path, Self::export_song(song)?)?;
+    }
+    pub fn export_model_tab(tab: &Arc<Tab>, title: &str, artist: &str) -> String {
+        let meta = tab.meta.as_ref();
+        let mut lines = Vec::new();
+        if !title.is_empty() {
+            lines.push(format!("{{title: {}}}", title));
+        }
+        if !artist.is_empty() {
+            lines.push(format!("{{artist: {}}}", artist));
+        }
+        lines.push(format!("{{key: {}}}", ChordProUtil::key_text(meta)));
+        lines.push(format!("{{time: {}}}", meta.signature));
+        lines.push(format!("{{tempo: {}}}", Bpm::from(meta.tempo)));
+        let track_index = |kind: TrackKind| {
+            tab.tracks
+                .iter()
+                .find(|x| x.kind == kind)
+                .map(|x| x.props.index)
+        };
+        let chord_index = track_index(TrackKind::Chord);
+        let lyrics_index = track_index(TrackKind::Lyrics);
+        let mut inexact_chords = 0;
+        let mut written: HashMap<String, Vec<String>> = HashMap::new();
+        let mut begin = 0;
+        while begin < tab.bars.len() {
+            let ordinal = tab.bars[begin].props.section_ordinal;
+            let end = tab.bars[begin..]
+                .iter()
+                .position(|x| x.props.section_ordinal != ordinal)
+                .map(|x| begin + x)
+                .unwrap_or(tab.bars.len());
+            let section = &tab.bars[begin].section;
+            let name = match section.kind {
+                SectionKind::Chorus => "chorus",
+                SectionKind::Bridge => "bridge",
+                _ => "verse",
+            };
+            let mut section_lines = Vec::new();
+            let mut last_chord: Option<Chord> = None;
+            let mut line_begin = begin;
+            for length in Self::line_lengths(&tab.bars[begin..end], lyrics_index) {
+                let line_end = line_begin + length;
+                let line = Self::new_line(
+                    meta,
+                    &tab.bars[line_begin..line_end],
+                    chord_index,
+                    lyrics_index,
+                    &mut last_chord,
+                    &mut inexact_chords,
+                );
+                if !line.is_empty() {
+                    section_lines.push(line);
+                }
+                line_begin = line_end;
+            }
+            let repeated = section.kind == SectionKind::Chorus
+                && written.get(&section.id) == Some(&section_lines);
+            if repeated {
+                lines.push("".to_string());
+                lines.push("{chorus}".to_string());
+            } else if !section_lines.is_empty() {
+                lines.push("".to_string());
+                if section.id == name {
+                    lines.push(format!("{{start_of_{}}}", name));
+                } else {
+                    lines.push(format!("{{start_of_{}: {}}}", name, section.id));
+                }
+                lines.extend(section_lines.clone());
+                lines.push(format!("{{end_of_{}}}", name));
+                written.entry(section.id.clone()).or_insert(section_lines);
+            }
+            begin = end;
+        }
+        if inexact_chords > 0 {
+            println!(
+                "ChordProExport::export_model_tab(), chords named after the closest one: {}",
+                inexact_chords
+            );
+        }
+        let mut text = lines.join("\n");
+        text.push('\n');
+        text
+    }
+    /// Lengths of the lines of the bars, a line ends before a bar starting a new phrase
+    /// (after a bar ending without a word), or after 4 bars with lyrics.
+    ///
+    /// Bars without lyrics are kept with the line before, up to 8 bars.
+    fn line_lengths(bars: &[Arc<TabBar>], lyrics_index: Option<usize>) -> Vec<usize> {
+        let mut lengths = Vec::new();
+        let mut length = 0;
+        let mut has_words = false;
+        let mut phrase_end = false;
+        for bar in bars.iter() {
+            let last_beat = bar.bar_units() - Units::from(bar.signature().beat_unit);
+            let word_positions: Vec<Units> = lyrics_index
+                .and_then(|x| bar.get_lane_of_kind(LaneKind::Lyrics, Some(x)))
+                .map(|lane| {
+                    lane.entries
+                        .iter()
+                        .filter(|entry| {
+                            matches!(entry.proto(), ProtoEntry::Lyric(LyricEntry::Word(word, _)) if !word.text.is_empty())
+                        })
+                        .map(|entry| entry.props.in_bar_pos)
+                        .collect()
+                })
+                .unwrap_or_default();
+            let bar_has_words = !word_positions.is_empty();
+            let new_line = if bar_has_words {
+                has_words && (phrase_end || length >= Self::BARS_PER_LINE)
+            } else {
+                length >= Self::BARS_PER_LINE * if has_words { 2 } else { 1 }
+            };
+            if new_line {
+                lengths.push(length);
+                length = 0;
+                has_words = false;
+            }
+            length += 1;
+            if bar_has_words {
+                has_words = true;
+                phrase_end = !word_positions.iter().any(|x| *x >= last_beat);
+            } else {
+                phrase_end = true;
+            }
+        }
+        if length > 0 {
+            lengths.push(length);
+        }
+        lengths
+    }
+    fn new_line(
+        meta: &TabMeta,
+        bars: &[Arc<TabBar>],
+        chord_index: Option<usize>,
+        lyrics_index: Option<usize>,
+        last_chord: &mut Option<Chord>,
+        inexact_chords: &mut usize,
+    ) -> String {
+        let mut chords: Vec<(Units, Option<Chord>)> = Vec::new();
+        let mut words: Vec<(Units, String)> = Vec::new();
+        let line_pos = bars.first().map(|x| x.props.bar_pos).unwrap_or_default();
+        for bar in bars.iter() {
+            let offset = bar.props.bar_pos - line_pos;
+            if let Some(lane) =
+                chord_index.and_then(|x| bar.get_lane_of_kind(LaneKind::Chord, Some(x)))
+            {
+                for entry in lane.entries.iter() {
+                    let core = match entry.proto().as_core() {
+                        Some(x) => x,
+                        None => continue,
+                    };
+                    let chord = match (core.as_chord(), core.is_rest()) {
+                        (Some(chord), _) => Some(*chord),
+                        (None, true) => None,
+                        _ => continue,
+                    };
+                    chords.push((offset + entry.props.in_bar_pos, chord));
+                }
+            }
+            if let Some(lane) =
+                lyrics_index.and_then(|x| bar.get_lane_of_kind(LaneKind::Lyrics, Some(x)))
+            {
+                for entry in lane.entries.iter() {
+                    if let ProtoEntry::Lyric(LyricEntry::Word(word, _)) = entry.proto() {
+                        if !word.text.is_empty() {
+                            words.push((offset + entry.props.in_bar_pos, word.text.clone()));
+                        }
+                    }
+                }
+            }
+        }
+        let mut line = String::new();
+        let mut last = LinePart::Start;
+        let mut chord_iter = chords.into_iter().peekable();
+        let mut add_chords = |line: &mut String, last: &mut LinePart, until: Option<Units>| {
+            while let Some((pos, chord)) = chord_iter.peek().cloned() {
+                if until.map(|x| pos > x).unwrap_or(false) {
+                    break;
+                }
+                chord_iter.next();
+                if chord == *last_chord {
+                    continue;
+                }
+                let name = match chord {
+                    Some(chord) => {
+                        let (name, exact) = ChordProUtil::chord_name(meta, &chord);
+                        if !exact {
+                            *inexact_chords += 1;
+                        }
+                        name
+                    }
+                    None if last_chord.is_some() => "N.C.".to_string(),
+                    None => continue,
+                };
+                *last_chord = chord;
+                if *last == LinePart::Word || *last == LinePart::Chord {
+                    line.push(' ');
+                }
+                line.push_str(&format!("[{}]", name));
+                *last = LinePart::Chord;
+            }
+        };
+        for (pos, word) in words.iter() {
+            add_chords(&mut line, &mut last, Some(*pos));
+            if last == LinePart::Word {
+                line.push(' ');
+            }
+            match word.strip_suffix('-') {
+                Some(syllable) => {
+                    line.push_str(syllable);
+                    last = LinePart::Syllable;
+                }
+                None => {
+                    line.push_str(word);
+                    last = LinePart::Word;
+                }
+            }
+        }
+        add_chords(&mut line, &mut last, None);
+        if last == LinePart::Syllable {
+            line.push('-');
+        }
+        line
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum LinePart {
+    Start,
+    Word,
+    Syllable,
+    Chord,
+}
@@ -0,0 +1,382 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use fehler::{throw, throws};
+use notation_model::prelude::{
+    Chord, CoreEntry, Duration, LyricEntry, ProtoBar, ProtoBarLayer, ProtoEntry, ProtoSection,
+    ProtoTab, ProtoTrack, SectionKind, Slice, SliceBegin, SliceEnd, TabMeta, Tempo, TrackKind,
+    Units,
+};
+
+use crate::prelude::{ChordProError, ChordProSong, ChordProUtil};
+
+#[derive(Clone, Debug, Default)]
+struct ChordProBeat {
+    /// Set when a chord is written before the word, None for "N.C."
+    change: Option<Option<Chord>>,
+    word: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+struct ChordProSection {
+    id: String,
+    kind: SectionKind,
+    lines: Vec<Vec<ChordProBeat>>,
+}
+
+/// ChordPro lyric sheet import, into a chord track and a lyrics track.
+///
+/// ChordPro doesn't have rhythm, so every word (or chord without word) takes one beat,
+/// and every line starts a new bar. A chord written inside a word splits it into
+/// syllables, e.g. "won[G]der" becomes "won-" and "der".
+pub struct ChordProImport();
+
+impl ChordProImport {
+    pub const CHORD_TRACK_ID: &'static str = "chords";
+    pub const LYRICS_TRACK_ID: &'static str = "lyrics";
+
+    #[throws(ChordProError)]
+    pub fn read_song<P: AsRef<Path>>(path: P) -> ChordProSong {
+        let text = std::fs::read_to_string(path)?;
+        Self::import_song(&text)?
+    }
+    #[throws(ChordProError)]
+    pub fn import_tab(text: &str) -> ProtoTab {
+        Self::import_song(text)?.tab
+    }
+    #[throws(ChordProError)]
+    pub fn import_song(text: &str) -> ChordProSong {
+        let mut warnings: BTreeMap<String, usize> = BTreeMap::new();
+        let mut title = String::new();
+        let mut artist = String::new();
+        let mut meta = TabMeta::default();
+        let mut sections: Vec<ChordProSection> = Vec::new();
+        let mut form: Vec<String> = Vec::new();
+        let mut used_ids: Vec<String> = Vec::new();
+        let mut current: Option<ChordProSection> = None;
+        // Explicit sections end with their end directive, others with a blank line.
+        let mut explicit = false;
+        let mut skipping = false;
+        for line in text.lines() {
+            let line = line.trim_end();
+            if line.trim_start().starts_with('#') {
+                continue;
+            }
+            if let Some((name, value)) = Self::parse_directive(line) {
+                if skipping {
+                    if name.starts_with("end_of_") || name.starts_with("eo") {
+                        skipping = false;
+                    }
+                    continue;
+                }
+                match name.as_str() {
+                    "title" | "t" => title = value,
+                    "subtitle" | "st" | "artist" => {
+                        if artist.is_empty() {
+                            artist = value
+                        }
+                    }
+                    "key" | "time" | "tempo" if !sections.is_empty() || current.is_some() => {
+                        *warnings
+                            .entry(format!("{} change in song", name))
+                            .or_default() += 1;
+                    }
+                    "key" => match ChordProUtil::parse_key(&value) {
+                        Some((key, scale)) => {
+                            meta.key = key;
+                            meta.scale = scale;
+                        }
+                        None => *warnings.entry(format!("key: {}", value)).or_default() += 1,
+                    },
+                    "time" => match ChordProUtil::parse_signature(&value) {
+                        Some(signature) => meta.signature = signature,
+                        None => *warnings.entry(format!("time: {}", value)).or_default() += 1,
+                    },
+                    "tempo" => match value.trim().parse::<u16>() {
+                        Ok(bpm) if bpm > 0 => meta.tempo = Tempo::Bpm(bpm),
+                        _ => *warnings.entry(format!("tempo: {}", value)).or_default() += 1,
+                    },
+                    "start_of_verse" | "sov" | "start_of_chorus" | "soc" | "start_of_bridge"
+                    | "sob" => {
+                        Self::end_section(&mut current, &mut sections, &mut form);
+                        let kind = match name.as_str() {
+                            "start_of_chorus" | "soc" => SectionKind::Chorus,
+                            "start_of_bridge" | "sob" => SectionKind::Bridge,
+                            _ => SectionKind::Verse,
+                        };
+                        current = Some(Self::new_section(&mut used_ids, kind, &value));
+                        explicit = true;
+                    }
+                    "end_of_verse" | "eov" | "end_of_chorus" | "eoc" | "end_of_bridge" | "eob" => {
+                        Self::end_section(&mut current, &mut sections, &mut form);
+                        explicit = false;
+                    }
+                    "chorus" => {
+                        Self::end_section(&mut current, &mut sections, &mut form);
+                        match sections
+                            .iter()
+                            .rev()
+                            .find(|x| x.kind == SectionKind::Chorus)
+                        {
+                            Some(chorus) => form.push(chorus.id.clone()),
+                            None => {
+                                *warnings.entry("chorus not found".to_string()).or_default() += 1
+                            }
+                        }
+                    }
+                    "start_of_tab" | "sot" | "start_of_grid" | "sog" => {
+                        *warnings.entry(name.clone()).or_default() += 1;
+                        skipping = true;
+                    }
+                    "comment" | "c" | "comment_italic" | "ci" | "comment_box" | "cb" => {}
+                    _ => *warnings.entry(format!("{{{}}}", name)).or_default() += 1,
+                }
+                continue;
+            }
+            if skipping {
+                continue;
+            }
+            if line.trim().is_empty() {
+                if !explicit {
+                    Self::end_section(&mut current, &mut sections, &mut form);
+                }
+                continue;
+            }
+            let section = current
+                .get_or_insert_with(|| Self::new_section(&mut used_ids, SectionKind::Verse, ""));
+            let beats = Self::parse_line(&meta, line, &mut warnings);
+            if !beats.is_empty() {
+                section.lines.push(beats);
+            }
+        }
+        Self::end_section(&mut current, &mut sections, &mut form);
+        for (warning, count) in warnings.iter() {
+            println!(
+                "ChordProImport::import_song(), not supported, skipped: {} x {}",
+                warning, count
+            );
+        }
+        if sections.is_empty() {
+            throw!(ChordProError::Empty);
+        }
+        let tab = Self::new_tab(meta, &sections, form);
+        ChordProSong { title, artist, tab }
+    }
+    fn unique_id(used_ids: &mut Vec<String>, id: String) -> String {
+        let mut result = id.clone();
+        let mut index = 2;
+        while used_ids.contains(&result) {
+            result = format!("{} {}", id, index);
+            index += 1;
+        }
+        used_ids.push(result.clone());
+        result
+    }
+    fn new_section(used_ids: &mut Vec<String>, kind: SectionKind, label: &str) -> ChordProSection {
+        let id = if label.trim().is_empty() {
+            kind.to_string().to_lowercase()
+        } else {
+            label.trim().to_string()
+        };
+        ChordProSection {
+            id: Self::unique_id(used_ids, id),
+            kind,
+            lines: Vec::new(),
+        }
+    }
+    fn end_section(
+        current: &mut Option<ChordProSection>,
+        sections: &mut Vec<ChordProSection>,
+        form: &mut Vec<String>,
+    ) {
+        if let Some(section) = current.take() {
+            if !section.lines.is_empty() {
+                form.push(section.id.clone());
+                sections.push(section);
+            }
+        }
+    }
+    /// Name and value of lines like "{title: Song}" or "{soc}", names are in lower case.
+    fn parse_directive(line: &str) -> Option<(String, String)> {
+        let line = line.trim();
+        let inner = line.strip_prefix('{')?.strip_suffix('}')?;
+        let (name, value) = match inner.find(|c: char| c == ':' || c.is_whitespace()) {
+            Some(index) => (&inner[..index], inner[index + 1..].trim()),
+            None => (inner, ""),
+        };
+        Some((name.trim().to_lowercase(), value.to_string()))
+    }
+    fn parse_line(
+        meta: &TabMeta,
+        line: &str,
+        warnings: &mut BTreeMap<String, usize>,
+    ) -> Vec<ChordProBeat> {
+        let mut beats = Vec::new();
+        let mut change: Option<Option<Chord>> = None;
+        let mut word = String::new();
+        let mut chars = line.chars();
+        while let Some(c) = chars.next() {
+            if c == '[' {
+                let name: String = chars.by_ref().take_while(|x| *x != ']').collect();
+                let name = name.trim();
+                let chord = match name {
+                    "" | "N.C." | "NC" => None,
+                    _ => match ChordProUtil::parse_chord(meta, name) {
+                        Some(chord) => Some(chord),
+                        None => {
+                            *warnings.entry(format!("chord: {}", name)).or_default() += 1;
+                            continue;
+                        }
+                    },
+                };
+                if !word.is_empty() {
+                    beats.push(ChordProBeat {
+                        change: change.take(),
+                        word: Some(format!("{}-", word)),
+                    });
+                    word.clear();
+                }
+                if change.is_some() {
+                    beats.push(ChordProBeat {
+                        change: change.take(),
+                        word: None,
+                    });
+                }
+                change = Some(chord);
+            } else if c.is_whitespace() {
+                if !word.is_empty() {
+                    beats.push(ChordProBeat {
+                        change: change.take(),
+                        word: Some(word.clone()),
+                    });
+                    word.clear();
+                }
+            } else {
+                word.push(c);
+            }
+        }
+        if !word.is_empty() || change.is_some() {
+            beats.push(ChordProBeat {
+                change,
+                word: if word.is_empty() { None } else { Some(word) },
+            });
+        }
+        beats
+    }
+    /// Entries lasting for the units, split ones are tied when `tied` is set.
+    fn add_entries<F: Fn(Duration) -> ProtoEntry>(
+        entries: &mut Vec<ProtoEntry>,
+        units: Units,
+        tied: bool,
+        new_entry: F,
+    ) {
+        for (index, duration) in Duration::split_units(units).into_iter().enumerate() {
+            if index > 0 && tied {
+                entries.push(ProtoEntry::from(CoreEntry::from(())));
+            }
+            entries.push(new_entry(duration));
+        }
+    }
+    fn add_chord(entries: &mut Vec<ProtoEntry>, chord: Option<Chord>, units: Units) {
+        match chord {
+            Some(chord) => Self::add_entries(entries, units, true, |duration| {
+                ProtoEntry::from(CoreEntry::from((chord, duration)))
+            }),
+            None => Self::add_entries(entries, units, false, |duration| {
+                ProtoEntry::from(CoreEntry::Rest(duration))
+            }),
+        }
+    }
+    fn new_tab(meta: TabMeta, sections: &[ChordProSection], form: Vec<String>) -> ProtoTab {
+        let beats_per_bar = meta.signature.bar_beats.max(1) as usize;
+        let beat_units = Units::from(meta.signature.beat_unit);
+        let mut chord_entries: Vec<ProtoEntry> = Vec::new();
+        let mut lyric_entries: Vec<ProtoEntry> = Vec::new();
+        let mut proto_sections = Vec::new();
+        for section in sections.iter() {
+            let mut chord: Option<Chord> = None;
+            let mut bars = Vec::new();
+            for line in section.lines.iter() {
+                for bar_beats in line.chunks(beats_per_bar) {
+                    let chord_begin = chord_entries.len();
+                    let lyric_begin = lyric_entries.len();
+                    let mut chord_run: (Option<Chord>, usize) = (chord, 0);
+                    for index in 0..beats_per_bar {
+                        let beat = bar_beats.get(index).cloned().unwrap_or_default();
+                        if let Some(change) = beat.change {
+                            if chord_run.1 > 0 {
+                                Self::add_chord(
+                                    &mut chord_entries,
+                                    chord_run.0,
+                                    beat_units * chord_run.1 as i64,
+                                );
+                            }
+                            chord = change;
+                            chord_run = (chord, 0);
+                        }
+                        chord_run.1 += 1;
+                        match beat.word {
+                            Some(word) => Self::add_entries(
+                                &mut lyric_entries,
+                                beat_units,
+                                false,
+                                |duration| {
+                                    ProtoEntry::from(LyricEntry::from((word.clone(), duration)))
+                                },
+                            ),
+                            None => Self::add_entries(
+                                &mut lyric_entries,
+                                beat_units,
+                                false,
+                                |duration| ProtoEntry::from(CoreEntry::Rest(duration)),
+                            ),
+                        }
+                    }
+                    Self::add_chord(
+                        &mut chord_entries,
+                        chord_run.0,
+                        beat_units * chord_run.1 as i64,
+                    );
+                    bars.push(ProtoBar::from(vec![
+                        Self::new_layer(Self::CHORD_TRACK_ID, chord_begin, chord_entries.len()),
+                        Self::new_layer(Self::LYRICS_TRACK_ID, lyric_begin, lyric_entries.len()),
+                    ]));
+                }
+            }
+            proto_sections.push(ProtoSection::new(
+                section.id.clone(),
+                section.kind.clone(),
+                bars,
+            ));
+        }
+        let tracks = vec![
+            ProtoTrack::new(
+                Self::CHORD_TRACK_ID.to_string(),
+                TrackKind::Chord,
+                chord_entries,
+            ),
+            ProtoTrack::new(
+                Self::LYRICS_TRACK_ID.to_string(),
+                TrackKind::Lyrics,
+                lyric_entries,
+            ),
+        ];
+        ProtoTab::new(
+            &ProtoTab::new_uuid(),
+            meta,
+            tracks,
+            proto_sections,
+            form.into(),
+        )
+    }
+    fn new_layer(track: &str, begin: usize, end: usize) -> ProtoBarLayer {
+        ProtoBarLayer::new(
+            track.to_string(),
+            vec![Slice::new(
+                SliceBegin::Index(begin),
+                SliceEnd::Count(end - begin),
+                None,
+            )],
+        )
+    }
+}
@@ -0,0 +1,109 @@
+use notation_model::prelude::{
+    Chord, ChordQuality, ChordSymbol, Key, Pitch, PitchName, PitchSign, ProtoTab, Scale,
+    Semitones, Signature, TabMeta, Unit,
+};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ChordProError {
+    #[error("read or write chordpro file failed")]
+    Io(#[from] std::io::Error),
+    #[error("parse tab failed")]
+    Parse(#[from] notation_model::prelude::ParseError),
+    #[error("no lyrics or chords found")]
+    Empty,
+}
+
+/// A ChordPro song, the title and artist are not part of the tab.
+#[derive(Clone, Debug)]
+pub struct ChordProSong {
+    pub title: String,
+    pub artist: String,
+    pub tab: ProtoTab,
+}
+
+/// Mappings between the tab model and ChordPro values, shared by import and export.
+pub struct ChordProUtil();
+
+impl ChordProUtil {
+    /// Split a name like "F#m7" into the root pitch and the rest.
+    pub fn parse_pitch(text: &str) -> Option<(Pitch, &str)> {
+        let name = match text.chars().next()? {
+            'C' => PitchName::C,
+            'D' => PitchName::D,
+            'E' => PitchName::E,
+            'F' => PitchName::F,
+            'G' => PitchName::G,
+            'A' => PitchName::A,
+            'B' => PitchName::B,
+            _ => return None,
+        };
+        let (sign, len) = match text[1..].chars().next() {
+            Some('#') => (PitchSign::Sharp, 2),
+            Some('b') => (PitchSign::Flat, 2),
+            _ => (PitchSign::Natural, 1),
+        };
+        Some((Pitch::new(name, sign), &text[len..]))
+    }
+    /// Key and scale from names like "G" or "Em".
+    pub fn parse_key(text: &str) -> Option<(Key, Scale)> {
+        let (pitch, rest) = Self::parse_pitch(text.trim())?;
+        let scale = match rest {
+            "" => Scale::Major,
+            "m" | "min" => Scale::Minor,
+            _ => return None,
+        };
+        let key = Key::ALL
+            .into_iter()
+            .find(|x| Pitch::from(*x) == pitch)
+            .unwrap_or_else(|| {
+                Key::ALL
+                    .into_iter()
+                    .find(|x| Semitones::from(Pitch::from(*x)) == Semitones::from(pitch))
+                    .unwrap_or_default()
+            });
+        Some((key, scale))
+    }
+    /// Scales without a major third are written as minor keys.
+    pub fn key_text(meta: &TabMeta) -> String {
+        let minor = meta.scale.is_minor();
+        format!("{}{}", Pitch::from(meta.key), if minor { "m" } else { "" })
+    }
+    pub fn parse_signature(text: &str) -> Option<Signature> {
+        let (beats, unit) = text.trim().split_once('/')?;
+        let beat_unit = match unit.trim() {
+            "1" => Unit::Whole,
+            "2" => Unit::Half,
+            "4" => Unit::Quarter,
+            "8" => Unit::Eighth,
+            "16" => Unit::Sixteenth,
+            "32" => Unit::ThirtySecondth,
+            _ => return None,
+        };
+        let bar_beats = beats.trim().parse::<u8>().ok().filter(|x| *x > 0)?;
+        Some(Signature::new(beat_unit, bar_beats))
+    }
+    /// Chord from names like "Am7" or "G/B", the root is converted to a syllable
+    /// of the tab's key and scale.
+    pub fn parse_chord(meta: &TabMeta, name: &str) -> Option<Chord> {
+        ChordSymbol::parse(name).map(|x| x.to_chord(&meta.key, &meta.scale))
+    }
+    /// Name of the chord in the tab's key and scale, chords without a matching
+    /// quality are named after the closest one.
+    pub fn chord_name(meta: &TabMeta, chord: &Chord) -> (String, bool) {
+        if let Some(symbol) = ChordSymbol::from_chord(chord, &meta.key, &meta.scale) {
+            return (symbol.to_letter_text(), true);
+        }
+        let intervals = chord.intervals.get_intervals();
+        let quality = ChordQuality::ALL
+            .into_iter()
+            .filter(|x| intervals.starts_with(&x.intervals()))
+            .max_by_key(|x| x.intervals().len())
+            .unwrap_or(ChordQuality::Major);
+        let closest = Chord::new(chord.root, quality.intervals().into(), chord.bass);
+        let name = ChordSymbol::from_chord(&closest, &meta.key, &meta.scale)
+            .map(|x| x.to_letter_text())
+            .unwrap_or_default();
+        (name, false)
+    }
+}
@@ -0,0 +1,14 @@
+pub use notation_model;
+
+pub mod chordpro_export;
+pub mod chordpro_import;
+pub mod chordpro_util;
+
+pub mod prelude {
+    #[doc(hidden)]
+    pub use crate::chordpro_export::ChordProExport;
+    #[doc(hidden)]
+    pub use crate::chordpro_import::ChordProImport;
+    #[doc(hidden)]
+    pub use crate::chordpro_util::{ChordProError, ChordProSong, ChordProUtil};
+}
@@ -2,7 +2,9 @@ use std::fmt::Display;
 
 use serde::{Deserialize, Serialize};
 
-use crate::prelude::{Pitch, Syllable};
+use crate::prelude::{
+    Chord, Interval, IntervalQuality, Key, Pitch, PitchSign, Scale, Semitones, Syllable,
+};
 
 // https://hellomusictheory.com/learn/chord-inversions/
 #[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
@@ -35,13 +37,335 @@ pub enum ChordQuality {
     Dominant7th,
     Diminished7th,
     HaldDiminished7th,
+    MinorMajor7th,
+    Augmented7th,
+    Suspended4th7th,
+    Major6th,
+    Minor6th,
+    Add9th,
+    MinorAdd9th,
+    Dominant9th,
+    Major9th,
+    Minor9th,
+    Dominant11th,
+    Minor11th,
+    Dominant13th,
+    Major13th,
+    Minor13th,
+    Dominant7thFlat9th,
+    Dominant7thSharp9th,
+    Dominant7thSharp11th,
+    Dominant7thFlat13th,
+    Power,
 }
-//Extended, Altered, Quartal and Quintal not supported
+//Quartal and Quintal not supported
 impl Display for ChordQuality {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{:?}", self)
     }
 }
+impl ChordQuality {
+    pub const ALL: [ChordQuality; 31] = [
+        ChordQuality::Major,
+        ChordQuality::Minor,
+        ChordQuality::Diminished,
+        ChordQuality::Augmented,
+        ChordQuality::Suspended2th,
+        ChordQuality::Suspended4th,
+        ChordQuality::Major7th,
+        ChordQuality::Minor7th,
+        ChordQuality::Dominant7th,
+        ChordQuality::Diminished7th,
+        ChordQuality::HaldDiminished7th,
+        ChordQuality::MinorMajor7th,
+        ChordQuality::Augmented7th,
+        ChordQuality::Suspended4th7th,
+        ChordQuality::Major6th,
+        ChordQuality::Minor6th,
+        ChordQuality::Add9th,
+        ChordQuality::MinorAdd9th,
+        ChordQuality::Dominant9th,
+        ChordQuality::Major9th,
+        ChordQuality::Minor9th,
+        ChordQuality::Dominant11th,
+        ChordQuality::Minor11th,
+        ChordQuality::Dominant13th,
+        ChordQuality::Major13th,
+        ChordQuality::Minor13th,
+        ChordQuality::Dominant7thFlat9th,
+        ChordQuality::Dominant7thSharp9th,
+        ChordQuality::Dominant7thSharp11th,
+        ChordQuality::Dominant7thFlat13th,
+        ChordQuality::Power,
+    ];
+    /// Other spellings of the suffixes, used when parsing.
+    pub const SUFFIX_ALIASES: [(&'static str, &'static str); 25] = [
+        ("M", ""),
+        ("maj", ""),
+        ("min", "m"),
+        ("-", "m"),
+        ("M7", "maj7"),
+        ("ma7", "maj7"),
+        ("Δ", "maj7"),
+        ("Δ7", "maj7"),
+        ("min7", "m7"),
+        ("-7", "m7"),
+        ("°", "dim"),
+        ("o", "dim"),
+        ("°7", "dim7"),
+        ("o7", "dim7"),
+        ("ø", "m7b5"),
+        ("ø7", "m7b5"),
+        ("m7-5", "m7b5"),
+        ("+", "aug"),
+        ("+7", "aug7"),
+        ("7+5", "aug7"),
+        ("sus", "sus4"),
+        ("M9", "maj9"),
+        ("M13", "maj13"),
+        ("7-9", "7b9"),
+        ("7+9", "7#9"),
+    ];
+    /// Intervals above the root, 13th chords leave out the 11th.
+    pub fn intervals(&self) -> Vec<Interval> {
+        match self {
+            ChordQuality::Major => vec![Interval::Major3nd, Interval::Perfect5th],
+            ChordQuality::Minor => vec![Interval::Minor3nd, Interval::Perfect5th],
+            ChordQuality::Diminished => vec![Interval::Minor3nd, Interval::Diminished5th],
+            ChordQuality::Augmented => vec![Interval::Major3nd, Interval::Augmented5th],
+            ChordQuality::Suspended2th => vec![Interval::Major2nd, Interval::Perfect5th],
+            ChordQuality::Suspended4th => vec![Interval::Perfect4th, Interval::Perfect5th],
+            ChordQuality::Major7th => {
+                vec![Interval::Major3nd, Interval::Perfect5th, Interval::Major7th]
+            }
+            ChordQuality::Minor7th => {
+                vec![Interval::Minor3nd, Interval::Perfect5th, Interval::Minor7th]
+            }
+            ChordQuality::Dominant7th => {
+                vec![Interval::Major3nd, Interval::Perfect5th, Interval::Minor7th]
+            }
+            ChordQuality::Diminished7th => vec![
+                Interval::Minor3nd,
+                Interval::Diminished5th,
+                Interval::Diminished7th,
+            ],
+            ChordQuality::HaldDiminished7th => vec![
+                Interval::Minor3nd,
+                Interval::Diminished5th,
+                Interval::Minor7th,
+            ],
+            ChordQuality::MinorMajor7th => {
+                vec![Interval::Minor3nd, Interval::Perfect5th, Interval::Major7th]
+            }
+            ChordQuality::Augmented7th => vec![
+                Interval::Major3nd,
+                Interval::Augmented5th,
+                Interval::Minor7th,
+            ],
+            ChordQuality::Suspended4th7th => vec![
+                Interval::Perfect4th,
+                Interval::Perfect5th,
+                Interval::Minor7th,
+            ],
+            ChordQuality::Major6th => {
+                vec![Interval::Major3nd, Interval::Perfect5th, Interval::Major6th]
+            }
+            ChordQuality::Minor6th => {
+                vec![Interval::Minor3nd, Interval::Perfect5th, Interval::Major6th]
+            }
+            ChordQuality::Add9th => {
+                vec![Interval::Major3nd, Interval::Perfect5th, Interval::Major9th]
+            }
+            ChordQuality::MinorAdd9th => {
+                vec![Interval::Minor3nd, Interval::Perfect5th, Interval::Major9th]
+            }
+            ChordQuality::Dominant9th => vec![
+                Interval::Major3nd,
+                Interval::Perfect5th,
+                Interval::Minor7th,
+                Interval::Major9th,
+            ],
+            ChordQuality::Major9th => vec![
+                Interval::Major3nd,
+                Interval::Perfect5th,
+                Interval::Major7th,
+                Interval::Major9th,
+            ],
+            ChordQuality::Minor9th => vec![
+                Interval::Minor3nd,
+                Interval::Perfect5th,
+                Interval::Minor7th,
+                Interval::Major9th,
+            ],
+            ChordQuality::Dominant11th => {
+                vec![
+                    Interval::Major3nd,
+                    Interval::Perfect5th,
+                    Interval::Minor7th,
+                    Interval::Major9th,
+                    Interval::Perfect11th,
+                ]
+            }
+            ChordQuality::Minor11th => {
+                vec![
+                    Interval::Minor3nd,
+                    Interval::Perfect5th,
+                    Interval::Minor7th,
+                    Interval::Major9th,
+                    Interval::Perfect11th,
+                ]
+            }
+            ChordQuality::Dominant13th => {
+                vec![
+                    Interval::Major3nd,
+                    Interval::Perfect5th,
+                    Interval::Minor7th,
+                    Interval::Major9th,
+                    Interval::Major13th,
+                ]
+            }
+            ChordQuality::Major13th => vec![
+                Interval::Major3nd,
+                Interval::Perfect5th,
+                Interval::Major7th,
+                Interval::Major9th,
+                Interval::Major13th,
+            ],
+            ChordQuality::Minor13th => vec![
+                Interval::Minor3nd,
+                Interval::Perfect5th,
+                Interval::Minor7th,
+                Interval::Major9th,
+                Interval::Major13th,
+            ],
+            ChordQuality::Dominant7thFlat9th => vec![
+                Interval::Major3nd,
+                Interval::Perfect5th,
+                Interval::Minor7th,
+                Interval::Minor9th,
+            ],
+            ChordQuality::Dominant7thSharp9th => {
+                vec![
+                    Interval::Major3nd,
+                    Interval::Perfect5th,
+                    Interval::Minor7th,
+                    Interval::Augmented9th,
+                ]
+            }
+            ChordQuality::Dominant7thSharp11th => {
+                vec![
+                    Interval::Major3nd,
+                    Interval::Perfect5th,
+                    Interval::Minor7th,
+                    Interval::Augmented11th,
+                ]
+            }
+            ChordQuality::Dominant7thFlat13th => {
+                vec![
+                    Interval::Major3nd,
+                    Interval::Perfect5th,
+                    Interval::Minor7th,
+                    Interval::Minor13th,
+                ]
+            }
+            ChordQuality::Power => vec![Interval::Perfect5th],
+        }
+    }
+    pub fn is_extended(&self) -> bool {
+        self.intervals().iter().any(|x| x.is_compound())
+    }
+    pub fn is_minor(&self) -> bool {
+        self.intervals().contains(&Interval::Minor3nd)
+    }
+    /// Suffix after the root in letter chord symbols, e.g. "m7b5".
+    pub fn to_suffix(&self) -> &'static str {
+        match self {
+            ChordQuality::Major => "",
+            ChordQuality::Minor => "m",
+            ChordQuality::Diminished => "dim",
+            ChordQuality::Augmented => "aug",
+            ChordQuality::Suspended2th => "sus2",
+            ChordQuality::Suspended4th => "sus4",
+            ChordQuality::Major7th => "maj7",
+            ChordQuality::Minor7th => "m7",
+            ChordQuality::Dominant7th => "7",
+            ChordQuality::Diminished7th => "dim7",
+            ChordQuality::HaldDiminished7th => "m7b5",
+            ChordQuality::MinorMajor7th => "mmaj7",
+            ChordQuality::Augmented7th => "aug7",
+            ChordQuality::Suspended4th7th => "7sus4",
+            ChordQuality::Major6th => "6",
+            ChordQuality::Minor6th => "m6",
+            ChordQuality::Add9th => "add9",
+            ChordQuality::MinorAdd9th => "madd9",
+            ChordQuality::Dominant9th => "9",
+            ChordQuality::Major9th => "maj9",
+            ChordQuality::Minor9th => "m9",
+            ChordQuality::Dominant11th => "11",
+            ChordQuality::Minor11th => "m11",
+            ChordQuality::Dominant13th => "13",
+            ChordQuality::Major13th => "maj13",
+            ChordQuality::Minor13th => "m13",
+            ChordQuality::Dominant7thFlat9th => "7b9",
+            ChordQuality::Dominant7thSharp9th => "7#9",
+            ChordQuality::Dominant7thSharp11th => "7#11",
+            ChordQuality::Dominant7thFlat13th => "7b13",
+            ChordQuality::Power => "5",
+        }
+    }
+    pub fn from_suffix(suffix: &str) -> Option<Self> {
+        let suffix = Self::SUFFIX_ALIASES
+            .iter()
+            .find(|x| x.0 == suffix)
+            .map(|x| x.1)
+            .unwrap_or(suffix);
+        Self::ALL.into_iter().find(|x| x.to_suffix() == suffix)
+    }
+    /// Suffix after roman numerals, minor chords are written in lower case instead of "m".
+    pub fn to_roman_suffix(&self) -> &'static str {
+        match self {
+            ChordQuality::Diminished => "°",
+            ChordQuality::Diminished7th => "°7",
+            ChordQuality::HaldDiminished7th => "ø7",
+            ChordQuality::Augmented => "+",
+            ChordQuality::Augmented7th => "+7",
+            _ => {
+                let suffix = self.to_suffix();
+                if self.is_minor() {
+                    suffix.strip_prefix('m').unwrap_or(suffix)
+                } else {
+                    suffix
+                }
+            }
+        }
+    }
+    /// Quality with the same intervals, in any order.
+    pub fn from_intervals(intervals: &[Interval]) -> Option<Self> {
+        let mut sorted = intervals.to_vec();
+        sorted.sort();
+        Self::ALL.into_iter().find(|x| {
+            let mut quality_intervals = x.intervals();
+            quality_intervals.sort();
+            quality_intervals == sorted
+        })
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug, Default)]
+pub enum ChordSymbolFormat {
+    /// Letter names, e.g. "Am7/G".
+    #[default]
+    Letter,
+    /// Movable-do numbers, e.g. "6m7/5" in C major.
+    Number,
+    /// Roman numerals from the tonic of the scale, e.g. "vi65" in C major.
+    Roman,
+}
+impl Display for ChordSymbolFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
 
 #[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
 pub struct ChordSymbol {
@@ -52,7 +376,7 @@ pub struct ChordSymbol {
 }
 impl Display for ChordSymbol {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{} {} {}", self.pitch, self.quality, self.inversion)
+        write!(f, "{}", self.to_letter_text())
     }
 }
 
@@ -71,3 +395,265 @@ impl ChordSymbol {
         }
     }
 }
+
+impl ChordSymbol {
+    const ROMAN_NUMERALS: [&'static str; 7] = ["I", "II", "III", "IV", "V", "VI", "VII"];
+
+    fn parse_pitch(text: &str) -> Option<(Pitch, &str)> {
+        if !text.starts_with(|c: char| ('A'..='G').contains(&c)) {
+            return None;
+        }
+        let len = 1 + text[1..]
+            .chars()
+            .take(2)
+            .take_while(|c| *c == '#' || *c == 'b')
+            .count();
+        // Only a double sign if it's "##" or "bb", e.g. "Bb" but not "Bb#".
+        let len = match &text[1..len] {
+            "#b" | "b#" => 2,
+            _ => len,
+        };
+        Some((Pitch::from_text(&text[0..len]), &text[len..]))
+    }
+    /// Parse letter chord symbols like "F#m7b5", "Csus4/G" or "Bbmaj9".
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim().replace('♯', "#").replace('♭', "b");
+        let (name, bass) = match text.rsplit_once('/') {
+            Some((name, bass)) => (name, Some(bass)),
+            None => (text.as_str(), None),
+        };
+        let (pitch, suffix) = Self::parse_pitch(name)?;
+        let quality = ChordQuality::from_suffix(suffix)?;
+        let inversion = match bass {
+            Some(bass) => match Self::parse_pitch(bass)? {
+                (bass, "") => ChordInversion::SlashBass(bass),
+                _ => return None,
+            },
+            None => ChordInversion::RootPosition,
+        };
+        Some(Self::new(pitch, quality, inversion, None))
+    }
+    /// Interval from the root to the bass note, None in root position.
+    pub fn bass_interval(&self) -> Option<Interval> {
+        let intervals = self.quality.intervals();
+        match self.inversion {
+            ChordInversion::RootPosition => None,
+            ChordInversion::FirstInversion => intervals.first().copied(),
+            ChordInversion::SecondInversion => intervals.get(1).copied(),
+            ChordInversion::ThirdInversion => intervals.get(2).copied(),
+            ChordInversion::SlashBass(bass) => Some(Interval::from(Semitones(
+                (Semitones::from(bass).0 - Semitones::from(self.pitch).0).rem_euclid(12),
+            ))),
+        }
+    }
+    pub fn bass_pitch(&self) -> Option<Pitch> {
+        match self.inversion {
+            ChordInversion::SlashBass(bass) => Some(bass),
+            _ => self.bass_interval().map(|x| {
+                let pitch = Pitch::from(Semitones::from(self.pitch) + Semitones::from(x));
+                if Self::is_flat_interval(&x) {
+                    Self::flat_pitch(pitch)
+                } else {
+                    pitch
+                }
+            }),
+        }
+    }
+    /// Slash basses keep their own spelling, natural ones follow the key signature.
+    fn bass_syllable(&self, root: &Syllable, key: &Key, scale: &Scale) -> Option<Syllable> {
+        self.bass_interval().map(|x| {
+            let syllable = x.syllable_on_root(root);
+            match self.inversion {
+                ChordInversion::SlashBass(bass) if bass.sign == PitchSign::Sharp => syllable,
+                ChordInversion::SlashBass(bass) if bass.sign == PitchSign::Flat => {
+                    Self::flat_syllable(syllable)
+                }
+                _ if Self::is_flat_interval(&x) => Self::flat_syllable(syllable),
+                _ => Self::key_syllable(key, scale, syllable),
+            }
+        })
+    }
+    /// Keys with flats in the signature, e.g. F major or D minor.
+    fn is_flat_key(key: &Key, scale: &Scale) -> bool {
+        match key {
+            Key::Natural(_) => (7..12).contains(&scale.calc_key_index(*key)),
+            Key::Sharp(_) => false,
+            Key::Flat(_) => true,
+        }
+    }
+    /// Syllables outside of the scale are written as flats in flat keys.
+    fn key_syllable(key: &Key, scale: &Scale, syllable: Syllable) -> Syllable {
+        if Self::is_flat_key(key, scale) && !scale.get_syllables().contains(&syllable) {
+            Self::flat_syllable(syllable)
+        } else {
+            syllable
+        }
+    }
+    fn is_flat_syllable(syllable: &Syllable) -> bool {
+        matches!(
+            syllable,
+            Syllable::Ra | Syllable::Me | Syllable::Se | Syllable::Le | Syllable::Te
+        )
+    }
+    fn is_flat_interval(interval: &Interval) -> bool {
+        matches!(
+            IntervalQuality::from(*interval),
+            IntervalQuality::Minor | IntervalQuality::Diminished
+        )
+    }
+    /// Semitones based conversions always give sharps, e.g. "Bb" would be 6# in C.
+    fn flat_syllable(syllable: Syllable) -> Syllable {
+        match syllable {
+            Syllable::Di => Syllable::Ra,
+            Syllable::Ri => Syllable::Me,
+            Syllable::Fi => Syllable::Se,
+            Syllable::Si => Syllable::Le,
+            Syllable::Li => Syllable::Te,
+            _ => syllable,
+        }
+    }
+    fn flat_pitch(pitch: Pitch) -> Pitch {
+        if pitch.sign != PitchSign::Sharp {
+            return pitch;
+        }
+        match Semitones::from(pitch).0.rem_euclid(12) {
+            1 => Pitch::D_FLAT,
+            3 => Pitch::E_FLAT,
+            6 => Pitch::G_FLAT,
+            8 => Pitch::A_FLAT,
+            10 => Pitch::B_FLAT,
+            _ => pitch,
+        }
+    }
+    fn calc_pitch(key: &Key, scale: &Scale, syllable: &Syllable) -> Pitch {
+        let pitch = scale.calc_pitch(key, syllable);
+        if Self::is_flat_syllable(syllable) {
+            Self::flat_pitch(pitch)
+        } else {
+            pitch
+        }
+    }
+    pub fn to_chord(&self, key: &Key, scale: &Scale) -> Chord {
+        let root = self.calc_syllable(key, scale);
+        Chord::new(root, self.quality.intervals().into(), self.bass_interval())
+    }
+    /// Chords with intervals not matching any quality are not supported, roots
+    /// and basses outside of the scale are spelled as flats in flat keys.
+    pub fn from_chord(chord: &Chord, key: &Key, scale: &Scale) -> Option<Self> {
+        let quality = ChordQuality::from_intervals(&chord.intervals.get_intervals())?;
+        let root = Self::key_syllable(key, scale, chord.root);
+        let pitch = Self::calc_pitch(key, scale, &root);
+        let intervals = quality.intervals();
+        let inversion = match chord.bass {
+            None | Some(Interval::Unison) => ChordInversion::RootPosition,
+            Some(bass) => match intervals.iter().position(|x| *x == bass) {
+                Some(0) => ChordInversion::FirstInversion,
+                Some(1) => ChordInversion::SecondInversion,
+                Some(2) => ChordInversion::ThirdInversion,
+                _ => {
+                    let syllable = bass.syllable_on_root(&root);
+                    let syllable = if Self::is_flat_interval(&bass) {
+                        Self::flat_syllable(syllable)
+                    } else {
+                        Self::key_syllable(key, scale, syllable)
+                    };
+                    ChordInversion::SlashBass(Self::calc_pitch(key, scale, &syllable))
+                }
+            },
+        };
+        Some(Self::new(pitch, quality, inversion, Some(root)))
+    }
+    pub fn to_letter_text(&self) -> String {
+        let bass = match self.bass_pitch() {
+            Some(bass) => format!("/{}", bass),
+            None => "".to_owned(),
+        };
+        format!("{}{}{}", self.pitch, self.quality.to_suffix(), bass)
+    }
+    fn number_text(syllable: &Syllable) -> String {
+        let text = syllable.to_text();
+        match text.split_at(1) {
+            (number, "") => number.to_owned(),
+            (number, sign) => format!("{}{}", sign, number),
+        }
+    }
+    /// Natural roots outside of the scale follow the key signature, e.g. "E" is b2 in Eb.
+    fn calc_syllable(&self, key: &Key, scale: &Scale) -> Syllable {
+        self.syllable.unwrap_or_else(|| {
+            let syllable = scale.calc_syllable(key, &self.pitch);
+            match self.pitch.sign {
+                PitchSign::Natural => Self::key_syllable(key, scale, syllable),
+                PitchSign::Flat => Self::flat_syllable(syllable),
+                _ => syllable,
+            }
+        })
+    }
+    pub fn to_number_text(&self, key: &Key, scale: &Scale) -> String {
+        let syllable = self.calc_syllable(key, scale);
+        let bass = match self.bass_syllable(&syllable, key, scale) {
+            Some(bass) => format!("/{}", Self::number_text(&bass)),
+            None => "".to_owned(),
+        };
+        format!(
+            "{}{}{}",
+            Self::number_text(&syllable),
+            self.quality.to_suffix(),
+            bass
+        )
+    }
+    /// Inversions of triads and 7th chords use figured bass, e.g. "V65", others
+    /// got the bass as number, e.g. "ii9/1".
+    pub fn to_roman_text(&self, key: &Key, scale: &Scale) -> String {
+        let syllable = self.calc_syllable(key, scale);
+        let degree = Semitones::from(syllable).0 - Semitones::from(scale.calc_root_syllable()).0;
+        // The tritone is a raised 4th unless the root is spelled flat, e.g. "bv" for Ebm in A minor.
+        let (sign, index) = match degree.rem_euclid(12) {
+            6 if Self::is_flat_syllable(&syllable) => ("b", 4),
+            0 => ("", 0),
+            1 => ("b", 1),
+            2 => ("", 1),
+            3 => ("b", 2),
+            4 => ("", 2),
+            5 => ("", 3),
+            6 => ("#", 3),
+            7 => ("", 4),
+            8 => ("b", 5),
+            9 => ("", 5),
+            10 => ("b", 6),
+            _ => ("", 6),
+        };
+        let numeral = if self.quality.is_minor() {
+            Self::ROMAN_NUMERALS[index].to_lowercase()
+        } else {
+            Self::ROMAN_NUMERALS[index].to_owned()
+        };
+        let suffix = self.quality.to_roman_suffix();
+        let intervals = self.quality.intervals();
+        let is_triad = intervals.len() == 2 && !suffix.ends_with(char::is_numeric);
+        let is_seventh = intervals.len() == 3 && suffix.ends_with('7');
+        let position = self
+            .bass_interval()
+            .map(|bass| intervals.iter().position(|x| *x == bass));
+        let (suffix, figure) = match (position, is_triad, is_seventh) {
+            (None, _, _) => (suffix.to_owned(), "".to_owned()),
+            (Some(Some(0)), true, _) => (suffix.to_owned(), "6".to_owned()),
+            (Some(Some(1)), true, _) => (suffix.to_owned(), "64".to_owned()),
+            (Some(Some(index)), _, true) => (
+                suffix.trim_end_matches('7').to_owned(),
+                ["65", "43", "42"][index].to_owned(),
+            ),
+            (Some(_), _, _) => {
+                let bass = self.bass_syllable(&syllable, key, scale).unwrap();
+                (suffix.to_owned(), format!("/{}", Self::number_text(&bass)))
+            }
+        };
+        format!("{}{}{}{}", sign, numeral, suffix, figure)
+    }
+    pub fn format(&self, format: ChordSymbolFormat, key: &Key, scale: &Scale) -> String {
+        match format {
+            ChordSymbolFormat::Letter => self.to_letter_text(),
+            ChordSymbolFormat::Number => self.to_number_text(key, scale),
+            ChordSymbolFormat::Roman => self.to_roman_text(key, scale),
+        }
+    }
+}
@@ -2,7 +2,7 @@ use std::fmt::Display;
 
 use serde::{Deserialize, Serialize};
 
-use crate::prelude::{Chord, Duration, Entry, EntryPassMode, Tone};
+use crate::prelude::{Accent, Chord, Duration, Entry, EntryPassMode, Ornament, Tone};
 
 #[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
 pub enum CoreEntry {
@@ -10,6 +10,10 @@ pub enum CoreEntry {
     Rest(Duration),
     Tone(Tone, Duration),
     Chord(Chord, Duration),
+    /// Marks the next entry with duration.
+    Accent(Accent),
+    /// Ornaments the next entry with duration.
+    Ornament(Ornament),
 }
 impl Display for CoreEntry {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -18,6 +22,8 @@ impl Display for CoreEntry {
             CoreEntry::Rest(duration) => write!(f, "Rest({})", duration),
             CoreEntry::Tone(tone, duration) => write!(f, "Tone({}, {})", tone, duration),
             CoreEntry::Chord(chord, duration) => write!(f, "Chord({}, {})", chord, duration),
+            CoreEntry::Accent(accent) => write!(f, "Accent({})", accent),
+            CoreEntry::Ornament(ornament) => write!(f, "Ornament({})", ornament),
         }
     }
 }
@@ -28,6 +34,8 @@ impl CoreEntry {
             CoreEntry::Rest(duration) => *duration,
             CoreEntry::Tone(_, duration) => *duration,
             CoreEntry::Chord(_, duration) => *duration,
+            CoreEntry::Accent(_) => Duration::Zero,
+            CoreEntry::Ornament(_) => Duration::Zero,
         }
     }
     pub fn pass_mode(&self) -> EntryPassMode {
@@ -68,6 +76,16 @@ impl CoreEntry {
     pub fn is_chord(&self) -> bool {
         matches!(self, Self::Chord(..))
     }
+
+    /// Returns `true` if the entry is [`Accent`].
+    pub fn is_accent(&self) -> bool {
+        matches!(self, Self::Accent(..))
+    }
+
+    /// Returns `true` if the entry is [`Ornament`].
+    pub fn is_ornament(&self) -> bool {
+        matches!(self, Self::Ornament(..))
+    }
 }
 
 impl CoreEntry {
@@ -94,6 +112,22 @@ impl CoreEntry {
             None
         }
     }
+
+    pub fn as_accent(&self) -> Option<&Accent> {
+        if let Self::Accent(v) = self {
+            Some(v)
+        } else {
+            None
+        }
+    }
+
+    pub fn as_ornament(&self) -> Option<&Ornament> {
+        if let Self::Ornament(v) = self {
+            Some(v)
+        } else {
+            None
+        }
+    }
 }
 
 impl From<()> for CoreEntry {
@@ -119,3 +153,15 @@ impl From<(Chord, Duration)> for CoreEntry {
         Self::Chord(v.0, v.1)
     }
 }
+
+impl From<Accent> for CoreEntry {
+    fn from(v: Accent) -> Self {
+        Self::Accent(v)
+    }
+}
+
+impl From<Ornament> for CoreEntry {
+    fn from(v: Ornament) -> Self {
+        Self::Ornament(v)
+    }
+}
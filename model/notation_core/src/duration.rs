@@ -1,6 +1,7 @@
 use std::cmp::Ordering;
 use std::fmt::Display;
-use std::ops::{Add, Sub};
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
 
 use serde::{Deserialize, Serialize};
 
@@ -12,6 +13,7 @@ pub enum Unit {
     Eighth,
     Sixteenth,
     ThirtySecondth,
+    SixtyFourth,
 }
 
 impl Default for Unit {
@@ -29,6 +31,7 @@ impl Display for Unit {
             Unit::Eighth => "_1_8",
             Unit::Sixteenth => "_1_16",
             Unit::ThirtySecondth => "_1_32",
+            Unit::SixtyFourth => "_1_64",
         })
     }
 }
@@ -41,7 +44,8 @@ impl Unit {
             Self::Quarter => Self::Eighth,
             Self::Eighth => Self::Sixteenth,
             Self::Sixteenth => Self::ThirtySecondth,
-            Self::ThirtySecondth => Self::ThirtySecondth,
+            Self::ThirtySecondth => Self::SixtyFourth,
+            Self::SixtyFourth => Self::SixtyFourth,
         }
     }
     pub fn doubled(&self) -> Self {
@@ -52,6 +56,7 @@ impl Unit {
             Self::Eighth => Self::Quarter,
             Self::Sixteenth => Self::Eighth,
             Self::ThirtySecondth => Self::Sixteenth,
+            Self::SixtyFourth => Self::ThirtySecondth,
         }
     }
 }
@@ -63,6 +68,16 @@ impl Unit {
     pub const _1_8: Self = Unit::Eighth;
     pub const _1_16: Self = Unit::Sixteenth;
     pub const _1_32: Self = Unit::ThirtySecondth;
+    pub const _1_64: Self = Unit::SixtyFourth;
+    pub const ALL: [Unit; 7] = [
+        Unit::Whole,
+        Unit::Half,
+        Unit::Quarter,
+        Unit::Eighth,
+        Unit::Sixteenth,
+        Unit::ThirtySecondth,
+        Unit::SixtyFourth,
+    ];
     pub fn to_ident(&self) -> String {
         format!("{}", self)
     }
@@ -74,31 +89,156 @@ impl Unit {
             "_1_8" => Self::_1_8,
             "_1_16" => Self::_1_16,
             "_1_32" => Self::_1_32,
+            "_1_64" => Self::_1_64,
             _ => Self::_1_4,
         }
     }
 }
 
-#[derive(Copy, Clone, PartialEq, PartialOrd, Serialize, Deserialize, Debug, Default)]
-pub struct Units(pub f32);
+/// Exact time in whole notes, kept as a reduced fraction, converted to f32 only when
+/// rendering or talking to audio.
+#[derive(Copy, Clone, Serialize, Deserialize, Debug)]
+#[serde(try_from = "UnitsData", into = "UnitsData")]
+pub struct Units {
+    numerator: i64,
+    denominator: i64,
+}
+
+/// Serialized form of units, still reading the f32 used before the fractions.
+#[derive(Copy, Clone, Serialize, Deserialize, Debug)]
+#[serde(untagged)]
+enum UnitsData {
+    Fraction { numerator: i64, denominator: i64 },
+    Whole(f32),
+}
+
+impl TryFrom<UnitsData> for Units {
+    type Error = String;
+    fn try_from(v: UnitsData) -> Result<Self, Self::Error> {
+        match v {
+            UnitsData::Fraction {
+                numerator,
+                denominator,
+            } => {
+                if denominator == 0 {
+                    Err(format!("Invalid Units: {}/{}", numerator, denominator))
+                } else {
+                    Ok(Self::new(numerator, denominator))
+                }
+            }
+            UnitsData::Whole(v) => {
+                if v.is_finite() {
+                    Ok(Self::from_f32(v))
+                } else {
+                    Err(format!("Invalid Units: {}", v))
+                }
+            }
+        }
+    }
+}
+
+impl From<Units> for UnitsData {
+    fn from(v: Units) -> Self {
+        Self::Fraction {
+            numerator: v.numerator,
+            denominator: v.denominator,
+        }
+    }
+}
+
+const fn gcd(a: i64, b: i64) -> i64 {
+    let mut a = a.abs();
+    let mut b = b.abs();
+    while b != 0 {
+        let t = a % b;
+        a = b;
+        b = t;
+    }
+    a
+}
 
 impl Units {
-    pub const _MIN_ACCURACY: f32 = 0.00001;
-    pub const MIN_ACCURACY: Units = Units(Self::_MIN_ACCURACY);
-    /*
-     * This is quite messy here, the pasition passed checking needs some cleanup,
-     * mainly for control the guitar view timing, and seeking next note
-     */
-    pub const HALF_MIN_ACCURACY: Units = Units(Self::_MIN_ACCURACY * 0.5);
+    pub const ZERO: Units = Units::new(0, 1);
+    /// Resolution used when converting from f32, e.g. for the playing clock.
+    pub const F32_RESOLUTION: i64 = 1 << 20;
+    /// Smallest step, used to order events at the same position, e.g. a note off
+    /// right before the next note on.
+    pub const TICK: Units = Units::new(1, Self::F32_RESOLUTION);
+
+    pub const fn new(numerator: i64, denominator: i64) -> Self {
+        let (numerator, denominator) = if denominator < 0 {
+            (-numerator, -denominator)
+        } else {
+            (numerator, denominator)
+        };
+        let divisor = gcd(numerator, denominator);
+        if divisor <= 1 {
+            Self {
+                numerator,
+                denominator,
+            }
+        } else {
+            Self {
+                numerator: numerator / divisor,
+                denominator: denominator / divisor,
+            }
+        }
+    }
+    pub fn numerator(&self) -> i64 {
+        self.numerator
+    }
+    pub fn denominator(&self) -> i64 {
+        self.denominator
+    }
+    pub fn from_f32(v: f32) -> Self {
+        Self::new(
+            (v as f64 * Self::F32_RESOLUTION as f64).round() as i64,
+            Self::F32_RESOLUTION,
+        )
+    }
+    pub fn as_f32(&self) -> f32 {
+        (self.numerator as f64 / self.denominator as f64) as f32
+    }
+    pub fn is_zero(&self) -> bool {
+        self.numerator == 0
+    }
+    pub fn is_positive(&self) -> bool {
+        self.numerator > 0
+    }
+    /// Largest integer not bigger than the value.
+    pub fn floor(&self) -> i64 {
+        self.numerator.div_euclid(self.denominator)
+    }
+}
 
-    pub fn is_bigger_than(&self, other: &Units) -> bool {
-        self.0 > other.0 + Self::_MIN_ACCURACY
+impl Default for Units {
+    fn default() -> Self {
+        Self::ZERO
     }
 }
 
 impl Display for Units {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "<Units>({})", self.0)
+        write!(f, "<Units>({}/{})", self.numerator, self.denominator)
+    }
+}
+
+impl PartialEq for Units {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+impl Eq for Units {}
+
+impl PartialOrd for Units {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for Units {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.numerator as i128 * other.denominator as i128)
+            .cmp(&(other.numerator as i128 * self.denominator as i128))
     }
 }
 
@@ -106,7 +246,11 @@ impl Add for Units {
     type Output = Self;
 
     fn add(self, rhs: Self) -> Self::Output {
-        Units(self.0 + rhs.0)
+        let divisor = gcd(self.denominator, rhs.denominator);
+        Units::new(
+            self.numerator * (rhs.denominator / divisor) + rhs.numerator * (self.denominator / divisor),
+            self.denominator / divisor * rhs.denominator,
+        )
     }
 }
 
@@ -114,20 +258,121 @@ impl Sub for Units {
     type Output = Self;
 
     fn sub(self, rhs: Self) -> Self::Output {
-        Units(self.0 - rhs.0)
+        self + (-rhs)
     }
 }
 
-impl Units {
-    pub fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        if self.0 == other.0 {
-            Ordering::Equal
-        } else if self.0 < other.0 {
-            Ordering::Less
+impl Neg for Units {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Units::new(-self.numerator, self.denominator)
+    }
+}
+
+impl AddAssign for Units {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Units {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<i64> for Units {
+    type Output = Self;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        Units::new(self.numerator * rhs, self.denominator)
+    }
+}
+
+impl Div<i64> for Units {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: i64) -> Self::Output {
+        Units::new(self.numerator, self.denominator * rhs)
+    }
+}
+
+impl Mul for Units {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Units::new(
+            self.numerator * rhs.numerator,
+            self.denominator * rhs.denominator,
+        )
+    }
+}
+
+/// Ratio between two units, e.g. how many beats fit in a bar.
+impl Div for Units {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        Units::new(
+            self.numerator * rhs.denominator,
+            self.denominator * rhs.numerator,
+        )
+    }
+}
+
+impl Sum for Units {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Units::ZERO, |a, b| a + b)
+    }
+}
+
+/// Actual notes played in the time of normal ones, e.g. 5:4 for quintuplets.
+#[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct Tuplet {
+    pub actual: u8,
+    pub normal: u8,
+}
+
+impl Display for Tuplet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.actual, self.normal)
+    }
+}
+
+impl Tuplet {
+    pub const NONE: Self = Self::new(1, 1);
+    pub const TRIPLET: Self = Self::new(3, 2);
+
+    pub const fn new(actual: u8, normal: u8) -> Self {
+        if actual == 0 || normal == 0 {
+            Self {
+                actual: 1,
+                normal: 1,
+            }
         } else {
-            Ordering::Greater
+            Self { actual, normal }
         }
     }
+    /// The usual ratio for the number of notes, duplets are 2:3, others are played in
+    /// the time of the largest power of 2 below, e.g. 5:4, 6:4, 7:4 and 9:8.
+    pub fn of_actual(actual: u8) -> Self {
+        match actual {
+            0 | 1 => Self::NONE,
+            2 => Self::new(2, 3),
+            _ => {
+                let mut normal = 1;
+                while normal * 2 < actual {
+                    normal *= 2;
+                }
+                Self::new(actual, normal)
+            }
+        }
+    }
+    pub fn is_none(&self) -> bool {
+        self.actual == self.normal
+    }
 }
 
 // https://hellomusictheory.com/learn/tuplets/
@@ -138,6 +383,9 @@ pub enum Duration {
     Dotted(Unit),
     Triplet(Unit),
     DottedTriplet(Unit),
+    /// Any number of dots and any tuplet, only used when the ones above can't express it,
+    /// see `Duration::new()`.
+    Custom(Unit, u8, Tuplet),
 }
 
 impl Display for Duration {
@@ -157,6 +405,18 @@ impl Display for Duration {
                 write!(f, "DT")?;
                 d.fmt(f)
             }
+            Duration::Custom(d, dots, tuplet) => {
+                write!(f, "{}", "D".repeat(*dots as usize))?;
+                if tuplet.is_none() {
+                } else if *tuplet == Tuplet::TRIPLET {
+                    write!(f, "T")?;
+                } else if *tuplet == Tuplet::of_actual(tuplet.actual) {
+                    write!(f, "T{}", tuplet.actual)?;
+                } else {
+                    write!(f, "T{}in{}", tuplet.actual, tuplet.normal)?;
+                }
+                d.fmt(f)
+            }
         }
     }
 }
@@ -187,6 +447,51 @@ impl Duration {
     pub const DT_1_8: Self = Duration::DottedTriplet(Unit::Eighth);
     pub const DT_1_16: Self = Duration::DottedTriplet(Unit::Sixteenth);
     pub const DT_1_32: Self = Duration::DottedTriplet(Unit::ThirtySecondth);
+    pub const _1_64: Self = Duration::Simple(Unit::SixtyFourth);
+    pub const D_1_64: Self = Duration::Dotted(Unit::SixtyFourth);
+    pub const T_1_64: Self = Duration::Triplet(Unit::SixtyFourth);
+    pub const DT_1_64: Self = Duration::DottedTriplet(Unit::SixtyFourth);
+    pub const MAX_DOTS: u8 = 3;
+
+    /// Uses the shorthand variants when possible, dots are limited to `MAX_DOTS`.
+    pub fn new(unit: Unit, dots: u8, tuplet: Tuplet) -> Self {
+        let dots = dots.min(Self::MAX_DOTS);
+        let tuplet = if tuplet.is_none() {
+            Tuplet::NONE
+        } else {
+            tuplet
+        };
+        match (dots, tuplet) {
+            (0, Tuplet::NONE) => Self::Simple(unit),
+            (1, Tuplet::NONE) => Self::Dotted(unit),
+            (0, Tuplet::TRIPLET) => Self::Triplet(unit),
+            (1, Tuplet::TRIPLET) => Self::DottedTriplet(unit),
+            _ => Self::Custom(unit, dots, tuplet),
+        }
+    }
+    pub fn unit(&self) -> Option<Unit> {
+        match self {
+            Self::Zero => None,
+            Self::Simple(v) | Self::Dotted(v) | Self::Triplet(v) | Self::DottedTriplet(v) => {
+                Some(*v)
+            }
+            Self::Custom(v, _, _) => Some(*v),
+        }
+    }
+    pub fn dots(&self) -> u8 {
+        match self {
+            Self::Dotted(_) | Self::DottedTriplet(_) => 1,
+            Self::Custom(_, dots, _) => *dots,
+            _ => 0,
+        }
+    }
+    pub fn tuplet(&self) -> Tuplet {
+        match self {
+            Self::Triplet(_) | Self::DottedTriplet(_) => Tuplet::TRIPLET,
+            Self::Custom(_, _, tuplet) => *tuplet,
+            _ => Tuplet::NONE,
+        }
+    }
 
     /// Returns `true` if the duration is [`Simple`].
     pub fn is_simple(&self) -> bool {
@@ -200,41 +505,65 @@ impl Duration {
             None
         }
     }
+    /// Split the units into durations that can be represented, longest first.
+    pub fn split_units(units: Units) -> Vec<Duration> {
+        let mut candidates: Vec<(Units, Duration)> = Vec::new();
+        for unit in Unit::ALL {
+            for duration in [
+                Duration::Dotted(unit),
+                Duration::Simple(unit),
+                Duration::DottedTriplet(unit),
+                Duration::Triplet(unit),
+            ] {
+                candidates.push((Units::from(duration), duration));
+            }
+        }
+        candidates.sort_by_key(|x| std::cmp::Reverse(x.0));
+        let mut result = Vec::new();
+        let mut remain = units;
+        while remain.is_positive() {
+            match candidates.iter().find(|(x, _)| *x <= remain) {
+                Some((value, duration)) => {
+                    result.push(*duration);
+                    remain -= *value;
+                }
+                None => break,
+            }
+        }
+        result
+    }
 }
 
 impl Duration {
     pub fn to_ident(&self) -> String {
         format!("{}", self)
     }
+    /// Dots are written as leading `D`s, tuplets as `T` for triplets, `T5` for the usual
+    /// ratio of 5 notes, or `T5in6` for other ratios, e.g. `DD_1_4` or `T5_1_16`.
     pub fn from_ident(ident: &str) -> Self {
-        match ident {
-            "_0" => Self::_0,
-            "_1" => Self::_1,
-            "_1_2" => Self::_1_2,
-            "_1_4" => Self::_1_4,
-            "_1_8" => Self::_1_8,
-            "_1_16" => Self::_1_16,
-            "_1_32" => Self::_1_32,
-            "D_1" => Self::D_1,
-            "D_1_2" => Self::D_1_2,
-            "D_1_4" => Self::D_1_4,
-            "D_1_8" => Self::D_1_8,
-            "D_1_16" => Self::D_1_16,
-            "D_1_32" => Self::D_1_32,
-            "T_1" => Self::T_1,
-            "T_1_2" => Self::T_1_2,
-            "T_1_4" => Self::T_1_4,
-            "T_1_8" => Self::T_1_8,
-            "T_1_16" => Self::T_1_16,
-            "T_1_32" => Self::T_1_32,
-            "DT_1" => Self::DT_1,
-            "DT_1_2" => Self::DT_1_2,
-            "DT_1_4" => Self::DT_1_4,
-            "DT_1_8" => Self::DT_1_8,
-            "DT_1_16" => Self::DT_1_16,
-            "DT_1_32" => Self::DT_1_32,
-            _ => Self::_0,
+        Self::parse_ident(ident).unwrap_or(Self::_0)
+    }
+    fn parse_ident(ident: &str) -> Option<Self> {
+        if ident == "_0" {
+            return Some(Self::_0);
         }
+        let (prefix, unit) = ident.split_at(ident.find('_')?);
+        let unit = Unit::ALL.into_iter().find(|x| x.to_ident() == unit)?;
+        let dots = prefix.chars().take_while(|x| *x == 'D').count();
+        let tuplet = match &prefix[dots..] {
+            "" => Tuplet::NONE,
+            "T" => Tuplet::TRIPLET,
+            ratio => {
+                let ratio = ratio.strip_prefix('T')?;
+                match ratio.split_once("in") {
+                    Some((actual, normal)) => {
+                        Tuplet::new(actual.parse().ok()?, normal.parse().ok()?)
+                    }
+                    None => Tuplet::of_actual(ratio.parse().ok()?),
+                }
+            }
+        };
+        Some(Self::new(unit, dots as u8, tuplet))
     }
 }
 
@@ -250,35 +579,34 @@ impl From<Unit> for Duration {
     }
 }
 
-impl From<f32> for Units {
-    fn from(v: f32) -> Self {
-        Self(v)
-    }
-}
-
 impl From<Unit> for Units {
     fn from(v: Unit) -> Self {
         match v {
-            Unit::Whole => 1.0,
-            Unit::Half => 1.0 / 2.0,
-            Unit::Quarter => 1.0 / 4.0,
-            Unit::Eighth => 1.0 / 8.0,
-            Unit::Sixteenth => 1.0 / 16.0,
-            Unit::ThirtySecondth => 1.0 / 32.0,
+            Unit::Whole => Units::new(1, 1),
+            Unit::Half => Units::new(1, 2),
+            Unit::Quarter => Units::new(1, 4),
+            Unit::Eighth => Units::new(1, 8),
+            Unit::Sixteenth => Units::new(1, 16),
+            Unit::ThirtySecondth => Units::new(1, 32),
+            Unit::SixtyFourth => Units::new(1, 64),
         }
-        .into()
     }
 }
 
 impl From<Duration> for Units {
     fn from(v: Duration) -> Self {
         match v {
-            Duration::Zero => 0.0,
-            Duration::Simple(v) => Units::from(v).0,
-            Duration::Dotted(v) => Units::from(v).0 * 1.5,
-            Duration::Triplet(v) => Units::from(v).0 * 2.0 / 3.0,
-            Duration::DottedTriplet(v) => Units::from(v).0 * 4.0 / 3.0,
+            Duration::Zero => Units::ZERO,
+            Duration::Simple(v) => Units::from(v),
+            Duration::Dotted(v) => Units::from(v) * 3 / 2,
+            Duration::Triplet(v) => Units::from(v) * 2 / 3,
+            Duration::DottedTriplet(v) => Units::from(v) * 4 / 3,
+            Duration::Custom(v, dots, tuplet) => {
+                let dots = dots.min(Duration::MAX_DOTS) as u32;
+                Units::from(v) * (2_i64.pow(dots + 1) - 1) / 2_i64.pow(dots)
+                    * tuplet.normal as i64
+                    / tuplet.actual as i64
+            }
         }
-        .into()
     }
 }
@@ -0,0 +1,169 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+/// Dynamic markings, notes without any marking are played as mezzo forte.
+#[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug, Default)]
+pub enum Dynamic {
+    Pianissimo,
+    Piano,
+    MezzoPiano,
+    #[default]
+    MezzoForte,
+    Forte,
+    Fortissimo,
+}
+impl Display for Dynamic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_ident())
+    }
+}
+impl Dynamic {
+    pub fn to_ident(&self) -> &'static str {
+        match self {
+            Self::Pianissimo => "pp",
+            Self::Piano => "p",
+            Self::MezzoPiano => "mp",
+            Self::MezzoForte => "mf",
+            Self::Forte => "f",
+            Self::Fortissimo => "ff",
+        }
+    }
+    pub fn from_ident(ident: &str) -> Option<Self> {
+        match ident {
+            "pp" => Some(Self::Pianissimo),
+            "p" => Some(Self::Piano),
+            "mp" => Some(Self::MezzoPiano),
+            "mf" => Some(Self::MezzoForte),
+            "f" => Some(Self::Forte),
+            "ff" => Some(Self::Fortissimo),
+            _ => None,
+        }
+    }
+    /// Applied to the channel velocity.
+    pub fn velocity_factor(&self) -> f32 {
+        match self {
+            Self::Pianissimo => 0.45,
+            Self::Piano => 0.6,
+            Self::MezzoPiano => 0.8,
+            Self::MezzoForte => 1.0,
+            Self::Forte => 1.15,
+            Self::Fortissimo => 1.3,
+        }
+    }
+    pub fn louder(&self) -> Self {
+        match self {
+            Self::Pianissimo => Self::Piano,
+            Self::Piano => Self::MezzoPiano,
+            Self::MezzoPiano => Self::MezzoForte,
+            Self::MezzoForte => Self::Forte,
+            _ => Self::Fortissimo,
+        }
+    }
+    pub fn softer(&self) -> Self {
+        match self {
+            Self::Fortissimo => Self::Forte,
+            Self::Forte => Self::MezzoForte,
+            Self::MezzoForte => Self::MezzoPiano,
+            Self::MezzoPiano => Self::Piano,
+            _ => Self::Pianissimo,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub enum DynamicCurveKind {
+    Crescendo,
+    Diminuendo,
+}
+impl Display for DynamicCurveKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+impl DynamicCurveKind {
+    pub fn to_ident(&self) -> String {
+        format!("{}", self)
+    }
+    pub fn from_ident(ident: &str) -> Self {
+        match ident {
+            "Dim" | "Diminuendo" => Self::Diminuendo,
+            _ => Self::Crescendo,
+        }
+    }
+}
+
+/// Crescendo or diminuendo over the beats, to the target dynamic, or one step from the
+/// current one if not given.
+#[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct DynamicCurve {
+    pub kind: DynamicCurveKind,
+    pub beats: u8,
+    pub target: Option<Dynamic>,
+}
+impl Display for DynamicCurve {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.target {
+            Some(target) => write!(f, "{} {} {}", self.kind, self.beats, target),
+            None => write!(f, "{} {}", self.kind, self.beats),
+        }
+    }
+}
+impl DynamicCurve {
+    pub fn new(kind: DynamicCurveKind, beats: u8, target: Option<Dynamic>) -> Self {
+        Self {
+            kind,
+            beats,
+            target,
+        }
+    }
+    pub fn is_crescendo(&self) -> bool {
+        self.kind == DynamicCurveKind::Crescendo
+    }
+    pub fn calc_target(&self, from: Dynamic) -> Dynamic {
+        self.target.unwrap_or_else(|| match self.kind {
+            DynamicCurveKind::Crescendo => from.louder(),
+            DynamicCurveKind::Diminuendo => from.softer(),
+        })
+    }
+}
+
+/// Marks the next entry in the track.
+#[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub enum Accent {
+    Accent,
+    Marcato,
+    Ghost,
+}
+impl Display for Accent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+impl Accent {
+    pub fn to_ident(&self) -> String {
+        format!("{}", self)
+    }
+    pub fn from_ident(ident: &str) -> Self {
+        match ident {
+            "Marcato" => Self::Marcato,
+            "Ghost" => Self::Ghost,
+            _ => Self::Accent,
+        }
+    }
+    pub fn to_glyph(&self) -> &'static str {
+        match self {
+            Self::Accent => ">",
+            Self::Marcato => "^",
+            Self::Ghost => "( )",
+        }
+    }
+    /// Applied on top of the dynamics.
+    pub fn velocity_factor(&self) -> f32 {
+        match self {
+            Self::Accent => 1.25,
+            Self::Marcato => 1.4,
+            Self::Ghost => 0.5,
+        }
+    }
+}
@@ -2,6 +2,7 @@ pub mod chord;
 pub mod chord_symbol;
 pub mod core_entry;
 pub mod duration;
+pub mod dynamics;
 pub mod entry;
 pub mod interval;
 pub mod intervals;
@@ -9,23 +10,27 @@ pub mod key;
 pub mod meta_entry;
 pub mod note;
 pub mod octave;
+pub mod ornament;
 pub mod pitch;
 pub mod semitones;
 pub mod scale;
 pub mod signature;
 pub mod syllable;
 pub mod tempo;
+pub mod tempo_curve;
 pub mod tone;
 
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::chord::Chord;
     #[doc(hidden)]
-    pub use crate::chord_symbol::{ChordInversion, ChordQuality, ChordSymbol};
+    pub use crate::chord_symbol::{ChordInversion, ChordQuality, ChordSymbol, ChordSymbolFormat};
     #[doc(hidden)]
     pub use crate::core_entry::CoreEntry;
     #[doc(hidden)]
-    pub use crate::duration::{Duration, Unit, Units};
+    pub use crate::duration::{Duration, Tuplet, Unit, Units};
+    #[doc(hidden)]
+    pub use crate::dynamics::{Accent, Dynamic, DynamicCurve, DynamicCurveKind};
     #[doc(hidden)]
     pub use crate::entry::{Entry, EntryPassMode};
     #[doc(hidden)]
@@ -41,6 +46,8 @@ pub mod prelude {
     #[doc(hidden)]
     pub use crate::octave::Octave;
     #[doc(hidden)]
+    pub use crate::ornament::Ornament;
+    #[doc(hidden)]
     pub use crate::pitch::{Pitch, PitchName, PitchSign};
     #[doc(hidden)]
     pub use crate::semitones::Semitones;
@@ -53,5 +60,7 @@ pub mod prelude {
     #[doc(hidden)]
     pub use crate::tempo::{Bpm, BpmRange, Tempo};
     #[doc(hidden)]
+    pub use crate::tempo_curve::{TempoCurve, TempoCurveKind};
+    #[doc(hidden)]
     pub use crate::tone::Tone;
 }
@@ -2,7 +2,7 @@ use std::fmt::Display;
 
 use serde::{Deserialize, Serialize};
 
-use crate::prelude::{Entry, Key, Scale, Signature, Tempo};
+use crate::prelude::{Dynamic, DynamicCurve, Entry, Key, Scale, Signature, Tempo, TempoCurve};
 
 #[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
 pub enum MetaEntry {
@@ -10,6 +10,9 @@ pub enum MetaEntry {
     Tempo(Tempo),
     Scale(Scale),
     Key(Key),
+    TempoCurve(TempoCurve),
+    Dynamic(Dynamic),
+    DynamicCurve(DynamicCurve),
 }
 impl Display for MetaEntry {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -18,6 +21,9 @@ impl Display for MetaEntry {
             MetaEntry::Tempo(x) => write!(f, "Tempo({})", x),
             MetaEntry::Scale(x) => write!(f, "Scale({})", x),
             MetaEntry::Key(x) => write!(f, "Key({})", x),
+            MetaEntry::TempoCurve(x) => write!(f, "TempoCurve({})", x),
+            MetaEntry::Dynamic(x) => write!(f, "Dynamic({})", x),
+            MetaEntry::DynamicCurve(x) => write!(f, "DynamicCurve({})", x),
         }
     }
 }
@@ -34,6 +40,16 @@ impl MetaEntry {
     pub fn is_tempo(&self) -> bool {
         matches!(self, Self::Tempo(..))
     }
+
+    /// Returns `true` if the entry is [`TempoCurve`].
+    pub fn is_tempo_curve(&self) -> bool {
+        matches!(self, Self::TempoCurve(..))
+    }
+
+    /// Returns `true` if the entry is [`Dynamic`] or [`DynamicCurve`].
+    pub fn is_dynamics(&self) -> bool {
+        matches!(self, Self::Dynamic(..) | Self::DynamicCurve(..))
+    }
 }
 
 impl From<Signature> for MetaEntry {
@@ -47,3 +63,21 @@ impl From<Tempo> for MetaEntry {
         Self::Tempo(v)
     }
 }
+
+impl From<TempoCurve> for MetaEntry {
+    fn from(v: TempoCurve) -> Self {
+        Self::TempoCurve(v)
+    }
+}
+
+impl From<Dynamic> for MetaEntry {
+    fn from(v: Dynamic) -> Self {
+        Self::Dynamic(v)
+    }
+}
+
+impl From<DynamicCurve> for MetaEntry {
+    fn from(v: DynamicCurve) -> Self {
+        Self::DynamicCurve(v)
+    }
+}
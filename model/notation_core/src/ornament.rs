@@ -0,0 +1,56 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::{Key, Note, Scale, Semitones};
+
+/// Marks the next entry in the track, the ornament notes take no time in the bar.
+#[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub enum Ornament {
+    /// Acciaccatura, played before the beat, in semitones from the main note.
+    Grace(Semitones),
+    /// Played on the beat, in semitones from the main note.
+    Appoggiatura(Semitones),
+    /// Main note and the one above it in the scale.
+    Mordent,
+    /// Main note and the one below it in the scale.
+    LowerMordent,
+    /// Notes above and below the main note in the scale, around it.
+    Turn,
+}
+impl Display for Ornament {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Grace(x) => write!(f, "Grace({})", x),
+            Self::Appoggiatura(x) => write!(f, "Appoggiatura({})", x),
+            _ => write!(f, "{:?}", self),
+        }
+    }
+}
+impl Ornament {
+    /// The time is taken from the previous note, otherwise from the main note.
+    pub fn is_before_beat(&self) -> bool {
+        matches!(self, Self::Grace(_))
+    }
+    pub fn to_glyph(&self) -> &'static str {
+        match self {
+            Self::Grace(_) | Self::Appoggiatura(_) => "",
+            Self::Mordent => "~",
+            Self::LowerMordent => "~|",
+            Self::Turn => "S",
+        }
+    }
+    /// Notes played before the main note.
+    pub fn calc_notes(&self, scale: &Scale, key: &Key, note: &Note) -> Vec<Note> {
+        let upper = || scale.calc_neighbour_note(key, note, true);
+        let lower = || scale.calc_neighbour_note(key, note, false);
+        match self {
+            Self::Grace(x) | Self::Appoggiatura(x) => {
+                vec![scale.calc_note_from_semitones(key, Semitones::from(*note) + *x)]
+            }
+            Self::Mordent => vec![*note, upper()],
+            Self::LowerMordent => vec![*note, lower()],
+            Self::Turn => vec![upper(), *note, lower()],
+        }
+    }
+}
@@ -14,6 +14,13 @@ pub enum Scale {
     Mixolydian,
     Aeolian,
     Locrian,
+    HarmonicMinor,
+    MelodicMinor,
+    MajorPentatonic,
+    MinorPentatonic,
+    Blues,
+    WholeTone,
+    Diminished,
 }
 impl Display for Scale {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -30,8 +37,10 @@ impl Scale {
     pub const Major: Scale = Scale::Ionian;
     #[allow(non_upper_case_globals)]
     pub const Minor: Scale = Scale::Aeolian;
-    pub const ALL: [ Scale; 7 ] = [
+    pub const ALL: [ Scale; 14 ] = [
         Scale::Ionian, Scale::Dorian, Scale::Phrygian, Scale::Lydian, Scale::Mixolydian, Scale::Aeolian, Scale::Locrian,
+        Scale::HarmonicMinor, Scale::MelodicMinor, Scale::MajorPentatonic, Scale::MinorPentatonic, Scale::Blues,
+        Scale::WholeTone, Scale::Diminished,
     ];
 
     pub fn to_ident(&self) -> String {
@@ -48,9 +57,41 @@ impl Scale {
             "Mixolydian" => Self::Mixolydian,
             "Aeolian" => Self::Aeolian,
             "Locrian" => Self::Locrian,
+            "HarmonicMinor" => Self::HarmonicMinor,
+            "MelodicMinor" => Self::MelodicMinor,
+            "MajorPentatonic" => Self::MajorPentatonic,
+            "MinorPentatonic" => Self::MinorPentatonic,
+            "Blues" => Self::Blues,
+            "WholeTone" => Self::WholeTone,
+            "Diminished" => Self::Diminished,
             _ => Self::default(),
         }
     }
+    /// The mode providing do and key signatures, other scales are named as
+    /// altered or skipped degrees of it, e.g. Si for the raised 7th in harmonic minor.
+    pub fn diatonic_mode(&self) -> Scale {
+        match self {
+            Scale::HarmonicMinor | Scale::MelodicMinor | Scale::MinorPentatonic | Scale::Blues => {
+                Scale::Aeolian
+            }
+            Scale::MajorPentatonic | Scale::WholeTone | Scale::Diminished => Scale::Ionian,
+            _ => *self,
+        }
+    }
+    pub fn is_diatonic(&self) -> bool {
+        self.diatonic_mode() == *self
+    }
+    /// Got a minor third but no major third above the root.
+    pub fn is_minor(&self) -> bool {
+        let root = Semitones::from(self.calc_root_syllable()).0;
+        let thirds: Vec<i8> = self
+            .get_syllables()
+            .iter()
+            .map(|x| (Semitones::from(*x).0 - root).rem_euclid(12))
+            .filter(|x| *x == 3 || *x == 4)
+            .collect();
+        thirds == vec![3]
+    }
     pub fn get_syllables(&self) -> Vec<Syllable> {
         match self {
             Scale::Ionian => vec![
@@ -116,6 +157,65 @@ impl Scale {
                 Syllable::So,
                 Syllable::La,
             ],
+            Scale::HarmonicMinor => vec![
+                Syllable::La,
+                Syllable::Ti,
+                Syllable::Do,
+                Syllable::Re,
+                Syllable::Mi,
+                Syllable::Fa,
+                Syllable::Si,
+            ],
+            Scale::MelodicMinor => vec![
+                Syllable::La,
+                Syllable::Ti,
+                Syllable::Do,
+                Syllable::Re,
+                Syllable::Mi,
+                Syllable::Fi,
+                Syllable::Si,
+            ],
+            Scale::MajorPentatonic => vec![
+                Syllable::Do,
+                Syllable::Re,
+                Syllable::Mi,
+                Syllable::So,
+                Syllable::La,
+            ],
+            Scale::MinorPentatonic => vec![
+                Syllable::La,
+                Syllable::Do,
+                Syllable::Re,
+                Syllable::Mi,
+                Syllable::So,
+            ],
+            Scale::Blues => vec![
+                Syllable::La,
+                Syllable::Do,
+                Syllable::Re,
+                Syllable::Me,
+                Syllable::Mi,
+                Syllable::So,
+            ],
+            Scale::WholeTone => vec![
+                Syllable::Do,
+                Syllable::Re,
+                Syllable::Mi,
+                Syllable::Fi,
+                Syllable::Si,
+                Syllable::Li,
+            ],
+            // Whole-half diminished
+            Scale::Diminished => vec![
+                Syllable::Do,
+                Syllable::Re,
+                Syllable::Me,
+                Syllable::Fa,
+                Syllable::Se,
+                Syllable::Le,
+                Syllable::La,
+                Syllable::Ti,
+            ],
         }
     }
     pub fn calc_key_index(&self, key: Key) -> usize {
@@ -238,6 +338,7 @@ impl Scale {
                 Key::A,
                 Key::E,
             ],
+            _ => self.diatonic_mode().get_keys(),
         }
     }
 }
@@ -252,6 +353,7 @@ impl Scale {
             Scale::Mixolydian => 5,
             Scale::Aeolian => 3,
             Scale::Locrian => 1,
+            _ => self.diatonic_mode().calc_do_offset(),
         }
     }
     pub fn calc_do_semitones(&self, key: &Key) -> Semitones {
@@ -309,6 +411,23 @@ impl Scale {
         let (pitch, octave) = semitones.as_pitch_octave();
         self.calc_note_from_pitch(key, &pitch, &octave)
     }
+    /// Next note in the scale above or below the note, e.g. for ornaments.
+    pub fn calc_neighbour_note(&self, key: &Key, note: &Note, upper: bool) -> Note {
+        let scale_semitones: Vec<i8> = self
+            .get_syllables()
+            .iter()
+            .map(|x| Semitones::from(*x).0)
+            .collect();
+        let syllable = Semitones::from(note.syllable).0;
+        let step = (1..12)
+            .find(|x| {
+                let neighbour = if upper { syllable + x } else { syllable - x };
+                scale_semitones.contains(&neighbour.rem_euclid(12))
+            })
+            .unwrap_or(if upper { 2 } else { 1 });
+        let offset = if upper { step } else { -step };
+        self.calc_note_from_semitones(key, Semitones::from(*note) + Semitones(offset))
+    }
     pub fn calc_click_note(&self, key: &Key, octave: &Octave, syllable: &Syllable) -> Note {
         let pitch = self.calc_pitch(key, syllable);
         Note::new(*octave, pitch, *syllable)
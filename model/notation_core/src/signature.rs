@@ -21,10 +21,17 @@ impl Display for Signature {
             Unit::Eighth => 8,
             Unit::Sixteenth => 16,
             Unit::ThirtySecondth => 32,
+            Unit::SixtyFourth => 64,
         })
     }
 }
 
+impl Default for Signature {
+    fn default() -> Self {
+        Self::_4_4
+    }
+}
+
 impl Signature {
     pub fn new(beat_unit: Unit, bar_beats: u8) -> Self {
         Self {
@@ -61,7 +68,7 @@ impl From<f32> for Beats {
 
 impl From<Signature> for Units {
     fn from(v: Signature) -> Self {
-        Self::from(Units::from(v.beat_unit).0 * (v.bar_beats as f32))
+        Units::from(v.beat_unit) * v.bar_beats as i64
     }
 }
 
@@ -73,6 +80,6 @@ impl From<Signature> for Beats {
 
 impl From<(Signature, Units)> for Beats {
     fn from((signature, units): (Signature, Units)) -> Self {
-        Self::from(units.0 / Units::from(signature.beat_unit).0)
+        Self::from((units / Units::from(signature.beat_unit)).as_f32())
     }
 }
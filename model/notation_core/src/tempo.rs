@@ -5,7 +5,7 @@ use serde::{Deserialize, Serialize};
 // https://hellomusictheory.com/learn/musical-term-for-slow/
 // https://hellomusictheory.com/learn/musical-term-for-fast/
 
-#[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug, Default)]
 pub enum Tempo {
     Larghissimo,
     Adagissimo,
@@ -18,6 +18,7 @@ pub enum Tempo {
     Andante,
     Andantino,
     MarciaModerato,
+    #[default]
     Moderato,
     Allegretto,
     AllegroModerato,
@@ -0,0 +1,80 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub enum TempoCurveKind {
+    Ritardando,
+    Accelerando,
+    Fermata,
+}
+impl Display for TempoCurveKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+impl TempoCurveKind {
+    pub fn to_ident(&self) -> String {
+        format!("{}", self)
+    }
+    pub fn from_ident(ident: &str) -> Self {
+        match ident {
+            "Rit" | "Ritardando" => Self::Ritardando,
+            "Accel" | "Accelerando" => Self::Accelerando,
+            _ => Self::Fermata,
+        }
+    }
+    pub fn default_percent(&self) -> u8 {
+        match self {
+            Self::Ritardando => 30,
+            Self::Accelerando => 30,
+            Self::Fermata => 100,
+        }
+    }
+    pub fn to_mark(&self) -> &'static str {
+        match self {
+            Self::Ritardando => "rit.",
+            Self::Accelerando => "accel.",
+            Self::Fermata => "",
+        }
+    }
+}
+
+/// Gradual tempo change over the beats, rit. and accel. slow down or speed up by the percentage,
+/// and keep the new speed until the next tempo change.
+/// A fermata takes the percentage of extra time to play the beats, then returns to the tempo.
+#[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct TempoCurve {
+    pub kind: TempoCurveKind,
+    pub beats: u8,
+    pub percent: u8,
+}
+impl Display for TempoCurve {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} {}%", self.kind, self.beats, self.percent)
+    }
+}
+impl TempoCurve {
+    pub fn new(kind: TempoCurveKind, beats: u8, percent: u8) -> Self {
+        Self {
+            kind,
+            beats,
+            percent,
+        }
+    }
+    pub fn is_fermata(&self) -> bool {
+        self.kind == TempoCurveKind::Fermata
+    }
+    /// Speed factor at the end of the beats, for the factor at the start.
+    pub fn calc_end_factor(&self, factor: f32) -> f32 {
+        match self.kind {
+            TempoCurveKind::Ritardando => factor * (100.0 - self.percent.min(90) as f32) / 100.0,
+            TempoCurveKind::Accelerando => factor * (100.0 + self.percent as f32) / 100.0,
+            TempoCurveKind::Fermata => factor,
+        }
+    }
+    /// Speed factor while holding a fermata, for the factor before it.
+    pub fn calc_hold_factor(&self, factor: f32) -> f32 {
+        factor * 100.0 / (100.0 + self.percent as f32)
+    }
+}
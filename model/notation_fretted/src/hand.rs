@@ -21,6 +21,24 @@ impl Display for Finger {
     }
 }
 
+impl Finger {
+    /// Next finger towards the pinky for positive steps, the thumb is kept.
+    pub fn shift(&self, steps: i8) -> Option<Finger> {
+        const FINGERS: [Finger; 4] = [Finger::Index, Finger::Middle, Finger::Ring, Finger::Pinky];
+        match self {
+            Finger::Thumb => Some(Finger::Thumb),
+            _ => {
+                let index = FINGERS.iter().position(|x| x == self)? as i8 + steps;
+                if index < 0 {
+                    None
+                } else {
+                    FINGERS.get(index as usize).copied()
+                }
+            }
+        }
+    }
+}
+
 macro_rules! impl_hand_shape {
     ($type:ident, $strings:literal) => {
         #[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
@@ -106,6 +124,41 @@ macro_rules! impl_hand_shape {
             pub fn max_fret_with_barre(&self) -> u8 {
                 self.max_fret() + self.barre()
             }
+            /// Same shape moved along the neck, shapes with open strings are moved up
+            /// as barre, so the index finger is left for the barre.
+            pub fn refret(&self, offset: i8) -> Option<Self> {
+                let shift_fingers = |steps: i8| self.fingers.map(|x| x.and_then(|x| x.shift(steps)));
+                if offset == 0 {
+                    return Some(*self);
+                }
+                if let Some(barre) = self.barre {
+                    let barre = barre.checked_add_signed(offset)?;
+                    if barre > 0 {
+                        return Some(Self::new_barre(barre, self.frets, self.fingers));
+                    }
+                    let mut fingers = shift_fingers(-1);
+                    for (index, fret) in self.frets.iter().enumerate() {
+                        if *fret == Some(0) {
+                            fingers[index] = None;
+                        }
+                    }
+                    return Some(Self::new(self.frets, fingers));
+                }
+                if offset > 0 && self.frets.iter().any(|x| *x == Some(0)) {
+                    return Some(Self::new_barre(offset as u8, self.frets, shift_fingers(1)));
+                }
+                let mut frets = self.frets;
+                let mut fingers = self.fingers;
+                for (index, fret) in frets.iter_mut().enumerate() {
+                    if let Some(x) = fret {
+                        *x = x.checked_add_signed(offset)?;
+                        if *x == 0 {
+                            fingers[index] = None;
+                        }
+                    }
+                }
+                Some(Self::new(frets, fingers))
+            }
         }
 
         impl From<([Option<u8>; $strings], [Option<Finger>; $strings])> for $type {
@@ -3,6 +3,8 @@ pub mod fretted_entry;
 pub mod hand;
 pub mod pick;
 pub mod strum;
+pub mod technique;
+pub mod voicing;
 
 pub mod prelude {
     #[doc(hidden)]
@@ -14,5 +16,9 @@ pub mod prelude {
     #[doc(hidden)]
     pub use crate::pick::{Pick, PickNote};
     #[doc(hidden)]
-    pub use crate::strum::Strum;
+    pub use crate::strum::{Strum, StrumDirection, StrumStrings};
+    #[doc(hidden)]
+    pub use crate::technique::PickTechnique;
+    #[doc(hidden)]
+    pub use crate::voicing::{Voicing4, Voicing6};
 }
@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::prelude::Finger;
 use crate::strum::StrumDirection;
+use crate::technique::PickTechnique;
 
 #[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
 pub struct PickNote {
@@ -12,12 +13,14 @@ pub struct PickNote {
     pub fret_finger: Option<Finger>,
     pub pick_finger: Option<Finger>,
     pub pick_direction: Option<StrumDirection>,
+    #[serde(default)]
+    pub technique: Option<PickTechnique>,
 }
 impl Display for PickNote {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{}{}{}{}{}",
+            "{}{}{}{}{}{}",
             self.string,
             self.fret
                 .map(|x| format!("@{}", x))
@@ -31,6 +34,9 @@ impl Display for PickNote {
             self.pick_direction
                 .map(|x| format!("*{}", x))
                 .unwrap_or("".to_string()),
+            self.technique
+                .map(|x| format!("~{}", x))
+                .unwrap_or("".to_string()),
         )
     }
 }
@@ -41,6 +47,7 @@ impl PickNote {
         fret_finger: Option<Finger>,
         pick_finger: Option<Finger>,
         pick_direction: Option<StrumDirection>,
+        technique: Option<PickTechnique>,
     ) -> Self {
         Self {
             string,
@@ -48,13 +55,17 @@ impl PickNote {
             fret_finger,
             pick_finger,
             pick_direction,
+            technique,
         }
     }
     pub fn new_string(string: u8) -> Self {
-        Self::new(string, None, None, None, None)
+        Self::new(string, None, None, None, None, None)
     }
     pub fn new_string_fret(string: u8, fret: u8) -> Self {
-        Self::new(string, Some(fret), None, None, None)
+        Self::new(string, Some(fret), None, None, None, None)
+    }
+    pub fn is_legato(&self) -> bool {
+        self.technique.map(|x| x.is_legato()).unwrap_or(false)
     }
 }
 
@@ -181,6 +192,26 @@ impl Pick {
         }
         max
     }
+    /// Notes following the shape played on open strings, as with the default shape.
+    pub fn with_open_strings(&self) -> Pick {
+        let mut notes = self.get_notes();
+        for note in notes.iter_mut() {
+            if note.fret.is_none() {
+                note.fret = Some(0);
+            }
+        }
+        notes.into()
+    }
+    /// Fixed frets moved along the neck, notes following the shape are kept.
+    pub fn refret(&self, offset: i8) -> Option<Pick> {
+        let mut notes = self.get_notes();
+        for note in notes.iter_mut() {
+            if let Some(fret) = note.fret {
+                note.fret = Some(fret.checked_add_signed(offset)?);
+            }
+        }
+        Some(notes.into())
+    }
 }
 
 impl From<Pick> for Vec<PickNote> {
@@ -0,0 +1,103 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub enum PickTechnique {
+    HammerOn,
+    PullOff,
+    /// Slide from the previous note on the same string.
+    Slide,
+    /// Slide into the note from below.
+    SlideIn,
+    /// Slide down out of the note.
+    SlideOut,
+    /// Bend up by the semitones.
+    Bend(u8),
+    /// Release from a bend by the semitones.
+    Release(u8),
+    Vibrato,
+    NaturalHarmonic,
+    ArtificialHarmonic,
+    PalmMute,
+    DeadNote,
+}
+impl Display for PickTechnique {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_ident())
+    }
+}
+impl PickTechnique {
+    pub fn to_ident(&self) -> String {
+        match self {
+            Self::HammerOn => "h".to_owned(),
+            Self::PullOff => "p".to_owned(),
+            Self::Slide => "s".to_owned(),
+            Self::SlideIn => "si".to_owned(),
+            Self::SlideOut => "so".to_owned(),
+            Self::Bend(x) => format!("b{}", x),
+            Self::Release(x) => format!("r{}", x),
+            Self::Vibrato => "v".to_owned(),
+            Self::NaturalHarmonic => "nh".to_owned(),
+            Self::ArtificialHarmonic => "ah".to_owned(),
+            Self::PalmMute => "pm".to_owned(),
+            Self::DeadNote => "x".to_owned(),
+        }
+    }
+    pub fn from_ident(ident: &str) -> Option<Self> {
+        let semitones = |x: &str| x.parse::<u8>().ok();
+        match ident {
+            "h" => Some(Self::HammerOn),
+            "p" => Some(Self::PullOff),
+            "s" => Some(Self::Slide),
+            "si" => Some(Self::SlideIn),
+            "so" => Some(Self::SlideOut),
+            "v" => Some(Self::Vibrato),
+            "nh" => Some(Self::NaturalHarmonic),
+            "ah" => Some(Self::ArtificialHarmonic),
+            "pm" => Some(Self::PalmMute),
+            "x" => Some(Self::DeadNote),
+            _ if ident.starts_with('b') => semitones(&ident[1..]).map(Self::Bend),
+            _ if ident.starts_with('r') => semitones(&ident[1..]).map(Self::Release),
+            _ => None,
+        }
+    }
+    /// Played without picking, from the previous note on the same string.
+    pub fn is_legato(&self) -> bool {
+        matches!(
+            self,
+            Self::HammerOn | Self::PullOff | Self::Slide | Self::Release(_)
+        )
+    }
+    /// Text shown next to the fret, bends and releases show the fret they sound like.
+    pub fn to_glyph(&self, fret: u8) -> String {
+        match self {
+            Self::HammerOn => "h".to_owned(),
+            Self::PullOff => "p".to_owned(),
+            Self::Slide | Self::SlideIn => "/".to_owned(),
+            Self::SlideOut => "\\".to_owned(),
+            Self::Bend(x) => format!("b{}", fret as u16 + *x as u16),
+            Self::Release(_) => format!("r{}", fret),
+            Self::Vibrato => "~".to_owned(),
+            Self::NaturalHarmonic => "<>".to_owned(),
+            Self::ArtificialHarmonic => "AH".to_owned(),
+            Self::PalmMute => "PM".to_owned(),
+            Self::DeadNote => "".to_owned(),
+        }
+    }
+    /// The fret the note sounds like, harmonics sound higher than the fret they're touched at.
+    pub fn sounding_fret(&self, fret: u8) -> u8 {
+        match self {
+            Self::NaturalHarmonic => match fret {
+                12 | 19 | 24 => fret,
+                7 => 19,
+                5 => 24,
+                4 | 9 | 16 => 28,
+                3 => 31,
+                _ => fret.saturating_add(12),
+            },
+            Self::ArtificialHarmonic => fret.saturating_add(12),
+            _ => fret,
+        }
+    }
+}
@@ -0,0 +1,223 @@
+use std::fmt::Display;
+
+use notation_core::prelude::{Chord, Interval, Key, Scale, Semitones};
+
+use crate::prelude::{Finger, Fretboard4, Fretboard6, HandShape4, HandShape6};
+
+/// Frets reachable without moving the hand, counted from the lowest fretted fret.
+pub const VOICING_MAX_SPAN: u8 = 3;
+/// Voicings are searched in positions up to this fret.
+pub const VOICING_MAX_POSITION: u8 = 12;
+
+const FINGERS: [Finger; 4] = [Finger::Index, Finger::Middle, Finger::Ring, Finger::Pinky];
+
+macro_rules! impl_voicing {
+    ($type:ident, $strings:literal, $fretboard:ident, $hand_shape:ident) => {
+        #[derive(Copy, Clone, PartialEq, Eq, Debug)]
+        pub struct $type {
+            pub shape: $hand_shape,
+            /// Higher is easier to play.
+            pub score: i32,
+        }
+        impl Display for $type {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "<{}>({} {})", stringify!($type), self.shape, self.score)
+            }
+        }
+        impl $type {
+            /// Finger assignment in fret order, a barre takes the index finger.
+            fn calc_shape(frets: [Option<u8>; $strings]) -> Option<$hand_shape> {
+                let mut fretted: Vec<(u8, usize)> = frets
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(index, fret)| match fret {
+                        Some(fret) if *fret > 0 => Some((*fret, index)),
+                        _ => None,
+                    })
+                    .collect();
+                fretted.sort_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)));
+                let mut fingers = [None; $strings];
+                if fretted.len() <= FINGERS.len() {
+                    for (finger, (_fret, index)) in FINGERS.iter().zip(fretted.iter()) {
+                        fingers[*index] = Some(*finger);
+                    }
+                    return Some($hand_shape::new(frets, fingers));
+                }
+                let barre = fretted[0].0;
+                if frets.iter().any(|x| *x == Some(0)) {
+                    return None;
+                }
+                let others: Vec<&(u8, usize)> = fretted.iter().filter(|x| x.0 > barre).collect();
+                if others.len() >= FINGERS.len() {
+                    return None;
+                }
+                for (fret, index) in fretted.iter() {
+                    if *fret == barre {
+                        fingers[*index] = Some(Finger::Index);
+                    }
+                }
+                for (finger, (_fret, index)) in FINGERS[1..].iter().zip(others.iter()) {
+                    fingers[*index] = Some(*finger);
+                }
+                let relative_frets = frets.map(|x| x.map(|fret| fret - barre));
+                Some($hand_shape::new_barre(barre, relative_frets, fingers))
+            }
+            fn calc_score(shape: &$hand_shape, omitted_fifth: bool) -> i32 {
+                let frets: Vec<Option<u8>> = (1..=$strings)
+                    .map(|string| shape.string_fret_with_barre(string))
+                    .collect();
+                let fretted: Vec<u8> = frets.iter().filter_map(|x| *x).filter(|x| *x > 0).collect();
+                let min = fretted.iter().min().copied().unwrap_or(0) as i32;
+                let max = fretted.iter().max().copied().unwrap_or(0) as i32;
+                let open = frets.iter().filter(|x| **x == Some(0)).count() as i32;
+                let muted = frets.iter().filter(|x| x.is_none()).count() as i32;
+                let first = frets.iter().position(|x| x.is_some()).unwrap_or(0);
+                let last = frets.iter().rposition(|x| x.is_some()).unwrap_or(0);
+                let inner_muted = frets[first..=last].iter().filter(|x| x.is_none()).count() as i32;
+                let mut score =
+                    100 - 8 * (max - min) - 2 * min + 2 * open - 6 * muted - 20 * inner_muted;
+                if shape.barre.is_some() {
+                    score -= 10;
+                }
+                if omitted_fifth {
+                    score -= 10;
+                }
+                score
+            }
+        }
+        impl $fretboard {
+            fn string_class(&self, scale: &Scale, key: &Key, string: u8, fret: u8) -> Option<i8> {
+                self.fretted_note(scale, key, string, fret)
+                    .map(|x| Semitones::from(x.syllable).0.rem_euclid(12))
+            }
+            /// Playable voicings of the chord, sorted by score. The lowest sounding string is
+            /// the bass of the chord, only the 5th can be omitted.
+            pub fn calc_voicings(&self, scale: &Scale, key: &Key, chord: &Chord) -> Vec<$type> {
+                let root = Semitones::from(chord.root).0;
+                let class_of =
+                    |interval: &Interval| (root + Semitones::from(*interval).0).rem_euclid(12);
+                let intervals = chord.intervals.get_intervals();
+                let chord_classes: Vec<i8> = std::iter::once(root.rem_euclid(12))
+                    .chain(intervals.iter().map(class_of))
+                    .collect();
+                let fifth = intervals
+                    .iter()
+                    .find(|x| **x == Interval::Perfect5th)
+                    .map(class_of);
+                let bass = chord
+                    .bass
+                    .map(|x| class_of(&x))
+                    .unwrap_or(root.rem_euclid(12));
+                let max_muted = $strings / 3;
+                let mut result: Vec<$type> = Vec::new();
+                let max_position =
+                    (VOICING_MAX_POSITION as usize).min(self.fret_num().saturating_sub(1)) as u8;
+                for position in 1..=max_position {
+                    let options: Vec<Vec<Option<u8>>> = (1..=$strings as u8)
+                        .map(|string| {
+                            std::iter::once(None)
+                                .chain(
+                                    std::iter::once(0)
+                                        .chain(position..=position + VOICING_MAX_SPAN)
+                                        .filter(|fret| {
+                                            self.string_class(scale, key, string, *fret)
+                                                .map(|x| chord_classes.contains(&x))
+                                                .unwrap_or(false)
+                                        })
+                                        .map(Some),
+                                )
+                                .collect()
+                        })
+                        .collect();
+                    let mut frets = [None; $strings];
+                    let mut indexes = [0; $strings];
+                    loop {
+                        for (string, fret) in frets.iter_mut().enumerate() {
+                            *fret = options[string][indexes[string]];
+                        }
+                        if let Some(voicing) = self.check_voicing(
+                            scale,
+                            key,
+                            &frets,
+                            &chord_classes,
+                            fifth,
+                            bass,
+                            max_muted,
+                        ) {
+                            if !result.iter().any(|x| x.shape == voicing.shape) {
+                                result.push(voicing);
+                            }
+                        }
+                        let mut string = 0;
+                        while string < $strings {
+                            indexes[string] += 1;
+                            if indexes[string] < options[string].len() {
+                                break;
+                            }
+                            indexes[string] = 0;
+                            string += 1;
+                        }
+                        if string == $strings {
+                            break;
+                        }
+                    }
+                }
+                result.sort_by(|a, b| b.score.cmp(&a.score));
+                result
+            }
+            #[allow(clippy::too_many_arguments)]
+            fn check_voicing(
+                &self,
+                scale: &Scale,
+                key: &Key,
+                frets: &[Option<u8>; $strings],
+                chord_classes: &[i8],
+                fifth: Option<i8>,
+                bass: i8,
+                max_muted: usize,
+            ) -> Option<$type> {
+                if frets.iter().filter(|x| x.is_none()).count() > max_muted {
+                    return None;
+                }
+                let lowest = frets.iter().rposition(|x| x.is_some())?;
+                if self.string_class(scale, key, lowest as u8 + 1, frets[lowest]?) != Some(bass) {
+                    return None;
+                }
+                let classes: Vec<i8> = frets
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(index, fret)| {
+                        fret.and_then(|fret| self.string_class(scale, key, index as u8 + 1, fret))
+                    })
+                    .collect();
+                let missing: Vec<&i8> = chord_classes
+                    .iter()
+                    .filter(|x| !classes.contains(x))
+                    .collect();
+                let omitted_fifth = match missing.as_slice() {
+                    [] => false,
+                    [x] if Some(**x) == fifth => true,
+                    _ => return None,
+                };
+                let shape = $type::calc_shape(*frets)?;
+                Some($type {
+                    shape,
+                    score: $type::calc_score(&shape, omitted_fifth),
+                })
+            }
+            pub fn calc_best_voicing(
+                &self,
+                scale: &Scale,
+                key: &Key,
+                chord: &Chord,
+            ) -> Option<$hand_shape> {
+                self.calc_voicings(scale, key, chord)
+                    .first()
+                    .map(|x| x.shape)
+            }
+        }
+    };
+}
+
+impl_voicing!(Voicing6, 6, Fretboard6, HandShape6);
+impl_voicing!(Voicing4, 4, Fretboard4, HandShape4);
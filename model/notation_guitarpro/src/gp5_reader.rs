@@ -0,0 +1,496 @@
+use fehler::{throw, throws};
+
+use crate::prelude::{
+    GpBeat, GpChord, GpDuration, GpMeasure, GpNote, GpSong, GpTrack, GuitarProError,
+};
+
+/// Reader of the binary Guitar Pro 5 format (.gp5), versions 5.00 and 5.10.
+pub struct Gp5Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+    v510: bool,
+    /// Last fret of every string of every track, for tied notes.
+    last_frets: Vec<[u8; 7]>,
+}
+
+impl<'a> Gp5Reader<'a> {
+    pub const VERSION_500: &'static str = "FICHIER GUITAR PRO v5.00";
+    pub const VERSION_510: &'static str = "FICHIER GUITAR PRO v5.10";
+    pub const CHANNEL_NUM: usize = 64;
+    pub const DRUMS_CHANNEL: i32 = 9;
+
+    pub fn is_gp5(data: &[u8]) -> bool {
+        data.len() > 20 && data[1..].starts_with(b"FICHIER GUITAR PRO v5")
+    }
+    #[throws(GuitarProError)]
+    pub fn read_song(data: &'a [u8]) -> GpSong {
+        let mut reader = Self {
+            data,
+            pos: 0,
+            v510: false,
+            last_frets: Vec::new(),
+        };
+        let version = reader.read_byte_string(30)?;
+        reader.v510 = match version.as_str() {
+            Self::VERSION_500 => false,
+            Self::VERSION_510 => true,
+            _ => throw!(GuitarProError::UnsupportedVersion(version)),
+        };
+        reader.read()?
+    }
+    #[throws(GuitarProError)]
+    fn read(&mut self) -> GpSong {
+        let mut song = GpSong {
+            title: self.read_int_byte_string()?,
+            ..GpSong::default()
+        };
+        // subtitle, artist, album, words, music, copyright, tab, instructions
+        for _ in 0..8 {
+            self.read_int_byte_string()?;
+        }
+        let notice_lines = self.read_i32()?;
+        for _ in 0..notice_lines {
+            self.read_int_byte_string()?;
+        }
+        // lyrics
+        self.read_i32()?;
+        for _ in 0..5 {
+            self.read_i32()?;
+            self.read_int_string()?;
+        }
+        if self.v510 {
+            // master effect
+            self.skip(19)?;
+        }
+        // page setup
+        self.skip(30)?;
+        for _ in 0..10 {
+            self.read_int_byte_string()?;
+        }
+        // tempo name
+        self.read_int_byte_string()?;
+        song.tempo = self.read_i32()?.clamp(1, u16::MAX as i32) as u16;
+        if self.v510 {
+            self.skip(1)?;
+        }
+        let key = self.read_i8()?;
+        self.skip(4)?;
+        // midi channels
+        self.skip(Self::CHANNEL_NUM * 12)?;
+        // directions and master reverb
+        self.skip(42)?;
+        let measure_count = self.read_i32()?.max(0) as usize;
+        let track_count = self.read_i32()?.max(0) as usize;
+        let mut previous = GpMeasure {
+            key: (key, false),
+            ..GpMeasure::default()
+        };
+        for index in 0..measure_count {
+            if index > 0 {
+                self.skip(1)?;
+            }
+            previous = self.read_measure(&previous)?;
+            song.measures.push(previous.clone());
+        }
+        for index in 0..track_count {
+            song.tracks.push(self.read_track(index)?);
+        }
+        self.skip(if self.v510 { 1 } else { 2 })?;
+        self.last_frets = vec![[0; 7]; track_count];
+        for _ in 0..measure_count {
+            for (index, track) in song.tracks.iter_mut().enumerate() {
+                let mut voices = Vec::new();
+                for _ in 0..2 {
+                    let beat_count = self.read_i32()?;
+                    let mut beats = Vec::new();
+                    for _ in 0..beat_count {
+                        if let Some(beat) = self.read_beat(index, track)? {
+                            beats.push(beat);
+                        }
+                    }
+                    voices.push(beats);
+                }
+                // line break
+                self.skip(1)?;
+                track.measures.push(voices);
+            }
+        }
+        song
+    }
+    #[throws(GuitarProError)]
+    fn read_measure(&mut self, previous: &GpMeasure) -> GpMeasure {
+        let flags = self.read_u8()?;
+        let mut measure = GpMeasure {
+            marker: None,
+            ..previous.clone()
+        };
+        if flags & 0x01 != 0 {
+            measure.numerator = self.read_u8()?;
+        }
+        if flags & 0x02 != 0 {
+            measure.denominator = self.read_u8()?;
+        }
+        if flags & 0x08 != 0 {
+            // repeat close
+            self.skip(1)?;
+        }
+        if flags & 0x20 != 0 {
+            let text = self.read_int_byte_string()?.trim().to_string();
+            if !text.is_empty() {
+                measure.marker = Some(text);
+            }
+            // color
+            self.skip(4)?;
+        }
+        if flags & 0x10 != 0 {
+            // alternate endings
+            self.skip(1)?;
+        }
+        if flags & 0x40 != 0 {
+            let key = self.read_i8()?;
+            let minor = self.read_u8()? == 1;
+            measure.key = (key, minor);
+        }
+        if flags & 0x03 != 0 {
+            // beams
+            self.skip(4)?;
+        }
+        if flags & 0x10 == 0 {
+            self.skip(1)?;
+        }
+        // triplet feel
+        self.skip(1)?;
+        measure
+    }
+    #[throws(GuitarProError)]
+    fn read_track(&mut self, index: usize) -> GpTrack {
+        if index == 0 || !self.v510 {
+            self.skip(1)?;
+        }
+        let flags = self.read_u8()?;
+        let name = self.read_byte_string(40)?;
+        let string_count = self.read_i32()?.clamp(0, 7) as usize;
+        let mut strings = Vec::new();
+        for string in 0..7 {
+            let key = self.read_i32()?;
+            if string < string_count {
+                strings.push(key.clamp(0, 127) as u8);
+            }
+        }
+        // port
+        self.read_i32()?;
+        let channel = self.read_i32()? - 1;
+        // effect channel
+        self.read_i32()?;
+        let fret_count = self.read_i32()?.clamp(0, u8::MAX as i32) as u8;
+        let capo = self.read_i32()?.clamp(0, u8::MAX as i32) as u8;
+        // color and rse
+        self.skip(4)?;
+        self.skip(if self.v510 { 49 } else { 44 })?;
+        if self.v510 {
+            self.read_int_byte_string()?;
+            self.read_int_byte_string()?;
+        }
+        GpTrack {
+            name,
+            strings,
+            capo,
+            fret_count,
+            percussion: flags & 0x01 != 0 || channel % 16 == Self::DRUMS_CHANNEL,
+            measures: Vec::new(),
+        }
+    }
+    /// Returns None for empty beats, which are taking no time.
+    #[throws(GuitarProError)]
+    fn read_beat(&mut self, track_index: usize, track: &GpTrack) -> Option<GpBeat> {
+        let flags = self.read_u8()?;
+        let mut empty = false;
+        if flags & 0x40 != 0 {
+            empty = self.read_u8()? & 0x02 == 0;
+        }
+        let mut beat = GpBeat {
+            duration: self.read_duration(flags)?,
+            ..GpBeat::default()
+        };
+        if flags & 0x02 != 0 {
+            beat.chord = self.read_chord(track.strings.len())?;
+        }
+        if flags & 0x04 != 0 {
+            // text
+            self.read_int_byte_string()?;
+        }
+        if flags & 0x08 != 0 {
+            self.read_beat_effects(&mut beat)?;
+        }
+        if flags & 0x10 != 0 {
+            self.read_mix_change()?;
+        }
+        let string_flags = self.read_u8()?;
+        for bit in (0..7).rev() {
+            let string = 7 - bit;
+            if string_flags & (1 << bit) != 0 && (string as usize) <= track.strings.len() {
+                let note = self.read_note(track_index, string)?;
+                beat.notes.push(note);
+            }
+        }
+        self.skip(1)?;
+        if self.read_u8()? & 0x08 != 0 {
+            self.skip(1)?;
+        }
+        if empty {
+            None
+        } else {
+            Some(beat)
+        }
+    }
+    #[throws(GuitarProError)]
+    fn read_duration(&mut self, flags: u8) -> GpDuration {
+        let value = self.read_i8()?.clamp(-2, 4);
+        let mut duration = GpDuration {
+            value: 1 << (value + 2),
+            dots: if flags & 0x01 != 0 { 1 } else { 0 },
+            tuplet: None,
+        };
+        if flags & 0x20 != 0 {
+            duration.tuplet = match self.read_i32()? {
+                3 => Some((3, 2)),
+                5 => Some((5, 4)),
+                6 => Some((6, 4)),
+                7 => Some((7, 4)),
+                9 => Some((9, 8)),
+                10 => Some((10, 8)),
+                11 => Some((11, 8)),
+                12 => Some((12, 8)),
+                13 => Some((13, 8)),
+                _ => None,
+            };
+        }
+        duration
+    }
+    #[throws(GuitarProError)]
+    fn read_chord(&mut self, string_count: usize) -> Option<GpChord> {
+        self.skip(17)?;
+        let name = self.read_byte_string(21)?;
+        self.skip(4)?;
+        // first fret
+        self.read_i32()?;
+        let mut frets = Vec::new();
+        for string in 0..7 {
+            let fret = self.read_i32()?;
+            if string < string_count {
+                frets.push(if fret < 0 { None } else { Some(fret as u8) });
+            }
+        }
+        // barres, omissions and fingering
+        self.skip(32)?;
+        if frets.iter().any(|x| x.is_some()) {
+            Some(GpChord { name, frets })
+        } else {
+            None
+        }
+    }
+    #[throws(GuitarProError)]
+    fn read_beat_effects(&mut self, beat: &mut GpBeat) {
+        let flags1 = self.read_u8()?;
+        let flags2 = self.read_u8()?;
+        if flags1 & 0x02 != 0 {
+            beat.effects.push("vibrato");
+        }
+        if flags1 & 0x10 != 0 {
+            beat.effects.push("fade in");
+        }
+        if flags1 & 0x20 != 0 {
+            self.skip(1)?;
+            beat.effects.push("tapping");
+        }
+        if flags2 & 0x04 != 0 {
+            self.read_bend()?;
+            beat.effects.push("tremolo bar");
+        }
+        if flags1 & 0x40 != 0 {
+            self.skip(2)?;
+            beat.effects.push("stroke");
+        }
+        if flags2 & 0x02 != 0 {
+            self.skip(1)?;
+            beat.effects.push("pick stroke");
+        }
+    }
+    #[throws(GuitarProError)]
+    fn read_mix_change(&mut self) {
+        // instrument and rse
+        self.skip(17)?;
+        let mut durations = 0;
+        for _ in 0..6 {
+            if self.read_i8()? >= 0 {
+                durations += 1;
+            }
+        }
+        // tempo name
+        self.read_int_byte_string()?;
+        let tempo = self.read_i32()?;
+        self.skip(durations)?;
+        if tempo >= 0 {
+            self.skip(if self.v510 { 2 } else { 1 })?;
+        }
+        self.skip(2)?;
+        if self.v510 {
+            self.read_int_byte_string()?;
+            self.read_int_byte_string()?;
+        }
+    }
+    #[throws(GuitarProError)]
+    fn read_note(&mut self, track_index: usize, string: u8) -> GpNote {
+        let flags = self.read_u8()?;
+        let mut note = GpNote {
+            string,
+            ..GpNote::default()
+        };
+        if flags & 0x02 != 0 || flags & 0x40 != 0 {
+            note.effects.push("accent");
+        }
+        if flags & 0x04 != 0 {
+            note.effects.push("ghost note");
+        }
+        let mut note_type = 1;
+        if flags & 0x20 != 0 {
+            note_type = self.read_u8()?;
+        }
+        if flags & 0x10 != 0 {
+            // velocity
+            self.skip(1)?;
+        }
+        if flags & 0x20 != 0 {
+            note.fret = self.read_i8()?.clamp(0, 99) as u8;
+        }
+        match note_type {
+            2 => {
+                note.tie = true;
+                note.fret = self.last_frets[track_index][string as usize - 1];
+            }
+            3 => note.effects.push("dead note"),
+            _ => (),
+        }
+        self.last_frets[track_index][string as usize - 1] = note.fret;
+        if flags & 0x80 != 0 {
+            // fingering
+            self.skip(2)?;
+        }
+        if flags & 0x01 != 0 {
+            // duration percent
+            self.skip(8)?;
+        }
+        self.skip(1)?;
+        if flags & 0x08 != 0 {
+            self.read_note_effects(&mut note)?;
+        }
+        note
+    }
+    #[throws(GuitarProError)]
+    fn read_note_effects(&mut self, note: &mut GpNote) {
+        let flags1 = self.read_u8()?;
+        let flags2 = self.read_u8()?;
+        if flags1 & 0x01 != 0 {
+            self.read_bend()?;
+            note.effects.push("bend");
+        }
+        if flags1 & 0x10 != 0 {
+            self.skip(5)?;
+            note.effects.push("grace note");
+        }
+        if flags2 & 0x04 != 0 {
+            self.skip(1)?;
+            note.effects.push("tremolo picking");
+        }
+        if flags2 & 0x08 != 0 {
+            self.skip(1)?;
+            note.effects.push("slide");
+        }
+        if flags2 & 0x10 != 0 {
+            match self.read_i8()? {
+                2 => self.skip(3)?,
+                3 => self.skip(1)?,
+                _ => (),
+            }
+            note.effects.push("harmonic");
+        }
+        if flags2 & 0x20 != 0 {
+            self.skip(2)?;
+            note.effects.push("trill");
+        }
+        if flags1 & 0x02 != 0 {
+            note.effects.push("hammer-on");
+        }
+        if flags1 & 0x08 != 0 {
+            note.effects.push("let ring");
+        }
+        if flags2 & 0x01 != 0 {
+            note.effects.push("staccato");
+        }
+        if flags2 & 0x02 != 0 {
+            note.effects.push("palm mute");
+        }
+        if flags2 & 0x40 != 0 {
+            note.effects.push("vibrato");
+        }
+    }
+    #[throws(GuitarProError)]
+    fn read_bend(&mut self) {
+        self.skip(5)?;
+        let points = self.read_i32()?.max(0) as usize;
+        self.skip(points * 9)?;
+    }
+    #[throws(GuitarProError)]
+    fn read_bytes(&mut self, count: usize) -> &'a [u8] {
+        if self.pos + count > self.data.len() {
+            throw!(GuitarProError::UnexpectedEnd(self.pos));
+        }
+        let bytes = &self.data[self.pos..self.pos + count];
+        self.pos += count;
+        bytes
+    }
+    #[throws(GuitarProError)]
+    fn skip(&mut self, count: usize) {
+        self.read_bytes(count)?;
+    }
+    #[throws(GuitarProError)]
+    fn read_u8(&mut self) -> u8 {
+        self.read_bytes(1)?[0]
+    }
+    #[throws(GuitarProError)]
+    fn read_i8(&mut self) -> i8 {
+        self.read_u8()? as i8
+    }
+    #[throws(GuitarProError)]
+    fn read_i32(&mut self) -> i32 {
+        let bytes = self.read_bytes(4)?;
+        i32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+    }
+    fn to_string(bytes: &[u8]) -> String {
+        match std::str::from_utf8(bytes) {
+            Ok(text) => text.to_string(),
+            Err(_) => bytes.iter().map(|x| *x as char).collect(),
+        }
+    }
+    /// Length byte, then a fixed size buffer.
+    #[throws(GuitarProError)]
+    fn read_byte_string(&mut self, size: usize) -> String {
+        let len = self.read_u8()? as usize;
+        let bytes = self.read_bytes(size)?;
+        Self::to_string(&bytes[..len.min(size)])
+    }
+    /// Length int, then the text.
+    #[throws(GuitarProError)]
+    fn read_int_string(&mut self) -> String {
+        let len = self.read_i32()?.max(0) as usize;
+        Self::to_string(self.read_bytes(len)?)
+    }
+    /// Size int, then a length byte and the text.
+    #[throws(GuitarProError)]
+    fn read_int_byte_string(&mut self) -> String {
+        let size = self.read_i32()?.max(1) as usize;
+        let len = self.read_u8()? as usize;
+        let bytes = self.read_bytes(size - 1)?;
+        Self::to_string(&bytes[..len.min(size - 1)])
+    }
+}
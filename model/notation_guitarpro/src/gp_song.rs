@@ -0,0 +1,126 @@
+use notation_model::prelude::{Duration, Tuplet, Unit, Units};
+
+/// The parts of a Guitar Pro song that can be imported, shared by the file format readers.
+#[derive(Clone, Debug, Default)]
+pub struct GpSong {
+    pub title: String,
+    /// Quarter notes per minute.
+    pub tempo: u16,
+    pub measures: Vec<GpMeasure>,
+    pub tracks: Vec<GpTrack>,
+}
+
+#[derive(Clone, Debug)]
+pub struct GpMeasure {
+    pub numerator: u8,
+    pub denominator: u8,
+    /// Sharps (positive) or flats (negative), and whether it's minor.
+    pub key: (i8, bool),
+    pub marker: Option<String>,
+}
+
+impl Default for GpMeasure {
+    fn default() -> Self {
+        Self {
+            numerator: 4,
+            denominator: 4,
+            key: (0, false),
+            marker: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct GpTrack {
+    pub name: String,
+    /// Midi keys of the open strings, from string 1 (the highest one).
+    pub strings: Vec<u8>,
+    pub capo: u8,
+    pub fret_count: u8,
+    pub percussion: bool,
+    /// Voices of each measure.
+    pub measures: Vec<Vec<Vec<GpBeat>>>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct GpBeat {
+    pub duration: GpDuration,
+    /// Empty for rests.
+    pub notes: Vec<GpNote>,
+    pub chord: Option<GpChord>,
+    pub effects: Vec<&'static str>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct GpNote {
+    /// 1-based, string 1 is the highest one.
+    pub string: u8,
+    /// Relative to the capo.
+    pub fret: u8,
+    pub tie: bool,
+    pub effects: Vec<&'static str>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct GpChord {
+    pub name: String,
+    /// From string 1, None for muted strings.
+    pub frets: Vec<Option<u8>>,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct GpDuration {
+    /// 1 for whole notes, 4 for quarter notes, up to 64.
+    pub value: u8,
+    pub dots: u8,
+    /// Played notes in the time of normal notes, e.g. (3, 2) for triplets.
+    pub tuplet: Option<(u8, u8)>,
+}
+
+impl Default for GpDuration {
+    fn default() -> Self {
+        Self {
+            value: 4,
+            dots: 0,
+            tuplet: None,
+        }
+    }
+}
+
+impl GpDuration {
+    pub fn units(&self) -> Units {
+        let mut units = Units::new(1, self.value.max(1) as i64);
+        let mut dot = units;
+        for _ in 0..self.dots {
+            dot = dot / 2;
+            units += dot;
+        }
+        if let Some((played, normal)) = self.tuplet {
+            if played > 0 {
+                units = units * normal as i64 / played as i64;
+            }
+        }
+        units
+    }
+    /// None if it can't be written as a single duration.
+    pub fn to_duration(&self) -> Option<Duration> {
+        let unit = match self.value {
+            1 => Unit::Whole,
+            2 => Unit::Half,
+            4 => Unit::Quarter,
+            8 => Unit::Eighth,
+            16 => Unit::Sixteenth,
+            32 => Unit::ThirtySecondth,
+            64 => Unit::SixtyFourth,
+            _ => return None,
+        };
+        if self.dots > Duration::MAX_DOTS {
+            return None;
+        }
+        let tuplet = match self.tuplet {
+            Some((played, normal)) => Tuplet::new(played, normal),
+            None => Tuplet::NONE,
+        };
+        Some(Duration::new(unit, self.dots, tuplet))
+    }
+}
@@ -0,0 +1,462 @@
+use std::collections::HashMap;
+
+use fehler::{throw, throws};
+use roxmltree::{Document, Node};
+
+use crate::prelude::{
+    GpBeat, GpChord, GpDuration, GpMeasure, GpNote, GpSong, GpTrack, GuitarProError,
+};
+
+struct BitReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> BitReader<'a> {
+    #[throws(GuitarProError)]
+    fn read_bit(&mut self) -> u32 {
+        let byte = match self.data.get(self.pos / 8) {
+            Some(byte) => *byte,
+            None => throw!(GuitarProError::UnexpectedEnd(self.pos / 8)),
+        };
+        let bit = (byte >> (7 - self.pos % 8)) & 0x01;
+        self.pos += 1;
+        bit as u32
+    }
+    /// Most significant bit first.
+    #[throws(GuitarProError)]
+    fn read_bits(&mut self, count: u32) -> u32 {
+        let mut bits = 0;
+        for index in (0..count).rev() {
+            bits |= self.read_bit()? << index;
+        }
+        bits
+    }
+    /// Least significant bit first.
+    #[throws(GuitarProError)]
+    fn read_bits_reversed(&mut self, count: u32) -> u32 {
+        let mut bits = 0;
+        for index in 0..count {
+            bits |= self.read_bit()? << index;
+        }
+        bits
+    }
+}
+
+/// Reader of the Guitar Pro 6 format (.gpx), a compressed container of the score as xml.
+pub struct GpxReader();
+
+impl GpxReader {
+    pub const COMPRESSED_HEADER: &'static [u8] = b"BCFZ";
+    pub const UNCOMPRESSED_HEADER: &'static [u8] = b"BCFS";
+    pub const SECTOR_SIZE: usize = 0x1000;
+    pub const SCORE_FILE: &'static str = "score.gpif";
+
+    pub fn is_gpx(data: &[u8]) -> bool {
+        data.starts_with(Self::COMPRESSED_HEADER) || data.starts_with(Self::UNCOMPRESSED_HEADER)
+    }
+    #[throws(GuitarProError)]
+    pub fn read_song(data: &[u8]) -> GpSong {
+        let files = if data.starts_with(Self::COMPRESSED_HEADER) {
+            Self::decompress(&data[Self::COMPRESSED_HEADER.len()..])?
+        } else {
+            data.to_vec()
+        };
+        if !files.starts_with(Self::UNCOMPRESSED_HEADER) {
+            throw!(GuitarProError::UnsupportedFormat);
+        }
+        let score = Self::find_file(&files[Self::UNCOMPRESSED_HEADER.len()..], Self::SCORE_FILE)?;
+        Self::read_gpif(&String::from_utf8_lossy(&score))?
+    }
+    #[throws(GuitarProError)]
+    fn decompress(data: &[u8]) -> Vec<u8> {
+        if data.len() < 4 {
+            throw!(GuitarProError::UnexpectedEnd(data.len()));
+        }
+        let expected = i32::from_le_bytes([data[0], data[1], data[2], data[3]]).max(0) as usize;
+        let mut reader = BitReader {
+            data: &data[4..],
+            pos: 0,
+        };
+        // the sizes are read from the file, don't reserve more than the data can hold
+        let mut result: Vec<u8> = Vec::with_capacity(expected.min(data.len()));
+        while result.len() < expected {
+            if reader.read_bits(1)? == 1 {
+                // copy from the decompressed data
+                let word_size = reader.read_bits(4)?;
+                let offset = reader.read_bits_reversed(word_size)? as usize;
+                let size = reader.read_bits_reversed(word_size)? as usize;
+                if offset == 0 || offset > result.len() {
+                    throw!(GuitarProError::UnsupportedFormat);
+                }
+                let begin = result.len() - offset;
+                for index in 0..size.min(offset) {
+                    result.push(result[begin + index]);
+                }
+            } else {
+                let size = reader.read_bits_reversed(2)?;
+                for _ in 0..size {
+                    result.push(reader.read_bits(8)? as u8);
+                }
+            }
+        }
+        result
+    }
+    fn read_int(data: &[u8], pos: usize) -> usize {
+        match data.get(pos..pos + 4) {
+            Some(bytes) => {
+                i32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]).max(0) as usize
+            }
+            None => 0,
+        }
+    }
+    /*
+     * The files are stored in sectors, a file entry sector starts with 2, followed by
+     * the file name at 0x04, the file size at 0x8C, and the indices of data sectors from 0x94.
+     */
+    #[throws(GuitarProError)]
+    fn find_file(data: &[u8], name: &str) -> Vec<u8> {
+        let mut offset = Self::SECTOR_SIZE;
+        while offset + 4 <= data.len() {
+            if Self::read_int(data, offset) == 2 {
+                let name_bytes =
+                    &data[(offset + 0x04).min(data.len())..(offset + 0x83).min(data.len())];
+                let name_len = name_bytes
+                    .iter()
+                    .position(|x| *x == 0)
+                    .unwrap_or(name_bytes.len());
+                if &name_bytes[..name_len] == name.as_bytes() {
+                    let size = Self::read_int(data, offset + 0x8C);
+                    let mut result = Vec::with_capacity(size.min(data.len()));
+                    let mut pointer = offset + 0x94;
+                    while result.len() < size {
+                        let sector = Self::read_int(data, pointer);
+                        if sector == 0 {
+                            break;
+                        }
+                        let begin = sector * Self::SECTOR_SIZE;
+                        let end = (begin + Self::SECTOR_SIZE).min(data.len());
+                        if begin >= end {
+                            throw!(GuitarProError::UnexpectedEnd(begin));
+                        }
+                        result.extend_from_slice(&data[begin..end]);
+                        pointer += 4;
+                    }
+                    result.truncate(size);
+                    return result;
+                }
+            }
+            offset += Self::SECTOR_SIZE;
+        }
+        throw!(GuitarProError::MissingFile(name.to_string()));
+    }
+    fn children<'a, 'input: 'a>(
+        node: Node<'a, 'input>,
+        tag: &'a str,
+    ) -> impl Iterator<Item = Node<'a, 'input>> + 'a {
+        node.children().filter(move |x| x.has_tag_name(tag))
+    }
+    fn child<'a, 'input>(node: Node<'a, 'input>, tag: &str) -> Option<Node<'a, 'input>> {
+        node.children().find(|x| x.has_tag_name(tag))
+    }
+    fn child_text(node: Node, tag: &str) -> Option<String> {
+        Self::child(node, tag)
+            .and_then(|x| x.text())
+            .map(|x| x.trim().to_string())
+    }
+    fn child_ids(node: Node, tag: &str) -> Vec<String> {
+        Self::child_text(node, tag)
+            .map(|x| x.split_whitespace().map(|y| y.to_string()).collect())
+            .unwrap_or_default()
+    }
+    fn property<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+        node.descendants()
+            .find(|x| x.has_tag_name("Property") && x.attribute("name") == Some(name))
+    }
+    /// Elements of a collection by their id attribute.
+    fn id_map<'a, 'input>(root: Node<'a, 'input>, tag: &str) -> HashMap<String, Node<'a, 'input>> {
+        let mut map = HashMap::new();
+        if let Some(collection) = Self::child(root, &format!("{}s", tag)) {
+            for node in collection.children().filter(|x| x.has_tag_name(tag)) {
+                if let Some(id) = node.attribute("id") {
+                    map.insert(id.to_string(), node);
+                }
+            }
+        }
+        map
+    }
+    #[throws(GuitarProError)]
+    pub fn read_gpif(text: &str) -> GpSong {
+        let doc = Document::parse(text)?;
+        let root = doc.root_element();
+        let mut song = GpSong {
+            title: Self::child(root, "Score")
+                .and_then(|x| Self::child_text(x, "Title"))
+                .unwrap_or_default(),
+            tempo: 120,
+            ..GpSong::default()
+        };
+        if let Some(tempo) = root
+            .descendants()
+            .filter(|x| x.has_tag_name("Automation"))
+            .filter(|x| Self::child_text(*x, "Type").as_deref() == Some("Tempo"))
+            .find(|x| Self::child_text(*x, "Bar").as_deref() == Some("0"))
+            .and_then(|x| Self::child_text(x, "Value"))
+            .and_then(|x| {
+                x.split_whitespace()
+                    .next()
+                    .and_then(|y| y.parse::<f32>().ok())
+            })
+        {
+            song.tempo = tempo.round().clamp(1.0, u16::MAX as f32) as u16;
+        }
+        let bars = Self::id_map(root, "Bar");
+        let voices = Self::id_map(root, "Voice");
+        let beats = Self::id_map(root, "Beat");
+        let notes = Self::id_map(root, "Note");
+        let rhythms = Self::id_map(root, "Rhythm");
+        let track_nodes: Vec<Node> = Self::child(root, "Tracks")
+            .map(|x| Self::children(x, "Track").collect())
+            .unwrap_or_default();
+        let mut diagrams = Vec::new();
+        for node in track_nodes.iter() {
+            song.tracks.push(Self::read_track(*node));
+            diagrams.push(Self::read_diagrams(*node));
+        }
+        let master_bars: Vec<Node> = Self::child(root, "MasterBars")
+            .map(|x| Self::children(x, "MasterBar").collect())
+            .unwrap_or_default();
+        let mut previous = GpMeasure::default();
+        for master_bar in master_bars.iter() {
+            previous = Self::read_measure(*master_bar, &previous);
+            song.measures.push(previous.clone());
+            let bar_ids = Self::child_ids(*master_bar, "Bars");
+            for (index, track) in song.tracks.iter_mut().enumerate() {
+                let mut measure_voices = Vec::new();
+                let voice_ids = bar_ids
+                    .get(index)
+                    .and_then(|x| bars.get(x))
+                    .map(|x| Self::child_ids(*x, "Voices"))
+                    .unwrap_or_default();
+                for voice in voice_ids.iter().filter_map(|x| voices.get(x)) {
+                    let mut voice_beats = Vec::new();
+                    let mut grace = false;
+                    for beat in Self::child_ids(*voice, "Beats")
+                        .iter()
+                        .filter_map(|x| beats.get(x))
+                    {
+                        // grace notes are beats of their own, reported on the main beat
+                        if Self::child(*beat, "GraceNotes").is_some() {
+                            grace = true;
+                            continue;
+                        }
+                        let mut beat =
+                            Self::read_beat(*beat, track, &rhythms, &notes, &diagrams[index]);
+                        if grace {
+                            beat.effects.push("grace note");
+                            grace = false;
+                        }
+                        voice_beats.push(beat);
+                    }
+                    measure_voices.push(voice_beats);
+                }
+                track.measures.push(measure_voices);
+            }
+        }
+        song
+    }
+    fn read_track(node: Node) -> GpTrack {
+        let strings: Vec<u8> = Self::property(node, "Tuning")
+            .and_then(|x| Self::child_text(x, "Pitches"))
+            .map(|x| {
+                x.split_whitespace()
+                    .filter_map(|y| y.parse::<u8>().ok())
+                    .rev()
+                    .collect()
+            })
+            .unwrap_or_default();
+        let percussion = node.descendants().any(|x| {
+            (x.has_tag_name("Type") && x.text() == Some("drumKit"))
+                || (x.has_tag_name("GeneralMidi") && x.attribute("table") == Some("Percussion"))
+        });
+        GpTrack {
+            name: Self::child_text(node, "Name").unwrap_or_default(),
+            strings,
+            capo: Self::property(node, "CapoFret")
+                .and_then(|x| Self::child_text(x, "Fret"))
+                .and_then(|x| x.parse::<u8>().ok())
+                .unwrap_or(0),
+            fret_count: 24,
+            percussion,
+            measures: Vec::new(),
+        }
+    }
+    fn read_diagrams(node: Node) -> HashMap<String, GpChord> {
+        let mut diagrams = HashMap::new();
+        for name in ["DiagramCollection", "DiagramWorkingSet"] {
+            let items = Self::property(node, name)
+                .into_iter()
+                .flat_map(|x| x.descendants().filter(|y| y.has_tag_name("Item")));
+            for item in items {
+                let diagram = match Self::child(item, "Diagram") {
+                    Some(diagram) => diagram,
+                    None => continue,
+                };
+                let attribute = |name: &str| {
+                    diagram
+                        .attribute(name)
+                        .and_then(|x| x.parse::<usize>().ok())
+                        .unwrap_or(0)
+                };
+                let string_count = attribute("stringCount");
+                let base_fret = attribute("baseFret");
+                let mut frets = vec![None; string_count];
+                for fret in Self::children(diagram, "Fret") {
+                    let string = fret
+                        .attribute("string")
+                        .and_then(|x| x.parse::<usize>().ok());
+                    let value = fret.attribute("fret").and_then(|x| x.parse::<usize>().ok());
+                    if let (Some(string), Some(value)) = (string, value) {
+                        if string < string_count {
+                            frets[string_count - 1 - string] = Some((base_fret + value) as u8);
+                        }
+                    }
+                }
+                if let Some(id) = item.attribute("id") {
+                    diagrams.entry(id.to_string()).or_insert(GpChord {
+                        name: item.attribute("name").unwrap_or_default().to_string(),
+                        frets,
+                    });
+                }
+            }
+        }
+        diagrams
+    }
+    fn read_measure(node: Node, previous: &GpMeasure) -> GpMeasure {
+        let mut measure = GpMeasure {
+            marker: None,
+            ..previous.clone()
+        };
+        if let Some((numerator, denominator)) = Self::child_text(node, "Time")
+            .as_deref()
+            .and_then(|x| x.split_once('/'))
+        {
+            if let (Ok(numerator), Ok(denominator)) = (numerator.parse(), denominator.parse()) {
+                measure.numerator = numerator;
+                measure.denominator = denominator;
+            }
+        }
+        if let Some(key) = Self::child(node, "Key") {
+            let accidentals = Self::child_text(key, "AccidentalCount")
+                .and_then(|x| x.parse::<i8>().ok())
+                .unwrap_or(0);
+            let minor = Self::child_text(key, "Mode").as_deref() == Some("Minor");
+            measure.key = (accidentals, minor);
+        }
+        if let Some(section) = Self::child(node, "Section") {
+            let text = Self::child_text(section, "Text")
+                .filter(|x| !x.is_empty())
+                .or_else(|| Self::child_text(section, "Letter"));
+            measure.marker = text.filter(|x| !x.is_empty());
+        }
+        measure
+    }
+    fn read_beat(
+        node: Node,
+        track: &GpTrack,
+        rhythms: &HashMap<String, Node>,
+        notes: &HashMap<String, Node>,
+        diagrams: &HashMap<String, GpChord>,
+    ) -> GpBeat {
+        let mut beat = GpBeat::default();
+        if let Some(rhythm) = Self::child(node, "Rhythm")
+            .and_then(|x| x.attribute("ref"))
+            .and_then(|x| rhythms.get(x))
+        {
+            beat.duration = Self::read_duration(*rhythm);
+        }
+        beat.chord = Self::child_text(node, "Chord").and_then(|x| diagrams.get(&x).cloned());
+        if Self::child(node, "Whammy").is_some() {
+            beat.effects.push("tremolo bar");
+        }
+        if Self::child(node, "Arpeggio").is_some() || Self::property(node, "Brush").is_some() {
+            beat.effects.push("stroke");
+        }
+        for note in Self::child_ids(node, "Notes")
+            .iter()
+            .filter_map(|x| notes.get(x))
+        {
+            if let Some(note) = Self::read_note(*note, track) {
+                beat.notes.push(note);
+            }
+        }
+        beat
+    }
+    fn read_duration(node: Node) -> GpDuration {
+        let value = match Self::child_text(node, "NoteValue").as_deref() {
+            Some("Whole") => 1,
+            Some("Half") => 2,
+            Some("Eighth") => 8,
+            Some("16th") => 16,
+            Some("32nd") => 32,
+            Some("64th") => 64,
+            _ => 4,
+        };
+        let dots = Self::child(node, "AugmentationDot")
+            .and_then(|x| x.attribute("count"))
+            .and_then(|x| x.parse::<u8>().ok())
+            .unwrap_or(0);
+        let tuplet = Self::child(node, "PrimaryTuplet").and_then(|x| {
+            let played = x.attribute("num")?.parse::<u8>().ok()?;
+            let normal = x.attribute("den")?.parse::<u8>().ok()?;
+            Some((played, normal))
+        });
+        GpDuration {
+            value,
+            dots,
+            tuplet,
+        }
+    }
+    fn read_note(node: Node, track: &GpTrack) -> Option<GpNote> {
+        let value = |property: &str| {
+            Self::property(node, property)
+                .and_then(|x| Self::child_text(x, property))
+                .and_then(|x| x.parse::<usize>().ok())
+        };
+        let string = value("String")?;
+        if string >= track.strings.len() {
+            return None;
+        }
+        let mut note = GpNote {
+            string: (track.strings.len() - string) as u8,
+            fret: value("Fret").unwrap_or(0) as u8,
+            tie: Self::child(node, "Tie").and_then(|x| x.attribute("destination")) == Some("true"),
+            effects: Vec::new(),
+        };
+        for (property, effect) in [
+            ("Bended", "bend"),
+            ("Slide", "slide"),
+            ("HarmonicType", "harmonic"),
+            ("Tapped", "tapping"),
+            ("PalmMuted", "palm mute"),
+            ("Muted", "dead note"),
+            ("HopoOrigin", "hammer-on"),
+        ] {
+            if Self::property(node, property).is_some() {
+                note.effects.push(effect);
+            }
+        }
+        for (tag, effect) in [
+            ("Vibrato", "vibrato"),
+            ("LetRing", "let ring"),
+            ("Trill", "trill"),
+            ("Accent", "accent"),
+            ("AntiAccent", "ghost note"),
+        ] {
+            if Self::child(node, tag).is_some() {
+                note.effects.push(effect);
+            }
+        }
+        Some(note)
+    }
+}
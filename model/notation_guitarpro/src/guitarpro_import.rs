@@ -0,0 +1,362 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use fehler::{throw, throws};
+use notation_model::notation_proto::prelude::{
+    Bar, BarLayer, CoreEntry, Duration, Form, Fretboard6, FrettedEntry6, HandShape6, Key, Pick,
+    PickNote, ProtoEntry, Scale, Section, SectionKind, Semitones, Signature, Slice, SliceBegin,
+    SliceEnd, Tab, TabMeta, Tempo, Track, TrackKind, Unit, Units, GUITAR_FRET_NUM_ACOUSTIC,
+};
+use thiserror::Error;
+
+use crate::prelude::{Gp5Reader, GpBeat, GpSong, GpTrack, GpxReader};
+
+#[derive(Error, Debug)]
+pub enum GuitarProError {
+    #[error("read guitar pro file failed")]
+    Io(#[from] std::io::Error),
+    #[error("parse score xml failed")]
+    Xml(#[from] roxmltree::Error),
+    #[error("unexpected end of data")]
+    UnexpectedEnd(usize),
+    #[error("guitar pro version not supported")]
+    UnsupportedVersion(String),
+    #[error("not a supported guitar pro file (.gp5 or .gpx)")]
+    UnsupportedFormat,
+    #[error("file missing in container")]
+    MissingFile(String),
+}
+
+/// Guitar Pro (.gp5 and .gpx) import, one guitar track per 6 strings track.
+///
+/// Notes are picked with explicit frets, chord diagrams become hand shapes,
+/// effects that can't be written in tabs are reported and skipped.
+pub struct GuitarProImport();
+
+impl GuitarProImport {
+    pub const BARS_PER_SECTION: usize = 8;
+
+    #[throws(GuitarProError)]
+    pub fn read_tab<P: AsRef<Path>>(path: P) -> Tab {
+        let bytes = std::fs::read(path)?;
+        Self::import_tab(&bytes)?
+    }
+    #[throws(GuitarProError)]
+    pub fn read_song(bytes: &[u8]) -> GpSong {
+        if bytes.len() > 1 && bytes[1..].starts_with(b"FICHIER GUITAR PRO") {
+            Gp5Reader::read_song(bytes)?
+        } else if GpxReader::is_gpx(bytes) {
+            GpxReader::read_song(bytes)?
+        } else {
+            throw!(GuitarProError::UnsupportedFormat);
+        }
+    }
+    #[throws(GuitarProError)]
+    pub fn import_tab(bytes: &[u8]) -> Tab {
+        Self::import_song(&Self::read_song(bytes)?)
+    }
+    pub fn import_song(song: &GpSong) -> Tab {
+        let meta = Self::new_meta(song);
+        let bar_count = song.measures.len().max(1);
+        let mut warnings: BTreeMap<&'static str, usize> = BTreeMap::new();
+        let mut tracks = Vec::new();
+        let mut bar_layers: Vec<Vec<BarLayer>> = vec![Vec::new(); bar_count];
+        let mut used_ids: Vec<String> = Vec::new();
+        for (index, track) in song.tracks.iter().enumerate() {
+            if track.percussion {
+                println!(
+                    "GuitarProImport::import_song(), drums track skipped: {}",
+                    track.name
+                );
+                continue;
+            }
+            if track.strings.len() != 6 {
+                println!(
+                    "GuitarProImport::import_song(), {} strings track skipped: {}",
+                    track.strings.len(),
+                    track.name
+                );
+                continue;
+            }
+            let name = track.name.trim();
+            let id = Self::unique_id(
+                &mut used_ids,
+                if name.is_empty() {
+                    format!("track{}", index + 1)
+                } else {
+                    name.to_string()
+                },
+            );
+            tracks.push(Self::new_guitar_track(
+                song,
+                track,
+                id,
+                &mut bar_layers,
+                &mut warnings,
+            ));
+        }
+        for (effect, count) in warnings.iter() {
+            println!(
+                "GuitarProImport::import_song(), not supported, skipped: {} x {}",
+                effect, count
+            );
+        }
+        let markers: Vec<(usize, String)> = song
+            .measures
+            .iter()
+            .enumerate()
+            .filter_map(|(index, x)| x.marker.clone().map(|text| (index, text)))
+            .collect();
+        let (sections, form) = Self::new_sections(bar_layers, &markers);
+        Tab::new(&Tab::new_uuid(), meta, tracks, sections, form)
+    }
+    fn new_meta(song: &GpSong) -> TabMeta {
+        let measure = song.measures.first().cloned().unwrap_or_default();
+        let beat_unit = match measure.denominator {
+            1 => Unit::Whole,
+            2 => Unit::Half,
+            8 => Unit::Eighth,
+            16 => Unit::Sixteenth,
+            32 => Unit::ThirtySecondth,
+            64 => Unit::SixtyFourth,
+            _ => Unit::Quarter,
+        };
+        let signature = Signature::new(beat_unit, measure.numerator.max(1));
+        let quarters_per_beat = (Units::from(beat_unit) / Units::from(Unit::Quarter)).as_f32();
+        let bpm = song.tempo as f32 / quarters_per_beat;
+        let tempo = Tempo::Bpm(bpm.round().clamp(1.0, u16::MAX as f32) as u16);
+        let (key, scale) = Self::key_of_signature(measure.key.0, measure.key.1);
+        TabMeta::new(key, scale, signature, tempo)
+    }
+    pub fn key_of_signature(sharps: i8, minor: bool) -> (Key, Scale) {
+        let index = (sharps.clamp(-7, 7) + 7) as usize;
+        if minor {
+            let keys = [
+                Key::A_FLAT,
+                Key::E_FLAT,
+                Key::B_FLAT,
+                Key::F,
+                Key::C,
+                Key::G,
+                Key::D,
+                Key::A,
+                Key::E,
+                Key::B,
+                Key::F_SHARP,
+                Key::C_SHARP,
+                Key::G_SHARP,
+                Key::D_SHARP,
+                Key::A_SHARP,
+            ];
+            (keys[index], Scale::Minor)
+        } else {
+            let keys = [
+                Key::B,
+                Key::G_FLAT,
+                Key::D_FLAT,
+                Key::A_FLAT,
+                Key::E_FLAT,
+                Key::B_FLAT,
+                Key::F,
+                Key::C,
+                Key::G,
+                Key::D,
+                Key::A,
+                Key::E,
+                Key::B,
+                Key::F_SHARP,
+                Key::C_SHARP,
+            ];
+            (keys[index], Scale::Major)
+        }
+    }
+    fn unique_id(used_ids: &mut Vec<String>, id: String) -> String {
+        let mut result = id.clone();
+        let mut index = 2;
+        while used_ids.contains(&result) {
+            result = format!("{} {}", id, index);
+            index += 1;
+        }
+        used_ids.push(result.clone());
+        result
+    }
+    fn beat_durations(
+        beat: &GpBeat,
+        warnings: &mut BTreeMap<&'static str, usize>,
+    ) -> Vec<Duration> {
+        if let Some(duration) = beat.duration.to_duration() {
+            return vec![duration];
+        }
+        let units = beat.duration.units();
+        let durations = Duration::split_units(units);
+        let total: Units = durations.iter().map(|x| Units::from(*x)).sum();
+        if total != units {
+            *warnings.entry("duration not representable, split").or_default() += 1;
+        }
+        durations
+    }
+    fn new_fretboard(track: &GpTrack) -> Fretboard6 {
+        let mut string_notes = [Semitones(0); 6];
+        for (index, key) in track.strings.iter().enumerate().take(6) {
+            string_notes[index] = Semitones(*key as i8 - 12);
+        }
+        let fret_num = if track.fret_count > 0 {
+            track.fret_count as usize
+        } else {
+            GUITAR_FRET_NUM_ACOUSTIC
+        };
+        Fretboard6::new(fret_num, string_notes, track.capo)
+    }
+    fn new_shape(frets: &[Option<u8>]) -> HandShape6 {
+        let mut shape_frets = [None; 6];
+        for (index, fret) in frets.iter().enumerate().take(6) {
+            shape_frets[index] = *fret;
+        }
+        HandShape6::from(shape_frets)
+    }
+    fn add_layer(layers: &mut Vec<BarLayer>, id: &str, slices: Vec<(usize, usize)>) {
+        if slices.is_empty() {
+            return;
+        }
+        let slices = slices
+            .into_iter()
+            .map(|(begin, count)| {
+                Slice::new(SliceBegin::Index(begin), SliceEnd::Count(count), None)
+            })
+            .collect();
+        layers.push(BarLayer::new(id.to_string(), slices));
+    }
+    fn new_guitar_track(
+        song: &GpSong,
+        track: &GpTrack,
+        id: String,
+        bar_layers: &mut [Vec<BarLayer>],
+        warnings: &mut BTreeMap<&'static str, usize>,
+    ) -> Track {
+        let mut entries = vec![ProtoEntry::from(FrettedEntry6::from(Self::new_fretboard(
+            track,
+        )))];
+        let mut last_shape: Option<HandShape6> = None;
+        for (bar_index, (voices, layers)) in
+            track.measures.iter().zip(bar_layers.iter_mut()).enumerate()
+        {
+            let bar_units = song
+                .measures
+                .get(bar_index)
+                .map(|x| Units::new(x.numerator as i64, x.denominator.max(1) as i64))
+                .unwrap_or(Units::ZERO);
+            let mut slices = Vec::new();
+            let mut shapes: Vec<(Units, HandShape6)> = Vec::new();
+            for beats in voices.iter() {
+                let mut pos = Units::ZERO;
+                for beat in beats.iter() {
+                    if let Some(chord) = &beat.chord {
+                        if !shapes.iter().any(|x| x.0 == pos) {
+                            shapes.push((pos, Self::new_shape(&chord.frets)));
+                        }
+                    }
+                    pos += beat.duration.units();
+                }
+            }
+            shapes.sort_by_key(|x| x.0);
+            if let Some(shape) = last_shape {
+                if shapes.first().map(|x| x.0.is_positive()).unwrap_or(true) {
+                    shapes.insert(0, (Units::ZERO, shape));
+                }
+            }
+            if !shapes.is_empty() && bar_units.is_positive() {
+                let begin = entries.len();
+                if shapes[0].0.is_positive() {
+                    for duration in Duration::split_units(shapes[0].0) {
+                        entries.push(ProtoEntry::from(CoreEntry::Rest(duration)));
+                    }
+                }
+                for (index, (pos, shape)) in shapes.iter().enumerate() {
+                    let end = shapes.get(index + 1).map(|x| x.0).unwrap_or(bar_units);
+                    for duration in Duration::split_units(end - *pos) {
+                        entries.push(ProtoEntry::from(FrettedEntry6::from((*shape, duration))));
+                    }
+                    last_shape = Some(*shape);
+                }
+                slices.push((begin, entries.len() - begin));
+            }
+            for beats in voices.iter() {
+                if beats.iter().all(|x| x.notes.is_empty()) {
+                    continue;
+                }
+                let begin = entries.len();
+                for beat in beats.iter() {
+                    for effect in beat
+                        .effects
+                        .iter()
+                        .chain(beat.notes.iter().flat_map(|x| x.effects.iter()))
+                    {
+                        *warnings.entry(effect).or_default() += 1;
+                    }
+                    let durations = Self::beat_durations(beat, warnings);
+                    if beat.notes.is_empty() {
+                        for duration in durations {
+                            entries.push(ProtoEntry::from(CoreEntry::Rest(duration)));
+                        }
+                        continue;
+                    }
+                    let pick = Pick::from(
+                        beat.notes
+                            .iter()
+                            .map(|x| PickNote::new_string_fret(x.string, x.fret))
+                            .collect::<Vec<PickNote>>(),
+                    );
+                    if beat.notes.iter().all(|x| x.tie) {
+                        entries.push(ProtoEntry::from(CoreEntry::from(())));
+                    }
+                    for (index, duration) in durations.iter().enumerate() {
+                        if index > 0 {
+                            entries.push(ProtoEntry::from(CoreEntry::from(())));
+                        }
+                        entries.push(ProtoEntry::from(FrettedEntry6::from((pick, *duration))));
+                    }
+                }
+                slices.push((begin, entries.len() - begin));
+            }
+            Self::add_layer(layers, &id, slices);
+        }
+        Track::new(id, TrackKind::Guitar, entries)
+    }
+    fn new_sections(
+        bar_layers: Vec<Vec<BarLayer>>,
+        markers: &[(usize, String)],
+    ) -> (Vec<Section>, Form) {
+        let mut starts: Vec<(usize, String)> = Vec::new();
+        if markers.is_empty() {
+            for bar_index in (0..bar_layers.len()).step_by(Self::BARS_PER_SECTION) {
+                starts.push((
+                    bar_index,
+                    format!("{}", bar_index / Self::BARS_PER_SECTION + 1),
+                ));
+            }
+        } else {
+            starts = markers.to_vec();
+            if starts.first().map(|x| x.0 > 0).unwrap_or(true) {
+                starts.insert(0, (0, "start".to_string()));
+            }
+        }
+        let mut sections = Vec::new();
+        let mut form = Vec::new();
+        let mut used_ids = Vec::new();
+        for (index, (bar_index, text)) in starts.iter().enumerate() {
+            let end = starts
+                .get(index + 1)
+                .map(|x| x.0)
+                .unwrap_or(bar_layers.len());
+            let bars = bar_layers[*bar_index..end]
+                .iter()
+                .map(|x| Bar::from(x.clone()))
+                .collect();
+            let id = Self::unique_id(&mut used_ids, text.clone());
+            let kind = SectionKind::from_ident(text.as_str());
+            form.push(id.clone());
+            sections.push(Section::new(id, kind, bars));
+        }
+        (sections, form.into())
+    }
+}
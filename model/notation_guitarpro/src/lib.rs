@@ -0,0 +1,17 @@
+pub use notation_model;
+
+pub mod gp5_reader;
+pub mod gp_song;
+pub mod gpx_reader;
+pub mod guitarpro_import;
+
+pub mod prelude {
+    #[doc(hidden)]
+    pub use crate::gp5_reader::Gp5Reader;
+    #[doc(hidden)]
+    pub use crate::gp_song::{GpBeat, GpChord, GpDuration, GpMeasure, GpNote, GpSong, GpTrack};
+    #[doc(hidden)]
+    pub use crate::gpx_reader::GpxReader;
+    #[doc(hidden)]
+    pub use crate::guitarpro_import::{GuitarProError, GuitarProImport};
+}
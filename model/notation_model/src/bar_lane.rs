@@ -133,7 +133,7 @@ impl BarLane {
         predicate: &F,
     ) -> Option<T> {
         self.get_entry(&|x: &LaneEntry| {
-            if x.props.in_bar_pos.is_bigger_than(&in_bar_pos) {
+            if x.props.in_bar_pos > in_bar_pos {
                 predicate(x)
             } else {
                 None
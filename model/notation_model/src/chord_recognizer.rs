@@ -0,0 +1,271 @@
+use std::fmt::Display;
+use std::sync::Arc;
+
+use notation_proto::prelude::{
+    Chord, ChordQuality, Fretboard4, Fretboard6, HandShape4, HandShape6, Interval, Key, Note,
+    Scale, Semitones, Syllable, Tone,
+};
+
+use crate::prelude::{LaneEntry, LaneKind, Tab, TabBar};
+
+#[derive(Clone, Debug)]
+pub struct ChordCandidate {
+    pub chord: Chord,
+    pub quality: ChordQuality,
+    /// Chord tones not sounding, only the 5th can be omitted.
+    pub omitted: Vec<Interval>,
+}
+impl Display for ChordCandidate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "<ChordCandidate>({} {}, O:{})",
+            self.chord,
+            self.quality,
+            self.omitted.len()
+        )
+    }
+}
+impl ChordCandidate {
+    pub fn is_exact(&self) -> bool {
+        self.omitted.is_empty()
+    }
+    pub fn is_inverted(&self) -> bool {
+        self.chord.bass.is_some()
+    }
+    /// Same root and chord tones, the bass is not compared since shapes often
+    /// got a different bass note than the chord track.
+    pub fn is_matched(&self, chord: &Chord) -> bool {
+        chord.root == self.chord.root
+            && ChordRecognizer::pitch_classes(&chord.intervals.get_intervals())
+                == ChordRecognizer::pitch_classes(&self.chord.intervals.get_intervals())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ShapeChordMismatch {
+    pub entry: Arc<LaneEntry>,
+    pub chord: Chord,
+    pub candidates: Vec<ChordCandidate>,
+}
+impl Display for ShapeChordMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "<ShapeChordMismatch>({}, {} -> {:?})",
+            self.entry,
+            self.chord,
+            self.candidates.first().map(|x| x.chord.to_string())
+        )
+    }
+}
+
+pub struct ChordRecognizer();
+
+impl ChordRecognizer {
+    fn pitch_classes(intervals: &[Interval]) -> Vec<i8> {
+        let mut result: Vec<i8> = intervals
+            .iter()
+            .map(|x| Semitones::from(*x).0.rem_euclid(12))
+            .collect();
+        result.sort_unstable();
+        result.dedup();
+        result
+    }
+    fn syllable_class(syllable: &Syllable) -> i8 {
+        Semitones::from(*syllable).0.rem_euclid(12)
+    }
+    /// Ranked candidates, exact matches first, then root position, then simpler chords.
+    pub fn recognize(notes: &[Note]) -> Vec<ChordCandidate> {
+        let bass = match notes.iter().min_by_key(|x| Semitones::from(**x).0) {
+            Some(bass) => bass.syllable,
+            None => return vec![],
+        };
+        let mut syllables: Vec<Syllable> = Vec::new();
+        for note in notes.iter() {
+            let class = Self::syllable_class(&note.syllable);
+            if !syllables.iter().any(|x| Self::syllable_class(x) == class) {
+                syllables.push(note.syllable);
+            }
+        }
+        if syllables.len() < 2 {
+            return vec![];
+        }
+        let mut candidates = Vec::new();
+        for root in syllables.iter() {
+            let root_class = Self::syllable_class(root);
+            let classes: Vec<i8> = syllables
+                .iter()
+                .map(|x| (Self::syllable_class(x) - root_class).rem_euclid(12))
+                .filter(|x| *x != 0)
+                .collect();
+            for quality in ChordQuality::ALL.iter() {
+                if let Some(candidate) = Self::check_quality(root, &bass, &classes, quality) {
+                    candidates.push(candidate);
+                }
+            }
+        }
+        candidates.sort_by_key(|x| {
+            (
+                x.omitted.len(),
+                x.is_inverted(),
+                x.chord.intervals.get_intervals().len(),
+            )
+        });
+        candidates
+    }
+    fn check_quality(
+        root: &Syllable,
+        bass: &Syllable,
+        classes: &[i8],
+        quality: &ChordQuality,
+    ) -> Option<ChordCandidate> {
+        let intervals = quality.intervals();
+        let quality_classes = Self::pitch_classes(&intervals);
+        if classes.iter().any(|x| !quality_classes.contains(x)) {
+            return None;
+        }
+        let omitted: Vec<Interval> = intervals
+            .iter()
+            .filter(|x| !classes.contains(&Semitones::from(**x).0.rem_euclid(12)))
+            .copied()
+            .collect();
+        if omitted.iter().any(|x| *x != Interval::Perfect5th) || omitted.len() == intervals.len() {
+            return None;
+        }
+        let bass = if Self::syllable_class(bass) == Self::syllable_class(root) {
+            None
+        } else {
+            let bass_class =
+                (Self::syllable_class(bass) - Self::syllable_class(root)).rem_euclid(12);
+            intervals
+                .iter()
+                .find(|x| Semitones::from(**x).0.rem_euclid(12) == bass_class)
+                .map(|x| x.to_simple())
+        };
+        Some(ChordCandidate {
+            chord: Chord::new(*root, intervals.into(), bass),
+            quality: *quality,
+            omitted,
+        })
+    }
+    pub fn recognize_tone(tone: &Tone) -> Vec<ChordCandidate> {
+        Self::recognize(&tone.get_notes())
+    }
+    /// All chord tones except the 5th are sounding, extra notes are allowed.
+    pub fn is_compatible(chord: &Chord, notes: &[Note]) -> bool {
+        let classes: Vec<i8> = notes
+            .iter()
+            .map(|x| Self::syllable_class(&x.syllable))
+            .collect();
+        let root_class = Self::syllable_class(&chord.root);
+        classes.contains(&root_class)
+            && chord
+                .intervals
+                .get_intervals()
+                .iter()
+                .filter(|x| **x != Interval::Perfect5th)
+                .all(|x| classes.contains(&(root_class + Semitones::from(*x).0).rem_euclid(12)))
+    }
+}
+
+macro_rules! impl_recognize_shape {
+    ($name:ident, $shape_notes:ident, $strings:literal, $fretboard:ident, $hand_shape:ident) => {
+        impl ChordRecognizer {
+            pub fn $shape_notes(
+                fretboard: &$fretboard,
+                scale: &Scale,
+                key: &Key,
+                shape: &$hand_shape,
+            ) -> Vec<Note> {
+                (1..=$strings)
+                    .filter_map(|string| fretboard.shape_note(scale, key, shape, string))
+                    .collect()
+            }
+            pub fn $name(
+                fretboard: &$fretboard,
+                scale: &Scale,
+                key: &Key,
+                shape: &$hand_shape,
+            ) -> Vec<ChordCandidate> {
+                Self::recognize(&Self::$shape_notes(fretboard, scale, key, shape))
+            }
+        }
+    };
+}
+
+impl_recognize_shape!(recognize_shape6, shape_notes6, 6, Fretboard6, HandShape6);
+impl_recognize_shape!(recognize_shape4, shape_notes4, 4, Fretboard4, HandShape4);
+
+impl TabBar {
+    pub fn get_shape_notes(&self, entry: &LaneEntry) -> Option<Vec<Note>> {
+        let meta = self.tab_meta();
+        let track = entry.track()?;
+        if let Some(shape) = entry.model().as_fretted6().and_then(|x| x.as_shape()) {
+            let fretboard = track.get_fretboard6()?;
+            Some(ChordRecognizer::shape_notes6(
+                &fretboard,
+                &meta.scale,
+                &meta.key,
+                shape,
+            ))
+        } else if let Some(shape) = entry.model().as_fretted4().and_then(|x| x.as_shape()) {
+            let fretboard = track.get_fretboard4()?;
+            Some(ChordRecognizer::shape_notes4(
+                &fretboard,
+                &meta.scale,
+                &meta.key,
+                shape,
+            ))
+        } else {
+            None
+        }
+    }
+    pub fn recognize_shape_of_entry(&self, entry: &LaneEntry) -> Option<Vec<ChordCandidate>> {
+        self.get_shape_notes(entry)
+            .map(|notes| ChordRecognizer::recognize(&notes))
+    }
+    /// Shapes disagreeing with the chord track at the same position, shapes
+    /// that can't be recognized as chords are skipped.
+    pub fn check_shape_chords(&self) -> Vec<ShapeChordMismatch> {
+        let mut result = Vec::new();
+        for ((kind, _index), lane) in self.lanes.iter() {
+            if *kind != LaneKind::Shapes {
+                continue;
+            }
+            for entry in lane.entries.iter() {
+                let chord = match self.get_chord_of_entry(entry) {
+                    Some(chord) => chord,
+                    None => continue,
+                };
+                let notes = match self.get_shape_notes(entry) {
+                    Some(notes) => notes,
+                    None => continue,
+                };
+                let candidates = ChordRecognizer::recognize(&notes);
+                if candidates.is_empty() {
+                    continue;
+                }
+                if !candidates.iter().any(|x| x.is_matched(&chord))
+                    && !ChordRecognizer::is_compatible(&chord, &notes)
+                {
+                    result.push(ShapeChordMismatch {
+                        entry: entry.clone(),
+                        chord,
+                        candidates,
+                    });
+                }
+            }
+        }
+        result
+    }
+}
+
+impl Tab {
+    pub fn check_shape_chords(&self) -> Vec<ShapeChordMismatch> {
+        self.bars
+            .iter()
+            .flat_map(|x| x.check_shape_chords())
+            .collect()
+    }
+}
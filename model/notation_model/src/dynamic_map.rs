@@ -0,0 +1,148 @@
+use std::fmt::Display;
+use std::sync::Arc;
+
+use notation_proto::prelude::{Accent, Dynamic, DynamicCurve, MetaEntry, Units};
+
+use crate::prelude::{Tab, TabBar};
+
+/// Dynamic from a position in the tab, during crescendo and diminuendo the velocity factor
+/// moves linearly to the one of the dynamic over the units.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct DynamicChange {
+    pub in_tab_pos: Units,
+    pub dynamic: Dynamic,
+    pub factor: f32,
+    pub units: Units,
+}
+impl Display for DynamicChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "<DynamicChange>({} {} {} {})",
+            self.in_tab_pos, self.dynamic, self.factor, self.units
+        )
+    }
+}
+impl DynamicChange {
+    pub fn new(in_tab_pos: Units, dynamic: Dynamic) -> Self {
+        Self {
+            in_tab_pos,
+            dynamic,
+            factor: dynamic.velocity_factor(),
+            units: Units::ZERO,
+        }
+    }
+    pub fn end_factor(&self) -> f32 {
+        self.dynamic.velocity_factor()
+    }
+    pub fn calc_factor(&self, in_tab_pos: Units) -> f32 {
+        if !self.units.is_positive() || in_tab_pos >= self.in_tab_pos + self.units {
+            return self.end_factor();
+        }
+        let ratio = ((in_tab_pos - self.in_tab_pos) / self.units).as_f32().max(0.0);
+        self.factor + (self.end_factor() - self.factor) * ratio
+    }
+}
+
+/// Velocity factors of the dynamics in the tab, the tab starts at mezzo forte.
+#[derive(Clone, Debug)]
+pub struct DynamicMap {
+    /// Sorted by position, never empty.
+    pub changes: Vec<DynamicChange>,
+}
+impl Default for DynamicMap {
+    fn default() -> Self {
+        Self::new(&[])
+    }
+}
+impl DynamicMap {
+    pub fn new(bars: &[Arc<TabBar>]) -> Self {
+        let mut result = Self {
+            changes: vec![DynamicChange::new(Units::ZERO, Dynamic::default())],
+        };
+        for bar in bars.iter() {
+            for (in_bar_pos, entry) in bar.get_meta_changes() {
+                let pos = bar.props.bar_pos + in_bar_pos;
+                match entry {
+                    MetaEntry::Dynamic(x) => result.push_change(DynamicChange::new(pos, x)),
+                    MetaEntry::DynamicCurve(x) => {
+                        let change = DynamicChange {
+                            in_tab_pos: pos,
+                            dynamic: x.calc_target(result.get_change(pos).dynamic),
+                            factor: result.calc_factor(pos),
+                            units: bar.calc_beats_units(x.beats),
+                        };
+                        result.push_change(change);
+                    }
+                    _ => (),
+                }
+            }
+        }
+        result
+    }
+    /// A change at the same position replaces the previous one.
+    fn push_change(&mut self, change: DynamicChange) {
+        while let Some(last) = self.changes.last() {
+            if self.changes.len() > 1 && last.in_tab_pos >= change.in_tab_pos {
+                self.changes.pop();
+            } else {
+                break;
+            }
+        }
+        if self.changes.len() == 1 && self.changes[0].in_tab_pos >= change.in_tab_pos {
+            self.changes[0] = change;
+        } else {
+            self.changes.push(change);
+        }
+    }
+    pub fn get_change(&self, in_tab_pos: Units) -> DynamicChange {
+        let index = self
+            .changes
+            .iter()
+            .rposition(|x| x.in_tab_pos <= in_tab_pos)
+            .unwrap_or(0);
+        self.changes[index]
+    }
+    pub fn calc_factor(&self, in_tab_pos: Units) -> f32 {
+        self.get_change(in_tab_pos).calc_factor(in_tab_pos)
+    }
+    pub fn calc_velocity(&self, in_tab_pos: Units, velocity: u8) -> u8 {
+        (velocity as f32 * self.calc_factor(in_tab_pos))
+            .round()
+            .clamp(0.0, 127.0) as u8
+    }
+}
+
+impl TabBar {
+    pub fn get_dynamic_curves(&self) -> Vec<(Units, DynamicCurve)> {
+        self.get_meta_changes()
+            .into_iter()
+            .filter_map(|(in_bar_pos, entry)| match entry {
+                MetaEntry::DynamicCurve(x) => Some((in_bar_pos, x)),
+                _ => None,
+            })
+            .collect()
+    }
+    /// Accents of all lanes, the same accent in several lanes is only kept once.
+    pub fn get_accents(&self) -> Vec<(Units, Accent)> {
+        let mut result: Vec<(Units, Accent)> = Vec::new();
+        for lane in self.lanes.values() {
+            for entry in lane.entries.iter() {
+                if let Some(accent) = entry.model.accent() {
+                    let value = (entry.props.in_bar_pos, accent);
+                    if !result.contains(&value) {
+                        result.push(value);
+                    }
+                }
+            }
+        }
+        result.sort_by_key(|x| x.0);
+        result
+    }
+}
+
+impl Tab {
+    pub fn dynamic_map(&self) -> DynamicMap {
+        DynamicMap::new(&self.bars)
+    }
+}
@@ -1,31 +1,276 @@
 use std::fmt::Display;
 use std::sync::Arc;
 
+use notation_proto::prelude::{FormJumpEnd, FormMark, FormRepeat, FormSign, FormVolta};
+
+use crate::prelude::{Tab, TabBar};
 use crate::section::Section;
 
 #[derive(Debug)]
 pub struct Form {
+    /// Sections in playing order, with repeats and jumps expanded.
     pub sections: Vec<Arc<Section>>,
+    /// Sections as notated, the indices in repeats and marks are into them.
+    pub notated: Vec<Arc<Section>>,
+    /// Index in notated of each of the sections.
+    pub notated_indices: Vec<usize>,
+    pub repeats: Vec<FormRepeat>,
+    pub marks: Vec<FormMark>,
 }
 impl Display for Form {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "<Form>(S:{})", self.sections.len())
+        write!(
+            f,
+            "<Form>(S:{} N:{} R:{} M:{})",
+            self.sections.len(),
+            self.notated.len(),
+            self.repeats.len(),
+            self.marks.len()
+        )
     }
 }
 impl Form {
     pub fn new(add_ready_section: bool, proto: notation_proto::prelude::Form, tab_section: &Vec<Arc<Section>>) -> Self {
-        let mut sections = Vec::new();
-        let mut add_section =
-            |section_id: String| match tab_section.iter().find(|x| x.id == section_id).cloned() {
-                Some(section) => sections.push(section),
-                None => println!("Form::from(), bad section: {}", section_id),
-            };
-        if add_ready_section {
-            add_section(notation_proto::prelude::Section::READY_ID.to_string());
-        }
-        for section_id in proto.sections {
-            add_section(section_id);
-        }
-        Self { sections }
+        let mut notated = Vec::new();
+        let mut add_section = |section_id: String| -> Option<usize> {
+            match tab_section.iter().find(|x| x.id == section_id).cloned() {
+                Some(section) => {
+                    notated.push(section);
+                    Some(notated.len() - 1)
+                }
+                None => {
+                    println!("Form::from(), bad section: {}", section_id);
+                    None
+                }
+            }
+        };
+        let start = if add_ready_section {
+            add_section(notation_proto::prelude::Section::READY_ID.to_string()).is_some() as usize
+        } else {
+            0
+        };
+        let index_map: Vec<Option<usize>> = proto.sections.into_iter().map(add_section).collect();
+        let map_index = |index: usize| index_map.get(index).cloned().flatten();
+        let repeats = proto
+            .repeats
+            .iter()
+            .filter_map(|repeat| match (map_index(repeat.begin), map_index(repeat.end)) {
+                (Some(begin), Some(end)) if begin <= end => {
+                    let voltas = repeat
+                        .voltas
+                        .iter()
+                        .filter_map(|x| map_index(x.index).map(|index| FormVolta::new(index, x.rounds.clone())))
+                        .collect();
+                    Some(FormRepeat::new(begin, end, repeat.times, voltas))
+                }
+                _ => {
+                    println!("Form::from(), bad repeat: {}", repeat);
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+        let marks = proto
+            .marks
+            .iter()
+            .filter_map(|x| map_index(x.index).map(|index| FormMark::new(index, x.sign)))
+            .collect::<Vec<_>>();
+        let notated_indices = Self::expand(start, notated.len(), &repeats, &marks);
+        let sections = notated_indices.iter().map(|x| notated[*x].clone()).collect();
+        Self {
+            sections,
+            notated,
+            notated_indices,
+            repeats,
+            marks,
+        }
+    }
+    pub fn has_mark(&self, index: usize, sign: FormSign) -> bool {
+        self.marks.iter().any(|x| x.index == index && x.sign == sign)
+    }
+    pub fn get_marks(&self, index: usize) -> Vec<FormSign> {
+        self.marks
+            .iter()
+            .filter(|x| x.index == index)
+            .map(|x| x.sign)
+            .collect()
+    }
+    /// Repeat begin, start signs and volta of the played section.
+    pub fn calc_start_label(&self, section_ordinal: usize) -> String {
+        let index = match self.notated_indices.get(section_ordinal) {
+            Some(x) => *x,
+            None => return "".to_owned(),
+        };
+        let mut parts = Vec::new();
+        if self.repeats.iter().any(|x| x.begin == index) {
+            parts.push("|:".to_owned());
+        }
+        for sign in self.get_marks(index).iter().filter(|x| x.is_at_start()) {
+            parts.push(sign.to_abbr());
+        }
+        for volta in self.repeats.iter().filter_map(|x| x.get_volta(index)) {
+            let rounds: Vec<String> = volta.rounds.iter().map(|x| format!("{}.", x)).collect();
+            parts.push(rounds.join(""));
+        }
+        parts.join(" ")
+    }
+    /// End signs of the played section, and repeat end if going back to the repeat begin.
+    pub fn calc_end_label(&self, section_ordinal: usize) -> String {
+        let index = match self.notated_indices.get(section_ordinal) {
+            Some(x) => *x,
+            None => return "".to_owned(),
+        };
+        let mut parts = Vec::new();
+        let signs = self.get_marks(index);
+        if let Some(next) = self.notated_indices.get(section_ordinal + 1) {
+            if *next <= index
+                && !signs.iter().any(|x| x.is_jump())
+                && self.repeats.iter().any(|x| x.begin == *next && x.contains(index))
+            {
+                parts.push(":|".to_owned());
+            }
+        }
+        for sign in signs.iter().filter(|x| !x.is_at_start()) {
+            parts.push(sign.to_abbr());
+        }
+        parts.join(" ")
+    }
+    /// Notated indices in playing order, the sections before start are only played once.
+    ///
+    /// Repeats are only taken before a D.C. or D.S. jump, after it the last ending is played.
+    fn expand(start: usize, len: usize, repeats: &[FormRepeat], marks: &[FormMark]) -> Vec<usize> {
+        let find_mark = |sign: FormSign| marks.iter().find(|x| x.sign == sign).map(|x| x.index);
+        let has_mark = |index: usize, sign: FormSign| {
+            marks.iter().any(|x| x.index == index && x.sign == sign)
+        };
+        let mut inner_repeats: Vec<usize> = (0..repeats.len()).collect();
+        inner_repeats.sort_by_key(|x| repeats[*x].end - repeats[*x].begin);
+        let mut result = Vec::new();
+        let mut rounds = vec![1; repeats.len()];
+        let mut jump_end: Option<FormJumpEnd> = None;
+        let mut index = 0;
+        'sections: while index < len {
+            let skipped = repeats.iter().enumerate().any(|(r, repeat)| {
+                let round = if jump_end.is_some() { repeat.times } else { rounds[r] };
+                repeat
+                    .get_volta(index)
+                    .map(|volta| !volta.rounds.contains(&round))
+                    .unwrap_or(false)
+            });
+            if !skipped {
+                result.push(index);
+                match jump_end {
+                    Some(FormJumpEnd::Fine) if has_mark(index, FormSign::Fine) => break,
+                    Some(FormJumpEnd::Coda) if has_mark(index, FormSign::ToCoda) => {
+                        if let Some(coda) = find_mark(FormSign::Coda) {
+                            jump_end = Some(FormJumpEnd::End);
+                            index = coda;
+                            continue;
+                        }
+                    }
+                    _ => (),
+                }
+            }
+            if jump_end.is_none() {
+                for r in inner_repeats.iter() {
+                    let repeat = &repeats[*r];
+                    if repeat.end != index {
+                        continue;
+                    }
+                    if rounds[*r] < repeat.times {
+                        rounds[*r] += 1;
+                        index = repeat.begin;
+                        continue 'sections;
+                    }
+                    rounds[*r] = 1;
+                }
+            }
+            if jump_end.is_none() && !skipped {
+                for mark in marks.iter().filter(|x| x.index == index) {
+                    let (target, end) = match mark.sign {
+                        FormSign::DaCapo(end) => (Some(start), end),
+                        FormSign::DalSegno(end) => (find_mark(FormSign::Segno), end),
+                        _ => continue,
+                    };
+                    match target {
+                        Some(target) => {
+                            jump_end = Some(end);
+                            index = target;
+                            continue 'sections;
+                        }
+                        None => println!("Form::expand(), segno not found: {}", mark),
+                    }
+                }
+            }
+            index += 1;
+        }
+        result
+    }
+}
+
+impl Tab {
+    /// Bars as notated, each of them once, in notated order.
+    pub fn notated_bars(&self) -> Vec<Arc<TabBar>> {
+        let mut result: Vec<Arc<TabBar>> = Vec::new();
+        for bar in self.bars.iter() {
+            let key = self.calc_notated_key(bar);
+            if !result.iter().any(|x| self.calc_notated_key(x) == key) {
+                result.push(bar.clone());
+            }
+        }
+        result.sort_by_key(|x| self.calc_notated_key(x));
+        result
+    }
+    /// Index in notated_bars() of each of the bars.
+    pub fn calc_notated_bar_indices(&self) -> Vec<usize> {
+        let notated_bars = self.notated_bars();
+        self.bars
+            .iter()
+            .map(|bar| {
+                let key = self.calc_notated_key(bar);
+                notated_bars
+                    .iter()
+                    .position(|x| self.calc_notated_key(x) == key)
+                    .unwrap_or(0)
+            })
+            .collect()
+    }
+    /// Form labels before and after the bar number, only set on the first and last bar of a section.
+    pub fn calc_form_labels(&self, bar: &TabBar) -> (String, String) {
+        let start = if bar.props.bar_index == 0 {
+            self.form.calc_start_label(bar.props.section_ordinal)
+        } else {
+            "".to_owned()
+        };
+        let end = if bar.props.bar_index + 1 == bar.section.bars.len() {
+            self.form.calc_end_label(bar.props.section_ordinal)
+        } else {
+            "".to_owned()
+        };
+        (start, end)
+    }
+    pub fn get_notated_index(&self, bar: &TabBar) -> Option<usize> {
+        self.form.notated_indices.get(bar.props.section_ordinal).cloned()
+    }
+    fn calc_notated_key(&self, bar: &TabBar) -> (usize, usize) {
+        (
+            self.get_notated_index(bar).unwrap_or(bar.props.section_ordinal),
+            bar.props.bar_index,
+        )
+    }
+}
+
+impl TabBar {
+    /// Bar number with the form labels around it.
+    pub fn calc_bar_number_text(&self) -> String {
+        let (start, end) = match self.tab() {
+            Some(tab) => tab.calc_form_labels(self),
+            None => ("".to_owned(), "".to_owned()),
+        };
+        let number = self.props.bar_number.to_string();
+        [start, number, end]
+            .into_iter()
+            .filter(|x| !x.is_empty())
+            .collect::<Vec<String>>()
+            .join(" ")
     }
 }
@@ -0,0 +1,294 @@
+use std::fmt::Display;
+use std::sync::Arc;
+
+use notation_proto::prelude::{
+    Chord, Key, Note, Scale, Semitones, Strum, StrumStrings, TabMeta, Units,
+};
+
+use crate::prelude::{LaneEntry, Section, Tab, TabBar};
+
+/// Weights of the 12 pitch classes, indexed by semitones from C.
+pub type PitchWeights = [f32; 12];
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct KeyCandidate {
+    pub key: Key,
+    pub scale: Scale,
+    /// Correlation with the key profile, from -1.0 to 1.0.
+    pub confidence: f32,
+}
+impl Display for KeyCandidate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "<KeyCandidate>({} {} {:.2})",
+            self.key, self.scale, self.confidence
+        )
+    }
+}
+impl KeyCandidate {
+    /// Syllables only depend on the position of do, so relative keys (e.g. C Ionian
+    /// and A Aeolian) get the same syllables.
+    pub fn has_same_do(&self, key: &Key, scale: &Scale) -> bool {
+        self.scale.calc_do_semitones(&self.key).0.rem_euclid(12)
+            == scale.calc_do_semitones(key).0.rem_euclid(12)
+    }
+    pub fn has_same_do_as_meta(&self, meta: &TabMeta) -> bool {
+        self.has_same_do(&meta.key, &meta.scale)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SectionKeys {
+    pub section: Arc<Section>,
+    pub candidates: Vec<KeyCandidate>,
+}
+impl Display for SectionKeys {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "<SectionKeys>({} -> {:?})",
+            self.section.id,
+            self.candidates.first().map(|x| x.to_string())
+        )
+    }
+}
+
+pub struct KeyDetector();
+
+impl KeyDetector {
+    /// Krumhansl-Kessler major key profile, starting from the tonic.
+    pub const MAJOR_PROFILE: PitchWeights = [
+        6.35, 2.23, 3.48, 2.33, 4.38, 4.09, 2.52, 5.19, 2.39, 3.66, 2.29, 2.88,
+    ];
+    /// Krumhansl-Kessler minor key profile, starting from the tonic.
+    pub const MINOR_PROFILE: PitchWeights = [
+        6.33, 2.68, 3.52, 5.38, 2.60, 3.53, 2.54, 4.75, 3.98, 2.69, 3.34, 3.17,
+    ];
+    pub fn pitch_class(semitones: Semitones) -> usize {
+        semitones.0.rem_euclid(12) as usize
+    }
+    pub fn add_notes(weights: &mut PitchWeights, notes: &[Note], weight: f32) {
+        for note in notes.iter() {
+            weights[Self::pitch_class(Semitones::from(note.pitch))] += weight;
+        }
+    }
+    /// Chord syllables are resolved with the given key and scale.
+    pub fn add_chord(
+        weights: &mut PitchWeights,
+        scale: &Scale,
+        key: &Key,
+        chord: &Chord,
+        weight: f32,
+    ) {
+        let root = Semitones::from(chord.root) + scale.calc_do_semitones(key);
+        weights[Self::pitch_class(root)] += weight;
+        for interval in chord.intervals.get_intervals().iter() {
+            weights[Self::pitch_class(root + Semitones::from(*interval))] += weight;
+        }
+    }
+    fn correlate(weights: &PitchWeights, profile: &PitchWeights, tonic: usize) -> f32 {
+        let weights_mean = weights.iter().sum::<f32>() / 12.0;
+        let profile_mean = profile.iter().sum::<f32>() / 12.0;
+        let mut sum = 0.0;
+        let mut weights_sum = 0.0;
+        let mut profile_sum = 0.0;
+        for (index, profile_weight) in profile.iter().enumerate() {
+            let x = weights[(tonic + index) % 12] - weights_mean;
+            let y = profile_weight - profile_mean;
+            sum += x * y;
+            weights_sum += x * x;
+            profile_sum += y * y;
+        }
+        if weights_sum <= 0.0 {
+            return 0.0;
+        }
+        sum / (weights_sum * profile_sum).sqrt()
+    }
+    fn tonic_key(scale: &Scale, tonic: usize) -> Key {
+        scale
+            .get_keys()
+            .iter()
+            .find(|x| Self::pitch_class(Semitones::from(**x)) == tonic)
+            .copied()
+            .unwrap_or_default()
+    }
+    /// Weight of the notes in the scale.
+    pub fn scale_weight(weights: &PitchWeights, key: &Key, scale: &Scale) -> f32 {
+        let do_semitones = scale.calc_do_semitones(key);
+        scale
+            .get_syllables()
+            .iter()
+            .map(|x| weights[Self::pitch_class(do_semitones + Semitones::from(*x))])
+            .sum()
+    }
+    /// Modes sharing the tonic and the 3rd are picked by the weight of their notes,
+    /// Ionian and Aeolian are kept on ties.
+    fn refine_mode(weights: &PitchWeights, scale: Scale, tonic: usize) -> (Key, Scale) {
+        let modes = match scale {
+            Scale::Ionian => [Scale::Ionian, Scale::Mixolydian, Scale::Lydian],
+            _ => [Scale::Aeolian, Scale::Dorian, Scale::Phrygian],
+        };
+        let mut result = (Self::tonic_key(&scale, tonic), scale);
+        let mut max_weight = Self::scale_weight(weights, &result.0, &result.1);
+        for mode in modes.iter().skip(1) {
+            let key = Self::tonic_key(mode, tonic);
+            let weight = Self::scale_weight(weights, &key, mode);
+            if weight > max_weight {
+                result = (key, *mode);
+                max_weight = weight;
+            }
+        }
+        result
+    }
+    /// Keys ranked by confidence, empty if nothing is sounding. The tonic is found with
+    /// the major and minor profiles, then refined to the closest diatonic mode.
+    pub fn detect(weights: &PitchWeights) -> Vec<KeyCandidate> {
+        if weights.iter().all(|x| *x <= 0.0) {
+            return vec![];
+        }
+        let mut candidates = Vec::new();
+        for (scale, profile) in [
+            (Scale::Ionian, &Self::MAJOR_PROFILE),
+            (Scale::Aeolian, &Self::MINOR_PROFILE),
+        ] {
+            for tonic in 0..12 {
+                let (key, scale) = Self::refine_mode(weights, scale, tonic);
+                candidates.push(KeyCandidate {
+                    key,
+                    scale,
+                    confidence: Self::correlate(weights, profile, tonic),
+                });
+            }
+        }
+        candidates.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+        candidates
+    }
+    fn is_strummed(strum: &Strum, string: u8) -> bool {
+        match strum.strings {
+            StrumStrings::All => true,
+            StrumStrings::Between(x, y) => string >= x.min(y) && string <= x.max(y),
+        }
+    }
+}
+
+macro_rules! impl_entry_notes {
+    ($name:ident, $strings:literal, $as_fretted:ident, $get_fretted_shape:ident) => {
+        impl TabBar {
+            fn $name(&self, entry: &LaneEntry) -> Option<Vec<Note>> {
+                let meta = self.tab_meta();
+                let fretted = entry.model().$as_fretted()?;
+                if let Some(pick) = fretted.as_pick() {
+                    let (fretboard, shape) = self.$get_fretted_shape(entry)?;
+                    Some(
+                        fretboard
+                            .pick_tone(&meta.scale, &meta.key, &shape, pick)
+                            .get_notes(),
+                    )
+                } else if let Some(strum) = fretted.as_strum() {
+                    let (fretboard, shape) = self.$get_fretted_shape(entry)?;
+                    Some(
+                        (1..=$strings)
+                            .filter(|string| KeyDetector::is_strummed(strum, *string))
+                            .filter_map(|string| {
+                                fretboard.shape_note(&meta.scale, &meta.key, &shape, string)
+                            })
+                            .collect(),
+                    )
+                } else {
+                    None
+                }
+            }
+        }
+    };
+}
+
+impl_entry_notes!(get_fretted_notes6, 6, as_fretted6, get_fretted_shape6);
+impl_entry_notes!(get_fretted_notes4, 4, as_fretted4, get_fretted_shape4);
+
+impl TabBar {
+    /// Sounding notes weighted by duration, picks and strums are resolved with the
+    /// current shape, chords with the declared key.
+    pub fn add_pitch_weights(&self, weights: &mut PitchWeights) {
+        let meta = self.tab_meta();
+        for lane in self.lanes.values() {
+            for entry in lane.entries.iter() {
+                let weight = Units::from(entry.props.duration).as_f32();
+                if weight <= 0.0 {
+                    continue;
+                }
+                if let Some(core) = entry.proto().as_core() {
+                    if let Some(tone) = core.as_tone() {
+                        KeyDetector::add_notes(weights, &tone.get_notes(), weight);
+                    } else if let Some(chord) = core.as_chord() {
+                        KeyDetector::add_chord(weights, &meta.scale, &meta.key, chord, weight);
+                    }
+                } else if let Some(notes) = self
+                    .get_fretted_notes6(entry)
+                    .or_else(|| self.get_fretted_notes4(entry))
+                {
+                    KeyDetector::add_notes(weights, &notes, weight);
+                }
+            }
+        }
+    }
+    pub fn detect_keys(&self) -> Vec<KeyCandidate> {
+        let mut weights = PitchWeights::default();
+        self.add_pitch_weights(&mut weights);
+        KeyDetector::detect(&weights)
+    }
+}
+
+impl Tab {
+    pub fn detect_keys(&self) -> Vec<KeyCandidate> {
+        let mut weights = PitchWeights::default();
+        for bar in self.bars.iter() {
+            bar.add_pitch_weights(&mut weights);
+        }
+        KeyDetector::detect(&weights)
+    }
+    /// Keys of each section in form order, sections without notes are skipped.
+    pub fn detect_section_keys(&self) -> Vec<SectionKeys> {
+        let mut result: Vec<SectionKeys> = Vec::new();
+        for bar in self.bars.iter() {
+            if result.iter().any(|x| x.section.index == bar.section.index) {
+                continue;
+            }
+            let mut weights = PitchWeights::default();
+            for section_bar in self.bars.iter() {
+                if section_bar.props.section_ordinal == bar.props.section_ordinal {
+                    section_bar.add_pitch_weights(&mut weights);
+                }
+            }
+            let candidates = KeyDetector::detect(&weights);
+            if !candidates.is_empty() {
+                result.push(SectionKeys {
+                    section: bar.section.clone(),
+                    candidates,
+                });
+            }
+        }
+        result
+    }
+    /// Sections with a different do than the whole tab.
+    pub fn detect_modulations(&self) -> Vec<SectionKeys> {
+        let tab_key = match self.detect_keys().first() {
+            Some(x) => *x,
+            None => return vec![],
+        };
+        self.detect_section_keys()
+            .into_iter()
+            .filter(|x| match x.candidates.first() {
+                Some(candidate) => !candidate.has_same_do(&tab_key.key, &tab_key.scale),
+                None => false,
+            })
+            .collect()
+    }
+    /// The detected key when it gives different syllables than the declared meta.
+    pub fn check_meta_key(&self) -> Option<KeyCandidate> {
+        self.detect_keys()
+            .first()
+            .filter(|x| !x.has_same_do_as_meta(&self.meta))
+            .copied()
+    }
+}
@@ -69,11 +69,7 @@ impl LaneEntry {
         self.props.in_bar_pos
     }
     pub fn bar_position(&self) -> BarPosition {
-        BarPosition::new(
-            self.bar_props().bar_units,
-            self.bar_props().bar_ordinal,
-            self.props.in_bar_pos,
-        )
+        BarPosition::from(self.bar_props()).with_in_bar_pos(self.props.in_bar_pos)
     }
 }
 impl LaneEntry {
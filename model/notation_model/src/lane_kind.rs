@@ -1,7 +1,7 @@
 use std::fmt::Display;
 use std::sync::Arc;
 
-use notation_proto::prelude::{FrettedEntry6, ProtoEntry, TrackKind};
+use notation_proto::prelude::{FrettedEntry4, FrettedEntry6, ProtoEntry, TrackKind};
 
 use crate::prelude::ModelEntry;
 
@@ -16,6 +16,7 @@ pub enum LaneKind {
     Keyboard,
     Shapes,
     Strings,
+    Drums,
 }
 impl Display for LaneKind {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -28,7 +29,7 @@ impl Default for LaneKind {
     }
 }
 impl LaneKind {
-    pub const LEN: usize = 9;
+    pub const LEN: usize = 10;
     pub fn order(&self) -> usize {
         match self {
             LaneKind::None => 0,
@@ -40,6 +41,7 @@ impl LaneKind {
             LaneKind::Keyboard => 6,
             LaneKind::Shapes => 7,
             LaneKind::Strings => 8,
+            LaneKind::Drums => 9,
         }
     }
     pub fn of_entry(track_kind: &TrackKind, entry: &ProtoEntry) -> Self {
@@ -60,8 +62,19 @@ impl LaneKind {
             },
             TrackKind::Synth => Self::Keyboard,
             TrackKind::Piano => Self::Keyboard,
-            TrackKind::Drums => Self::None,
-            TrackKind::Bass => Self::None,
+            TrackKind::Drums => match entry {
+                ProtoEntry::Drum(_) => Self::Drums,
+                _ => Self::None,
+            },
+            TrackKind::Bass => match entry {
+                ProtoEntry::Fretted4(entry) => match entry {
+                    FrettedEntry4::Pick(_, _) => Self::Strings,
+                    FrettedEntry4::Strum(_, _) => Self::Strings,
+                    FrettedEntry4::Shape(_, _) => Self::Shapes,
+                    FrettedEntry4::Fretboard(_) => Self::None,
+                },
+                _ => Self::None,
+            },
         }
     }
     pub fn of_entries(track_kind: &TrackKind, entries: &Vec<Arc<ModelEntry>>) -> LaneKind {
@@ -136,4 +149,11 @@ impl LaneKind {
     pub fn is_shapes(&self) -> bool {
         matches!(self, Self::Shapes)
     }
+
+    /// Returns `true` if the lane kind is [`Drums`].
+    ///
+    /// [`Drums`]: LaneKind::Drums
+    pub fn is_drums(&self) -> bool {
+        matches!(self, Self::Drums)
+    }
 }
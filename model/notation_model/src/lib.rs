@@ -2,7 +2,10 @@ pub use notation_proto;
 
 pub mod bar;
 pub mod bar_lane;
+pub mod chord_recognizer;
+pub mod dynamic_map;
 pub mod form;
+pub mod key_detector;
 pub mod lane_entry;
 pub mod lane_kind;
 pub mod model_entry;
@@ -11,7 +14,9 @@ pub mod section;
 pub mod tab;
 pub mod tab_bar;
 pub mod tab_chord;
+pub mod tempo_map;
 pub mod track;
+pub mod tuplet_group;
 pub mod util;
 
 pub mod prelude {
@@ -22,8 +27,14 @@ pub mod prelude {
     #[doc(hidden)]
     pub use crate::bar_lane::{BarLane, BarLaneProps};
     #[doc(hidden)]
+    pub use crate::chord_recognizer::{ChordCandidate, ChordRecognizer, ShapeChordMismatch};
+    #[doc(hidden)]
+    pub use crate::dynamic_map::{DynamicChange, DynamicMap};
+    #[doc(hidden)]
     pub use crate::form::Form;
     #[doc(hidden)]
+    pub use crate::key_detector::{KeyCandidate, KeyDetector, PitchWeights, SectionKeys};
+    #[doc(hidden)]
     pub use crate::lane_entry::{LaneEntry, LaneEntryProps};
     #[doc(hidden)]
     pub use crate::lane_kind::LaneKind;
@@ -36,12 +47,16 @@ pub mod prelude {
     #[doc(hidden)]
     pub use crate::tab::Tab;
     #[doc(hidden)]
-    pub use crate::tab_bar::{TabBar, TabBarProps};
+    pub use crate::tab_bar::{BarTiming, TabBar, TabBarProps};
     #[doc(hidden)]
     pub use crate::tab_chord::TabChord;
     #[doc(hidden)]
+    pub use crate::tempo_map::{TempoChange, TempoMap};
+    #[doc(hidden)]
     pub use crate::track::{Track, TrackProps};
     #[doc(hidden)]
+    pub use crate::tuplet_group::TupletGroup;
+    #[doc(hidden)]
     pub use notation_proto::prelude::Bar as ProtoBar;
     #[doc(hidden)]
     pub use notation_proto::prelude::BarLayer as ProtoBarLayer;
@@ -2,13 +2,18 @@ use std::sync::{Arc, Weak};
 
 use crate::prelude::{Tab, Track};
 use notation_proto::prelude::{
-    Duration, Entry, EntryPassMode, FrettedEntry4, FrettedEntry6, ProtoEntry, TrackKind, Units,
+    Accent, Duration, Entry, EntryPassMode, FrettedEntry4, FrettedEntry6, Ornament, ProtoEntry,
+    TrackKind, Units,
 };
 
 #[derive(Copy, Clone, Debug)]
 pub struct ModelEntryProps {
     pub index: usize,
     pub tied_units: Units,
+    /// From the accent entries right before it.
+    pub accent: Option<Accent>,
+    /// From the ornament entries right before it.
+    pub ornament: Option<Ornament>,
 }
 
 #[derive(Debug)]
@@ -23,8 +28,15 @@ impl ModelEntry {
         proto: Arc<ProtoEntry>,
         index: usize,
         tied_units: Units,
+        accent: Option<Accent>,
+        ornament: Option<Ornament>,
     ) -> Self {
-        let props = ModelEntryProps { index, tied_units };
+        let props = ModelEntryProps {
+            index,
+            tied_units,
+            accent,
+            ornament,
+        };
         Self {
             track,
             proto,
@@ -56,6 +68,12 @@ impl ModelEntry {
     pub fn tab(&self) -> Option<Arc<Tab>> {
         self.track().and_then(|x| x.tab())
     }
+    pub fn accent(&self) -> Option<Accent> {
+        self.props.accent
+    }
+    pub fn ornament(&self) -> Option<Ornament> {
+        self.props.ornament
+    }
     pub fn as_fretted6(&self) -> Option<&FrettedEntry6> {
         self.proto.as_fretted6()
     }
@@ -4,8 +4,8 @@ use std::collections::HashMap;
 use std::sync::{Arc, Weak};
 use thiserror::Error;
 
-use crate::prelude::{BarLane, Form, LaneEntry, ModelEntry, Section, Tab, TabBar, TabMeta, Track, Slice};
-use notation_proto::prelude::{Duration, Entry, ProtoEntry, Units};
+use crate::prelude::{BarLane, BarTiming, Form, LaneEntry, ModelEntry, Section, Tab, TabBar, TabMeta, Track, Slice};
+use notation_proto::prelude::{Accent, Duration, Entry, Ornament, ProtoEntry, Units};
 
 #[derive(Error, Clone, Debug)]
 pub enum ParseError {
@@ -49,6 +49,11 @@ impl Tab {
             let bars = if let Some((begin, end)) = bars_range {
                 if begin < all_bars.len() && end < all_bars.len() && end >= begin {
                     let ready_added = add_ready_section && begin > 0;
+                    let mut bar_pos = if ready_added {
+                        all_bars[0].props.bar_units
+                    } else {
+                        Units::ZERO
+                    };
                     let mut bars: Vec<Arc<TabBar>> = all_bars[begin..=end].iter()
                         .enumerate()
                         .map(|(index, bar)| {
@@ -58,6 +63,12 @@ impl Tab {
                             } else {
                                 begin + index + 1
                             };
+                            let timing = BarTiming::new(
+                                bar.props.signature,
+                                bar.props.tempo,
+                                bar_pos,
+                            );
+                            bar_pos += bar.props.bar_units;
                             TabBar::new_arc(
                                 bar.tab.clone(),
                                 bar.section.clone(),
@@ -67,7 +78,7 @@ impl Tab {
                                 bar.props.bar_index,
                                 bar_ordinal,
                                 bar_number,
-                                bar.props.bar_units,
+                                timing,
                             )
                         }).collect();
                     if ready_added {
@@ -95,21 +106,26 @@ impl Tab {
         let mut section_ordinal: usize = 0;
         let mut bar_ordinal: usize = 0;
         let mut bars: Vec<Arc<TabBar>> = vec![];
+        let mut timing = BarTiming::from(meta);
         for section in form.sections.iter() {
             let section_round = match section_rounds.get(&section.id) {
                 Some(r) => r + 1,
                 None => 1,
             };
             section_rounds.insert(section.id.clone(), section_round);
-            bars.extend(section.new_tab_bars(
+            let section_bars = section.new_tab_bars(
                 add_ready_section,
                 section.clone(),
                 weak_self.clone(),
                 section_round,
                 section_ordinal,
                 bar_ordinal,
-                meta.bar_units(),
-            ));
+                timing,
+            );
+            if let Some(last) = section_bars.last() {
+                timing = last.next_timing();
+            }
+            bars.extend(section_bars);
             section_ordinal += 1;
             bar_ordinal += section.bars.len();
             println!(
@@ -132,8 +148,9 @@ impl Section {
         section_round: usize,
         section_ordinal: usize,
         section_bar_ordinal: usize,
-        bar_units: Units,
+        timing: BarTiming,
     ) -> Vec<Arc<TabBar>> {
+        let mut timing = timing;
         self.bars
             .iter()
             .enumerate()
@@ -144,7 +161,7 @@ impl Section {
                 } else {
                     bar_ordinal
                 };
-                TabBar::new_arc(
+                let tab_bar = TabBar::new_arc(
                     tab.clone(),
                     arc_section.clone(),
                     bar.clone(),
@@ -153,17 +170,19 @@ impl Section {
                     bar_index,
                     bar_ordinal,
                     bar_number,
-                    bar_units,
-                )
+                    timing,
+                );
+                timing = tab_bar.next_timing();
+                tab_bar
             })
             .collect()
     }
 }
 impl ModelEntry {
     pub fn calc_tied_units(entries: &Vec<ProtoEntry>, index: usize) -> Units {
-        let mut units = Units(0.0);
+        let mut units = Units::ZERO;
         if let Some(entry) = entries.get(index) {
-            units = units + Units::from(entry.duration());
+            units += Units::from(entry.duration());
             if let Some(next_entry) = entries.get(index + 1) {
                 if next_entry.is_core_tie() {
                     for i in index + 2..entries.len() {
@@ -177,6 +196,33 @@ impl ModelEntry {
         }
         units
     }
+    /// The last match among the entries without duration before it, e.g. marks.
+    fn calc_prefix<T>(
+        entries: &[ProtoEntry],
+        index: usize,
+        get_value: impl Fn(&ProtoEntry) -> Option<T>,
+    ) -> Option<T> {
+        match entries.get(index) {
+            Some(entry) if entry.duration() != Duration::Zero => (),
+            _ => return None,
+        }
+        for i in (0..index).rev() {
+            let entry = entries.get(i).unwrap();
+            if entry.duration() != Duration::Zero {
+                break;
+            }
+            if let Some(value) = get_value(entry) {
+                return Some(value);
+            }
+        }
+        None
+    }
+    pub fn calc_accent(entries: &[ProtoEntry], index: usize) -> Option<Accent> {
+        Self::calc_prefix(entries, index, ProtoEntry::as_core_accent)
+    }
+    pub fn calc_ornament(entries: &[ProtoEntry], index: usize) -> Option<Ornament> {
+        Self::calc_prefix(entries, index, ProtoEntry::as_core_ornament)
+    }
     pub fn new_entries(v: Vec<ProtoEntry>, track: &Weak<Track>) -> Vec<Arc<ModelEntry>> {
         let entries = v.clone();
         v.into_iter()
@@ -184,7 +230,9 @@ impl ModelEntry {
             .enumerate()
             .map(|(index, entry)| {
                 let tied_units = Self::calc_tied_units(&entries, index);
-                ModelEntry::new(track.clone(), entry, index, tied_units)
+                let accent = Self::calc_accent(&entries, index);
+                let ornament = Self::calc_ornament(&entries, index);
+                ModelEntry::new(track.clone(), entry, index, tied_units, accent, ornament)
             })
             .map(Arc::new)
             .collect()
@@ -192,13 +240,13 @@ impl ModelEntry {
 }
 impl LaneEntry {
     pub fn new_entries(v: Vec<Arc<ModelEntry>>, lane: &Weak<BarLane>, slice: Slice) -> Vec<Arc<LaneEntry>> {
-        let mut pos = 0.0;
+        let mut pos = Units::ZERO;
         v.into_iter()
             .enumerate()
             .map(|(index, entry)| {
                 let in_bar_pos = pos;
-                pos += Units::from(entry.as_ref().duration()).0;
-                LaneEntry::new(lane.clone(), slice.clone(), index, index, entry, Units(in_bar_pos))
+                pos += Units::from(entry.as_ref().duration());
+                LaneEntry::new(lane.clone(), slice.clone(), index, index, entry, in_bar_pos)
             })
             .map(Arc::new)
             .collect()
@@ -3,8 +3,8 @@ use std::sync::{Arc, Weak};
 use std::collections::HashMap;
 
 use notation_proto::prelude::{
-    BarPosition, Chord, Fretboard4, Fretboard6, HandShape4, HandShape6, Note, Position,
-    TabPosition, Octave,
+    BarPosition, Chord, CoreEntry, Fretboard4, Fretboard6, HandShape4, HandShape6, MetaEntry,
+    Note, Position, ProtoEntry, Semitones, TabPosition, Octave, Tempo, TempoCurve, Tone,
 };
 
 use crate::prelude::{
@@ -12,6 +12,29 @@ use crate::prelude::{
     Units,
 };
 
+/// Where a bar starts and the signature and tempo it starts with, before applying its own
+/// meta entries.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct BarTiming {
+    pub signature: Signature,
+    pub tempo: Tempo,
+    pub bar_pos: Units,
+}
+impl BarTiming {
+    pub fn new(signature: Signature, tempo: Tempo, bar_pos: Units) -> Self {
+        Self {
+            signature,
+            tempo,
+            bar_pos,
+        }
+    }
+}
+impl From<&TabMeta> for BarTiming {
+    fn from(v: &TabMeta) -> Self {
+        Self::new(v.signature, v.tempo, Units::ZERO)
+    }
+}
+
 #[derive(Copy, Clone, Debug, Default)]
 pub struct TabBarProps {
     pub section_index: usize,
@@ -21,11 +44,16 @@ pub struct TabBarProps {
     pub bar_ordinal: usize,
     pub bar_number: usize,
     pub bar_units: Units,
+    /// Start of the bar in the tab.
+    pub bar_pos: Units,
+    pub signature: Signature,
+    /// Tempo at the start of the bar.
+    pub tempo: Tempo,
 }
 
 impl From<TabBarProps> for BarPosition {
     fn from(v: TabBarProps) -> Self {
-        BarPosition::new(v.bar_units, v.bar_ordinal, Units(0.0))
+        BarPosition::new(v.bar_units, v.bar_ordinal, v.bar_pos, Units::ZERO)
     }
 }
 
@@ -72,7 +100,7 @@ impl TabBar {
         bar_index: usize,
         bar_ordinal: usize,
         bar_number: usize,
-        bar_units: Units,
+        timing: BarTiming,
     ) -> Arc<Self> {
         Arc::<Self>::new_cyclic(|weak_self| {
             let mut lanes: HashMap<(LaneKind, usize), Arc<BarLane>> = HashMap::new();
@@ -100,6 +128,15 @@ impl TabBar {
                     }
                 }
             }
+            let mut signature = timing.signature;
+            let mut tempo = timing.tempo;
+            for (in_bar_pos, entry) in Self::collect_meta_changes(&lanes) {
+                match entry {
+                    MetaEntry::Signature(x) => signature = x,
+                    MetaEntry::Tempo(x) if in_bar_pos == Units::ZERO => tempo = x,
+                    _ => (),
+                }
+            }
             let props = TabBarProps {
                 section_index: section.index,
                 section_round,
@@ -107,7 +144,10 @@ impl TabBar {
                 bar_index,
                 bar_ordinal,
                 bar_number,
-                bar_units,
+                bar_units: Units::from(signature),
+                bar_pos: timing.bar_pos,
+                signature,
+                tempo,
             };
             Self {
                 tab: tab,
@@ -119,7 +159,7 @@ impl TabBar {
         })
     }
     pub fn tab_position(&self) -> TabPosition {
-        TabPosition::new(Units(self.props.bar_ordinal as f32 * self.bar_units().0))
+        TabPosition::new(self.props.bar_pos)
     }
     pub fn tab_meta(&self) -> Arc<TabMeta> {
         match self.tab.upgrade() {
@@ -131,16 +171,78 @@ impl TabBar {
         }
     }
     pub fn bar_units(&self) -> Units {
-        self.tab_meta().bar_units()
+        self.props.bar_units
     }
     pub fn bar_beats(&self) -> u8 {
-        self.tab_meta().signature.bar_beats
+        self.props.signature.bar_beats
     }
     pub fn signature(&self) -> Signature {
-        self.tab_meta().signature
+        self.props.signature
     }
     pub fn beat_unit(&self) -> Unit {
-        self.tab_meta().signature.beat_unit
+        self.props.signature.beat_unit
+    }
+    /// Signature and tempo entries of the meta lanes, sorted by position.
+    fn collect_meta_changes(
+        lanes: &HashMap<(LaneKind, usize), Arc<BarLane>>,
+    ) -> Vec<(Units, MetaEntry)> {
+        let mut result = Vec::new();
+        for ((kind, _i), lane) in lanes.iter() {
+            if *kind != LaneKind::Meta {
+                continue;
+            }
+            for entry in lane.entries.iter() {
+                if let Some(meta) = entry.proto().as_meta() {
+                    if meta.is_signature()
+                        || meta.is_tempo()
+                        || meta.is_tempo_curve()
+                        || meta.is_dynamics()
+                    {
+                        result.push((entry.props.in_bar_pos, *meta));
+                    }
+                }
+            }
+        }
+        result.sort_by_key(|x| x.0);
+        result
+    }
+    pub fn get_meta_changes(&self) -> Vec<(Units, MetaEntry)> {
+        Self::collect_meta_changes(&self.lanes)
+    }
+    /// Tempo changes inside the bar, the one at the start is already in props.
+    pub fn get_tempo_changes(&self) -> Vec<(Units, Tempo)> {
+        self.get_meta_changes()
+            .into_iter()
+            .filter_map(|(in_bar_pos, entry)| match entry {
+                MetaEntry::Tempo(x) if in_bar_pos > Units::ZERO => Some((in_bar_pos, x)),
+                _ => None,
+            })
+            .collect()
+    }
+    pub fn get_tempo_curves(&self) -> Vec<(Units, TempoCurve)> {
+        self.get_meta_changes()
+            .into_iter()
+            .filter_map(|(in_bar_pos, entry)| match entry {
+                MetaEntry::TempoCurve(x) => Some((in_bar_pos, x)),
+                _ => None,
+            })
+            .collect()
+    }
+    /// Units of the beats in the bar's signature.
+    pub fn calc_beats_units(&self, beats: u8) -> Units {
+        Units::from(self.props.signature.beat_unit) * beats as i64
+    }
+    pub fn next_timing(&self) -> BarTiming {
+        let tempo = self
+            .get_tempo_changes()
+            .last()
+            .map(|x| x.1)
+            .unwrap_or(self.props.tempo);
+        BarTiming::new(
+            self.props.signature,
+            tempo,
+            self.props.bar_pos + self.props.bar_units,
+        )
     }
     pub fn calc_syllable(&self, pitch: &Pitch) -> Syllable {
         self.tab_meta().calc_syllable(pitch)
@@ -182,7 +284,7 @@ impl TabBar {
             .iter()
             .filter_map(| ((_k, _i), lane) | {
                 lane.get_entry(&|x: &LaneEntry| {
-                    if x.props.in_bar_pos.is_bigger_than(&in_bar_pos) {
+                    if x.props.in_bar_pos > in_bar_pos {
                         predicate(x)
                     } else {
                         None
@@ -196,13 +298,13 @@ impl TabBar {
         in_bar_pos: Units,
         predicate: &F,
     ) -> Option<T> {
-        let mut result_in_bar_pos = Units(f32::MAX);
+        let mut result_in_bar_pos: Option<Units> = None;
         let mut result = None;
         for ((_k, _i), lane) in self.lanes.iter() {
             if let Some((in_bar_pos, entry)) = lane.get_entry(&|x: &LaneEntry| {
-                if result_in_bar_pos < x.props.in_bar_pos {
+                if matches!(result_in_bar_pos, Some(pos) if pos < x.props.in_bar_pos) {
                     None
-                } else if x.props.in_bar_pos.is_bigger_than(&in_bar_pos) {
+                } else if x.props.in_bar_pos > in_bar_pos {
                     if let Some(t) = predicate(x) {
                         Some((x.props.in_bar_pos, t))
                     } else {
@@ -212,7 +314,7 @@ impl TabBar {
                     None
                 }
             }) {
-                result_in_bar_pos = in_bar_pos;
+                result_in_bar_pos = Some(in_bar_pos);
                 result = Some(entry)
             }
         }
@@ -256,6 +358,40 @@ impl TabBar {
     pub fn get_chord_of_entry(&self, entry: &LaneEntry) -> Option<Chord> {
         self.get_chord(Some(entry.props.in_bar_pos))
     }
+    /// Sounding tone of tone and pick entries.
+    pub fn calc_entry_tone(&self, entry: &LaneEntry) -> Option<Tone> {
+        let meta = self.tab_meta();
+        match entry.proto() {
+            ProtoEntry::Core(CoreEntry::Tone(tone, _)) => Some(*tone),
+            ProtoEntry::Fretted6(fretted_entry) => {
+                let pick = fretted_entry.as_pick()?;
+                let (fretboard, shape) = self.get_fretted_shape6(entry)?;
+                Some(fretboard.pick_tone(&meta.scale, &meta.key, &shape, pick))
+            }
+            ProtoEntry::Fretted4(fretted_entry) => {
+                let pick = fretted_entry.as_pick()?;
+                let (fretboard, shape) = self.get_fretted_shape4(entry)?;
+                Some(fretboard.pick_tone(&meta.scale, &meta.key, &shape, pick))
+            }
+            _ => None,
+        }
+    }
+    /// Notes of the entry's ornament, around the highest note of the entry.
+    pub fn calc_ornament_notes(&self, entry: &LaneEntry) -> Vec<Note> {
+        let ornament = match entry.model.ornament() {
+            Some(x) => x,
+            None => return vec![],
+        };
+        let meta = self.tab_meta();
+        self.calc_entry_tone(entry)
+            .and_then(|tone| {
+                tone.get_notes()
+                    .into_iter()
+                    .max_by_key(|x| Semitones::from(*x))
+            })
+            .map(|note| ornament.calc_notes(&meta.scale, &meta.key, &note))
+            .unwrap_or_default()
+    }
 }
 
 macro_rules! impl_get_fretted_shape {
@@ -0,0 +1,357 @@
+use std::fmt::Display;
+use std::sync::Arc;
+
+use notation_proto::prelude::{
+    BarPosition, Bpm, MetaEntry, Position, TabMeta, TabPosition, Tempo, TempoCurve, Unit, Units,
+};
+
+use crate::prelude::{Tab, TabBar};
+
+/// Tempo from a position in the tab, until the next change.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct TempoChange {
+    pub in_tab_pos: Units,
+    pub tempo: Tempo,
+    pub beat_unit: Unit,
+    /// Speed relative to the tempo at the start of the change.
+    pub factor: f32,
+    /// Speed relative to the tempo when reaching the next change, differs from `factor`
+    /// during rit. and accel.
+    pub end_factor: f32,
+}
+impl Display for TempoChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "<TempoChange>({} {} {} {}->{})",
+            self.in_tab_pos, self.tempo, self.beat_unit, self.factor, self.end_factor
+        )
+    }
+}
+impl TempoChange {
+    pub fn new(in_tab_pos: Units, tempo: Tempo, beat_unit: Unit) -> Self {
+        Self {
+            in_tab_pos,
+            tempo,
+            beat_unit,
+            factor: 1.0,
+            end_factor: 1.0,
+        }
+    }
+    pub fn is_constant(&self) -> bool {
+        self.factor == self.end_factor
+    }
+    pub fn calc_bpm(&self, factor: f32) -> f32 {
+        Bpm::from(self.tempo) as f32 * factor
+    }
+    pub fn calc_units_per_second(&self, factor: f32) -> f32 {
+        self.calc_bpm(factor) / 60.0 * Units::from(self.beat_unit).as_f32()
+    }
+    pub fn units_per_second(&self) -> f32 {
+        self.calc_units_per_second(self.factor)
+    }
+}
+
+/// End of the rit., accel. or fermata being added.
+#[derive(Copy, Clone, Debug)]
+struct TempoCurveEnd {
+    in_tab_pos: Units,
+    factor: f32,
+    /// The speed moves to the factor linearly, otherwise it's held until the end.
+    ramp: bool,
+}
+
+/// Converts between time and positions in the tab, bars can be of different length and
+/// the tempo can change between or inside them, the speed changes linearly with the position
+/// during rit. and accel.
+#[derive(Clone, Debug)]
+pub struct TempoMap {
+    /// Start of each bar, indexed by bar ordinal, never empty.
+    pub bars: Vec<BarPosition>,
+    /// Sorted by position, never empty.
+    pub changes: Vec<TempoChange>,
+}
+impl Default for TempoMap {
+    fn default() -> Self {
+        Self::new(&TabMeta::default(), &[])
+    }
+}
+impl TempoMap {
+    pub fn new(meta: &TabMeta, bars: &[Arc<TabBar>]) -> Self {
+        let mut result = Self {
+            bars: bars.iter().map(|x| BarPosition::from(x.props)).collect(),
+            changes: vec![TempoChange::new(
+                Units::ZERO,
+                meta.tempo,
+                meta.signature.beat_unit,
+            )],
+        };
+        if result.bars.is_empty() {
+            result
+                .bars
+                .push(BarPosition::new(meta.bar_units(), 0, Units::ZERO, Units::ZERO));
+        }
+        let mut curve_end = None;
+        for bar in bars.iter() {
+            let bar_pos = bar.props.bar_pos;
+            let beat_unit = bar.props.signature.beat_unit;
+            result.end_curve(&mut curve_end, bar_pos);
+            let last = result.last_change();
+            if last.beat_unit != beat_unit {
+                let factor = result.calc_curve_factor(&curve_end, bar_pos);
+                result.push_change(
+                    &curve_end,
+                    TempoChange {
+                        in_tab_pos: bar_pos,
+                        beat_unit,
+                        factor,
+                        end_factor: factor,
+                        ..last
+                    },
+                );
+                curve_end = None;
+            }
+            for (in_bar_pos, entry) in bar.get_meta_changes() {
+                let pos = bar_pos + in_bar_pos;
+                match entry {
+                    MetaEntry::Tempo(x) => {
+                        result.end_curve(&mut curve_end, pos);
+                        let change = TempoChange::new(pos, x, beat_unit);
+                        let last = result.last_change();
+                        if curve_end.is_none()
+                            && last.tempo == x
+                            && last.beat_unit == beat_unit
+                            && last.factor == change.factor
+                        {
+                            continue;
+                        }
+                        result.push_change(&curve_end, change);
+                        curve_end = None;
+                    }
+                    MetaEntry::TempoCurve(x) => {
+                        let units = bar.calc_beats_units(x.beats);
+                        result.add_curve(&mut curve_end, pos, &x, units);
+                    }
+                    _ => (),
+                }
+            }
+        }
+        if let Some(end) = curve_end {
+            result.end_curve(&mut curve_end, end.in_tab_pos);
+        }
+        result
+    }
+    fn last_change(&self) -> TempoChange {
+        self.changes[self.changes.len() - 1]
+    }
+    /// Speed factor at the position while adding the curve.
+    fn calc_curve_factor(&self, curve_end: &Option<TempoCurveEnd>, in_tab_pos: Units) -> f32 {
+        let last = self.last_change();
+        match curve_end {
+            Some(end) if end.ramp && end.in_tab_pos > last.in_tab_pos => {
+                let ratio = (in_tab_pos - last.in_tab_pos) / (end.in_tab_pos - last.in_tab_pos);
+                last.factor + (end.factor - last.factor) * ratio.as_f32()
+            }
+            _ => last.factor,
+        }
+    }
+    /// The last change ends with the speed reached at the new one.
+    fn push_change(&mut self, curve_end: &Option<TempoCurveEnd>, change: TempoChange) {
+        let end_factor = self.calc_curve_factor(curve_end, change.in_tab_pos);
+        let last = self.changes.last_mut().unwrap();
+        if last.in_tab_pos == change.in_tab_pos {
+            *last = change;
+            return;
+        }
+        last.end_factor = end_factor;
+        self.changes.push(change);
+    }
+    /// Adds the end of the curve if the position is passed it.
+    fn end_curve(&mut self, curve_end: &mut Option<TempoCurveEnd>, in_tab_pos: Units) {
+        if let Some(end) = *curve_end {
+            if end.in_tab_pos <= in_tab_pos {
+                let last = self.last_change();
+                self.push_change(
+                    curve_end,
+                    TempoChange {
+                        in_tab_pos: end.in_tab_pos,
+                        factor: end.factor,
+                        end_factor: end.factor,
+                        ..last
+                    },
+                );
+                *curve_end = None;
+            }
+        }
+    }
+    fn add_curve(
+        &mut self,
+        curve_end: &mut Option<TempoCurveEnd>,
+        in_tab_pos: Units,
+        curve: &TempoCurve,
+        units: Units,
+    ) {
+        self.end_curve(curve_end, in_tab_pos);
+        let factor = self.calc_curve_factor(curve_end, in_tab_pos);
+        let last = self.last_change();
+        let (start_factor, end) = if curve.is_fermata() {
+            (curve.calc_hold_factor(factor), factor)
+        } else {
+            (factor, curve.calc_end_factor(factor))
+        };
+        if !units.is_positive() {
+            if !curve.is_fermata() {
+                self.push_change(
+                    curve_end,
+                    TempoChange {
+                        in_tab_pos,
+                        factor: end,
+                        end_factor: end,
+                        ..last
+                    },
+                );
+                *curve_end = None;
+            }
+            return;
+        }
+        self.push_change(
+            curve_end,
+            TempoChange {
+                in_tab_pos,
+                factor: start_factor,
+                end_factor: start_factor,
+                ..last
+            },
+        );
+        *curve_end = Some(TempoCurveEnd {
+            in_tab_pos: in_tab_pos + units,
+            factor: end,
+            ramp: !curve.is_fermata(),
+        });
+    }
+    fn last_bar(&self) -> BarPosition {
+        self.bars.last().copied().unwrap_or(BarPosition::ZERO)
+    }
+    /// Bars after the last one are extrapolated with the length of the last one.
+    pub fn get_bar_position(&self, bar_ordinal: usize, in_bar_pos: Units) -> BarPosition {
+        if let Some(bar) = self.bars.get(bar_ordinal) {
+            return bar.with_in_bar_pos(in_bar_pos);
+        }
+        let last = self.last_bar();
+        let extra = (bar_ordinal - last.bar_ordinal) as i64;
+        BarPosition::new(
+            last.bar_units,
+            bar_ordinal,
+            last.bar_pos + last.bar_units * extra,
+            in_bar_pos,
+        )
+    }
+    pub fn calc_bar_position(&self, in_tab_pos: Units) -> BarPosition {
+        let last = self.last_bar();
+        let bar = if in_tab_pos >= last.next_bar_pos() && last.bar_units.is_positive() {
+            let extra = ((in_tab_pos - last.bar_pos) / last.bar_units).floor() as usize;
+            self.get_bar_position(last.bar_ordinal + extra, Units::ZERO)
+        } else {
+            let index = self.bars.partition_point(|x| x.bar_pos <= in_tab_pos);
+            self.bars[index.saturating_sub(1)]
+        };
+        bar.with_in_bar_pos(in_tab_pos - bar.bar_pos)
+    }
+    pub fn calc_position(&self, in_tab_pos: Units) -> Position {
+        Position {
+            tab: TabPosition::new(in_tab_pos),
+            bar: self.calc_bar_position(in_tab_pos),
+        }
+    }
+    fn get_change_index(&self, in_tab_pos: Units) -> usize {
+        self.changes
+            .partition_point(|x| x.in_tab_pos <= in_tab_pos)
+            .saturating_sub(1)
+    }
+    pub fn get_change(&self, in_tab_pos: Units) -> TempoChange {
+        self.changes[self.get_change_index(in_tab_pos)]
+    }
+    /// Speed factor change per unit inside the change.
+    fn calc_slope(&self, index: usize) -> f32 {
+        let change = self.changes[index];
+        match self.changes.get(index + 1) {
+            Some(next) if !change.is_constant() => {
+                (change.end_factor - change.factor) / (next.in_tab_pos - change.in_tab_pos).as_f32()
+            }
+            _ => 0.0,
+        }
+    }
+    fn calc_factor_in(&self, index: usize, in_tab_pos: Units) -> f32 {
+        let change = self.changes[index];
+        change.factor + self.calc_slope(index) * (in_tab_pos - change.in_tab_pos).as_f32()
+    }
+    /// Speed relative to the tempo at the position.
+    pub fn calc_factor(&self, in_tab_pos: Units) -> f32 {
+        self.calc_factor_in(self.get_change_index(in_tab_pos), in_tab_pos)
+    }
+    pub fn calc_bpm(&self, in_tab_pos: Units) -> f32 {
+        let index = self.get_change_index(in_tab_pos);
+        self.changes[index].calc_bpm(self.calc_factor_in(index, in_tab_pos))
+    }
+    /// Seconds between the positions inside the change, integrating the linear speed.
+    fn calc_seconds_in(&self, index: usize, from: Units, to: Units) -> f32 {
+        let base = self.changes[index].calc_units_per_second(1.0);
+        let from_factor = self.calc_factor_in(index, from);
+        let to_factor = self.calc_factor_in(index, to);
+        let units = (to - from).as_f32();
+        if (to_factor - from_factor).abs() < 0.0001 {
+            units / (base * (from_factor + to_factor) / 2.0)
+        } else {
+            units * (to_factor / from_factor).ln() / (base * (to_factor - from_factor))
+        }
+    }
+    fn calc_units_in(&self, index: usize, from: Units, seconds: f32) -> Units {
+        let base = self.changes[index].calc_units_per_second(1.0);
+        let factor = self.calc_factor_in(index, from);
+        let slope = self.calc_slope(index);
+        if slope.abs() < 0.000001 {
+            Units::from_f32(seconds * base * factor)
+        } else {
+            Units::from_f32(factor / slope * ((seconds * base * slope).exp() - 1.0))
+        }
+    }
+    /// Units played in the seconds, starting from the position.
+    pub fn calc_units(&self, from: Units, seconds: f32) -> Units {
+        let mut index = self.get_change_index(from);
+        let mut pos = from;
+        let mut seconds = seconds;
+        loop {
+            match self.changes.get(index + 1) {
+                Some(next) if self.calc_seconds_in(index, pos, next.in_tab_pos) < seconds => {
+                    seconds -= self.calc_seconds_in(index, pos, next.in_tab_pos);
+                    pos = next.in_tab_pos;
+                    index += 1;
+                }
+                _ => return pos + self.calc_units_in(index, pos, seconds) - from,
+            }
+        }
+    }
+    /// Seconds to play the units, starting from the position.
+    pub fn calc_seconds(&self, from: Units, units: Units) -> f32 {
+        let end = from + units;
+        let mut index = self.get_change_index(from);
+        let mut pos = from;
+        let mut seconds = 0.0;
+        loop {
+            match self.changes.get(index + 1) {
+                Some(next) if next.in_tab_pos < end => {
+                    seconds += self.calc_seconds_in(index, pos, next.in_tab_pos);
+                    pos = next.in_tab_pos;
+                    index += 1;
+                }
+                _ => return seconds + self.calc_seconds_in(index, pos, end),
+            }
+        }
+    }
+}
+
+impl Tab {
+    pub fn tempo_map(&self) -> TempoMap {
+        TempoMap::new(&self.meta, &self.bars)
+    }
+}
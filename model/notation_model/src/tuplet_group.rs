@@ -0,0 +1,97 @@
+use std::fmt::Display;
+
+use notation_proto::prelude::{Duration, Tuplet, Units};
+
+use crate::prelude::{BarLane, TabBar};
+
+/// Consecutive entries played as one tuplet, e.g. 3 triplet eighths.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct TupletGroup {
+    pub tuplet: Tuplet,
+    pub in_bar_pos: Units,
+    pub units: Units,
+}
+impl Display for TupletGroup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "<TupletGroup>({} {} {})",
+            self.tuplet, self.in_bar_pos, self.units
+        )
+    }
+}
+impl TupletGroup {
+    pub fn end_pos(&self) -> Units {
+        self.in_bar_pos + self.units
+    }
+    /// Written length of the group, i.e. without the tuplet ratio.
+    fn written_units(&self) -> Units {
+        self.units * self.tuplet.actual as i64 / self.tuplet.normal as i64
+    }
+}
+
+impl BarLane {
+    /// A group is closed when its written length reaches `actual` of its shortest unit,
+    /// or by any other entry after it, entries sounding along with it are ignored.
+    pub fn get_tuplet_groups(&self) -> Vec<TupletGroup> {
+        let mut result = Vec::new();
+        let mut current: Option<(TupletGroup, Units)> = None;
+        for entry in self.entries.iter() {
+            let duration = entry.props.duration;
+            if duration == Duration::Zero {
+                continue;
+            }
+            let tuplet = duration.tuplet();
+            let units = Units::from(duration);
+            let unit_units = duration.unit().map(Units::from).unwrap_or(units);
+            if let Some((group, min_unit)) = current.as_mut() {
+                if entry.props.in_bar_pos < group.end_pos() {
+                    continue;
+                }
+                if group.tuplet == tuplet && group.end_pos() == entry.props.in_bar_pos {
+                    group.units += units;
+                    *min_unit = (*min_unit).min(unit_units);
+                } else {
+                    result.push(*group);
+                    current = None;
+                }
+            }
+            if current.is_none() && !tuplet.is_none() {
+                current = Some((
+                    TupletGroup {
+                        tuplet,
+                        in_bar_pos: entry.props.in_bar_pos,
+                        units,
+                    },
+                    unit_units,
+                ));
+            }
+            if let Some((group, min_unit)) = current {
+                if group.written_units() >= min_unit * tuplet.actual as i64 {
+                    result.push(group);
+                    current = None;
+                }
+            }
+        }
+        if let Some((group, _)) = current {
+            result.push(group);
+        }
+        result
+    }
+}
+
+impl TabBar {
+    /// Tuplet groups of all lanes, the same group in several lanes is only kept once.
+    pub fn get_tuplet_groups(&self) -> Vec<TupletGroup> {
+        let mut result: Vec<TupletGroup> = Vec::new();
+        for lane in self.lanes.values() {
+            for group in lane.get_tuplet_groups() {
+                if !result.contains(&group) {
+                    result.push(group);
+                }
+            }
+        }
+        result.sort_by_key(|x| x.in_bar_pos);
+        result
+    }
+}
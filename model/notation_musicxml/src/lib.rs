@@ -0,0 +1,14 @@
+pub use notation_model;
+
+pub mod musicxml_export;
+pub mod musicxml_import;
+pub mod musicxml_util;
+
+pub mod prelude {
+    #[doc(hidden)]
+    pub use crate::musicxml_export::MusicXmlExport;
+    #[doc(hidden)]
+    pub use crate::musicxml_import::MusicXmlImport;
+    #[doc(hidden)]
+    pub use crate::musicxml_util::{MusicXmlError, MusicXmlUtil};
+}
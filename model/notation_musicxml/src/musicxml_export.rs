@@ -0,0 +1,652 @@
+use std::fmt::Display;
+use std::path::Path;
+use std::sync::Arc;
+
+use notation_model::prelude::{
+    Chord, CoreEntry, Duration, Entry, Fretboard6, FrettedEntry6, GuitarUtil, HandShape6,
+    LyricEntry, ModelEntry, Note, Ornament, Pitch, ProtoEntry, ProtoTab, Semitones, Signature,
+    Syllable, Tab, TabBar, TabBarProps, TabMeta, Tempo, Track, TrackKind, Unit, Units,
+};
+
+use fehler::throws;
+use notation_model::notation_proto::notation_fretted::strum::StrumStrings;
+
+use crate::prelude::{MusicXmlError, MusicXmlUtil};
+
+#[derive(Clone, Debug)]
+struct ExportNote {
+    pitch: Pitch,
+    octave: i8,
+    string_fret: Option<(u8, u8)>,
+}
+
+#[derive(Clone, Debug, Default)]
+struct ExportEvent {
+    duration: Duration,
+    notes: Vec<ExportNote>,
+    hidden: bool,
+    unpitched: bool,
+    tie_start: bool,
+    tie_stop: bool,
+    harmony: Option<Chord>,
+    lyric: Option<String>,
+    ornament: Option<Ornament>,
+    grace: Option<ExportNote>,
+}
+
+#[derive(Default)]
+struct XmlWriter {
+    text: String,
+    depth: usize,
+}
+
+impl XmlWriter {
+    fn escape(text: &str) -> String {
+        text.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+    }
+    fn line(&mut self, line: &str) {
+        for _ in 0..self.depth {
+            self.text.push_str("  ");
+        }
+        self.text.push_str(line);
+        self.text.push('\n');
+    }
+    fn tag(tag: &str, attrs: &[(&str, String)]) -> String {
+        let mut result = tag.to_string();
+        for (key, value) in attrs.iter() {
+            result.push_str(&format!(" {}=\"{}\"", key, Self::escape(value)));
+        }
+        result
+    }
+    fn open(&mut self, tag: &str, attrs: &[(&str, String)]) {
+        self.line(&format!("<{}>", Self::tag(tag, attrs)));
+        self.depth += 1;
+    }
+    fn close(&mut self, tag: &str) {
+        self.depth -= 1;
+        self.line(&format!("</{}>", tag));
+    }
+    fn empty(&mut self, tag: &str, attrs: &[(&str, String)]) {
+        self.line(&format!("<{}/>", Self::tag(tag, attrs)));
+    }
+    fn leaf<T: Display>(&mut self, tag: &str, value: T) {
+        self.line(&format!(
+            "<{}>{}</{}>",
+            tag,
+            Self::escape(&value.to_string()),
+            tag
+        ));
+    }
+}
+
+/// MusicXML (partwise) export, one part per track, with sections as rehearsal marks.
+///
+/// Chord and lyrics tracks are written as parts of hidden notes carrying the harmonies
+/// and lyrics, guitar tracks get a tablature staff with string and fret on every note.
+/// Frets are relative to the capo, same as in the tab.
+pub struct MusicXmlExport();
+
+impl MusicXmlExport {
+    #[throws(MusicXmlError)]
+    pub fn export_tab(tab: &ProtoTab) -> String {
+        let tab = Tab::try_parse_arc(tab.clone(), false, None)?;
+        Self::export_model_tab(&tab)
+    }
+    #[throws(MusicXmlError)]
+    pub fn write_tab<P: AsRef<Path>>(tab: &ProtoTab, path: P) {
+        std::fs::write(path, Self::export_tab(tab)?)?
+    }
+    pub fn is_supported_kind(kind: TrackKind) -> bool {
+        matches!(
+            kind,
+            TrackKind::Chord
+                | TrackKind::Lyrics
+                | TrackKind::Vocal
+                | TrackKind::Guitar
+                | TrackKind::Synth
+                | TrackKind::Piano
+        )
+    }
+    pub fn export_model_tab(tab: &Arc<Tab>) -> String {
+        let mut tracks = Vec::new();
+        for track in tab.tracks.iter() {
+            if Self::is_supported_kind(track.kind) {
+                tracks.push(track.clone());
+            } else if track.kind != TrackKind::Meta {
+                println!(
+                    "MusicXmlExport::export_tab(), track skipped: {} <{}>",
+                    track.id, track.kind
+                );
+            }
+        }
+        let mut w = XmlWriter::default();
+        w.line(r#"<?xml version="1.0" encoding="UTF-8" standalone="no"?>"#);
+        w.line(r#"<!DOCTYPE score-partwise PUBLIC "-//Recordare//DTD MusicXML 4.0 Partwise//EN" "http://www.musicxml.org/dtds/partwise.dtd">"#);
+        w.open("score-partwise", &[("version", "4.0".to_string())]);
+        w.open("identification", &[]);
+        w.open("encoding", &[]);
+        w.leaf("software", "notation");
+        w.close("encoding");
+        w.open("miscellaneous", &[]);
+        w.line(&format!(
+            r#"<miscellaneous-field name="uuid">{}</miscellaneous-field>"#,
+            tab.uuid
+        ));
+        w.close("miscellaneous");
+        w.close("identification");
+        w.open("part-list", &[]);
+        for (index, track) in tracks.iter().enumerate() {
+            let part_id = format!("P{}", index + 1);
+            w.open("score-part", &[("id", part_id.clone())]);
+            w.leaf("part-name", &track.id);
+            w.open("score-instrument", &[("id", format!("{}-I1", part_id))]);
+            w.leaf("instrument-name", track.kind);
+            w.close("score-instrument");
+            w.close("score-part");
+        }
+        w.close("part-list");
+        for (index, track) in tracks.iter().enumerate() {
+            w.open("part", &[("id", format!("P{}", index + 1))]);
+            Self::write_part(&mut w, tab, track, index == 0);
+            w.close("part");
+        }
+        w.close("score-partwise");
+        w.text
+    }
+    fn write_part(w: &mut XmlWriter, tab: &Arc<Tab>, track: &Arc<Track>, first_part: bool) {
+        let fretboard = match track.kind {
+            TrackKind::Guitar => Some(
+                track
+                    .get_fretboard6()
+                    .unwrap_or_else(GuitarUtil::new_default_fretboard),
+            ),
+            _ => None,
+        };
+        let mut in_word = false;
+        let mut prev_props: Option<TabBarProps> = None;
+        for bar in tab.bars.iter() {
+            let bar_divisions = MusicXmlUtil::units_to_divisions(bar.bar_units());
+            w.open(
+                "measure",
+                &[("number", format!("{}", bar.props.bar_ordinal + 1))],
+            );
+            match prev_props {
+                None => Self::write_attributes(w, &tab.meta, &bar.signature(), fretboard.as_ref()),
+                Some(prev) if prev.signature != bar.signature() => {
+                    w.open("attributes", &[]);
+                    Self::write_time(w, &bar.signature());
+                    w.close("attributes");
+                }
+                _ => (),
+            }
+            if first_part && prev_props.map(|x| x.tempo) != Some(bar.props.tempo) {
+                Self::write_tempo(w, &bar.props.tempo, &bar.signature());
+            }
+            prev_props = Some(bar.props);
+            if first_part && bar.props.bar_index == 0 {
+                w.open("direction", &[("placement", "above".to_string())]);
+                w.open("direction-type", &[]);
+                w.leaf("rehearsal", &bar.section.id);
+                w.close("direction-type");
+                w.close("direction");
+            }
+            let voices = Self::get_voices(bar, track);
+            let events: Vec<Vec<ExportEvent>> = match fretboard {
+                Some(fretboard) => Self::guitar_events(&tab.meta, &fretboard, &voices),
+                None => voices
+                    .iter()
+                    .map(|x| Self::track_events(&tab.meta, track.kind, x))
+                    .filter(|x| !x.is_empty())
+                    .collect(),
+            };
+            if events.is_empty() {
+                w.open("note", &[]);
+                w.empty("rest", &[("measure", "yes".to_string())]);
+                w.leaf("duration", bar_divisions);
+                w.leaf("voice", 1);
+                w.close("note");
+            }
+            let mut last_divisions = 0;
+            for (index, voice) in events.iter().enumerate() {
+                if index > 0 {
+                    w.open("backup", &[]);
+                    w.leaf("duration", last_divisions);
+                    w.close("backup");
+                }
+                last_divisions = 0;
+                for event in voice.iter() {
+                    Self::write_event(w, &tab.meta, event, index + 1, &mut in_word);
+                    last_divisions += MusicXmlUtil::units_to_divisions(Units::from(event.duration));
+                }
+            }
+            w.close("measure");
+        }
+    }
+    // Entries of each slice of the track in this bar, one voice per slice.
+    fn get_voices(bar: &TabBar, track: &Arc<Track>) -> Vec<Vec<Arc<ModelEntry>>> {
+        let mut voices = Vec::new();
+        for layer in bar.proto.layers.iter() {
+            if layer.track.id != track.id {
+                continue;
+            }
+            for slice in layer.slices.iter() {
+                if slice.in_round(bar.props.section_round) {
+                    voices.push(layer.track.get_entries(&slice.begin, &slice.end));
+                }
+            }
+        }
+        voices
+    }
+    fn track_events(
+        meta: &TabMeta,
+        kind: TrackKind,
+        entries: &[Arc<ModelEntry>],
+    ) -> Vec<ExportEvent> {
+        let mut events = Vec::new();
+        let mut has_content = false;
+        for entry in entries.iter() {
+            let duration = entry.proto.duration();
+            if duration == Duration::Zero {
+                continue;
+            }
+            let mut event = ExportEvent {
+                duration,
+                hidden: matches!(kind, TrackKind::Chord | TrackKind::Lyrics),
+                ..Default::default()
+            };
+            match entry.proto.as_ref() {
+                ProtoEntry::Core(CoreEntry::Chord(chord, _)) => {
+                    event.harmony = Some(*chord);
+                }
+                ProtoEntry::Core(CoreEntry::Tone(tone, _)) => {
+                    let notes = tone.get_notes();
+                    event.notes = notes.iter().map(Self::export_note).collect();
+                    event.ornament = entry.ornament();
+                    event.grace = Self::export_grace(
+                        meta,
+                        event.ornament,
+                        &notes.into_iter().map(|x| (x, None)).collect::<Vec<_>>(),
+                    );
+                    event.tie_start = entry.next_is_tie();
+                    event.tie_stop = entry.prev_is_tie();
+                }
+                ProtoEntry::Lyric(LyricEntry::Word(word, _)) if !word.text.is_empty() => {
+                    event.unpitched = true;
+                    event.lyric = Some(word.text.clone());
+                }
+                _ => (),
+            }
+            has_content = has_content || !Self::is_rest(entry);
+            events.push(event);
+        }
+        if has_content {
+            events
+        } else {
+            Vec::new()
+        }
+    }
+    fn guitar_events(
+        meta: &TabMeta,
+        fretboard: &Fretboard6,
+        voices: &[Vec<Arc<ModelEntry>>],
+    ) -> Vec<Vec<ExportEvent>> {
+        let mut shapes: Vec<(Units, Units, HandShape6)> = Vec::new();
+        for voice in voices.iter() {
+            let mut pos = Units::ZERO;
+            for entry in voice.iter() {
+                let units = Units::from(entry.proto.duration());
+                if let Some(shape) = entry.as_fretted6().and_then(|x| x.as_shape()) {
+                    shapes.push((pos, pos + units, *shape));
+                }
+                pos += units;
+            }
+        }
+        let mut result = Vec::new();
+        for voice in voices.iter() {
+            let mut events = Vec::new();
+            let mut has_notes = false;
+            let mut pos = Units::ZERO;
+            for entry in voice.iter() {
+                let duration = entry.proto.duration();
+                let units = Units::from(duration);
+                let shape = shapes
+                    .iter()
+                    .rev()
+                    .find(|(begin, end, _)| *begin <= pos && pos < *end)
+                    .map(|x| x.2)
+                    .unwrap_or_default();
+                pos += units;
+                let pick_notes: Vec<(u8, Option<u8>)> = match entry.as_fretted6() {
+                    _ if Self::is_rest(entry) => Vec::new(),
+                    Some(FrettedEntry6::Pick(pick, _)) => pick
+                        .get_notes()
+                        .iter()
+                        .map(|x| (x.string, x.fret))
+                        .collect(),
+                    Some(FrettedEntry6::Strum(strum, _)) => {
+                        let (low, high) = match strum.strings {
+                            StrumStrings::All => (1, fretboard.string_num() as u8),
+                            StrumStrings::Between(x, y) => (x.min(y), x.max(y)),
+                        };
+                        (low..=high).map(|x| (x, None)).collect()
+                    }
+                    _ => continue,
+                };
+                let mut notes = Vec::new();
+                let mut fret_notes = Vec::new();
+                for (string, fret) in pick_notes {
+                    let fret_note = match fret {
+                        Some(fret) => fretboard
+                            .fretted_note(&meta.scale, &meta.key, string, fret)
+                            .map(|x| (fret, x)),
+                        None => fretboard.shape_fret_note(&meta.scale, &meta.key, &shape, string),
+                    };
+                    if let Some((fret, note)) = fret_note {
+                        notes.push(ExportNote {
+                            string_fret: Some((string, fret)),
+                            ..Self::export_note(&note)
+                        });
+                        fret_notes.push((note, Some((string, fret))));
+                    }
+                }
+                has_notes = has_notes || !notes.is_empty();
+                let ornament = entry.ornament().filter(|_| !notes.is_empty());
+                events.push(ExportEvent {
+                    duration,
+                    tie_start: !notes.is_empty() && entry.next_is_tie(),
+                    tie_stop: !notes.is_empty() && entry.prev_is_tie(),
+                    notes,
+                    ornament,
+                    grace: Self::export_grace(meta, ornament, &fret_notes),
+                    ..Default::default()
+                });
+            }
+            if has_notes {
+                result.push(events);
+            }
+        }
+        result
+    }
+    fn is_rest(entry: &ModelEntry) -> bool {
+        entry.proto.as_core().map(|x| x.is_rest()).unwrap_or(false)
+    }
+    fn export_note(note: &Note) -> ExportNote {
+        ExportNote {
+            pitch: note.pitch,
+            octave: MusicXmlUtil::octave_number(note.octave),
+            string_fret: None,
+        }
+    }
+    // Grace note before the highest note, on the same string if the fret is in range.
+    fn export_grace(
+        meta: &TabMeta,
+        ornament: Option<Ornament>,
+        notes: &[(Note, Option<(u8, u8)>)],
+    ) -> Option<ExportNote> {
+        if !matches!(ornament, Some(Ornament::Grace(_) | Ornament::Appoggiatura(_))) {
+            return None;
+        }
+        let (note, string_fret) = notes.iter().max_by_key(|x| Semitones::from(x.0))?;
+        let grace = ornament?
+            .calc_notes(&meta.scale, &meta.key, note)
+            .into_iter()
+            .next()?;
+        let offset = (Semitones::from(grace) - Semitones::from(*note)).0;
+        let string_fret = string_fret.and_then(|(string, fret)| {
+            let fret = fret as i8 + offset;
+            (fret >= 0).then_some((string, fret as u8))
+        });
+        Some(ExportNote {
+            string_fret,
+            ..Self::export_note(&grace)
+        })
+    }
+    fn write_pitch(w: &mut XmlWriter, note: &ExportNote) {
+        w.open("pitch", &[]);
+        w.leaf("step", MusicXmlUtil::pitch_step(note.pitch.name));
+        let alter = MusicXmlUtil::pitch_alter(note.pitch.sign);
+        if alter != 0 {
+            w.leaf("alter", alter);
+        }
+        w.leaf("octave", note.octave);
+        w.close("pitch");
+    }
+    fn write_technical(w: &mut XmlWriter, string: u8, fret: u8) {
+        w.open("technical", &[]);
+        w.leaf("string", string);
+        w.leaf("fret", fret);
+        w.close("technical");
+    }
+    // Acciaccaturas are slashed, appoggiaturas are not.
+    fn write_grace(w: &mut XmlWriter, ornament: Ornament, grace: &ExportNote, voice: usize) {
+        w.open("note", &[]);
+        let slash = if ornament.is_before_beat() { "yes" } else { "no" };
+        w.empty("grace", &[("slash", slash.to_string())]);
+        Self::write_pitch(w, grace);
+        w.leaf("voice", voice);
+        w.leaf("type", MusicXmlUtil::unit_type(Unit::Eighth));
+        if let Some((string, fret)) = grace.string_fret {
+            w.open("notations", &[]);
+            Self::write_technical(w, string, fret);
+            w.close("notations");
+        }
+        w.close("note");
+    }
+    fn write_time(w: &mut XmlWriter, signature: &Signature) {
+        w.open("time", &[]);
+        w.leaf("beats", signature.bar_beats);
+        w.leaf(
+            "beat-type",
+            Units::from(signature.beat_unit).denominator() as u32,
+        );
+        w.close("time");
+    }
+    fn write_attributes(
+        w: &mut XmlWriter,
+        meta: &TabMeta,
+        signature: &Signature,
+        fretboard: Option<&Fretboard6>,
+    ) {
+        w.open("attributes", &[]);
+        w.leaf("divisions", MusicXmlUtil::DIVISIONS);
+        w.open("key", &[]);
+        w.leaf("fifths", MusicXmlUtil::key_fifths(meta));
+        w.leaf("mode", MusicXmlUtil::scale_mode(meta.scale));
+        w.close("key");
+        Self::write_time(w, signature);
+        match fretboard {
+            Some(fretboard) => {
+                w.open("clef", &[]);
+                w.leaf("sign", "TAB");
+                w.leaf("line", 5);
+                w.close("clef");
+                w.open("staff-details", &[]);
+                w.leaf("staff-lines", fretboard.string_num());
+                for (index, semitones) in fretboard.string_notes.iter().rev().enumerate() {
+                    let (pitch, octave) = semitones.as_pitch_octave();
+                    w.open("staff-tuning", &[("line", format!("{}", index + 1))]);
+                    w.leaf("tuning-step", MusicXmlUtil::pitch_step(pitch.name));
+                    let alter = MusicXmlUtil::pitch_alter(pitch.sign);
+                    if alter != 0 {
+                        w.leaf("tuning-alter", alter);
+                    }
+                    w.leaf("tuning-octave", MusicXmlUtil::octave_number(octave));
+                    w.close("staff-tuning");
+                }
+                if fretboard.capo > 0 {
+                    w.leaf("capo", fretboard.capo);
+                }
+                w.close("staff-details");
+            }
+            None => {
+                w.open("clef", &[]);
+                w.leaf("sign", "G");
+                w.leaf("line", 2);
+                w.close("clef");
+            }
+        }
+        w.close("attributes");
+    }
+    fn write_tempo(w: &mut XmlWriter, tempo: &Tempo, signature: &Signature) {
+        let bpm = notation_model::prelude::Bpm::from(*tempo);
+        let quarters_per_beat =
+            (Units::from(signature.beat_unit) / Units::from(Unit::Quarter)).as_f32();
+        w.open("direction", &[("placement", "above".to_string())]);
+        w.open("direction-type", &[]);
+        w.open("metronome", &[]);
+        w.leaf(
+            "beat-unit",
+            MusicXmlUtil::unit_type(signature.beat_unit),
+        );
+        w.leaf("per-minute", bpm);
+        w.close("metronome");
+        w.close("direction-type");
+        w.empty(
+            "sound",
+            &[("tempo", format!("{}", bpm as f32 * quarters_per_beat))],
+        );
+        w.close("direction");
+    }
+    fn write_harmony(w: &mut XmlWriter, meta: &TabMeta, chord: &Chord) {
+        let write_pitch = |w: &mut XmlWriter, tag: &str, syllable: &Syllable| {
+            let pitch = MusicXmlUtil::syllable_pitch(meta, syllable);
+            w.open(tag, &[]);
+            w.leaf(
+                &format!("{}-step", tag),
+                MusicXmlUtil::pitch_step(pitch.name),
+            );
+            let alter = MusicXmlUtil::pitch_alter(pitch.sign);
+            if alter != 0 {
+                w.leaf(&format!("{}-alter", tag), alter);
+            }
+            w.close(tag);
+        };
+        let (kind, degrees) = MusicXmlUtil::chord_kind(&chord.intervals);
+        w.open("harmony", &[]);
+        write_pitch(w, "root", &chord.root);
+        w.leaf("kind", kind);
+        if let Some(bass) = chord.bass {
+            write_pitch(w, "bass", &bass.syllable_on_root(&chord.root));
+        }
+        for interval in degrees {
+            let (value, alter) = MusicXmlUtil::interval_degree(interval);
+            w.open("degree", &[]);
+            w.leaf("degree-value", value);
+            w.leaf("degree-alter", alter);
+            w.leaf("degree-type", "add");
+            w.close("degree");
+        }
+        w.close("harmony");
+    }
+    fn write_event(
+        w: &mut XmlWriter,
+        meta: &TabMeta,
+        event: &ExportEvent,
+        voice: usize,
+        in_word: &mut bool,
+    ) {
+        if let Some(chord) = &event.harmony {
+            Self::write_harmony(w, meta, chord);
+        }
+        let unit = match event.duration.unit() {
+            Some(x) => x,
+            None => return,
+        };
+        if let (Some(ornament), Some(grace)) = (event.ornament, &event.grace) {
+            Self::write_grace(w, ornament, grace, voice);
+        }
+        let ornament_tag = event.ornament.and_then(MusicXmlUtil::ornament_tag);
+        let tuplet = event.duration.tuplet();
+        let notes: Vec<Option<&ExportNote>> = if event.notes.is_empty() {
+            vec![None]
+        } else {
+            event.notes.iter().map(Some).collect()
+        };
+        for (index, note) in notes.iter().enumerate() {
+            let attrs = if event.hidden {
+                vec![("print-object", "no".to_string())]
+            } else {
+                vec![]
+            };
+            w.open("note", &attrs);
+            if index > 0 {
+                w.empty("chord", &[]);
+            }
+            match note {
+                Some(note) => Self::write_pitch(w, note),
+                None if event.unpitched => {
+                    w.open("unpitched", &[]);
+                    w.leaf("display-step", "B");
+                    w.leaf("display-octave", 4);
+                    w.close("unpitched");
+                }
+                None => w.empty("rest", &[]),
+            }
+            w.leaf(
+                "duration",
+                MusicXmlUtil::units_to_divisions(Units::from(event.duration)),
+            );
+            if event.tie_stop {
+                w.empty("tie", &[("type", "stop".to_string())]);
+            }
+            if event.tie_start {
+                w.empty("tie", &[("type", "start".to_string())]);
+            }
+            w.leaf("voice", voice);
+            w.leaf("type", MusicXmlUtil::unit_type(unit));
+            for _ in 0..event.duration.dots() {
+                w.empty("dot", &[]);
+            }
+            if !tuplet.is_none() {
+                w.open("time-modification", &[]);
+                w.leaf("actual-notes", tuplet.actual);
+                w.leaf("normal-notes", tuplet.normal);
+                w.close("time-modification");
+            }
+            let string_fret = note.and_then(|x| x.string_fret);
+            let ornament_tag = ornament_tag.filter(|_| index == 0);
+            if event.tie_start || event.tie_stop || string_fret.is_some() || ornament_tag.is_some()
+            {
+                w.open("notations", &[]);
+                if event.tie_stop {
+                    w.empty("tied", &[("type", "stop".to_string())]);
+                }
+                if event.tie_start {
+                    w.empty("tied", &[("type", "start".to_string())]);
+                }
+                if let Some(tag) = ornament_tag {
+                    w.open("ornaments", &[]);
+                    w.empty(tag, &[]);
+                    w.close("ornaments");
+                }
+                if let Some((string, fret)) = string_fret {
+                    Self::write_technical(w, string, fret);
+                }
+                w.close("notations");
+            }
+            if index == 0 {
+                if let Some(text) = &event.lyric {
+                    let (text, hyphen) = match text.strip_suffix('-') {
+                        Some(text) => (text, true),
+                        None => (text.as_str(), false),
+                    };
+                    let syllabic = match (*in_word, hyphen) {
+                        (false, false) => "single",
+                        (false, true) => "begin",
+                        (true, true) => "middle",
+                        (true, false) => "end",
+                    };
+                    *in_word = hyphen;
+                    w.open("lyric", &[("number", "1".to_string())]);
+                    w.leaf("syllabic", syllabic);
+                    w.leaf("text", text);
+                    w.close("lyric");
+                }
+            }
+            w.close("note");
+        }
+    }
+}
@@ -0,0 +1,971 @@
+use std::path::Path;
+
+use fehler::{throw, throws};
+use notation_model::notation_proto::prelude::{
+    Bar, BarLayer, Chord, CoreEntry, Duration, Form, Fretboard6, FrettedEntry6, GuitarUtil,
+    Interval, LyricEntry, Note, Ornament, Pick, PickNote, Pitch, ProtoEntry, Scale, Section,
+    SectionKind,
+    Semitones, Signature, Slice, SliceBegin, SliceEnd, Tab, TabMeta, Tempo, Tone, Track, TrackKind,
+    Tuplet, Unit, Units, Uuid, GUITAR_FRET_NUM_ACOUSTIC,
+};
+use roxmltree::{Document, Node, ParsingOptions};
+
+use crate::prelude::{MusicXmlError, MusicXmlUtil};
+
+#[derive(Clone, Debug)]
+struct ImportNote {
+    pitch: Pitch,
+    octave: i8,
+    string_fret: Option<(u8, u8)>,
+}
+
+#[derive(Clone, Debug, Default)]
+struct ImportEvent {
+    pos: Units,
+    durations: Vec<Duration>,
+    notes: Vec<ImportNote>,
+    measure_rest: bool,
+    tie_start: bool,
+    tie_stop: bool,
+    lyrics: Vec<(String, String)>,
+    ornament: Option<Ornament>,
+    /// Grace note before the event, slashed or not.
+    grace: Option<(bool, ImportNote)>,
+}
+
+#[derive(Clone, Debug)]
+struct ImportHarmony {
+    root: Pitch,
+    kind: String,
+    bass: Option<Pitch>,
+    degrees: Vec<(u8, i8, String)>,
+}
+
+#[derive(Clone, Debug, Default)]
+struct ImportMeasure {
+    voices: Vec<(String, Vec<ImportEvent>)>,
+    harmonies: Vec<(Units, ImportHarmony)>,
+    rehearsal: Option<String>,
+}
+
+#[derive(Clone, Debug, Default)]
+struct ImportPart {
+    name: Option<String>,
+    instrument: Option<String>,
+    tuning: Vec<(u8, Semitones)>,
+    capo: u8,
+    measures: Vec<ImportMeasure>,
+}
+
+#[derive(Clone, Debug, Default)]
+struct ImportMeta {
+    fifths: Option<(i8, Scale)>,
+    signature: Option<Signature>,
+    tempo: Option<Tempo>,
+}
+
+/// MusicXML (partwise, uncompressed) import, one track per part.
+///
+/// Harmonies and lyrics found in melody parts are moved into their own chord and lyrics
+/// tracks, rehearsal marks start new sections.
+pub struct MusicXmlImport();
+
+impl MusicXmlImport {
+    pub const BARS_PER_SECTION: usize = 8;
+
+    #[throws(MusicXmlError)]
+    pub fn read_tab<P: AsRef<Path>>(path: P) -> Tab {
+        let bytes = std::fs::read(path)?;
+        if bytes.starts_with(b"PK") {
+            throw!(MusicXmlError::Compressed);
+        }
+        Self::import_tab(&String::from_utf8_lossy(&bytes))?
+    }
+    #[throws(MusicXmlError)]
+    pub fn import_tab(text: &str) -> Tab {
+        let options = ParsingOptions {
+            allow_dtd: true,
+            ..ParsingOptions::default()
+        };
+        let doc = Document::parse_with_options(text, options)?;
+        let root = doc.root_element();
+        if !root.has_tag_name("score-partwise") {
+            throw!(MusicXmlError::NotPartwise(
+                root.tag_name().name().to_string()
+            ));
+        }
+        let uuid = root
+            .descendants()
+            .find(|x| x.has_tag_name("miscellaneous-field") && x.attribute("name") == Some("uuid"))
+            .and_then(|x| x.text())
+            .and_then(|x| Uuid::parse_str(x.trim()).ok())
+            .map(|x| x.to_string())
+            .unwrap_or_else(Tab::new_uuid);
+        let mut meta = ImportMeta::default();
+        let mut parts = Vec::new();
+        for part in Self::children(root, "part") {
+            let mut import_part = ImportPart::default();
+            if let Some(score_part) = Self::children(root, "part-list")
+                .flat_map(|x| Self::children(x, "score-part"))
+                .find(|x| x.attribute("id") == part.attribute("id"))
+            {
+                import_part.name = Self::child_text(score_part, "part-name");
+                import_part.instrument = Self::children(score_part, "score-instrument")
+                    .find_map(|x| Self::child_text(x, "instrument-name"));
+            }
+            Self::parse_part(part, &mut meta, &mut import_part);
+            parts.push(import_part);
+        }
+        let meta = Self::new_meta(&meta);
+        let bar_count = parts
+            .iter()
+            .map(|x| x.measures.len())
+            .max()
+            .unwrap_or(0)
+            .max(1);
+        let mut bar_layers: Vec<Vec<BarLayer>> = vec![Vec::new(); bar_count];
+        let mut tracks = Vec::new();
+        let mut used_ids: Vec<String> = Vec::new();
+        for (index, part) in parts.iter().enumerate() {
+            let id = Self::unique_id(
+                &mut used_ids,
+                part.name
+                    .clone()
+                    .filter(|x| !x.trim().is_empty())
+                    .unwrap_or(format!("P{}", index + 1)),
+            );
+            let kind = Self::track_kind(part);
+            let mut lyric_numbers = Self::lyric_numbers(part);
+            match kind {
+                TrackKind::Chord => {
+                    tracks.push(Self::new_chord_track(
+                        &meta,
+                        part,
+                        id.clone(),
+                        &mut bar_layers,
+                    ));
+                }
+                TrackKind::Lyrics => {
+                    let number = if lyric_numbers.is_empty() {
+                        "1".to_string()
+                    } else {
+                        lyric_numbers.remove(0)
+                    };
+                    tracks.push(Self::new_lyrics_track(
+                        part,
+                        id.clone(),
+                        &number,
+                        &mut bar_layers,
+                    ));
+                }
+                TrackKind::Unsupported => {
+                    println!("MusicXmlImport::import_tab(), part skipped: {}", id);
+                    continue;
+                }
+                _ => {
+                    tracks.push(Self::new_notes_track(
+                        &meta,
+                        part,
+                        id.clone(),
+                        kind,
+                        &mut bar_layers,
+                    ));
+                    if part.measures.iter().any(|x| !x.harmonies.is_empty()) {
+                        let chord_id = Self::unique_id(&mut used_ids, format!("{}_chords", id));
+                        tracks.push(Self::new_span_chord_track(
+                            &meta,
+                            part,
+                            chord_id,
+                            &mut bar_layers,
+                        ));
+                    }
+                }
+            }
+            for number in lyric_numbers.iter() {
+                let lyrics_id = match number.as_str() {
+                    "1" => format!("{}_lyrics", id),
+                    _ => format!("{}_lyrics{}", id, number),
+                };
+                let lyrics_id = Self::unique_id(&mut used_ids, lyrics_id);
+                tracks.push(Self::new_lyrics_track(
+                    part,
+                    lyrics_id,
+                    number,
+                    &mut bar_layers,
+                ));
+            }
+        }
+        let mut markers: Vec<(usize, String)> = Vec::new();
+        for bar_index in 0..bar_count {
+            if let Some(text) = parts
+                .iter()
+                .find_map(|x| x.measures.get(bar_index).and_then(|y| y.rehearsal.clone()))
+            {
+                markers.push((bar_index, text));
+            }
+        }
+        let (sections, form) = Self::new_sections(bar_layers, &markers);
+        Tab::new(&uuid, meta, tracks, sections, form)
+    }
+    fn children<'a, 'input: 'a>(
+        node: Node<'a, 'input>,
+        tag: &'a str,
+    ) -> impl Iterator<Item = Node<'a, 'input>> + 'a {
+        node.children().filter(move |x| x.has_tag_name(tag))
+    }
+    fn child<'a, 'input>(node: Node<'a, 'input>, tag: &str) -> Option<Node<'a, 'input>> {
+        node.children().find(|x| x.has_tag_name(tag))
+    }
+    fn child_text(node: Node, tag: &str) -> Option<String> {
+        Self::child(node, tag)
+            .and_then(|x| x.text())
+            .map(|x| x.trim().to_string())
+    }
+    fn child_number(node: Node, tag: &str) -> Option<f32> {
+        Self::child_text(node, tag).and_then(|x| x.parse::<f32>().ok())
+    }
+    fn parse_part(part: Node, meta: &mut ImportMeta, import_part: &mut ImportPart) {
+        let mut divisions = 1.0;
+        for measure in Self::children(part, "measure") {
+            let mut import_measure = ImportMeasure::default();
+            let mut cursor = Units::ZERO;
+            let mut last_voice = "1".to_string();
+            let mut graces: Vec<(String, bool, ImportNote)> = Vec::new();
+            for node in measure.children().filter(|x| x.is_element()) {
+                match node.tag_name().name() {
+                    "attributes" => {
+                        divisions = Self::child_number(node, "divisions").unwrap_or(divisions);
+                        Self::parse_attributes(node, meta, import_part);
+                    }
+                    "direction" => {
+                        for direction_type in Self::children(node, "direction-type") {
+                            if let Some(text) = Self::child_text(direction_type, "rehearsal") {
+                                if import_measure.rehearsal.is_none() && !text.is_empty() {
+                                    import_measure.rehearsal = Some(text);
+                                }
+                            }
+                            if let Some(metronome) = Self::child(direction_type, "metronome") {
+                                let beat_unit = Self::child_text(metronome, "beat-unit")
+                                    .and_then(|x| MusicXmlUtil::type_unit(&x));
+                                let per_minute = Self::child_number(metronome, "per-minute");
+                                if let (Some(beat_unit), Some(per_minute)) = (beat_unit, per_minute)
+                                {
+                                    let mut units = Units::from(beat_unit);
+                                    if Self::child(metronome, "beat-unit-dot").is_some() {
+                                        units = units * 3 / 2;
+                                    }
+                                    let quarters = per_minute
+                                        * (units / Units::from(Unit::Quarter)).as_f32();
+                                    if meta.tempo.is_none() {
+                                        meta.tempo = Some(Self::quarters_tempo(meta, quarters));
+                                    }
+                                }
+                            }
+                        }
+                        if let Some(tempo) = Self::child(node, "sound")
+                            .and_then(|x| x.attribute("tempo"))
+                            .and_then(|x| x.parse::<f32>().ok())
+                        {
+                            if meta.tempo.is_none() {
+                                meta.tempo = Some(Self::quarters_tempo(meta, tempo));
+                            }
+                        }
+                    }
+                    "harmony" => {
+                        let offset = Self::child_number(node, "offset").unwrap_or(0.0);
+                        if let Some(harmony) = Self::parse_harmony(node) {
+                            let pos = cursor + MusicXmlUtil::divisions_to_units(offset, divisions);
+                            import_measure.harmonies.push((pos, harmony));
+                        }
+                    }
+                    "backup" => {
+                        let units = MusicXmlUtil::divisions_to_units(
+                            Self::child_number(node, "duration").unwrap_or(0.0),
+                            divisions,
+                        );
+                        cursor = (cursor - units).max(Units::ZERO);
+                    }
+                    "forward" => {
+                        let units = MusicXmlUtil::divisions_to_units(
+                            Self::child_number(node, "duration").unwrap_or(0.0),
+                            divisions,
+                        );
+                        let voice = Self::child_text(node, "voice").unwrap_or(last_voice.clone());
+                        let event = ImportEvent {
+                            pos: cursor,
+                            durations: Duration::split_units(units),
+                            ..Default::default()
+                        };
+                        Self::push_event(&mut import_measure, voice, event);
+                        cursor += units;
+                    }
+                    "note" => {
+                        if Self::child(node, "cue").is_some() {
+                            continue;
+                        }
+                        let voice = Self::child_text(node, "voice").unwrap_or("1".to_string());
+                        // Only one grace note is kept, the one next to the main note.
+                        if let Some(grace) = Self::child(node, "grace") {
+                            let note = Self::parse_note(node, cursor, Units::ZERO).notes.pop();
+                            if let (Some(note), None) = (note, Self::child(node, "chord")) {
+                                let slash = grace.attribute("slash") == Some("yes");
+                                graces.retain(|x| x.0 != voice);
+                                graces.push((voice, slash, note));
+                            }
+                            continue;
+                        }
+                        last_voice = voice.clone();
+                        let units = MusicXmlUtil::divisions_to_units(
+                            Self::child_number(node, "duration").unwrap_or(0.0),
+                            divisions,
+                        );
+                        let in_chord = Self::child(node, "chord").is_some();
+                        let pos = if in_chord {
+                            import_measure
+                                .voices
+                                .iter()
+                                .find(|x| x.0 == voice)
+                                .and_then(|x| x.1.last())
+                                .map(|x| x.pos)
+                                .unwrap_or(cursor)
+                        } else {
+                            cursor
+                        };
+                        let mut event = Self::parse_note(node, pos, units);
+                        if !in_chord {
+                            if let Some(index) = graces.iter().position(|x| x.0 == voice) {
+                                let (_, slash, note) = graces.remove(index);
+                                event.grace = Some((slash, note));
+                            }
+                        }
+                        match import_measure
+                            .voices
+                            .iter_mut()
+                            .find(|x| x.0 == voice)
+                            .and_then(|x| x.1.last_mut())
+                        {
+                            Some(last) if in_chord => {
+                                last.notes.extend(event.notes);
+                                last.tie_start = last.tie_start || event.tie_start;
+                                last.tie_stop = last.tie_stop || event.tie_stop;
+                                last.ornament = last.ornament.or(event.ornament);
+                            }
+                            _ => {
+                                Self::push_event(&mut import_measure, voice, event);
+                                cursor += units;
+                            }
+                        }
+                    }
+                    _ => (),
+                }
+            }
+            // Tied notes need to be next to each other in the track entries.
+            import_measure.voices.sort_by_key(|(_, events)| {
+                if events.first().map(|x| x.tie_stop).unwrap_or(false) {
+                    0
+                } else if events.last().map(|x| x.tie_start).unwrap_or(false) {
+                    2
+                } else {
+                    1
+                }
+            });
+            import_part.measures.push(import_measure);
+        }
+    }
+    fn push_event(measure: &mut ImportMeasure, voice: String, event: ImportEvent) {
+        match measure.voices.iter_mut().find(|x| x.0 == voice) {
+            Some(x) => x.1.push(event),
+            None => measure.voices.push((voice, vec![event])),
+        }
+    }
+    fn quarters_tempo(meta: &ImportMeta, quarters_per_minute: f32) -> Tempo {
+        let beat_unit = meta.signature.map(|x| x.beat_unit).unwrap_or(Unit::Quarter);
+        let bpm =
+            quarters_per_minute * (Units::from(Unit::Quarter) / Units::from(beat_unit)).as_f32();
+        Tempo::Bpm(bpm.round().clamp(1.0, u16::MAX as f32) as u16)
+    }
+    fn parse_attributes(node: Node, meta: &mut ImportMeta, import_part: &mut ImportPart) {
+        if let Some(key) = Self::child(node, "key") {
+            if let Some(fifths) = Self::child_number(key, "fifths") {
+                let scale = Self::child_text(key, "mode")
+                    .map(|x| MusicXmlUtil::mode_scale(&x))
+                    .unwrap_or(Scale::Major);
+                if meta.fifths.is_none() {
+                    meta.fifths = Some((fifths as i8, scale));
+                }
+            }
+        }
+        if let Some(time) = Self::child(node, "time") {
+            let beats = Self::child_number(time, "beats");
+            let beat_type = Self::child_text(time, "beat-type");
+            if let (Some(beats), Some(beat_type)) = (beats, beat_type) {
+                let beat_unit = match beat_type.as_str() {
+                    "1" => Some(Unit::Whole),
+                    "2" => Some(Unit::Half),
+                    "4" => Some(Unit::Quarter),
+                    "8" => Some(Unit::Eighth),
+                    "16" => Some(Unit::Sixteenth),
+                    "32" => Some(Unit::ThirtySecondth),
+                    _ => None,
+                };
+                if let Some(beat_unit) = beat_unit {
+                    if meta.signature.is_none() && beats >= 1.0 {
+                        meta.signature = Some(Signature::new(beat_unit, beats as u8));
+                    }
+                }
+            }
+        }
+        if let Some(staff_details) = Self::child(node, "staff-details") {
+            for tuning in Self::children(staff_details, "staff-tuning") {
+                let line = tuning
+                    .attribute("line")
+                    .and_then(|x| x.parse::<u8>().ok())
+                    .unwrap_or(0);
+                let name = Self::child_text(tuning, "tuning-step")
+                    .and_then(|x| MusicXmlUtil::step_pitch_name(&x));
+                let alter = Self::child_number(tuning, "tuning-alter").unwrap_or(0.0);
+                let octave = Self::child_number(tuning, "tuning-octave");
+                if let (Some(name), Some(octave)) = (name, octave) {
+                    let pitch = MusicXmlUtil::alter_pitch(name, alter);
+                    let semitones = Semitones::from(MusicXmlUtil::number_octave(octave as i8))
+                        + Semitones::from(pitch);
+                    import_part.tuning.retain(|x| x.0 != line);
+                    import_part.tuning.push((line, semitones));
+                }
+            }
+            if let Some(capo) = Self::child_number(staff_details, "capo") {
+                import_part.capo = capo as u8;
+            }
+        }
+    }
+    fn parse_pitch(node: Node, prefix: &str) -> Option<Pitch> {
+        let name = Self::child_text(node, &format!("{}-step", prefix))
+            .and_then(|x| MusicXmlUtil::step_pitch_name(&x))?;
+        let alter = Self::child_number(node, &format!("{}-alter", prefix)).unwrap_or(0.0);
+        Some(MusicXmlUtil::alter_pitch(name, alter))
+    }
+    fn parse_harmony(node: Node) -> Option<ImportHarmony> {
+        let root = Self::child(node, "root").and_then(|x| Self::parse_pitch(x, "root"))?;
+        let kind = Self::child_text(node, "kind").unwrap_or_default();
+        if kind == "none" {
+            return None;
+        }
+        let bass = Self::child(node, "bass").and_then(|x| Self::parse_pitch(x, "bass"));
+        let degrees = Self::children(node, "degree")
+            .filter_map(|x| {
+                let value = Self::child_number(x, "degree-value")?;
+                let alter = Self::child_number(x, "degree-alter").unwrap_or(0.0);
+                let degree_type = Self::child_text(x, "degree-type").unwrap_or("add".to_string());
+                Some((value as u8, alter.round() as i8, degree_type))
+            })
+            .collect();
+        Some(ImportHarmony {
+            root,
+            kind,
+            bass,
+            degrees,
+        })
+    }
+    fn parse_note(node: Node, pos: Units, units: Units) -> ImportEvent {
+        let mut event = ImportEvent {
+            pos,
+            durations: Self::parse_durations(node, units),
+            ..Default::default()
+        };
+        if let Some(rest) = Self::child(node, "rest") {
+            event.measure_rest = rest.attribute("measure") == Some("yes");
+        }
+        if let Some(pitch) = Self::child(node, "pitch") {
+            let name =
+                Self::child_text(pitch, "step").and_then(|x| MusicXmlUtil::step_pitch_name(&x));
+            let alter = Self::child_number(pitch, "alter").unwrap_or(0.0);
+            let octave = Self::child_number(pitch, "octave").unwrap_or(4.0);
+            if let Some(name) = name {
+                let technical = Self::children(node, "notations")
+                    .flat_map(|x| Self::children(x, "technical"))
+                    .next();
+                let string_fret = technical.and_then(|x| {
+                    let string = Self::child_number(x, "string")?;
+                    let fret = Self::child_number(x, "fret")?;
+                    Some((string as u8, fret as u8))
+                });
+                event.notes.push(ImportNote {
+                    pitch: MusicXmlUtil::alter_pitch(name, alter),
+                    octave: octave as i8,
+                    string_fret,
+                });
+            }
+        }
+        event.ornament = Self::children(node, "notations")
+            .flat_map(|x| Self::children(x, "ornaments"))
+            .flat_map(|x| x.children().filter(|x| x.is_element()))
+            .find_map(|x| MusicXmlUtil::tag_ornament(x.tag_name().name()));
+        let ties = Self::children(node, "tie")
+            .chain(Self::children(node, "notations").flat_map(|x| Self::children(x, "tied")));
+        for tie in ties {
+            match tie.attribute("type") {
+                Some("start") => event.tie_start = true,
+                Some("stop") => event.tie_stop = true,
+                _ => (),
+            }
+        }
+        for lyric in Self::children(node, "lyric") {
+            let number = lyric.attribute("number").unwrap_or("1").to_string();
+            let text: String = Self::children(lyric, "text")
+                .filter_map(|x| x.text())
+                .collect::<Vec<&str>>()
+                .join(" ");
+            if text.is_empty() {
+                continue;
+            }
+            let text = match Self::child_text(lyric, "syllabic").as_deref() {
+                Some("begin") | Some("middle") => format!("{}-", text),
+                _ => text,
+            };
+            event.lyrics.push((number, text));
+        }
+        event
+    }
+    // Use the written type when it agrees with the duration, otherwise split the duration.
+    fn parse_durations(node: Node, units: Units) -> Vec<Duration> {
+        let unit = Self::child_text(node, "type").and_then(|x| MusicXmlUtil::type_unit(&x));
+        let dots = Self::children(node, "dot").count() as u8;
+        let tuplet = Self::child(node, "time-modification")
+            .map(|x| {
+                Tuplet::new(
+                    Self::child_number(x, "actual-notes").unwrap_or(1.0) as u8,
+                    Self::child_number(x, "normal-notes").unwrap_or(1.0) as u8,
+                )
+            })
+            .unwrap_or(Tuplet::NONE);
+        if let Some(unit) = unit {
+            let duration = Duration::new(unit, dots, tuplet);
+            if Units::from(duration) == units {
+                return vec![duration];
+            }
+        }
+        Duration::split_units(units)
+    }
+    fn new_meta(meta: &ImportMeta) -> TabMeta {
+        let (key, scale) = match meta.fifths {
+            Some((fifths, scale)) => (MusicXmlUtil::fifths_key(fifths, scale), scale),
+            None => (Default::default(), Scale::Major),
+        };
+        TabMeta::new(
+            key,
+            scale,
+            meta.signature.unwrap_or(Signature::_4_4),
+            meta.tempo.unwrap_or(Tempo::Bpm(120)),
+        )
+    }
+    fn track_kind(part: &ImportPart) -> TrackKind {
+        if let Some(instrument) = &part.instrument {
+            match instrument.as_str() {
+                "Chord" | "Lyrics" | "Vocal" | "Guitar" | "Synth" | "Piano" => {
+                    return TrackKind::from_ident(instrument);
+                }
+                _ => (),
+            }
+        }
+        let events = || {
+            part.measures
+                .iter()
+                .flat_map(|x| x.voices.iter())
+                .flat_map(|x| x.1.iter())
+        };
+        if part.tuning.len() == 6
+            || events().any(|x| x.notes.iter().any(|y| y.string_fret.is_some()))
+        {
+            TrackKind::Guitar
+        } else if events().any(|x| x.notes.len() > 1)
+            || part.measures.iter().any(|x| x.voices.len() > 1)
+        {
+            TrackKind::Piano
+        } else if events().any(|x| !x.notes.is_empty()) {
+            TrackKind::Vocal
+        } else if part.measures.iter().any(|x| !x.harmonies.is_empty()) {
+            TrackKind::Chord
+        } else if events().any(|x| !x.lyrics.is_empty()) {
+            TrackKind::Lyrics
+        } else {
+            TrackKind::Unsupported
+        }
+    }
+    fn lyric_numbers(part: &ImportPart) -> Vec<String> {
+        let mut numbers: Vec<String> = Vec::new();
+        for event in part
+            .measures
+            .iter()
+            .flat_map(|x| x.voices.iter())
+            .flat_map(|x| x.1.iter())
+        {
+            for (number, _) in event.lyrics.iter() {
+                if !numbers.contains(number) {
+                    numbers.push(number.clone());
+                }
+            }
+        }
+        numbers
+    }
+    fn unique_id(used_ids: &mut Vec<String>, id: String) -> String {
+        let mut result = id.clone();
+        let mut index = 2;
+        while used_ids.contains(&result) {
+            result = format!("{} {}", id, index);
+            index += 1;
+        }
+        used_ids.push(result.clone());
+        result
+    }
+    fn new_chord(meta: &TabMeta, harmony: &ImportHarmony) -> Chord {
+        let root = MusicXmlUtil::pitch_syllable(meta, &harmony.root);
+        let mut intervals = MusicXmlUtil::kind_intervals(&harmony.kind);
+        for (value, alter, degree_type) in harmony.degrees.iter() {
+            let interval = MusicXmlUtil::degree_interval(*value, *alter);
+            let same_degree = |x: &Interval| {
+                MusicXmlUtil::degree_interval(MusicXmlUtil::interval_degree(*x).0, 0)
+                    == MusicXmlUtil::degree_interval(*value, 0)
+            };
+            match degree_type.as_str() {
+                "subtract" => intervals.retain(|x| !same_degree(x)),
+                "alter" => match intervals.iter_mut().find(|x| same_degree(x)) {
+                    Some(x) => *x = interval,
+                    None => intervals.push(interval),
+                },
+                _ => intervals.push(interval),
+            }
+        }
+        let bass = harmony.bass.map(|x| {
+            let bass = MusicXmlUtil::pitch_syllable(meta, &x);
+            Interval::from((root, bass))
+        });
+        MusicXmlUtil::new_chord(root, intervals, bass)
+    }
+    fn note_semitones(note: &ImportNote) -> Semitones {
+        Semitones::from(MusicXmlUtil::number_octave(note.octave)) + Semitones::from(note.pitch)
+    }
+    // Grace notes are relative to the highest note of the event.
+    fn event_ornament(event: &ImportEvent) -> Option<Ornament> {
+        match &event.grace {
+            Some((slash, grace)) => {
+                let main = event.notes.iter().map(Self::note_semitones).max()?;
+                let offset = Self::note_semitones(grace) - main;
+                Some(if *slash {
+                    Ornament::Grace(offset)
+                } else {
+                    Ornament::Appoggiatura(offset)
+                })
+            }
+            None => event.ornament,
+        }
+    }
+    fn new_note(meta: &TabMeta, note: &ImportNote) -> Note {
+        Note::new(
+            MusicXmlUtil::number_octave(note.octave),
+            note.pitch,
+            MusicXmlUtil::pitch_syllable(meta, &note.pitch),
+        )
+    }
+    fn add_rest_entries(entries: &mut Vec<ProtoEntry>, durations: &[Duration]) {
+        for duration in durations.iter() {
+            entries.push(ProtoEntry::from(CoreEntry::Rest(*duration)));
+        }
+    }
+    // Entries for one event, pieces of a split duration are tied together.
+    fn add_event_entries<F: Fn(Duration) -> ProtoEntry>(
+        entries: &mut Vec<ProtoEntry>,
+        event: &ImportEvent,
+        new_entry: F,
+    ) {
+        if event.notes.is_empty() {
+            Self::add_rest_entries(entries, &event.durations);
+            return;
+        }
+        if event.tie_stop {
+            entries.push(ProtoEntry::from(CoreEntry::from(())));
+        }
+        if let Some(ornament) = Self::event_ornament(event) {
+            entries.push(ProtoEntry::from(CoreEntry::from(ornament)));
+        }
+        for (index, duration) in event.durations.iter().enumerate() {
+            if index > 0 {
+                entries.push(ProtoEntry::from(CoreEntry::from(())));
+            }
+            entries.push(new_entry(*duration));
+        }
+    }
+    fn add_layer(layers: &mut Vec<BarLayer>, id: &str, slices: Vec<(usize, usize)>) {
+        if slices.is_empty() {
+            return;
+        }
+        let slices = slices
+            .into_iter()
+            .map(|(begin, count)| {
+                Slice::new(SliceBegin::Index(begin), SliceEnd::Count(count), None)
+            })
+            .collect();
+        layers.push(BarLayer::new(id.to_string(), slices));
+    }
+    fn is_empty_voice(events: &[ImportEvent]) -> bool {
+        events.iter().all(|x| x.notes.is_empty())
+    }
+    fn new_fretboard(part: &ImportPart) -> Fretboard6 {
+        let fretboard = if part.tuning.len() == 6 {
+            let mut tuning = part.tuning.clone();
+            tuning.sort_by_key(|x| x.0);
+            let mut string_notes = [Semitones(0); 6];
+            for (index, (_, semitones)) in tuning.iter().rev().enumerate() {
+                string_notes[index] = *semitones;
+            }
+            Fretboard6::new(GUITAR_FRET_NUM_ACOUSTIC, string_notes, 0)
+        } else {
+            if !part.tuning.is_empty() {
+                println!(
+                    "MusicXmlImport::new_fretboard(), unsupported tuning: {} strings",
+                    part.tuning.len()
+                );
+            }
+            GuitarUtil::new_default_fretboard()
+        };
+        fretboard.with_capo(part.capo)
+    }
+    // Notes without string and fret are put on the lowest fret of a free string.
+    fn new_pick(meta: &TabMeta, fretboard: &Fretboard6, notes: &[ImportNote]) -> Pick {
+        let mut pick_notes: Vec<PickNote> = Vec::new();
+        for note in notes.iter() {
+            let string_fret = note.string_fret.or_else(|| {
+                let semitones = Semitones::from(Self::new_note(meta, note)).0;
+                (1..=fretboard.string_num() as u8)
+                    .filter(|x| pick_notes.iter().all(|y| y.string != *x))
+                    .filter_map(|string| {
+                        let open =
+                            fretboard.string_notes[(string - 1) as usize].0 + fretboard.capo as i8;
+                        let fret = semitones - open;
+                        if fret >= 0 && (fret as usize) < fretboard.fret_num() {
+                            Some((string, fret as u8))
+                        } else {
+                            None
+                        }
+                    })
+                    .min_by_key(|x| x.1)
+            });
+            match string_fret {
+                Some((string, fret)) => pick_notes.push(PickNote::new_string_fret(string, fret)),
+                None => println!(
+                    "MusicXmlImport::new_pick(), note out of fretboard: {}",
+                    Self::new_note(meta, note)
+                ),
+            }
+        }
+        Pick::from(pick_notes)
+    }
+    fn new_notes_track(
+        meta: &TabMeta,
+        part: &ImportPart,
+        id: String,
+        kind: TrackKind,
+        bar_layers: &mut [Vec<BarLayer>],
+    ) -> Track {
+        let mut entries = Vec::new();
+        let fretboard = match kind {
+            TrackKind::Guitar => {
+                let fretboard = Self::new_fretboard(part);
+                entries.push(ProtoEntry::from(FrettedEntry6::from(fretboard)));
+                Some(fretboard)
+            }
+            _ => None,
+        };
+        for (measure, layers) in part.measures.iter().zip(bar_layers.iter_mut()) {
+            let mut slices = Vec::new();
+            for (_, events) in measure.voices.iter() {
+                if Self::is_empty_voice(events) {
+                    continue;
+                }
+                let begin = entries.len();
+                for event in events.iter() {
+                    match fretboard {
+                        Some(fretboard) => {
+                            let pick = Self::new_pick(meta, &fretboard, &event.notes);
+                            Self::add_event_entries(&mut entries, event, |duration| {
+                                ProtoEntry::from(FrettedEntry6::from((pick, duration)))
+                            });
+                        }
+                        None => {
+                            let tone = Tone::from(
+                                event
+                                    .notes
+                                    .iter()
+                                    .map(|x| Self::new_note(meta, x))
+                                    .collect::<Vec<Note>>(),
+                            );
+                            Self::add_event_entries(&mut entries, event, |duration| {
+                                ProtoEntry::from(CoreEntry::from((tone, duration)))
+                            });
+                        }
+                    }
+                }
+                slices.push((begin, entries.len() - begin));
+            }
+            Self::add_layer(layers, &id, slices);
+        }
+        Track::new(id, kind, entries)
+    }
+    fn new_chord_track(
+        meta: &TabMeta,
+        part: &ImportPart,
+        id: String,
+        bar_layers: &mut [Vec<BarLayer>],
+    ) -> Track {
+        let mut entries = Vec::new();
+        for (measure, layers) in part.measures.iter().zip(bar_layers.iter_mut()) {
+            if measure.harmonies.is_empty() {
+                continue;
+            }
+            let begin = entries.len();
+            for event in measure.voices.first().iter().flat_map(|x| x.1.iter()) {
+                let harmony = measure
+                    .harmonies
+                    .iter()
+                    .find(|x| x.0 == event.pos);
+                match harmony {
+                    Some((_, harmony)) => {
+                        let chord = Self::new_chord(meta, harmony);
+                        for duration in event.durations.iter() {
+                            entries.push(ProtoEntry::from(CoreEntry::from((chord, *duration))));
+                        }
+                    }
+                    None => Self::add_rest_entries(&mut entries, &event.durations),
+                }
+            }
+            Self::add_layer(layers, &id, vec![(begin, entries.len() - begin)]);
+        }
+        Track::new(id, TrackKind::Chord, entries)
+    }
+    // Chords of a melody part last until the next harmony, across bar lines.
+    fn new_span_chord_track(
+        meta: &TabMeta,
+        part: &ImportPart,
+        id: String,
+        bar_layers: &mut [Vec<BarLayer>],
+    ) -> Track {
+        let bar_units = meta.bar_units();
+        let mut entries = Vec::new();
+        let mut last_chord: Option<Chord> = None;
+        for (measure, layers) in part.measures.iter().zip(bar_layers.iter_mut()) {
+            let mut harmonies: Vec<(Units, Chord)> = measure
+                .harmonies
+                .iter()
+                .filter(|x| x.0 < bar_units)
+                .map(|(pos, harmony)| (*pos, Self::new_chord(meta, harmony)))
+                .collect();
+            harmonies.sort_by_key(|x| x.0);
+            if harmonies.first().map(|x| x.0.is_positive()).unwrap_or(true) {
+                if let Some(chord) = last_chord {
+                    harmonies.insert(0, (Units::ZERO, chord));
+                }
+            }
+            if harmonies.is_empty() {
+                continue;
+            }
+            let begin = entries.len();
+            if harmonies[0].0.is_positive() {
+                Self::add_rest_entries(&mut entries, &Duration::split_units(harmonies[0].0));
+            }
+            for (index, (pos, chord)) in harmonies.iter().enumerate() {
+                let end = harmonies.get(index + 1).map(|x| x.0).unwrap_or(bar_units);
+                for duration in Duration::split_units(end - *pos) {
+                    entries.push(ProtoEntry::from(CoreEntry::from((*chord, duration))));
+                }
+                last_chord = Some(*chord);
+            }
+            Self::add_layer(layers, &id, vec![(begin, entries.len() - begin)]);
+        }
+        Track::new(id, TrackKind::Chord, entries)
+    }
+    fn new_lyrics_track(
+        part: &ImportPart,
+        id: String,
+        number: &str,
+        bar_layers: &mut [Vec<BarLayer>],
+    ) -> Track {
+        let mut entries = Vec::new();
+        for (measure, layers) in part.measures.iter().zip(bar_layers.iter_mut()) {
+            let word = |event: &ImportEvent| {
+                event
+                    .lyrics
+                    .iter()
+                    .find(|x| x.0 == number)
+                    .map(|x| x.1.clone())
+            };
+            let events = match measure
+                .voices
+                .iter()
+                .find(|x| x.1.iter().any(|y| word(y).is_some()))
+            {
+                Some(x) => &x.1,
+                None => continue,
+            };
+            let begin = entries.len();
+            for event in events.iter() {
+                match word(event) {
+                    Some(text) => {
+                        if let Some(duration) = event.durations.first() {
+                            entries.push(ProtoEntry::from(LyricEntry::from((text, *duration))));
+                        }
+                        Self::add_rest_entries(&mut entries, &event.durations[1..]);
+                    }
+                    None => Self::add_rest_entries(&mut entries, &event.durations),
+                }
+            }
+            Self::add_layer(layers, &id, vec![(begin, entries.len() - begin)]);
+        }
+        Track::new(id, TrackKind::Lyrics, entries)
+    }
+    fn section_kind(text: &str) -> SectionKind {
+        let mut chars = text.chars();
+        let ident = match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+            None => String::new(),
+        };
+        match SectionKind::from_ident(&ident) {
+            SectionKind::Custom(_) => SectionKind::Custom(text.to_string()),
+            kind => kind,
+        }
+    }
+    fn new_sections(
+        bar_layers: Vec<Vec<BarLayer>>,
+        markers: &[(usize, String)],
+    ) -> (Vec<Section>, Form) {
+        let mut starts: Vec<(usize, String)> = Vec::new();
+        if markers.is_empty() {
+            for bar_index in (0..bar_layers.len()).step_by(Self::BARS_PER_SECTION) {
+                starts.push((
+                    bar_index,
+                    format!("{}", bar_index / Self::BARS_PER_SECTION + 1),
+                ));
+            }
+        } else {
+            starts = markers.to_vec();
+            if starts.first().map(|x| x.0 > 0).unwrap_or(true) {
+                starts.insert(0, (0, "start".to_string()));
+            }
+        }
+        let mut sections = Vec::new();
+        let mut form = Vec::new();
+        let mut used_ids = Vec::new();
+        for (index, (bar_index, text)) in starts.iter().enumerate() {
+            let end = starts
+                .get(index + 1)
+                .map(|x| x.0)
+                .unwrap_or(bar_layers.len());
+            let bars = bar_layers[*bar_index..end]
+                .iter()
+                .map(|x| Bar::from(x.clone()))
+                .collect();
+            let id = Self::unique_id(&mut used_ids, text.clone());
+            form.push(id.clone());
+            sections.push(Section::new(id, Self::section_kind(text), bars));
+        }
+        (sections, form.into())
+    }
+}
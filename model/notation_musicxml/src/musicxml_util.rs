@@ -0,0 +1,548 @@
+use notation_model::prelude::{
+    Chord, Interval, Intervals, Key, Octave, Ornament, ParseError, Pitch, PitchName, PitchSign,
+    Scale, Semitones, Syllable, TabMeta, Unit, Units,
+};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum MusicXmlError {
+    #[error("read or write musicxml file failed")]
+    Io(#[from] std::io::Error),
+    #[error("parse xml failed")]
+    Xml(#[from] roxmltree::Error),
+    #[error("parse tab failed")]
+    Parse(#[from] ParseError),
+    #[error("compressed musicxml (.mxl) not supported")]
+    Compressed,
+    #[error("not a partwise score")]
+    NotPartwise(String),
+}
+
+/// Mappings between the tab model and MusicXML values, shared by import and export.
+pub struct MusicXmlUtil();
+
+impl MusicXmlUtil {
+    /// Divisions per quarter note, exact for double dotted 64ths, triplets, quintuplets
+    /// and septuplets.
+    pub const DIVISIONS: u32 = 6720;
+
+    pub const UNITS: [Unit; 7] = Unit::ALL;
+
+    pub const CHORD_KINDS: [(&'static str, &'static [Interval]); 25] = [
+        ("major", &[Interval::Major3nd, Interval::Perfect5th]),
+        ("minor", &[Interval::Minor3nd, Interval::Perfect5th]),
+        ("augmented", &[Interval::Major3nd, Interval::Augmented5th]),
+        ("diminished", &[Interval::Minor3nd, Interval::Diminished5th]),
+        (
+            "dominant",
+            &[Interval::Major3nd, Interval::Perfect5th, Interval::Minor7th],
+        ),
+        (
+            "major-seventh",
+            &[Interval::Major3nd, Interval::Perfect5th, Interval::Major7th],
+        ),
+        (
+            "minor-seventh",
+            &[Interval::Minor3nd, Interval::Perfect5th, Interval::Minor7th],
+        ),
+        (
+            "diminished-seventh",
+            &[
+                Interval::Minor3nd,
+                Interval::Diminished5th,
+                Interval::Diminished7th,
+            ],
+        ),
+        (
+            "augmented-seventh",
+            &[
+                Interval::Major3nd,
+                Interval::Augmented5th,
+                Interval::Minor7th,
+            ],
+        ),
+        (
+            "half-diminished",
+            &[
+                Interval::Minor3nd,
+                Interval::Diminished5th,
+                Interval::Minor7th,
+            ],
+        ),
+        (
+            "major-minor",
+            &[Interval::Minor3nd, Interval::Perfect5th, Interval::Major7th],
+        ),
+        (
+            "major-sixth",
+            &[Interval::Major3nd, Interval::Perfect5th, Interval::Major6th],
+        ),
+        (
+            "minor-sixth",
+            &[Interval::Minor3nd, Interval::Perfect5th, Interval::Major6th],
+        ),
+        (
+            "dominant-ninth",
+            &[
+                Interval::Major3nd,
+                Interval::Perfect5th,
+                Interval::Minor7th,
+                Interval::Major9th,
+            ],
+        ),
+        (
+            "major-ninth",
+            &[
+                Interval::Major3nd,
+                Interval::Perfect5th,
+                Interval::Major7th,
+                Interval::Major9th,
+            ],
+        ),
+        (
+            "minor-ninth",
+            &[
+                Interval::Minor3nd,
+                Interval::Perfect5th,
+                Interval::Minor7th,
+                Interval::Major9th,
+            ],
+        ),
+        (
+            "dominant-11th",
+            &[
+                Interval::Major3nd,
+                Interval::Perfect5th,
+                Interval::Minor7th,
+                Interval::Major9th,
+                Interval::Perfect11th,
+            ],
+        ),
+        (
+            "major-11th",
+            &[
+                Interval::Major3nd,
+                Interval::Perfect5th,
+                Interval::Major7th,
+                Interval::Major9th,
+                Interval::Perfect11th,
+            ],
+        ),
+        (
+            "minor-11th",
+            &[
+                Interval::Minor3nd,
+                Interval::Perfect5th,
+                Interval::Minor7th,
+                Interval::Major9th,
+                Interval::Perfect11th,
+            ],
+        ),
+        (
+            "dominant-13th",
+            &[
+                Interval::Major3nd,
+                Interval::Perfect5th,
+                Interval::Minor7th,
+                Interval::Major9th,
+                Interval::Perfect11th,
+                Interval::Major13th,
+            ],
+        ),
+        (
+            "major-13th",
+            &[
+                Interval::Major3nd,
+                Interval::Perfect5th,
+                Interval::Major7th,
+                Interval::Major9th,
+                Interval::Perfect11th,
+                Interval::Major13th,
+            ],
+        ),
+        (
+            "minor-13th",
+            &[
+                Interval::Minor3nd,
+                Interval::Perfect5th,
+                Interval::Minor7th,
+                Interval::Major9th,
+                Interval::Perfect11th,
+                Interval::Major13th,
+            ],
+        ),
+        (
+            "suspended-second",
+            &[Interval::Major2nd, Interval::Perfect5th],
+        ),
+        (
+            "suspended-fourth",
+            &[Interval::Perfect4th, Interval::Perfect5th],
+        ),
+        ("power", &[Interval::Perfect5th]),
+    ];
+
+    pub fn units_to_divisions(units: Units) -> u32 {
+        let divisions = (units / Units::from(Unit::Quarter)).as_f32() * Self::DIVISIONS as f32;
+        if divisions > 0.0 {
+            divisions.round() as u32
+        } else {
+            0
+        }
+    }
+    /// Exact for whole numbers of divisions, which most files are using.
+    pub fn divisions_to_units(divisions: f32, divisions_per_quarter: f32) -> Units {
+        let divisions_per_quarter = divisions_per_quarter.max(1.0);
+        if divisions.fract() == 0.0 && divisions_per_quarter.fract() == 0.0 {
+            Units::from(Unit::Quarter) * divisions as i64 / divisions_per_quarter as i64
+        } else {
+            Units::from_f32(
+                divisions / divisions_per_quarter * Units::from(Unit::Quarter).as_f32(),
+            )
+        }
+    }
+    pub fn unit_type(unit: Unit) -> &'static str {
+        match unit {
+            Unit::Whole => "whole",
+            Unit::Half => "half",
+            Unit::Quarter => "quarter",
+            Unit::Eighth => "eighth",
+            Unit::Sixteenth => "16th",
+            Unit::ThirtySecondth => "32nd",
+            Unit::SixtyFourth => "64th",
+        }
+    }
+    pub fn type_unit(text: &str) -> Option<Unit> {
+        Self::UNITS
+            .into_iter()
+            .find(|x| Self::unit_type(*x) == text.trim())
+    }
+    /// Tag inside `<ornaments>`, grace notes and appoggiaturas are written as `<grace/>` notes.
+    pub fn ornament_tag(ornament: Ornament) -> Option<&'static str> {
+        match ornament {
+            Ornament::Mordent => Some("inverted-mordent"),
+            Ornament::LowerMordent => Some("mordent"),
+            Ornament::Turn => Some("turn"),
+            Ornament::Grace(_) | Ornament::Appoggiatura(_) => None,
+        }
+    }
+    pub fn tag_ornament(text: &str) -> Option<Ornament> {
+        match text.trim() {
+            "inverted-mordent" => Some(Ornament::Mordent),
+            "mordent" => Some(Ornament::LowerMordent),
+            "turn" => Some(Ornament::Turn),
+            _ => None,
+        }
+    }
+    pub fn octave_number(octave: Octave) -> i8 {
+        Semitones::from(octave).0 / 12
+    }
+    pub fn number_octave(number: i8) -> Octave {
+        Octave::from(Semitones(number.clamp(-1, 10) * 12))
+    }
+    pub fn pitch_step(name: PitchName) -> &'static str {
+        match name {
+            PitchName::C => "C",
+            PitchName::D => "D",
+            PitchName::E => "E",
+            PitchName::F => "F",
+            PitchName::G => "G",
+            PitchName::A => "A",
+            PitchName::B => "B",
+        }
+    }
+    pub fn step_pitch_name(text: &str) -> Option<PitchName> {
+        match text.trim() {
+            "C" => Some(PitchName::C),
+            "D" => Some(PitchName::D),
+            "E" => Some(PitchName::E),
+            "F" => Some(PitchName::F),
+            "G" => Some(PitchName::G),
+            "A" => Some(PitchName::A),
+            "B" => Some(PitchName::B),
+            _ => None,
+        }
+    }
+    pub fn pitch_alter(sign: PitchSign) -> i8 {
+        Semitones::from(sign).0
+    }
+    pub fn alter_pitch(name: PitchName, alter: f32) -> Pitch {
+        Pitch::new(name, PitchSign::from(Semitones(alter.round() as i8)))
+    }
+    fn name_index(name: PitchName) -> i8 {
+        match name {
+            PitchName::C => 0,
+            PitchName::D => 1,
+            PitchName::E => 2,
+            PitchName::F => 3,
+            PitchName::G => 4,
+            PitchName::A => 5,
+            PitchName::B => 6,
+        }
+    }
+    fn index_name(index: i8) -> PitchName {
+        match index.rem_euclid(7) {
+            0 => PitchName::C,
+            1 => PitchName::D,
+            2 => PitchName::E,
+            3 => PitchName::F,
+            4 => PitchName::G,
+            5 => PitchName::A,
+            _ => PitchName::B,
+        }
+    }
+    // Position in the natural scale and the semitones off it, e.g. Le is (5, -1).
+    fn syllable_degree(syllable: Syllable) -> (i8, i8) {
+        match syllable {
+            Syllable::Do => (0, 0),
+            Syllable::Di => (0, 1),
+            Syllable::Ra => (1, -1),
+            Syllable::Re => (1, 0),
+            Syllable::Ri => (1, 1),
+            Syllable::Me => (2, -1),
+            Syllable::Mi => (2, 0),
+            Syllable::Fa => (3, 0),
+            Syllable::Fi => (3, 1),
+            Syllable::Se => (4, -1),
+            Syllable::So => (4, 0),
+            Syllable::Si => (4, 1),
+            Syllable::Le => (5, -1),
+            Syllable::La => (5, 0),
+            Syllable::Li => (5, 1),
+            Syllable::Te => (6, -1),
+            Syllable::Ti => (6, 0),
+        }
+    }
+    fn degree_syllable(index: i8, offset: i8) -> Option<Syllable> {
+        [
+            Syllable::Do,
+            Syllable::Di,
+            Syllable::Ra,
+            Syllable::Re,
+            Syllable::Ri,
+            Syllable::Me,
+            Syllable::Mi,
+            Syllable::Fa,
+            Syllable::Fi,
+            Syllable::Se,
+            Syllable::So,
+            Syllable::Si,
+            Syllable::Le,
+            Syllable::La,
+            Syllable::Li,
+            Syllable::Te,
+            Syllable::Ti,
+        ]
+        .into_iter()
+        .find(|x| Self::syllable_degree(*x) == (index, offset))
+    }
+    fn normalize_semitones(v: i8) -> i8 {
+        let v = v.rem_euclid(12);
+        if v > 6 {
+            v - 12
+        } else {
+            v
+        }
+    }
+    pub fn do_pitch(meta: &TabMeta) -> Pitch {
+        meta.scale.calc_pitch(&meta.key, &Syllable::Do)
+    }
+    /// Spell the syllable by letter from do, so that flat syllables stay flat.
+    pub fn syllable_pitch(meta: &TabMeta, syllable: &Syllable) -> Pitch {
+        let do_pitch = Self::do_pitch(meta);
+        let (index, _) = Self::syllable_degree(*syllable);
+        let name = Self::index_name(Self::name_index(do_pitch.name) + index);
+        let target = Semitones::from(do_pitch) + Semitones::from(*syllable);
+        let alter = Self::normalize_semitones(target.0 - Semitones::from(name).0);
+        if alter.abs() <= 2 {
+            Pitch::new(name, PitchSign::from(Semitones(alter)))
+        } else {
+            Pitch::from(target)
+        }
+    }
+    /// Reverse of `syllable_pitch()`, falls back to the semitones for odd spellings.
+    pub fn pitch_syllable(meta: &TabMeta, pitch: &Pitch) -> Syllable {
+        let do_pitch = Self::do_pitch(meta);
+        let index = (Self::name_index(pitch.name) - Self::name_index(do_pitch.name)).rem_euclid(7);
+        let semitones = Semitones::from(*pitch) - Semitones::from(do_pitch);
+        let natural = Self::degree_syllable(index, 0).unwrap_or_default();
+        let offset = Self::normalize_semitones(semitones.0 - Semitones::from(natural).0);
+        Self::degree_syllable(index, offset).unwrap_or_else(|| meta.calc_syllable(pitch))
+    }
+    pub fn scale_mode(scale: Scale) -> &'static str {
+        match scale {
+            Scale::Ionian => "major",
+            Scale::Dorian => "dorian",
+            Scale::Phrygian => "phrygian",
+            Scale::Lydian => "lydian",
+            Scale::Mixolydian => "mixolydian",
+            Scale::Aeolian => "minor",
+            Scale::Locrian => "locrian",
+            // MusicXML only got the diatonic modes, key signatures are the same.
+            _ => Self::scale_mode(scale.diatonic_mode()),
+        }
+    }
+    pub fn mode_scale(text: &str) -> Scale {
+        match text.trim() {
+            "minor" | "aeolian" => Scale::Aeolian,
+            "dorian" => Scale::Dorian,
+            "phrygian" => Scale::Phrygian,
+            "lydian" => Scale::Lydian,
+            "mixolydian" => Scale::Mixolydian,
+            "locrian" => Scale::Locrian,
+            _ => Scale::Ionian,
+        }
+    }
+    /// Fifths of the key signature, which is the one of the major key sharing the same do.
+    pub fn key_fifths(meta: &TabMeta) -> i8 {
+        let do_pitch = Self::do_pitch(meta);
+        let base = match do_pitch.name {
+            PitchName::F => -1,
+            PitchName::C => 0,
+            PitchName::G => 1,
+            PitchName::D => 2,
+            PitchName::A => 3,
+            PitchName::E => 4,
+            PitchName::B => 5,
+        };
+        let fifths = base + 7 * Self::pitch_alter(do_pitch.sign);
+        if fifths > 7 {
+            fifths - 12
+        } else if fifths < -7 {
+            fifths + 12
+        } else {
+            fifths
+        }
+    }
+    pub fn fifths_key(fifths: i8, scale: Scale) -> Key {
+        let fifths = fifths.clamp(-7, 7);
+        let do_pitch = Pitch::new(
+            [
+                PitchName::F,
+                PitchName::C,
+                PitchName::G,
+                PitchName::D,
+                PitchName::A,
+                PitchName::E,
+                PitchName::B,
+            ][(fifths + 1).rem_euclid(7) as usize],
+            PitchSign::from(Semitones((fifths + 1).div_euclid(7))),
+        );
+        Key::ALL
+            .into_iter()
+            .find(|x| scale.calc_pitch(x, &Syllable::Do) == do_pitch)
+            .or_else(|| {
+                Key::ALL.into_iter().find(|x| {
+                    Self::normalize_semitones(
+                        Semitones::from(scale.calc_pitch(x, &Syllable::Do)).0
+                            - Semitones::from(do_pitch).0,
+                    ) == 0
+                })
+            })
+            .unwrap_or_default()
+    }
+    /// Pick the chord kind matching the most leading intervals, the rest goes to degrees.
+    pub fn chord_kind(intervals: &Intervals) -> (&'static str, Vec<Interval>) {
+        let intervals = intervals.get_intervals();
+        let mut result = ("other", intervals.clone());
+        for (kind, kind_intervals) in Self::CHORD_KINDS.iter() {
+            if kind_intervals.len() <= intervals.len()
+                && intervals.starts_with(kind_intervals)
+                && intervals.len() - kind_intervals.len() < result.1.len()
+            {
+                result = (kind, intervals[kind_intervals.len()..].to_vec());
+            }
+        }
+        result
+    }
+    pub fn kind_intervals(kind: &str) -> Vec<Interval> {
+        match kind.trim() {
+            "other" | "pedal" | "none" => vec![],
+            "Neapolitan" | "Italian" | "French" | "German" => Self::kind_intervals("major"),
+            "Tristan" => Self::kind_intervals("half-diminished"),
+            kind => Self::CHORD_KINDS
+                .iter()
+                .find(|x| x.0 == kind)
+                .map(|x| x.1.to_vec())
+                .unwrap_or_else(|| Self::kind_intervals("major")),
+        }
+    }
+    fn degree_semitones(value: u8) -> i8 {
+        match value {
+            2 => 2,
+            3 => 4,
+            4 => 5,
+            5 => 7,
+            6 => 9,
+            7 => 11,
+            _ => 0,
+        }
+    }
+    /// Added 2nd and 4th are written as 9th and 11th, as on chord symbols.
+    pub fn interval_degree(interval: Interval) -> (u8, i8) {
+        let simple = interval.to_simple();
+        let value = simple.dot_count() as u8;
+        let alter = Semitones::from(simple).0 - Self::degree_semitones(value);
+        let alter = if interval == Interval::Perfect8ve {
+            0
+        } else if interval == Interval::Augmented9th {
+            1
+        } else {
+            alter
+        };
+        match (value, interval.is_compound()) {
+            (2 | 3, true) => (9, alter),
+            (6, true) => (13, alter),
+            (2 | 4, _) => (value + 7, alter),
+            _ => (value, alter),
+        }
+    }
+    pub fn degree_interval(value: u8, alter: i8) -> Interval {
+        let compound = value > 8;
+        let value = (value.max(1) - 1) % 7 + 1;
+        let semitones = Self::degree_semitones(value) + alter;
+        if compound && value == 2 && semitones == 3 {
+            return Interval::Augmented9th;
+        }
+        let interval = Self::simple_degree_interval(value, semitones);
+        if compound {
+            interval.to_compound().unwrap_or(interval)
+        } else {
+            interval
+        }
+    }
+    fn simple_degree_interval(value: u8, semitones: i8) -> Interval {
+        match (value, semitones) {
+            (1, 0) => Interval::Unison,
+            (2, 1) => Interval::Minor2nd,
+            (2, 2) => Interval::Major2nd,
+            (3, 3) => Interval::Minor3nd,
+            (3, 4) => Interval::Major3nd,
+            (4, 5) => Interval::Perfect4th,
+            (4, 6) => Interval::Augmented4th,
+            (5, 6) => Interval::Diminished5th,
+            (5, 7) => Interval::Perfect5th,
+            (5, 8) => Interval::Augmented5th,
+            (6, 8) => Interval::Minor6th,
+            (6, 9) => Interval::Major6th,
+            (7, 9) => Interval::Diminished7th,
+            (7, 10) => Interval::Minor7th,
+            (7, 11) => Interval::Major7th,
+            _ => Interval::from(Semitones(semitones)),
+        }
+    }
+    pub fn new_chord(root: Syllable, intervals: Vec<Interval>, bass: Option<Interval>) -> Chord {
+        let intervals = match intervals.len() {
+            0 => Intervals::Monad,
+            1..=6 => Intervals::from(intervals),
+            _ => {
+                println!(
+                    "MusicXmlUtil::new_chord() intervals lost: {}",
+                    intervals.len() - 6
+                );
+                Intervals::from(intervals[0..6].to_vec())
+            }
+        };
+        Chord::new(root, intervals, bass)
+    }
+}
@@ -0,0 +1,147 @@
+use std::fmt::Display;
+
+use notation_core::prelude::EntryPassMode;
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::{Duration, Entry};
+
+/// Pieces of a drum kit, from the top row of a drum grid down.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Debug)]
+pub enum DrumPiece {
+    Crash,
+    Ride,
+    OpenHiHat,
+    HiHat,
+    HighTom,
+    MidTom,
+    Snare,
+    FloorTom,
+    Kick,
+}
+impl Display for DrumPiece {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+impl DrumPiece {
+    pub const ALL: [DrumPiece; 9] = [
+        DrumPiece::Crash,
+        DrumPiece::Ride,
+        DrumPiece::OpenHiHat,
+        DrumPiece::HiHat,
+        DrumPiece::HighTom,
+        DrumPiece::MidTom,
+        DrumPiece::Snare,
+        DrumPiece::FloorTom,
+        DrumPiece::Kick,
+    ];
+    /// Row in a drum grid, 0 is the top one.
+    pub fn row(&self) -> usize {
+        *self as usize
+    }
+    /// Key number in the General MIDI percussion map (channel 10).
+    pub fn midi_key(&self) -> u8 {
+        match self {
+            DrumPiece::Crash => 49,
+            DrumPiece::Ride => 51,
+            DrumPiece::OpenHiHat => 46,
+            DrumPiece::HiHat => 42,
+            DrumPiece::HighTom => 50,
+            DrumPiece::MidTom => 47,
+            DrumPiece::Snare => 38,
+            DrumPiece::FloorTom => 43,
+            DrumPiece::Kick => 36,
+        }
+    }
+    /// The closest piece for a General MIDI percussion key.
+    pub fn from_midi_key(key: u8) -> Option<Self> {
+        match key {
+            35 | 36 => Some(DrumPiece::Kick),
+            37..=40 => Some(DrumPiece::Snare),
+            41 | 43 => Some(DrumPiece::FloorTom),
+            42 | 44 => Some(DrumPiece::HiHat),
+            45 | 47 => Some(DrumPiece::MidTom),
+            46 => Some(DrumPiece::OpenHiHat),
+            48 | 50 => Some(DrumPiece::HighTom),
+            49 | 52 | 55 | 57 => Some(DrumPiece::Crash),
+            51 | 53 | 59 => Some(DrumPiece::Ride),
+            _ => None,
+        }
+    }
+    pub fn to_ident(&self) -> String {
+        format!("{}", self)
+    }
+    pub fn from_ident(ident: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|x| x.to_ident() == ident)
+    }
+}
+
+/// Drum pieces hit together.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct DrumHit {
+    pub pieces: Vec<DrumPiece>,
+}
+impl Display for DrumHit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let pieces: Vec<String> = self.pieces.iter().map(|x| x.to_string()).collect();
+        write!(f, "{}", pieces.join("+"))
+    }
+}
+impl From<Vec<DrumPiece>> for DrumHit {
+    fn from(v: Vec<DrumPiece>) -> Self {
+        Self { pieces: v }
+    }
+}
+impl From<DrumPiece> for DrumHit {
+    fn from(v: DrumPiece) -> Self {
+        Self { pieces: vec![v] }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub enum DrumEntry {
+    Hit(DrumHit, Duration),
+}
+impl Display for DrumEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DrumEntry::Hit(hit, duration) => write!(f, "Hit({}, {})", hit, duration),
+        }
+    }
+}
+impl DrumEntry {
+    pub fn duration(&self) -> Duration {
+        match self {
+            Self::Hit(_, duration) => *duration,
+        }
+    }
+    pub fn pass_mode(&self) -> EntryPassMode {
+        EntryPassMode::Immediate
+    }
+    pub fn as_hit(&self) -> Option<&DrumHit> {
+        match self {
+            Self::Hit(hit, _) => Some(hit),
+        }
+    }
+}
+
+impl Entry for DrumEntry {
+    fn duration(&self) -> Duration {
+        self.duration()
+    }
+    fn pass_mode(&self) -> EntryPassMode {
+        self.pass_mode()
+    }
+}
+
+impl From<(DrumHit, Duration)> for DrumEntry {
+    fn from(v: (DrumHit, Duration)) -> Self {
+        DrumEntry::Hit(v.0, v.1)
+    }
+}
+
+impl From<(Vec<DrumPiece>, Duration)> for DrumEntry {
+    fn from(v: (Vec<DrumPiece>, Duration)) -> Self {
+        DrumEntry::Hit(DrumHit::from(v.0), v.1)
+    }
+}
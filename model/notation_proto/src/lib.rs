@@ -1,7 +1,8 @@
 pub use uuid;
-pub use {notation_core, notation_fretted, notation_guitar};
+pub use {notation_bass, notation_core, notation_fretted, notation_guitar};
 
 pub mod bar;
+pub mod drum_entry;
 pub mod lyric_entry;
 pub mod position;
 pub mod proto_entry;
@@ -9,19 +10,24 @@ pub mod section;
 pub mod slice;
 pub mod tab;
 pub mod track;
+pub mod transpose;
 pub mod prelude {
     #[doc(hidden)]
     pub use uuid::Uuid;
     #[doc(hidden)]
     pub use crate::bar::{Bar, BarLayer};
     #[doc(hidden)]
+    pub use crate::drum_entry::{DrumEntry, DrumHit, DrumPiece};
+    #[doc(hidden)]
     pub use crate::lyric_entry::{LyricEntry, LyricWord};
     #[doc(hidden)]
     pub use crate::position::{BarPosition, Position, TabPosition};
     #[doc(hidden)]
     pub use crate::proto_entry::ProtoEntry;
     #[doc(hidden)]
-    pub use crate::section::{Form, Section, SectionKind};
+    pub use crate::section::{
+        Form, FormJumpEnd, FormMark, FormRepeat, FormSign, FormVolta, Section, SectionKind,
+    };
     #[doc(hidden)]
     pub use crate::slice::{Slice, SliceBegin, SliceEnd};
     #[doc(hidden)]
@@ -29,6 +35,10 @@ pub mod prelude {
     #[doc(hidden)]
     pub use crate::track::{Track, TrackKind};
     #[doc(hidden)]
+    pub use crate::transpose::{transpose, TransposeError, TransposeStrategy};
+    #[doc(hidden)]
+    pub use notation_bass::prelude::*;
+    #[doc(hidden)]
     pub use notation_core::prelude::*;
     #[doc(hidden)]
     pub use notation_fretted::prelude::*;
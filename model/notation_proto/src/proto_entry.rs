@@ -2,10 +2,12 @@ use std::fmt::Display;
 
 use serde::{Deserialize, Serialize};
 
-use notation_core::prelude::{CoreEntry, Duration, Entry, EntryPassMode, MetaEntry};
+use notation_core::prelude::{
+    Accent, CoreEntry, Duration, Entry, EntryPassMode, MetaEntry, Ornament,
+};
 use notation_fretted::prelude::{FrettedEntry4, FrettedEntry6};
 
-use crate::prelude::LyricEntry;
+use crate::prelude::{DrumEntry, LyricEntry};
 
 #[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
 pub enum ProtoEntry {
@@ -16,6 +18,7 @@ pub enum ProtoEntry {
     Fretted6(FrettedEntry6),
     Fretted4(FrettedEntry4),
     Extra(String, String),
+    Drum(DrumEntry),
 }
 impl Display for ProtoEntry {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -27,6 +30,7 @@ impl Display for ProtoEntry {
             ProtoEntry::Fretted6(x) => write!(f, "Fretted6({})", x),
             ProtoEntry::Fretted4(x) => write!(f, "Fretted4({})", x),
             ProtoEntry::Extra(x, y) => write!(f, "Extra({}, {})", x, y),
+            ProtoEntry::Drum(x) => write!(f, "Drum({})", x),
         }
     }
 }
@@ -40,6 +44,7 @@ impl ProtoEntry {
             ProtoEntry::Fretted6(entry) => entry.duration(),
             ProtoEntry::Fretted4(entry) => entry.duration(),
             ProtoEntry::Extra(_, _) => Duration::Zero,
+            ProtoEntry::Drum(entry) => entry.duration(),
         }
     }
     pub fn pass_mode(&self) -> EntryPassMode {
@@ -51,6 +56,7 @@ impl ProtoEntry {
             ProtoEntry::Fretted6(entry) => entry.pass_mode(),
             ProtoEntry::Fretted4(entry) => entry.pass_mode(),
             ProtoEntry::Extra(_, _) => EntryPassMode::Immediate,
+            ProtoEntry::Drum(entry) => entry.pass_mode(),
         }
     }
     /// Returns `true` if the proto_entry is [`Mark`].
@@ -147,12 +153,29 @@ impl ProtoEntry {
             Err(self)
         }
     }
+    /// Returns `true` if the proto_entry is [`Drum`].
+    pub fn is_drum(&self) -> bool {
+        matches!(self, Self::Drum(..))
+    }
+    pub fn as_drum(&self) -> Option<&DrumEntry> {
+        if let Self::Drum(v) = self {
+            Some(v)
+        } else {
+            None
+        }
+    }
 }
 impl ProtoEntry {
     pub const COMMENT_CHAR: char = ';';
     pub fn is_core_tie(&self) -> bool {
         self.as_core().map(|x| x.is_tie()).unwrap_or(false)
     }
+    pub fn as_core_accent(&self) -> Option<Accent> {
+        self.as_core().and_then(|x| x.as_accent()).copied()
+    }
+    pub fn as_core_ornament(&self) -> Option<Ornament> {
+        self.as_core().and_then(|x| x.as_ornament()).copied()
+    }
     pub fn trim_comments(mark: &String) -> String {
         if let Some(index) = mark.find(Self::COMMENT_CHAR) {
             return mark[..index].to_string();
@@ -234,3 +257,9 @@ impl From<FrettedEntry4> for ProtoEntry {
         ProtoEntry::Fretted4(v)
     }
 }
+
+impl From<DrumEntry> for ProtoEntry {
+    fn from(v: DrumEntry) -> Self {
+        ProtoEntry::Drum(v)
+    }
+}
@@ -66,24 +66,173 @@ impl Section {
     }
 }
 
+/// Ending of a repeat, the section is only played in the given rounds.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct FormVolta {
+    pub index: usize,
+    pub rounds: Vec<usize>,
+}
+impl Display for FormVolta {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<FormVolta>({} R:{:?})", self.index, self.rounds)
+    }
+}
+impl FormVolta {
+    pub fn new(index: usize, rounds: Vec<usize>) -> Self {
+        Self { index, rounds }
+    }
+}
+
+/// Sections from begin to end (both included) are played times in total,
+/// the voltas are the endings inside the repeat.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct FormRepeat {
+    pub begin: usize,
+    pub end: usize,
+    pub times: usize,
+    pub voltas: Vec<FormVolta>,
+}
+impl Display for FormRepeat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "<FormRepeat>({}-{} x{} V:{})",
+            self.begin,
+            self.end,
+            self.times,
+            self.voltas.len()
+        )
+    }
+}
+impl FormRepeat {
+    pub fn new(begin: usize, end: usize, times: usize, voltas: Vec<FormVolta>) -> Self {
+        Self {
+            begin,
+            end,
+            times,
+            voltas,
+        }
+    }
+    pub fn contains(&self, index: usize) -> bool {
+        self.begin <= index && index <= self.end
+    }
+    pub fn get_volta(&self, index: usize) -> Option<&FormVolta> {
+        self.voltas.iter().find(|x| x.index == index)
+    }
+}
+
+/// Where to stop after a D.C. or D.S. jump.
+#[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub enum FormJumpEnd {
+    End,
+    Fine,
+    Coda,
+}
+impl Display for FormJumpEnd {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub enum FormSign {
+    Segno,
+    Coda,
+    ToCoda,
+    Fine,
+    DaCapo(FormJumpEnd),
+    DalSegno(FormJumpEnd),
+}
+impl Display for FormSign {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::DaCapo(FormJumpEnd::End) => write!(f, "D.C."),
+            Self::DaCapo(end) => write!(f, "D.C. al {}", end),
+            Self::DalSegno(FormJumpEnd::End) => write!(f, "D.S."),
+            Self::DalSegno(end) => write!(f, "D.S. al {}", end),
+            Self::ToCoda => write!(f, "To Coda"),
+            _ => write!(f, "{:?}", self),
+        }
+    }
+}
+impl FormSign {
+    /// Segno and Coda are at the start of the section, the others at the end.
+    pub fn is_at_start(&self) -> bool {
+        matches!(self, Self::Segno | Self::Coda)
+    }
+    pub fn is_jump(&self) -> bool {
+        matches!(self, Self::DaCapo(_) | Self::DalSegno(_))
+    }
+    /// Short text for small views, `%` stands for the segno and `Ø` for the coda.
+    pub fn to_abbr(&self) -> String {
+        let end_abbr = |end: &FormJumpEnd| match end {
+            FormJumpEnd::End => "",
+            FormJumpEnd::Fine => ">Fine",
+            FormJumpEnd::Coda => ">Ø",
+        };
+        match self {
+            Self::Segno => "%".to_owned(),
+            Self::Coda => "Ø".to_owned(),
+            Self::ToCoda => "to Ø".to_owned(),
+            Self::Fine => "Fine".to_owned(),
+            Self::DaCapo(end) => format!("D.C.{}", end_abbr(end)),
+            Self::DalSegno(end) => format!("D.S.{}", end_abbr(end)),
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct FormMark {
+    pub index: usize,
+    pub sign: FormSign,
+}
+impl Display for FormMark {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<FormMark>({} {})", self.index, self.sign)
+    }
+}
+impl FormMark {
+    pub fn new(index: usize, sign: FormSign) -> Self {
+        Self { index, sign }
+    }
+}
+
+/// Sections as notated, the indices in repeats and marks are into the sections.
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct Form {
     pub sections: Vec<String>,
+    #[serde(default)]
+    pub repeats: Vec<FormRepeat>,
+    #[serde(default)]
+    pub marks: Vec<FormMark>,
 }
 impl Display for Form {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "<Form>(S:{})", self.sections.len())
+        write!(
+            f,
+            "<Form>(S:{} R:{} M:{})",
+            self.sections.len(),
+            self.repeats.len(),
+            self.marks.len()
+        )
+    }
+}
+impl Form {
+    pub fn new(sections: Vec<String>, repeats: Vec<FormRepeat>, marks: Vec<FormMark>) -> Self {
+        Self {
+            sections,
+            repeats,
+            marks,
+        }
     }
 }
 impl From<Vec<String>> for Form {
     fn from(v: Vec<String>) -> Self {
-        Self { sections: v }
+        Self::new(v, vec![], vec![])
     }
 }
 impl From<Vec<&str>> for Form {
     fn from(v: Vec<&str>) -> Self {
-        Self {
-            sections: v.iter().map(|x| x.to_string()).collect(),
-        }
+        Self::from(v.iter().map(|x| x.to_string()).collect::<Vec<String>>())
     }
 }
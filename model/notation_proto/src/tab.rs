@@ -105,7 +105,7 @@ impl Tab {
             TabMeta::default(),
             vec![],
             vec![],
-            Form { sections: vec![] },
+            Form::from(Vec::<String>::new()),
         )
     }
 }
@@ -0,0 +1,245 @@
+use fehler::{throw, throws};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use thiserror::Error;
+
+use crate::prelude::{ProtoEntry, Tab, TabMeta, Track};
+use notation_core::prelude::{CoreEntry, Key, MetaEntry, Scale, Semitones, Tone};
+use notation_fretted::prelude::{FrettedEntry4, FrettedEntry6};
+
+/// Highest capo position used when transposing.
+pub const TRANSPOSE_MAX_CAPO: u8 = 12;
+
+#[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug, Default)]
+pub enum TransposeStrategy {
+    /// Move the capo when the track got a fretboard and the capo stays in range,
+    /// re-fret otherwise.
+    #[default]
+    Auto,
+    /// Move the capo, frets are kept.
+    Capo,
+    /// Move frets and shapes, the capo is kept.
+    Refret,
+}
+impl Display for TransposeStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+impl TransposeStrategy {
+    pub const ALL: [TransposeStrategy; 3] = [
+        TransposeStrategy::Auto,
+        TransposeStrategy::Capo,
+        TransposeStrategy::Refret,
+    ];
+    pub fn to_ident(&self) -> String {
+        format!("{}", self)
+    }
+    pub fn from_ident(ident: &str) -> Self {
+        match ident {
+            "Capo" => Self::Capo,
+            "Refret" => Self::Refret,
+            _ => Self::Auto,
+        }
+    }
+}
+
+#[derive(Error, Clone, Debug)]
+pub enum TransposeError {
+    #[error("capo out of range")]
+    CapoOutOfRange(String),
+    #[error("fret out of range")]
+    FretOutOfRange(String),
+}
+
+/// Key of the scale with the tonic moved by the interval, spelled as in `Scale::get_keys()`.
+pub fn transpose_key(key: &Key, scale: &Scale, interval: Semitones) -> Key {
+    let semitones = (Semitones::from(*key) + interval).0.rem_euclid(12);
+    scale
+        .get_keys()
+        .iter()
+        .find(|x| Semitones::from(**x).0.rem_euclid(12) == semitones)
+        .copied()
+        .unwrap_or_else(|| Key::from(Semitones(semitones)))
+}
+
+fn transpose_tone(meta: &TabMeta, tone: &Tone, interval: Semitones) -> Tone {
+    tone.get_notes()
+        .into_iter()
+        .map(|x| {
+            meta.scale
+                .calc_note_from_semitones(&meta.key, Semitones::from(x) + interval)
+        })
+        .collect::<Vec<_>>()
+        .into()
+}
+
+macro_rules! impl_transpose_fretted {
+    ($transpose_fretted:ident, $transpose_capo:ident, $transpose_frets:ident, $max_fret:ident,
+        $as_fretted:ident, $fretted_entry:ident) => {
+        /// Fretted entries are moved together since the capo affects all of them.
+        #[throws(TransposeError)]
+        fn $transpose_fretted(
+            track: &Track,
+            interval: Semitones,
+            strategy: TransposeStrategy,
+        ) -> Vec<$fretted_entry> {
+            let entries: Vec<$fretted_entry> = track
+                .entries
+                .iter()
+                .filter_map(|x| x.$as_fretted())
+                .copied()
+                .collect();
+            if entries.is_empty() {
+                return entries;
+            }
+            let capo = match strategy {
+                TransposeStrategy::Refret => None,
+                _ => $transpose_capo(&entries, interval),
+            };
+            match capo {
+                Some(entries) => entries,
+                None if strategy == TransposeStrategy::Capo => {
+                    throw!(TransposeError::CapoOutOfRange(track.id.clone()))
+                }
+                None => $transpose_frets(&entries, interval)
+                    .ok_or_else(|| TransposeError::FretOutOfRange(track.id.clone()))?,
+            }
+        }
+        /// Fretted notes need to stay on the fretboard after the capo moved.
+        fn $transpose_capo(
+            entries: &[$fretted_entry],
+            interval: Semitones,
+        ) -> Option<Vec<$fretted_entry>> {
+            let max_fret = $max_fret(entries);
+            let mut has_fretboard = false;
+            let mut result = Vec::new();
+            for entry in entries.iter() {
+                result.push(match entry {
+                    $fretted_entry::Fretboard(fretboard) => {
+                        let capo = fretboard.capo.checked_add_signed(interval.0)?;
+                        let fretboard = fretboard.with_capo(capo);
+                        if capo > TRANSPOSE_MAX_CAPO || max_fret as usize >= fretboard.fret_num() {
+                            return None;
+                        }
+                        has_fretboard = true;
+                        $fretted_entry::Fretboard(fretboard)
+                    }
+                    _ => *entry,
+                });
+            }
+            if has_fretboard {
+                Some(result)
+            } else {
+                None
+            }
+        }
+        /// Without any shape, picks are played with the default shape of open strings.
+        fn $transpose_frets(
+            entries: &[$fretted_entry],
+            interval: Semitones,
+        ) -> Option<Vec<$fretted_entry>> {
+            let has_shape = entries.iter().any(|x| x.is_shape());
+            let mut result = Vec::new();
+            for entry in entries.iter() {
+                result.push(match entry {
+                    $fretted_entry::Pick(pick, duration) => {
+                        let pick = if has_shape {
+                            *pick
+                        } else {
+                            pick.with_open_strings()
+                        };
+                        $fretted_entry::Pick(pick.refret(interval.0)?, *duration)
+                    }
+                    $fretted_entry::Shape(shape, duration) => {
+                        $fretted_entry::Shape(shape.refret(interval.0)?, *duration)
+                    }
+                    _ => *entry,
+                });
+            }
+            let max_fret = $max_fret(&result);
+            for entry in result.iter() {
+                if let $fretted_entry::Fretboard(fretboard) = entry {
+                    if max_fret as usize >= fretboard.fret_num() {
+                        return None;
+                    }
+                }
+            }
+            Some(result)
+        }
+        fn $max_fret(entries: &[$fretted_entry]) -> u8 {
+            entries
+                .iter()
+                .map(|x| match x {
+                    $fretted_entry::Pick(pick, _) => pick.max_fret(),
+                    $fretted_entry::Shape(shape, _) => shape.max_fret_with_barre(),
+                    _ => 0,
+                })
+                .max()
+                .unwrap_or(0)
+        }
+    };
+}
+
+impl_transpose_fretted!(
+    transpose_fretted6,
+    transpose_capo6,
+    transpose_frets6,
+    max_fret6,
+    as_fretted6,
+    FrettedEntry6
+);
+impl_transpose_fretted!(
+    transpose_fretted4,
+    transpose_capo4,
+    transpose_frets4,
+    max_fret4,
+    as_fretted4,
+    FrettedEntry4
+);
+
+#[throws(TransposeError)]
+fn transpose_track(
+    meta: &TabMeta,
+    track: &Track,
+    interval: Semitones,
+    strategy: TransposeStrategy,
+) -> Track {
+    let mut fretted6 = transpose_fretted6(track, interval, strategy)?.into_iter();
+    let mut fretted4 = transpose_fretted4(track, interval, strategy)?.into_iter();
+    let mut entries = Vec::new();
+    for entry in track.entries.iter() {
+        entries.push(match entry {
+            ProtoEntry::Meta(MetaEntry::Key(key)) => {
+                ProtoEntry::Meta(MetaEntry::Key(transpose_key(key, &meta.scale, interval)))
+            }
+            ProtoEntry::Core(CoreEntry::Tone(tone, duration)) => ProtoEntry::Core(CoreEntry::Tone(
+                transpose_tone(meta, tone, interval),
+                *duration,
+            )),
+            ProtoEntry::Fretted6(x) => ProtoEntry::Fretted6(fretted6.next().unwrap_or(*x)),
+            ProtoEntry::Fretted4(x) => ProtoEntry::Fretted4(fretted4.next().unwrap_or(*x)),
+            _ => entry.clone(),
+        });
+    }
+    Track::new(track.id.clone(), track.kind, entries)
+}
+
+/// Moves the tab by the interval, in semitones. Chords are relative to the key so they
+/// are kept, notes in tones are moved, fretted tracks follow the strategy.
+#[throws(TransposeError)]
+pub fn transpose(proto: &Tab, interval: Semitones, strategy: TransposeStrategy) -> Tab {
+    let meta = TabMeta {
+        key: transpose_key(&proto.meta.key, &proto.meta.scale, interval),
+        ..proto.meta
+    };
+    let mut tracks = Vec::new();
+    for track in proto.tracks.iter() {
+        tracks.push(transpose_track(&meta, track, interval, strategy)?);
+    }
+    Tab {
+        meta,
+        tracks,
+        ..proto.clone()
+    }
+}
@@ -0,0 +1,60 @@
+use std::fmt::Display;
+
+use edger_bevy::bevy_prelude::*;
+
+use crate::prelude::{BarData, NotationAssets, NotationTheme};
+use crate::tab::tab_events::BarViewDoLayoutEvent;
+use notation_model::prelude::{Accent, TabBar, Units};
+
+/// Accent, marcato and ghost marks of the entries, shown above the bar.
+#[derive(Clone, Debug)]
+pub struct BarAccentValue {
+    pub in_bar_pos: Units,
+    pub accent: Accent,
+}
+impl Display for BarAccentValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+pub type BarAccentData = BarData<BarAccentValue>;
+
+impl BarAccentData {
+    pub fn spawn_all(
+        commands: &mut Commands,
+        assets: &NotationAssets,
+        theme: &NotationTheme,
+        entity: Entity,
+        bar: &TabBar,
+    ) {
+        for (in_bar_pos, accent) in bar.get_accents() {
+            let text_entity =
+                theme
+                    .texts
+                    .tab
+                    .spawn_accent_text(commands, assets, entity, accent.to_glyph());
+            commands
+                .entity(text_entity)
+                .insert(BarAccentData::new(bar, BarAccentValue { in_bar_pos, accent }));
+        }
+    }
+    pub fn update_x(
+        theme: Res<NotationTheme>,
+        mut evts: EventReader<BarViewDoLayoutEvent>,
+        mut text_query: Query<(&Parent, &BarAccentData, &mut Transform)>,
+    ) {
+        if theme._bypass_systems {
+            return;
+        }
+        for evt in evts.read() {
+            for (parent, data, mut transform) in text_query.iter_mut() {
+                if parent.get() == evt.entity && data.bar_props.bar_units.is_positive() {
+                    let x = evt.layout.size.width
+                        * (data.value.in_bar_pos / data.bar_props.bar_units).as_f32();
+                    theme.texts.tab.update_accent_x(&mut transform, x);
+                }
+            }
+        }
+    }
+}
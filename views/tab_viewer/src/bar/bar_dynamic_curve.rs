@@ -0,0 +1,89 @@
+use std::fmt::Display;
+
+use edger_bevy::bevy_prelude::*;
+use edger_bevy::prelude::{offscreen, LayoutSize, ShapeOp, StrokePath};
+
+use crate::prelude::{BarData, NotationTheme};
+use notation_model::prelude::{DynamicCurve, TabBar, Units};
+
+#[derive(Clone, Debug)]
+pub struct BarDynamicCurveValue {
+    pub in_bar_pos: Units,
+    pub curve: DynamicCurve,
+    pub units: Units,
+    pub bar_size: LayoutSize,
+}
+impl Display for BarDynamicCurveValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+impl BarDynamicCurveValue {
+    pub fn new(in_bar_pos: Units, curve: DynamicCurve, units: Units) -> Self {
+        Self {
+            in_bar_pos,
+            curve,
+            units,
+            bar_size: LayoutSize::ZERO,
+        }
+    }
+}
+
+pub type BarDynamicCurveData = BarData<BarDynamicCurveValue>;
+
+impl BarDynamicCurveData {
+    pub fn spawn_all(commands: &mut Commands, theme: &NotationTheme, entity: Entity, bar: &TabBar) {
+        for (in_bar_pos, curve) in bar.get_dynamic_curves() {
+            let units = bar.calc_beats_units(curve.beats);
+            let data =
+                BarDynamicCurveData::new(bar, BarDynamicCurveValue::new(in_bar_pos, curve, units));
+            data.create(commands, theme, entity);
+        }
+    }
+    fn calc_x(&self, units: Units) -> f32 {
+        self.value.bar_size.width * (units / self.bar_props.bar_units).as_f32()
+    }
+    /// Hairpin until the end of the curve or the bar, opening for crescendo.
+    fn hairpin_path(&self, theme: &NotationTheme) -> String {
+        let end = (self.value.in_bar_pos + self.value.units).min(self.bar_props.bar_units);
+        let width = self.calc_x(end - self.value.in_bar_pos) - theme.texts.tab.meta_x;
+        if width <= 0.0 {
+            return "".to_owned();
+        }
+        let half = theme.sizes.bar.dynamic_curve_height / 2.0;
+        let (point_x, open_x) = if self.value.curve.is_crescendo() {
+            (0.0, width)
+        } else {
+            (width, 0.0)
+        };
+        format!(
+            "M {} {} L {} 0 L {} {}",
+            open_x, -half, point_x, open_x, half
+        )
+    }
+}
+
+impl ShapeOp<NotationTheme, StrokePath> for BarDynamicCurveData {
+    fn get_shape(&self, theme: &NotationTheme) -> StrokePath {
+        let path = if self.value.bar_size.width <= 0.0 || !self.bar_props.bar_units.is_positive() {
+            "".to_owned()
+        } else {
+            self.hairpin_path(theme)
+        };
+        let offset = if path.is_empty() {
+            offscreen::offset()
+        } else {
+            let x = self.calc_x(self.value.in_bar_pos) + theme.texts.tab.meta_x;
+            Vec3::new(x, theme.sizes.bar.dynamic_curve_y, theme.z.dynamic_curve)
+        };
+        StrokePath {
+            size: Vec2::ZERO,
+            path,
+            color: theme.colors.bar.dynamic_curve_color,
+            line_width: theme.sizes.bar.dynamic_curve_line_width,
+            offset,
+            scale: 1.0,
+            angle: 0.0,
+        }
+    }
+}
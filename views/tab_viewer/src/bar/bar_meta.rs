@@ -0,0 +1,82 @@
+use std::fmt::Display;
+
+use edger_bevy::bevy_prelude::*;
+
+use crate::prelude::{BarData, NotationAssets, NotationTheme};
+use crate::tab::tab_events::BarViewDoLayoutEvent;
+use notation_model::prelude::{MetaEntry, TabBar, Tempo, Units};
+
+/// Signature, tempo changes, rit. / accel. and dynamics, shown as text above the bar.
+#[derive(Clone, Debug)]
+pub struct BarMetaValue {
+    pub in_bar_pos: Units,
+    pub text: String,
+}
+impl Display for BarMetaValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+pub type BarMetaData = BarData<BarMetaValue>;
+
+impl BarMetaData {
+    fn entry_text(entry: &MetaEntry) -> Option<String> {
+        match entry {
+            MetaEntry::Signature(x) => Some(x.to_string()),
+            MetaEntry::Tempo(Tempo::Bpm(x)) => Some(format!("{} bpm", x)),
+            MetaEntry::Tempo(x) => Some(x.to_string()),
+            MetaEntry::TempoCurve(x) if !x.is_fermata() => Some(x.kind.to_mark().to_owned()),
+            MetaEntry::Dynamic(x) => Some(x.to_ident().to_owned()),
+            _ => None,
+        }
+    }
+    /// Changes at the same position are joined into one text.
+    pub fn spawn_all(
+        commands: &mut Commands,
+        assets: &NotationAssets,
+        theme: &NotationTheme,
+        entity: Entity,
+        bar: &TabBar,
+    ) {
+        let mut values: Vec<BarMetaValue> = Vec::new();
+        for (in_bar_pos, entry) in bar.get_meta_changes() {
+            if let Some(text) = Self::entry_text(&entry) {
+                match values.last_mut() {
+                    Some(last) if last.in_bar_pos == in_bar_pos => {
+                        last.text = format!("{} {}", last.text, text);
+                    }
+                    _ => values.push(BarMetaValue { in_bar_pos, text }),
+                }
+            }
+        }
+        for value in values {
+            let text_entity =
+                theme
+                    .texts
+                    .tab
+                    .spawn_meta_text(commands, assets, entity, value.text.as_str());
+            commands
+                .entity(text_entity)
+                .insert(BarMetaData::new(bar, value));
+        }
+    }
+    pub fn update_x(
+        theme: Res<NotationTheme>,
+        mut evts: EventReader<BarViewDoLayoutEvent>,
+        mut text_query: Query<(&Parent, &BarMetaData, &mut Transform)>,
+    ) {
+        if theme._bypass_systems {
+            return;
+        }
+        for evt in evts.read() {
+            for (parent, data, mut transform) in text_query.iter_mut() {
+                if parent.get() == evt.entity && data.bar_props.bar_units.is_positive() {
+                    let x = evt.layout.size.width
+                        * (data.value.in_bar_pos / data.bar_props.bar_units).as_f32();
+                    theme.texts.tab.update_meta_x(&mut transform, x);
+                }
+            }
+        }
+    }
+}
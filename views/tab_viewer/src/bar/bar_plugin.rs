@@ -4,6 +4,9 @@ use edger_bevy::prelude::AssetsStates;
 
 use crate::tab::tab_events::BarViewDoLayoutEvent;
 
+use super::bar_accent::BarAccentData;
+use super::bar_meta::BarMetaData;
+use super::bar_tuplet::BarTupletData;
 use super::bar_view::BarView;
 
 pub struct BarPlugin;
@@ -14,6 +17,9 @@ impl Plugin for BarPlugin {
         app.add_systems(Update, (
             BarView::do_layout,
             BarView::update_number_text,
+            BarTupletData::update_text,
+            BarMetaData::update_x,
+            BarAccentData::update_x,
         ).run_if(in_state(AssetsStates::Loaded)));
     }
 }
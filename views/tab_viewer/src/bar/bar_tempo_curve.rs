@@ -0,0 +1,103 @@
+use std::fmt::Display;
+
+use edger_bevy::bevy_prelude::*;
+use edger_bevy::prelude::{offscreen, LayoutSize, ShapeOp, StrokePath};
+
+use crate::prelude::{BarData, NotationTheme};
+use notation_model::prelude::{TabBar, TempoCurve, Units};
+
+#[derive(Clone, Debug)]
+pub struct BarTempoCurveValue {
+    pub in_bar_pos: Units,
+    pub curve: TempoCurve,
+    pub units: Units,
+    pub bar_size: LayoutSize,
+}
+impl Display for BarTempoCurveValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+impl BarTempoCurveValue {
+    pub fn new(in_bar_pos: Units, curve: TempoCurve, units: Units) -> Self {
+        Self {
+            in_bar_pos,
+            curve,
+            units,
+            bar_size: LayoutSize::ZERO,
+        }
+    }
+}
+
+pub type BarTempoCurveData = BarData<BarTempoCurveValue>;
+
+impl BarTempoCurveData {
+    pub fn spawn_all(commands: &mut Commands, theme: &NotationTheme, entity: Entity, bar: &TabBar) {
+        for (in_bar_pos, curve) in bar.get_tempo_curves() {
+            let units = bar.calc_beats_units(curve.beats);
+            let data = BarTempoCurveData::new(bar, BarTempoCurveValue::new(in_bar_pos, curve, units));
+            data.create(commands, theme, entity);
+        }
+    }
+    fn calc_x(&self, units: Units) -> f32 {
+        self.value.bar_size.width * (units / self.bar_props.bar_units).as_f32()
+    }
+    /// Dashes after the rit. or accel. text, until the end of the curve or the bar.
+    fn dashes_path(&self, theme: &NotationTheme) -> String {
+        let end = (self.value.in_bar_pos + self.value.units).min(self.bar_props.bar_units);
+        let width = self.calc_x(end - self.value.in_bar_pos);
+        let dash = theme.sizes.bar.tempo_curve_dash;
+        let mut path = String::new();
+        let mut x = theme.sizes.bar.tempo_curve_text_width;
+        while x + dash <= width {
+            path.push_str(format!("M {} 0 L {} 0 ", x, x + dash).as_str());
+            x += dash * 2.0;
+        }
+        path
+    }
+    /// Arc over a dot, the dot is drawn as a short stroke.
+    fn fermata_path(&self, theme: &NotationTheme) -> String {
+        let radius = theme.sizes.bar.fermata_radius;
+        let dot = theme.sizes.bar.tempo_curve_line_width;
+        format!(
+            "M 0 {} A {} {} 0 0 1 {} {} M {} {} L {} {}",
+            radius,
+            radius,
+            radius,
+            radius * 2.0,
+            radius,
+            radius - dot / 2.0,
+            radius - dot,
+            radius + dot / 2.0,
+            radius - dot,
+        )
+    }
+}
+
+impl ShapeOp<NotationTheme, StrokePath> for BarTempoCurveData {
+    fn get_shape(&self, theme: &NotationTheme) -> StrokePath {
+        let path = if self.value.curve.is_fermata() {
+            self.fermata_path(theme)
+        } else {
+            self.dashes_path(theme)
+        };
+        let offset = if path.is_empty()
+            || self.value.bar_size.width <= 0.0
+            || !self.bar_props.bar_units.is_positive()
+        {
+            offscreen::offset()
+        } else {
+            let x = self.calc_x(self.value.in_bar_pos) + theme.texts.tab.meta_x;
+            Vec3::new(x, theme.sizes.bar.tempo_curve_y, theme.z.tempo_curve)
+        };
+        StrokePath {
+            size: Vec2::ZERO,
+            path,
+            color: theme.colors.bar.tempo_curve_color,
+            line_width: theme.sizes.bar.tempo_curve_line_width,
+            offset,
+            scale: 1.0,
+            angle: 0.0,
+        }
+    }
+}
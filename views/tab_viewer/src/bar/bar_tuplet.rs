@@ -0,0 +1,95 @@
+use std::fmt::Display;
+
+use edger_bevy::bevy_prelude::*;
+use edger_bevy::prelude::{offscreen, LayoutSize, ShapeOp, StrokePath};
+
+use crate::prelude::{BarData, NotationAssets, NotationTheme};
+use notation_model::prelude::{TabBar, TupletGroup};
+
+#[derive(Clone, Debug)]
+pub struct BarTupletValue {
+    pub group: TupletGroup,
+    pub bar_size: LayoutSize,
+}
+impl Display for BarTupletValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+impl BarTupletValue {
+    pub fn new(group: TupletGroup) -> Self {
+        Self {
+            group,
+            bar_size: LayoutSize::ZERO,
+        }
+    }
+}
+
+pub type BarTupletData = BarData<BarTupletValue>;
+
+impl BarTupletData {
+    fn width(&self) -> f32 {
+        self.value.bar_size.width * (self.value.group.units / self.bar_props.bar_units).as_f32()
+    }
+    pub fn spawn(
+        commands: &mut Commands,
+        assets: &NotationAssets,
+        theme: &NotationTheme,
+        entity: Entity,
+        bar: &TabBar,
+        group: TupletGroup,
+    ) -> Entity {
+        let data = BarTupletData::new(bar, BarTupletValue::new(group));
+        let tuplet_entity = data.create(commands, theme, entity);
+        theme.texts.tab.spawn_tuplet_text(
+            commands,
+            assets,
+            tuplet_entity,
+            group.tuplet.actual.to_string().as_str(),
+        );
+        tuplet_entity
+    }
+    /// The number is kept at the middle of the bracket.
+    pub fn update_text(
+        theme: Res<NotationTheme>,
+        tuplet_query: Query<(&BarTupletData, &Children), Changed<BarTupletData>>,
+        mut text_query: Query<&mut Transform, With<Text>>,
+    ) {
+        if theme._bypass_systems {
+            return;
+        }
+        for (data, children) in tuplet_query.iter() {
+            for child in children.iter() {
+                if let Ok(mut transform) = text_query.get_mut(*child) {
+                    transform.translation.x = data.width() / 2.0;
+                }
+            }
+        }
+    }
+}
+
+impl ShapeOp<NotationTheme, StrokePath> for BarTupletData {
+    fn get_shape(&self, theme: &NotationTheme) -> StrokePath {
+        let width = self.width();
+        let offset = if width <= 0.0 {
+            offscreen::offset()
+        } else {
+            let x = self.value.bar_size.width
+                * (self.value.group.in_bar_pos / self.bar_props.bar_units).as_f32();
+            Vec3::new(x, theme.sizes.bar.tuplet_y, theme.z.tuplet)
+        };
+        let height = theme.sizes.bar.tuplet_height;
+        StrokePath {
+            size: Vec2::ZERO,
+            path: format!(
+                "M 0 {} L 0 0 L {} 0 L {} {}",
+                height, width, width, height
+            ),
+            color: theme.colors.bar.tuplet_color,
+            line_width: theme.sizes.bar.tuplet_line_width,
+            offset,
+            scale: 1.0,
+            angle: 0.0,
+        }
+    }
+}
@@ -14,11 +14,24 @@ use edger_bevy::prelude::{
 };
 use notation_model::prelude::TabBar;
 
+use super::bar_accent::BarAccentData;
 use super::bar_beat::{BarBeatData, BarBeatValue};
+use super::bar_dynamic_curve::BarDynamicCurveData;
+use super::bar_meta::BarMetaData;
 use super::bar_separator::{BarSeparatorData, BarSeparatorValue};
+use super::bar_tempo_curve::BarTempoCurveData;
+use super::bar_tuplet::BarTupletData;
 
 pub type BarView = BarData<BarLayoutData>;
 
+/// Bar number texts, the texts of the bar meta and accents are not moved with them.
+pub type BarNumberTextQuery<'w, 's, 'p, 't> = Query<
+    'w,
+    's,
+    (&'p Parent, &'t mut Transform),
+    (With<Text>, Without<BarMetaData>, Without<BarAccentData>),
+>;
+
 impl<'a> View<NotationLayout<'a>> for BarView {}
 impl<'a> GridCell<NotationLayout<'a>> for BarView {
     fn order(&self) -> usize {
@@ -42,6 +55,9 @@ impl BarView {
         cell_query: ViewQuery<LaneView>,
         mut sep_query: Query<(Entity, &mut BarSeparatorData)>,
         mut beat_query: Query<(Entity, &mut BarBeatData)>,
+        mut tuplet_query: Query<(Entity, &mut BarTupletData)>,
+        mut tempo_curve_query: Query<(Entity, &mut BarTempoCurveData)>,
+        mut dynamic_curve_query: Query<(Entity, &mut BarDynamicCurveData)>,
     ) {
         if theme._bypass_systems {
             return;
@@ -75,12 +91,36 @@ impl BarView {
                 }
             }
         }
+        for (entity, mut data) in tuplet_query.iter_mut() {
+            for (view, layout) in bars.iter() {
+                if data.bar_props.bar_ordinal == view.bar_props.bar_ordinal {
+                    data.value.bar_size = layout.size;
+                    data.update(&mut commands, &theme, entity);
+                }
+            }
+        }
+        for (entity, mut data) in tempo_curve_query.iter_mut() {
+            for (view, layout) in bars.iter() {
+                if data.bar_props.bar_ordinal == view.bar_props.bar_ordinal {
+                    data.value.bar_size = layout.size;
+                    data.update(&mut commands, &theme, entity);
+                }
+            }
+        }
+        for (entity, mut data) in dynamic_curve_query.iter_mut() {
+            for (view, layout) in bars.iter() {
+                if data.bar_props.bar_ordinal == view.bar_props.bar_ordinal {
+                    data.value.bar_size = layout.size;
+                    data.update(&mut commands, &theme, entity);
+                }
+            }
+        }
     }
     pub fn update_number_text(
         theme: Res<NotationTheme>,
         settings: Res<NotationSettings>,
         mut evts: EventReader<BarViewDoLayoutEvent>,
-        mut text_query: Query<(&Parent, &mut Transform), With<Text>>,
+        mut text_query: BarNumberTextQuery,
     ) {
         if theme._bypass_systems {
             return;
@@ -132,12 +172,19 @@ impl BarView {
             let data = BarBeatData::new(bar, BarBeatValue::new(bar, &signature, beat));
             data.create(commands, theme, bar_entity);
         }
+        BarMetaData::spawn_all(commands, assets, theme, bar_entity, bar);
+        BarTempoCurveData::spawn_all(commands, theme, bar_entity, bar);
+        BarDynamicCurveData::spawn_all(commands, theme, bar_entity, bar);
+        BarAccentData::spawn_all(commands, assets, theme, bar_entity, bar);
+        for group in bar.get_tuplet_groups() {
+            BarTupletData::spawn(commands, assets, theme, bar_entity, bar, group);
+        }
         if !settings.hide_bar_number {
             theme.texts.tab.spawn_bar_number(
                 commands,
                 assets,
                 bar_entity,
-                bar.props.bar_number.to_string().as_str(),
+                bar.calc_bar_number_text().as_str(),
             );
         }
         bar_entity
@@ -1,7 +1,12 @@
+pub mod bar_accent;
 pub mod bar_beat;
 pub mod bar_bundle;
+pub mod bar_dynamic_curve;
 pub mod bar_layout;
+pub mod bar_meta;
 pub mod bar_playing;
 pub mod bar_plugin;
 pub mod bar_separator;
+pub mod bar_tempo_curve;
+pub mod bar_tuplet;
 pub mod bar_view;
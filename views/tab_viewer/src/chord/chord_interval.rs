@@ -20,7 +20,13 @@ impl ChordNoteExtra for ChordIntervalExtra {
         self.diagram_radius = diagram_radius;
     }
     fn radius(&self, theme: &NotationTheme) -> f32 {
-        self.diagram_radius * theme.sizes.chord.diagram_interval_radius_factor
+        // Extended chords got more than 4 intervals, shrink them to fit around the root.
+        let factor = if self.total > 4 {
+            4.0 / self.total as f32
+        } else {
+            1.0
+        };
+        self.diagram_radius * theme.sizes.chord.diagram_interval_radius_factor * factor
     }
     fn offset(&self, theme: &NotationTheme) -> Vec2 {
         let angle_offset = match self.total {
@@ -119,9 +119,11 @@ impl<T: ChordNoteExtra + 'static> ChordNoteData<T> {
         if self.value.extra.show_dots() {
             let quality = self.value.interval.into();
             let dot_count = self.value.interval.dot_count();
+            let compound = self.value.interval.is_compound();
             let note_radius = self.value.extra.radius(theme);
             for index in 0..dot_count {
-                let dot_data = IntervalDotData::new(quality, dot_count, index, note_radius);
+                let dot_data =
+                    IntervalDotData::new(quality, dot_count, index, note_radius, compound);
                 dot_data.create(commands, theme, note_entity);
             }
         }
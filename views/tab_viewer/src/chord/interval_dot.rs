@@ -12,18 +12,26 @@ pub struct IntervalDotData {
     pub total: usize,
     pub index: usize,
     pub note_radius: f32,
+    pub compound: bool,
     pub is_orphan: bool,
 }
 
 pub type DotQuery<'w, 's, 'p, 'd> = Query<'w, 's, (&'p Parent, Entity, &'d mut IntervalDotData)>;
 
 impl IntervalDotData {
-    pub fn new(quality: IntervalQuality, total: usize, index: usize, note_radius: f32) -> Self {
+    pub fn new(
+        quality: IntervalQuality,
+        total: usize,
+        index: usize,
+        note_radius: f32,
+        compound: bool,
+    ) -> Self {
         Self {
             quality,
             total,
             index,
             note_radius,
+            compound,
             is_orphan: false,
         }
     }
@@ -85,7 +93,11 @@ impl ShapeOp<NotationTheme, OutlineCircle> for IntervalDotData {
             self.note_radius * theme.sizes.chord.interval_dot_radius_factor
         };
         let color = theme.colors.chord.dot.of_quality(&self.quality);
-        let outline_width = theme.sizes.chord.interval_dot_outline;
+        let outline_width = if self.compound {
+            theme.sizes.chord.interval_dot_compound_outline
+        } else {
+            theme.sizes.chord.interval_dot_outline
+        };
         let outline_color = theme.colors.chord.dot_outline.of_quality(&self.quality);
         OutlineCircle {
             radius,
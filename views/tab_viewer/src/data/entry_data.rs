@@ -19,10 +19,6 @@ impl<T: Send + Sync + 'static> EntryData<T> {
         }
     }
     pub fn bar_position(&self) -> BarPosition {
-        BarPosition::new(
-            self.bar_props.bar_units,
-            self.bar_props.bar_ordinal,
-            self.entry_props.in_bar_pos,
-        )
+        BarPosition::from(self.bar_props).with_in_bar_pos(self.entry_props.in_bar_pos)
     }
 }
@@ -0,0 +1,81 @@
+use edger_bevy::bevy_prelude::*;
+use edger_bevy::bevy_prototype_lyon::prelude::*;
+use edger_bevy::prelude::{offscreen, OutlineRectangle, ShapeOp};
+use notation_model::prelude::DrumPiece;
+use notation_midi::prelude::PlayingState;
+
+use crate::prelude::{EntryData, NotationTheme};
+
+pub type DrumNoteData = EntryData<DrumNoteValue>;
+
+#[derive(Clone, Debug)]
+pub struct DrumNoteValue {
+    pub piece: DrumPiece,
+    pub playing_state: PlayingState,
+    pub bar_size: f32,
+}
+
+impl DrumNoteValue {
+    pub fn new(piece: DrumPiece) -> Self {
+        Self {
+            piece,
+            playing_state: PlayingState::Idle,
+            bar_size: 0.0,
+        }
+    }
+}
+
+impl DrumNoteData {
+    pub fn calc_outline(&self, theme: &NotationTheme) -> f32 {
+        theme
+            .sizes
+            .drums
+            .note_outline
+            .of_state(&self.value.playing_state)
+    }
+    pub fn calc_width_height(&self, theme: &NotationTheme) -> (f32, f32) {
+        let width =
+            self.value.bar_size / self.bar_props.bar_units.as_f32() * self.entry_props.tied_units.as_f32();
+        let mut height = theme.sizes.drums.note_height;
+        let outline = self.calc_outline(theme);
+        if self.value.playing_state.is_current() {
+            height += outline * 2.0;
+        }
+        (width - outline * 2.0, height)
+    }
+}
+
+impl ShapeOp<NotationTheme, OutlineRectangle> for DrumNoteData {
+    fn get_shape(&self, theme: &NotationTheme) -> OutlineRectangle {
+        let (width, height) = self.calc_width_height(theme);
+        let color = theme.colors.drums.of_piece(self.value.piece);
+        let outline_color = theme
+            .colors
+            .drums
+            .outline
+            .of_state(&self.value.playing_state);
+        let outline_width = self.calc_outline(theme);
+        let offset = if self.value.bar_size <= 0.0 {
+            offscreen::offset()
+        } else {
+            let x =
+                self.value.bar_size / self.bar_props.bar_units.as_f32() * self.entry_props.in_bar_pos.as_f32();
+            let y = theme.sizes.drums.calc_row_y(self.value.piece.row());
+            let extra_z = if self.value.playing_state.is_current() {
+                1.0
+            } else {
+                0.0
+            };
+            Vec3::new(x, y + height / 2.0, theme.z.drum + extra_z)
+        };
+        OutlineRectangle {
+            width,
+            height,
+            origin: shapes::RectangleOrigin::TopLeft,
+            color,
+            outline_width,
+            outline_color,
+            offset,
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use edger_bevy::bevy_prelude::*;
+
+use crate::prelude::NotationTheme;
+use edger_bevy::prelude::ShapeOp;
+use notation_model::prelude::{BarLane, DrumPiece};
+
+use super::drum_row::{DrumRowData, DrumRowValue};
+
+#[derive(Debug, Default, Component)]
+pub struct DrumsGrid();
+
+impl DrumsGrid {
+    pub fn add_rows(
+        &self,
+        commands: &mut Commands,
+        theme: &NotationTheme,
+        entity: Entity,
+        lane: &BarLane,
+    ) {
+        for piece in DrumPiece::ALL {
+            let data = DrumRowData::new(lane, DrumRowValue {
+                row: piece.row(),
+                bar_size: 0.0,
+            });
+            data.create(commands, theme, entity);
+        }
+    }
+}
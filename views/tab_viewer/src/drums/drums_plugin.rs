@@ -0,0 +1,78 @@
+use edger_bevy::bevy::ecs::system::EntityCommands;
+use edger_bevy::bevy_prelude::*;
+use edger_bevy::prelude::*;
+
+use crate::prelude::{EntryPlaying, NotationSettings, NotationTheme};
+use notation_model::prelude::{BarLane, DrumEntry, LaneEntry};
+
+use super::drum_note::{DrumNoteData, DrumNoteValue};
+use super::drums_grid::DrumsGrid;
+
+pub struct DrumsPlugin;
+
+impl Plugin for DrumsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (
+            DrumsPlugin::on_add_drums_grid,
+            DrumsPlugin::on_entry_playing_changed,
+        ).run_if(in_state(AssetsStates::Loaded)));
+    }
+}
+
+impl DrumsPlugin {
+    pub fn on_add_drums_grid(
+        mut commands: Commands,
+        theme: Res<NotationTheme>,
+        query: Query<(Entity, &SingleData<BarLane>, &DrumsGrid), Added<DrumsGrid>>,
+    ) {
+        if theme._bypass_systems {
+            return;
+        }
+        for (entity, lane, grid) in query.iter() {
+            grid.add_rows(&mut commands, &theme, entity, &lane.0);
+        }
+    }
+    pub fn on_entry_playing_changed(
+        mut commands: Commands,
+        settings: Res<NotationSettings>,
+        theme: Res<NotationTheme>,
+        query: Query<(Entity, &EntryPlaying, &Children), Changed<EntryPlaying>>,
+        mut note_query: Query<(Entity, &mut DrumNoteData)>,
+    ) {
+        if theme._bypass_systems {
+            return;
+        }
+        if settings.hide_drums_lane {
+            return;
+        }
+        for (_entity, playing, children) in query.iter() {
+            for child in children.iter() {
+                if let Ok((entity, mut data)) = note_query.get_mut(*child) {
+                    data.value.playing_state = playing.value;
+                    data.update(&mut commands, &theme, entity);
+                }
+            }
+        }
+    }
+    pub fn insert_lane_extra(commands: &mut EntityCommands, _lane: &BarLane) {
+        commands.insert(DrumsGrid::default());
+    }
+    pub fn insert_entry_extra(
+        commands: &mut Commands,
+        theme: &NotationTheme,
+        settings: &NotationSettings,
+        entity: Entity,
+        entry: &LaneEntry,
+        drum_entry: &DrumEntry,
+    ) {
+        if settings.hide_drums_lane {
+            return;
+        }
+        if let Some(hit) = drum_entry.as_hit() {
+            for piece in hit.pieces.iter() {
+                let data = DrumNoteData::new(entry, DrumNoteValue::new(*piece));
+                data.create(commands, theme, entity);
+            }
+        }
+    }
+}
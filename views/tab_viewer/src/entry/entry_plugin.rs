@@ -9,13 +9,17 @@ use crate::chord::chord_view::ChordView;
 use crate::tone::tone_line::ToneLineData;
 use crate::lane::lane_layout::LaneLayoutData;
 use crate::prelude::{
-    entity, ChordBundle, EntryBundle, LyricsPlugin, NotationAssets,
+    entity, ChordBundle, DrumsPlugin, EntryBundle, LyricsPlugin, NotationAssets,
     NotationSettings, NotationTheme, ShapesPlugin, StringsPlugin, ToneBundle,
 };
+use crate::drums::drum_note::DrumNoteData;
+use crate::drums::drum_row::DrumRowData;
 use crate::shapes::shape_diagram::{ShapeDiagramData4, ShapeDiagramData6};
+use crate::strings::pick_grace::PickGraceData;
 use crate::strings::pick_note::PickNoteData;
 use crate::strings::single_string::SingleStringData;
 use crate::tab::tab_events::TabBarsResizedEvent;
+use crate::tone::tone_grace::ToneGraceData;
 use crate::tone::tone_note::ToneNoteData;
 use crate::word::word_text::WordTextData;
 use notation_model::prelude::{CoreEntry, LaneEntry, ProtoEntry};
@@ -112,6 +116,14 @@ fn insert_entry_extra(
                 fretted_entry,
             );
         }
+        ProtoEntry::Drum(drum_entry) => DrumsPlugin::insert_entry_extra(
+            commands,
+            theme,
+            settings,
+            entry_entity,
+            entry,
+            drum_entry,
+        ),
         _ => {}
     }
 }
@@ -128,6 +140,8 @@ fn insert_core_entry_extra(
 ) {
     match core_entry {
         CoreEntry::Tie => (),
+        CoreEntry::Accent(_) => (),
+        CoreEntry::Ornament(_) => (),
         CoreEntry::Rest(_) => (),
         CoreEntry::Tone(tone, _) => {
             commands
@@ -152,8 +166,12 @@ fn on_tab_bars_resized(
     theme: Res<NotationTheme>,
     mut tone_note_query: Query<(Entity, &mut ToneNoteData), With<ToneNoteData>>,
     mut tone_line_query: Query<(Entity, &mut ToneLineData), With<ToneLineData>>,
+    mut tone_grace_query: Query<(Entity, &mut ToneGraceData), With<ToneGraceData>>,
     mut pick_note_query: Query<(Entity, &mut PickNoteData), With<PickNoteData>>,
+    mut pick_grace_query: Query<(Entity, &mut PickGraceData), With<PickGraceData>>,
     mut single_string_query: Query<(Entity, &mut SingleStringData), With<SingleStringData>>,
+    mut drum_note_query: Query<(Entity, &mut DrumNoteData), With<DrumNoteData>>,
+    mut drum_row_query: Query<(Entity, &mut DrumRowData), With<DrumRowData>>,
     mut word_text_query: Query<(Entity, &mut WordTextData), With<WordTextData>>,
     mut shape_diagram_6_query: Query<(Entity, &mut ShapeDiagramData6), With<ShapeDiagramData6>>,
     mut shape_diagram_4_query: Query<(Entity, &mut ShapeDiagramData4), With<ShapeDiagramData4>>,
@@ -179,6 +197,14 @@ fn on_tab_bars_resized(
                 }
             }
         }
+        for (entity, mut data) in tone_grace_query.iter_mut() {
+            for (view, layout) in bars.iter() {
+                if data.bar_props.bar_ordinal == view.bar_props.bar_ordinal {
+                    data.value.bar_size = layout.size.width;
+                    data.update(&mut commands, &theme, entity);
+                }
+            }
+        }
         for (entity, mut data) in word_text_query.iter_mut() {
             for (view, layout) in bars.iter() {
                 if data.bar_props.bar_ordinal == view.bar_props.bar_ordinal {
@@ -222,6 +248,32 @@ fn on_tab_bars_resized(
                     }
                 }
             }
+            for (entity, mut data) in pick_grace_query.iter_mut() {
+                for (view, layout) in bars.iter() {
+                    if data.bar_props.bar_ordinal == view.bar_props.bar_ordinal {
+                        data.value.bar_size = layout.size.width;
+                        data.update(&mut commands, &theme, entity);
+                    }
+                }
+            }
+        }
+        if !settings.hide_drums_lane {
+            for (entity, mut data) in drum_row_query.iter_mut() {
+                for (view, layout) in bars.iter() {
+                    if data.bar_props.bar_ordinal == view.bar_props.bar_ordinal {
+                        data.value.bar_size = layout.size.width;
+                        data.update(&mut commands, &theme, entity);
+                    }
+                }
+            }
+            for (entity, mut data) in drum_note_query.iter_mut() {
+                for (view, layout) in bars.iter() {
+                    if data.bar_props.bar_ordinal == view.bar_props.bar_ordinal {
+                        data.value.bar_size = layout.size.width;
+                        data.update(&mut commands, &theme, entity);
+                    }
+                }
+            }
         }
     }
 }
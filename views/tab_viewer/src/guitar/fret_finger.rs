@@ -3,7 +3,7 @@ use std::sync::Arc;
 use edger_bevy::bevy_prelude::*;
 use edger_bevy::prelude::{offscreen, LayoutSize, ShapeOp};
 use notation_model::prelude::{
-    Chord, Finger, Fretboard6, HandShape6, Interval, ModelEntryProps, Note, Pick, Syllable, TabMeta, Semitones,
+    Chord, Finger, Fretboard4, Fretboard6, HandShape4, HandShape6, Interval, ModelEntryProps, Note, Pick, Syllable, TabMeta, Semitones,
 };
 
 use crate::chord::chord_note::{ChordNoteData, ChordNoteExtra, ChordNoteValue};
@@ -13,6 +13,7 @@ use crate::prelude::{NotationAssets, NotationSettings, NotationTheme};
 pub struct FretFingerExtra {
     pub visible: bool,
     pub string: u8,
+    pub string_num: u8,
     pub pick: bool,
     pub fret: Option<u8>,
     pub finger: Option<Finger>,
@@ -24,10 +25,11 @@ pub struct FretFingerExtra {
 pub type FretFingerData = ChordNoteData<FretFingerExtra>;
 
 impl FretFingerExtra {
-    pub fn new(string: u8, pick: bool, fret: Option<u8>, finger: Option<Finger>) -> Self {
+    pub fn new(string: u8, string_num: u8, pick: bool, fret: Option<u8>, finger: Option<Finger>) -> Self {
         Self {
             visible: false,
             string,
+            string_num,
             pick,
             fret,
             finger,
@@ -44,11 +46,12 @@ impl FretFingerData {
         root: Syllable,
         interval: Interval,
         string: u8,
+        string_num: u8,
         pick: bool,
         fret: Option<u8>,
         finger: Option<Finger>,
     ) -> Self {
-        let extra = FretFingerExtra::new(string, pick, fret, finger);
+        let extra = FretFingerExtra::new(string, string_num, pick, fret, finger);
         Self::from((
             entry_props,
             ChordNoteValue::<FretFingerExtra>::new(root, interval, extra),
@@ -96,70 +99,6 @@ impl FretFingerData {
             self.value.extra.in_chord = false;
         }
     }
-    pub fn update_pick(
-        &mut self,
-        fretboard: Option<Fretboard6>,
-        chord: Option<Chord>,
-        pick: Pick,
-        meta: Option<Arc<TabMeta>>,
-    ) -> bool {
-        let visible = self.value.extra.visible;
-        let pick_note = pick.get_pick_note(self.value.extra.string);
-        if self.value.extra.pick {
-            self.reset();
-            self.value.extra.fret = pick_note.and_then(|x| x.fret);
-            if let Some(fretboard) = fretboard {
-                self.value.extra.capo = fretboard.capo;
-                let note = match (meta.as_ref(), pick_note) {
-                    (Some(meta), Some(pick_note)) => {
-                        pick_note.fret
-                            .and_then(|f| fretboard.fretted_note(&meta.scale, &meta.key, self.value.extra.string, f))
-                    },
-                    _ => None,
-                };
-                self.set_chord_meta_note(chord, meta, note);
-                true
-            } else {
-                visible != self.value.extra.visible
-            }
-        } else {
-            let pick_fret = pick_note.and_then(|x| x.fret);
-            self.value.extra.visible = pick_fret.is_none()
-                || self.value.extra.fret.is_none()
-                || pick_fret.unwrap() > self.value.extra.fret.unwrap();
-            visible != self.value.extra.visible
-        }
-    }
-    pub fn update_value(
-        &mut self,
-        shape: &HandShape6,
-        fretboard: Option<Fretboard6>,
-        chord: Option<Chord>,
-        pick: Option<Pick>,
-        meta: Option<Arc<TabMeta>>,
-    ) {
-        self.reset();
-        let pick_note = pick.and_then(|x| x.get_pick_note(self.value.extra.string));
-        if self.value.extra.pick {
-            self.value.extra.fret = pick_note.and_then(|x| x.fret);
-        } else {
-            self.value.extra.fret = shape.string_fret_with_barre(self.value.extra.string);
-        }
-        if let Some(fretboard) = fretboard {
-            self.value.extra.capo = fretboard.capo;
-            let note = match meta.as_ref() {
-                Some(meta) => {
-                    if self.value.extra.pick {
-                        pick_note.and_then(|x| x.fret.and_then(|_| fretboard.shape_pick_note(&meta.scale, &meta.key, shape, x)))
-                    } else {
-                        fretboard.shape_note(&meta.scale, &meta.key, shape, self.value.extra.string)
-                    }
-                },
-                None => None,
-            };
-            self.set_chord_meta_note(chord, meta, note);
-        }
-    }
     pub fn update_with_syllable(
         &self,
         commands: &mut Commands,
@@ -197,6 +136,80 @@ impl FretFingerData {
     }
 }
 
+macro_rules! impl_fret_finger_data {
+    ($update_pick:ident, $update_value:ident, $fretboard:ident, $hand_shape:ident) => {
+        impl FretFingerData {
+            pub fn $update_pick(
+                &mut self,
+                fretboard: Option<$fretboard>,
+                chord: Option<Chord>,
+                pick: Pick,
+                meta: Option<Arc<TabMeta>>,
+            ) -> bool {
+                let visible = self.value.extra.visible;
+                let pick_note = pick.get_pick_note(self.value.extra.string);
+                if self.value.extra.pick {
+                    self.reset();
+                    self.value.extra.fret = pick_note.and_then(|x| x.fret);
+                    if let Some(fretboard) = fretboard {
+                        self.value.extra.capo = fretboard.capo;
+                        let note = match (meta.as_ref(), pick_note) {
+                            (Some(meta), Some(pick_note)) => {
+                                pick_note.fret
+                                    .and_then(|f| fretboard.fretted_note(&meta.scale, &meta.key, self.value.extra.string, f))
+                            },
+                            _ => None,
+                        };
+                        self.set_chord_meta_note(chord, meta, note);
+                        true
+                    } else {
+                        visible != self.value.extra.visible
+                    }
+                } else {
+                    let pick_fret = pick_note.and_then(|x| x.fret);
+                    self.value.extra.visible = pick_fret.is_none()
+                        || self.value.extra.fret.is_none()
+                        || pick_fret.unwrap() > self.value.extra.fret.unwrap();
+                    visible != self.value.extra.visible
+                }
+            }
+            pub fn $update_value(
+                &mut self,
+                shape: &$hand_shape,
+                fretboard: Option<$fretboard>,
+                chord: Option<Chord>,
+                pick: Option<Pick>,
+                meta: Option<Arc<TabMeta>>,
+            ) {
+                self.reset();
+                let pick_note = pick.and_then(|x| x.get_pick_note(self.value.extra.string));
+                if self.value.extra.pick {
+                    self.value.extra.fret = pick_note.and_then(|x| x.fret);
+                } else {
+                    self.value.extra.fret = shape.string_fret_with_barre(self.value.extra.string);
+                }
+                if let Some(fretboard) = fretboard {
+                    self.value.extra.capo = fretboard.capo;
+                    let note = match meta.as_ref() {
+                        Some(meta) => {
+                            if self.value.extra.pick {
+                                pick_note.and_then(|x| x.fret.and_then(|_| fretboard.shape_pick_note(&meta.scale, &meta.key, shape, x)))
+                            } else {
+                                fretboard.shape_note(&meta.scale, &meta.key, shape, self.value.extra.string)
+                            }
+                        },
+                        None => None,
+                    };
+                    self.set_chord_meta_note(chord, meta, note);
+                }
+            }
+        }
+    };
+}
+
+impl_fret_finger_data!(update_pick6, update_value6, Fretboard6, HandShape6);
+impl_fret_finger_data!(update_pick4, update_value4, Fretboard4, HandShape4);
+
 impl ChordNoteExtra for FretFingerExtra {
     fn set_diagram_radius(&mut self, _diagram_radius: f32) {}
     fn radius(&self, theme: &NotationTheme) -> f32 {
@@ -208,7 +221,7 @@ impl ChordNoteExtra for FretFingerExtra {
         }
         let x = theme
             .guitar
-            .calc_string_x(self.string, self.guitar_size.width);
+            .calc_string_x(self.string, self.string_num, self.guitar_size.width);
         let fret = self.fret.unwrap_or(0);
         let y = theme
             .guitar
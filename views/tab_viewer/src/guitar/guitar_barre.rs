@@ -1,14 +1,16 @@
 use edger_bevy::bevy_prelude::*;
 use edger_bevy::bevy_prototype_lyon::prelude::*;
 use edger_bevy::prelude::{offscreen, FillRectangle, LayoutSize, ShapeOp};
-use notation_model::prelude::{HandShape6, Pick};
+use notation_model::prelude::{HandShape4, HandShape6, Pick};
 
 use crate::prelude::NotationTheme;
 
 #[derive(Clone, Debug, Component)]
 pub struct GuitarBarreData {
     pub capo: u8,
-    pub shape: Option<HandShape6>,
+    pub has_shape: bool,
+    pub shape_barre: u8,
+    pub shape_max_fret: u8,
     pub pick: Option<Pick>,
     pub view_size: LayoutSize,
     pub guitar_size: LayoutSize,
@@ -18,7 +20,9 @@ impl Default for GuitarBarreData {
     fn default() -> Self {
         Self {
             capo: 0,
-            shape: None,
+            has_shape: false,
+            shape_barre: 0,
+            shape_max_fret: 0,
             pick: None,
             view_size: LayoutSize::ZERO,
             guitar_size: LayoutSize::ZERO,
@@ -31,11 +35,26 @@ impl GuitarBarreData {
             self.pick = Some(pick.clone());
         }
     }
+    pub fn set_shape6(&mut self, shape: &HandShape6) {
+        self.has_shape = true;
+        self.shape_barre = shape.barre();
+        self.shape_max_fret = shape.max_fret_with_barre();
+    }
+    pub fn set_shape4(&mut self, shape: &HandShape4) {
+        self.has_shape = true;
+        self.shape_barre = shape.barre();
+        self.shape_max_fret = shape.max_fret_with_barre();
+    }
+    pub fn clear_shape(&mut self) {
+        self.has_shape = false;
+        self.shape_barre = 0;
+        self.shape_max_fret = 0;
+    }
     pub fn barre(&self) -> u8 {
-        self.shape.and_then(|x| x.barre).unwrap_or(0)
+        self.shape_barre
     }
     pub fn max_fret(&self) -> u8 {
-        let mut max = self.shape.map(|x| x.max_fret_with_barre()).unwrap_or(0);
+        let mut max = self.shape_max_fret;
         if let Some(pick) = self.pick {
             let pick_max = pick.max_fret();
             if pick_max > max {
@@ -4,15 +4,16 @@ use edger_bevy::bevy_prelude::*;
 use edger_bevy::bevy_prototype_lyon::prelude::*;
 use edger_bevy::prelude::{math, LayoutSize, OutlineRectangle, ShapeOp};
 use notation_model::prelude::{
-    Duration, Fretboard6, HandShape6, Pick, Note, TabMeta, Units,
+    Duration, Fretboard4, Fretboard6, HandShape4, HandShape6, Pick, Note, TabMeta, Units,
 };
-use notation_midi::prelude::{PlaySpeed, PlayingState};
+use notation_midi::prelude::{PlayControl, PlayingState};
 
 use crate::prelude::NotationTheme;
 
 #[derive(Clone, Debug, Component)]
 pub struct GuitarStringData {
     pub string: u8,
+    pub string_num: u8,
     pub upper: bool,
     pub fret: Option<u8>,
     pub pick_fret: Option<u8>,
@@ -27,10 +28,10 @@ pub struct GuitarStringData {
 }
 
 impl GuitarStringData {
-    pub fn new(string: u8, upper: bool, fretboard: Option<Fretboard6>) -> Self {
-        let capo = fretboard.map(|x| x.capo).unwrap_or(0);
+    pub fn new(string: u8, string_num: u8, upper: bool, capo: u8) -> Self {
         Self {
             string,
+            string_num,
             upper,
             fret: Some(0),
             pick_fret: None,
@@ -57,7 +58,9 @@ impl GuitarStringData {
         self.pick_fret.unwrap_or(self.fret.unwrap_or(0))
     }
     fn width(&self, theme: &NotationTheme) -> f32 {
-        let width = theme.guitar.get_string_width(self.string);
+        let width = theme
+            .guitar
+            .get_string_width(self.string, self.string_num);
         let width_with_extra = if !self.upper && self.state.is_current() {
             width + theme.guitar.current_extra_width
         } else {
@@ -75,9 +78,11 @@ impl GuitarStringData {
     fn calc_hit_seconds(
         hit_duration: Duration,
         hit_string_seconds_range: (f32, f32),
-        play_speed: PlaySpeed,
+        play_control: &PlayControl,
     ) -> f32 {
-        let seconds = play_speed.calc_seconds(Units::from(hit_duration));
+        let seconds = play_control
+            .play_speed
+            .calc_seconds(play_control.position.tab.in_tab_pos, Units::from(hit_duration));
         math::in_range(seconds * 0.5, hit_string_seconds_range)
     }
     pub fn set_hit(
@@ -86,7 +91,7 @@ impl GuitarStringData {
         hit_duration: Duration,
         time: &Time,
         hit_string_seconds_range: (f32, f32),
-        play_speed: PlaySpeed,
+        play_control: &PlayControl,
     ) {
         if self.hit && !hit && time.elapsed_seconds_f64() < self.hit_expired_seconds {
             return;
@@ -94,49 +99,59 @@ impl GuitarStringData {
         self.hit = hit;
         self.hit_duration = hit_duration;
         self.hit_seconds = if hit {
-            Self::calc_hit_seconds(hit_duration, hit_string_seconds_range, play_speed)
+            Self::calc_hit_seconds(hit_duration, hit_string_seconds_range, play_control)
         } else {
             0.0
         };
         self.hit_expired_seconds = time.elapsed_seconds_f64() + self.hit_seconds as f64;
     }
-    fn set_note(&mut self, fretboard: Option<Fretboard6>, meta: Option<Arc<TabMeta>>) {
-        self.note = None;
-        if let (Some(meta), Some(fretboard)) = (meta, fretboard) {
-            if let Some(note) = fretboard.fretted_note(&meta.scale, &meta.key, self.string, self.fret()) {
-                self.note = Some(note);
+}
+
+macro_rules! impl_guitar_string_data {
+    ($set_note:ident, $update_pick:ident, $update_value:ident, $fretboard:ident, $hand_shape:ident) => {
+        impl GuitarStringData {
+            fn $set_note(&mut self, fretboard: Option<$fretboard>, meta: Option<Arc<TabMeta>>) {
+                self.note = None;
+                if let (Some(meta), Some(fretboard)) = (meta, fretboard) {
+                    if let Some(note) = fretboard.fretted_note(&meta.scale, &meta.key, self.string, self.fret()) {
+                        self.note = Some(note);
+                    }
+                }
+            }
+            pub fn $update_pick(
+                &mut self,
+                fretboard: Option<$fretboard>,
+                pick: Pick,
+                meta: Option<Arc<TabMeta>>,
+            ) {
+                let pick_note = pick.get_pick_note(self.string);
+                self.pick_fret = pick_note.and_then(|x| x.fret);
+                self.$set_note(fretboard, meta);
+            }
+            pub fn $update_value(
+                &mut self,
+                shape: &$hand_shape,
+                fretboard: Option<$fretboard>,
+                pick: Option<Pick>,
+                meta: Option<Arc<TabMeta>>,
+            ) {
+                let pick_note = pick.and_then(|x| x.get_pick_note(self.string));
+                self.pick_fret = pick_note.and_then(|x| x.fret);
+                self.fret = shape.string_fret_with_barre(self.string);
+                if let Some(fretboard) = fretboard {
+                    self.capo = fretboard.capo;
+                } else {
+                    self.capo = 0;
+                }
+                self.$set_note(fretboard, meta);
             }
         }
-    }
-    pub fn update_pick(
-        &mut self,
-        fretboard: Option<Fretboard6>,
-        pick: Pick,
-        meta: Option<Arc<TabMeta>>,
-    ) {
-        let pick_note = pick.get_pick_note(self.string);
-        self.pick_fret = pick_note.and_then(|x| x.fret);
-        self.set_note(fretboard, meta);
-    }
-    pub fn update_value(
-        &mut self,
-        shape: &HandShape6,
-        fretboard: Option<Fretboard6>,
-        pick: Option<Pick>,
-        meta: Option<Arc<TabMeta>>,
-    ) {
-        let pick_note = pick.and_then(|x| x.get_pick_note(self.string));
-        self.pick_fret = pick_note.and_then(|x| x.fret);
-        self.fret = shape.string_fret_with_barre(self.string);
-        if let Some(fretboard) = fretboard {
-            self.capo = fretboard.capo;
-        } else {
-            self.capo = 0;
-        }
-        self.set_note(fretboard, meta);
-    }
+    };
 }
 
+impl_guitar_string_data!(set_note6, update_pick6, update_value6, Fretboard6, HandShape6);
+impl_guitar_string_data!(set_note4, update_pick4, update_value4, Fretboard4, HandShape4);
+
 impl ShapeOp<NotationTheme, OutlineRectangle> for GuitarStringData {
     fn get_shape(&self, theme: &NotationTheme) -> OutlineRectangle {
         let fret_y = theme
@@ -175,7 +190,7 @@ impl ShapeOp<NotationTheme, OutlineRectangle> for GuitarStringData {
         let outline_width = self.outline(theme);
         let x = theme
             .guitar
-            .calc_string_x(self.string, self.guitar_size.width);
+            .calc_string_x(self.string, self.string_num, self.guitar_size.width);
         let fret_y = theme
             .guitar
             .calc_fret_y(self.fret() + self.capo, self.guitar_size.height);
@@ -2,17 +2,20 @@ use float_eq::float_ne;
 use std::fmt::Display;
 use std::sync::Arc;
 
+use edger_bevy::bevy::ecs::system::SystemParam;
 use edger_bevy::bevy_prelude::*;
 use edger_bevy::prelude::{
     entity, offscreen, LayoutAnchor, LayoutChangedQuery, LayoutSize, ShapeOp, View, ViewBundle, SingleData,
 };
 use notation_model::prelude::{
-    Duration, Entry, HandShape6, Interval, LaneEntry, LaneKind, ModelEntryProps, Pick, Syllable,
+    Duration, Entry, HandShape4, HandShape6, Interval, LaneEntry, LaneKind, ModelEntryProps, Pick, Syllable,
     Tab, TrackKind, Units,
 };
 
 use crate::chord::interval_dot::DotQuery;
-use crate::prelude::{EntryPlaying, NotationAssets, NotationSettings, NotationTheme, TabState};
+use crate::prelude::{
+    EntryPlaying, NotationAssets, NotationSettings, NotationTheme, StringsPlugin, TabState,
+};
 use crate::prelude::NotationLayout;
 
 use super::fret_finger::FretFingerData;
@@ -23,13 +26,40 @@ use super::guitar_string::GuitarStringData;
 #[cfg(feature = "midi")]
 use notation_midi::prelude::MidiState;
 
+/// The view and its per-string parts, updated together when the playing entry changes.
+#[derive(SystemParam)]
+pub struct GuitarViewQueries<'w, 's> {
+    pub view_query: Query<'w, 's, &'static GuitarView>,
+    pub string_query: Query<'w, 's, (Entity, &'static mut GuitarStringData), With<GuitarStringData>>,
+    pub finger_query: Query<'w, 's, (Entity, &'static mut FretFingerData), With<FretFingerData>>,
+    pub capo_query: Query<'w, 's, (Entity, &'static mut GuitarCapoData), With<GuitarCapoData>>,
+    pub barre_query: Query<'w, 's, (Entity, &'static mut GuitarBarreData), With<GuitarBarreData>>,
+    pub dot_query: DotQuery<'w, 's, 'static, 'static>,
+    pub text_query: Query<'w, 's, (&'static Parent, Entity), With<Text>>,
+}
+
 #[derive(Clone, Debug, Component)]
 pub struct GuitarView {
     pub tab: Arc<Tab>,
+    pub track_kind: TrackKind,
+    pub string_num: u8,
 }
 impl GuitarView {
+    /// Shows the guitar track, or the bass track if there is no guitar in the tab.
     pub fn new(tab: Arc<Tab>) -> Self {
-        Self { tab }
+        let track_kind = if tab.get_track_of_kind(TrackKind::Guitar).is_none()
+            && tab.get_track_of_kind(TrackKind::Bass).is_some()
+        {
+            TrackKind::Bass
+        } else {
+            TrackKind::Guitar
+        };
+        let string_num = StringsPlugin::string_num(&track_kind);
+        Self {
+            tab,
+            track_kind,
+            string_num,
+        }
     }
 }
 impl Display for GuitarView {
@@ -53,11 +83,20 @@ impl GuitarView {
         entity: Entity,
         tab: &Arc<Tab>,
     ) -> Entity {
-        let guitar_entity = entity::spawn_child_bundle(
-            commands,
-            entity,
-            ViewBundle::from(GuitarView::new(tab.clone())),
-        );
+        let view = GuitarView::new(tab.clone());
+        let string_num = view.string_num;
+        let capo = match view.track_kind {
+            TrackKind::Bass => tab
+                .get_track_of_kind(TrackKind::Bass)
+                .and_then(|x| x.get_fretboard4())
+                .map(|x| x.capo),
+            _ => tab
+                .get_track_of_kind(TrackKind::Guitar)
+                .and_then(|x| x.get_fretboard6())
+                .map(|x| x.capo),
+        }
+        .unwrap_or(0);
+        let guitar_entity = entity::spawn_child_bundle(commands, entity, ViewBundle::from(view));
         let sprite_bundle = SpriteBundle {
             sprite: Sprite{
                 custom_size: Some(Vec2::new(
@@ -70,14 +109,10 @@ impl GuitarView {
             texture: assets.fretboard.clone(),
             ..Default::default()
         };
-        let fretboard = tab
-            .get_track_of_kind(TrackKind::Guitar)
-            .and_then(|x| x.get_fretboard6());
-
         entity::spawn_child_bundle(commands, guitar_entity, sprite_bundle);
-        for string in 1..=6 {
+        for string in 1..=string_num {
             for upper in [true, false] {
-                let string_data = GuitarStringData::new(string as u8, upper, fretboard);
+                let string_data = GuitarStringData::new(string, string_num, upper, capo);
                 string_data.create(commands, theme, guitar_entity);
             }
         }
@@ -92,11 +127,14 @@ impl GuitarView {
                 let mut finger_data = FretFingerData::new_data(
                     ModelEntryProps {
                         index: 0,
-                        tied_units: Units(0.0),
+                        tied_units: Units::ZERO,
+                        accent: None,
+                        ornament: None,
                     },
                     Syllable::Do,
                     Interval::Unison,
-                    string as u8,
+                    string,
+                    string_num,
                     false,
                     Some(fret as u8),
                     None,
@@ -104,22 +142,25 @@ impl GuitarView {
                 finger_data.value.extra.visible = true;
                 finger_data.spawn(commands, theme, guitar_entity);
                 string = string + 1;
-                if string > 6 {
+                if string > string_num {
                     string = 1;
                 }
                 fret = fret + 1;
             }
         } else {
-            for index in 1..=6 {
+            for index in 1..=string_num {
                 for pick in [true, false] {
                     let finger_data = FretFingerData::new_data(
                         ModelEntryProps {
                             index: 0,
-                            tied_units: Units(0.0),
+                            tied_units: Units::ZERO,
+                            accent: None,
+                            ornament: None,
                         },
                         Syllable::Do,
                         Interval::Unison,
-                        index as u8,
+                        index,
+                        string_num,
                         pick,
                         None,
                         None,
@@ -191,12 +232,17 @@ impl GuitarView {
         time: Res<Time>,
         theme: Res<NotationTheme>,
         query: Query<(&SingleData<LaneEntry>, &SingleData<Pick>, &EntryPlaying), Changed<EntryPlaying>>,
-        mut string_query: Query<(Entity, &mut GuitarStringData), With<GuitarStringData>>,
-        mut finger_query: Query<(Entity, &mut FretFingerData), With<FretFingerData>>,
-        mut barre_query: Query<(Entity, &mut GuitarBarreData), With<GuitarBarreData>>,
-        mut dot_query: DotQuery,
-        text_query: Query<(&Parent, Entity), With<Text>>,
+        queries: GuitarViewQueries,
     ) {
+        let GuitarViewQueries {
+            view_query,
+            mut string_query,
+            mut finger_query,
+            mut barre_query,
+            mut dot_query,
+            text_query,
+            ..
+        } = queries;
         if Self::CHECKING_FRETS {
             return;
         }
@@ -206,10 +252,17 @@ impl GuitarView {
         if settings.hide_guitar_view {
             return;
         }
+        let track_kind = match view_query.get_single() {
+            Ok(view) => view.track_kind,
+            Err(_) => return,
+        };
         let mut current_entry_pick = None;
         let mut string_states = [None; 6];
         let mut hit_strings = [(false, Duration::Zero); 6];
         for (entry, pick, playing) in query.iter() {
+            if entry.0.track().map(|x| x.kind) != Some(track_kind) {
+                continue;
+            }
             if playing.value.is_current() {
                 current_entry_pick = Some((entry.0.clone(), pick.0));
             }
@@ -221,8 +274,9 @@ impl GuitarView {
                 }
             }
         }
-        let fretboard = current_entry_pick.clone()
-            .and_then(|(entry, _)| entry.track().and_then(|x| x.get_fretboard6()));
+        let track = current_entry_pick.clone().and_then(|(entry, _)| entry.track());
+        let fretboard6 = track.clone().and_then(|x| x.get_fretboard6());
+        let fretboard4 = track.and_then(|x| x.get_fretboard4());
         let meta = current_entry_pick.clone().and_then(|(entry, _)| entry.bar().map(|x| x.tab_meta()));
 
         for (string_entity, mut string_data) in string_query.iter_mut() {
@@ -234,13 +288,16 @@ impl GuitarView {
                     hit_duration,
                     &time,
                     theme.guitar.hit_string_seconds_range,
-                    midi_state.play_control.play_speed,
+                    &midi_state.play_control,
                 );
                 if let Some(state) = string_states[(string_data.string - 1) as usize] {
                     string_data.state = state;
                 }
                 if let Some((_, pick)) = current_entry_pick {
-                    string_data.update_pick(fretboard, pick, meta.clone());
+                    match string_data.string_num {
+                        4 => string_data.update_pick4(fretboard4, pick, meta.clone()),
+                        _ => string_data.update_pick6(fretboard6, pick, meta.clone()),
+                    }
                 }
                 string_data.update(&mut commands, &theme, string_entity);
             }
@@ -248,7 +305,10 @@ impl GuitarView {
         if let Some((entry, pick)) = current_entry_pick {
             let chord = entry.bar().and_then(|x| x.get_chord_of_entry(&entry));
             for (finger_entity, mut finger_data) in finger_query.iter_mut() {
-                let changed = finger_data.update_pick(fretboard, chord, pick, meta.clone());
+                let changed = match finger_data.value.extra.string_num {
+                    4 => finger_data.update_pick4(fretboard4, chord, pick, meta.clone()),
+                    _ => finger_data.update_pick6(fretboard6, chord, pick, meta.clone()),
+                };
                 if changed {
                     if finger_data.value.extra.pick {
                         finger_data.respawn_dots(
@@ -274,106 +334,6 @@ impl GuitarView {
             }
         }
     }
-    pub fn update_hand_shape6(
-        mut commands: Commands,
-        assets: Res<NotationAssets>,
-        theme: Res<NotationTheme>,
-        settings: Res<NotationSettings>,
-        query: Query<(&SingleData<LaneEntry>, &SingleData<HandShape6>, &EntryPlaying), Changed<EntryPlaying>>,
-        mut finger_query: Query<(Entity, &mut FretFingerData), With<FretFingerData>>,
-        mut string_query: Query<(Entity, &mut GuitarStringData), With<GuitarStringData>>,
-        mut capo_query: Query<(Entity, &mut GuitarCapoData), With<GuitarCapoData>>,
-        mut barre_query: Query<(Entity, &mut GuitarBarreData), With<GuitarBarreData>>,
-        mut dot_query: DotQuery,
-        text_query: Query<(&Parent, Entity), With<Text>>,
-        tab_state_query: Query<(Entity, &TabState), With<TabState>>,
-    ) {
-        if Self::CHECKING_FRETS {
-            return;
-        }
-        if theme._bypass_systems {
-            return;
-        }
-        if settings.hide_guitar_view {
-            return;
-        }
-        let mut current_shape = None;
-        for (entry, shape, playing) in query.iter() {
-            if playing.value.is_current() {
-                //println!("GuitarView::update_hand_shape6(): found changed playing shape: {}", shape);
-                current_shape = Some((entry.0.clone(), shape.0));
-            }
-        }
-        if let Some((entry, shape)) = current_shape {
-            let fretboard = entry.track().and_then(|x| x.get_fretboard6());
-            let chord = entry.bar().and_then(|x| x.get_chord_of_entry(&entry));
-            let pick = entry.bar().and_then(|x| {
-                x.get_entry_in_other_lane(
-                    LaneKind::Strings,
-                    entry.track_index(),
-                    Some(entry.in_bar_pos()),
-                    &|x: &LaneEntry| {
-                        x.proto()
-                            .as_fretted6()
-                            .and_then(|y| y.as_pick())
-                            .map(|z| z.to_owned())
-                    },
-                )
-            });
-            let meta = entry.bar().map(|x| x.tab_meta());
-            //println!("GuitarView::update_hand_shape6(): {}, {:#?}, {:#?}", shape, fretboard, chord);
-            for (finger_entity, mut finger_data) in finger_query.iter_mut() {
-                finger_data.update_value(&shape, fretboard, chord, pick, meta.clone());
-                finger_data.respawn_dots(&mut commands, &theme, Some(&mut dot_query), finger_entity);
-                finger_data.update_with_syllable(
-                    &mut commands,
-                    &assets,
-                    &theme,
-                    &settings,
-                    &text_query,
-                    finger_entity,
-                    &meta.clone().unwrap_or_default(),
-                );
-            }
-            for (string_entity, mut string_data) in string_query.iter_mut() {
-                string_data.update_value(&shape, fretboard, pick, meta.clone());
-                string_data.update(&mut commands, &theme, string_entity);
-            }
-            if let Some(fretboard) = fretboard {
-                for (capo_entity, mut capo_data) in capo_query.iter_mut() {
-                    if fretboard.capo != capo_data.capo {
-                        capo_data.capo = fretboard.capo;
-                        capo_data.update(&mut commands, &theme, capo_entity);
-                    }
-                }
-                for (barre_entity, mut barre_data) in barre_query.iter_mut() {
-                    barre_data.capo = fretboard.capo;
-                    barre_data.shape = Some(shape.clone());
-                    barre_data.pick = None;
-                    barre_data.update(&mut commands, &theme, barre_entity);
-                }
-            }
-        } else {
-            let position = TabState::get_position(&tab_state_query, None);
-            if settings.add_ready_section && position.is_some() && position.unwrap().bar.bar_ordinal == 0 {
-                for (finger_entity, mut finger_data) in finger_query.iter_mut() {
-                    finger_data.reset();
-                    finger_data.update(&mut commands, &theme, finger_entity);
-                }
-                for (string_entity, mut string_data) in string_query.iter_mut() {
-                    string_data.reset();
-                    string_data.update(&mut commands, &theme, string_entity);
-                }
-                for (barre_entity, mut barre_data) in barre_query.iter_mut() {
-                    if barre_data.shape.is_some() {
-                        barre_data.shape = None;
-                        barre_data.pick = None;
-                        barre_data.update(&mut commands, &theme, barre_entity);
-                    }
-                }
-            }
-        }
-    }
     pub fn update_y(guitar_view_query: &mut Query<&mut Transform, With<GuitarView>>, y: f32) {
         if let Ok(mut transform) = guitar_view_query.get_single_mut() {
             let trans = transform.translation;
@@ -441,3 +401,121 @@ impl GuitarView {
         }
     }
 }
+
+macro_rules! impl_update_hand_shape {
+    ($update_hand_shape:ident, $strings:literal, $hand_shape:ident, $get_fretboard:ident, $as_fretted:ident, $update_value:ident, $set_shape:ident) => {
+        impl GuitarView {
+        pub fn $update_hand_shape(
+            mut commands: Commands,
+            assets: Res<NotationAssets>,
+            theme: Res<NotationTheme>,
+            settings: Res<NotationSettings>,
+            query: Query<(&SingleData<LaneEntry>, &SingleData<$hand_shape>, &EntryPlaying), Changed<EntryPlaying>>,
+            tab_state_query: Query<(Entity, &TabState), With<TabState>>,
+            queries: GuitarViewQueries,
+        ) {
+            let GuitarViewQueries {
+                view_query,
+                mut string_query,
+                mut finger_query,
+                mut capo_query,
+                mut barre_query,
+                mut dot_query,
+                text_query,
+            } = queries;
+            if Self::CHECKING_FRETS {
+                return;
+            }
+            if theme._bypass_systems {
+                return;
+            }
+            if settings.hide_guitar_view {
+                return;
+            }
+            match view_query.get_single() {
+                Ok(view) if view.string_num == $strings => (),
+                _ => return,
+            }
+            let mut current_shape = None;
+            for (entry, shape, playing) in query.iter() {
+                if playing.value.is_current() {
+                    //println!("GuitarView::$update_hand_shape(): found changed playing shape: {}", shape);
+                    current_shape = Some((entry.0.clone(), shape.0));
+                }
+            }
+            if let Some((entry, shape)) = current_shape {
+                let fretboard = entry.track().and_then(|x| x.$get_fretboard());
+                let chord = entry.bar().and_then(|x| x.get_chord_of_entry(&entry));
+                let pick = entry.bar().and_then(|x| {
+                    x.get_entry_in_other_lane(
+                        LaneKind::Strings,
+                        entry.track_index(),
+                        Some(entry.in_bar_pos()),
+                        &|x: &LaneEntry| {
+                            x.proto()
+                                .$as_fretted()
+                                .and_then(|y| y.as_pick())
+                                .map(|z| z.to_owned())
+                        },
+                    )
+                });
+                let meta = entry.bar().map(|x| x.tab_meta());
+                //println!("GuitarView::$update_hand_shape(): {}, {:#?}, {:#?}", shape, fretboard, chord);
+                for (finger_entity, mut finger_data) in finger_query.iter_mut() {
+                    finger_data.$update_value(&shape, fretboard, chord, pick, meta.clone());
+                    finger_data.respawn_dots(&mut commands, &theme, Some(&mut dot_query), finger_entity);
+                    finger_data.update_with_syllable(
+                        &mut commands,
+                        &assets,
+                        &theme,
+                        &settings,
+                        &text_query,
+                        finger_entity,
+                        &meta.clone().unwrap_or_default(),
+                    );
+                }
+                for (string_entity, mut string_data) in string_query.iter_mut() {
+                    string_data.$update_value(&shape, fretboard, pick, meta.clone());
+                    string_data.update(&mut commands, &theme, string_entity);
+                }
+                if let Some(fretboard) = fretboard {
+                    for (capo_entity, mut capo_data) in capo_query.iter_mut() {
+                        if fretboard.capo != capo_data.capo {
+                            capo_data.capo = fretboard.capo;
+                            capo_data.update(&mut commands, &theme, capo_entity);
+                        }
+                    }
+                    for (barre_entity, mut barre_data) in barre_query.iter_mut() {
+                        barre_data.capo = fretboard.capo;
+                        barre_data.$set_shape(&shape);
+                        barre_data.pick = None;
+                        barre_data.update(&mut commands, &theme, barre_entity);
+                    }
+                }
+            } else {
+                let position = TabState::get_position(&tab_state_query, None);
+                if settings.add_ready_section && position.is_some() && position.unwrap().bar.bar_ordinal == 0 {
+                    for (finger_entity, mut finger_data) in finger_query.iter_mut() {
+                        finger_data.reset();
+                        finger_data.update(&mut commands, &theme, finger_entity);
+                    }
+                    for (string_entity, mut string_data) in string_query.iter_mut() {
+                        string_data.reset();
+                        string_data.update(&mut commands, &theme, string_entity);
+                    }
+                    for (barre_entity, mut barre_data) in barre_query.iter_mut() {
+                        if barre_data.has_shape {
+                            barre_data.clear_shape();
+                            barre_data.pick = None;
+                            barre_data.update(&mut commands, &theme, barre_entity);
+                        }
+                    }
+                }
+            }
+        }
+        }
+    };
+}
+
+impl_update_hand_shape!(update_hand_shape6, 6, HandShape6, get_fretboard6, as_fretted6, update_value6, set_shape6);
+impl_update_hand_shape!(update_hand_shape4, 4, HandShape4, get_fretboard4, as_fretted4, update_value4, set_shape4);
@@ -1,5 +1,6 @@
 use edger_bevy::bevy_prelude::*;
 
+use crate::drums::drums_plugin::DrumsPlugin;
 use crate::entry::entry_plugin;
 use crate::harmony::harmony_plugin::HarmonyPlugin;
 use crate::lyrics::lyrics_plugin::LyricsPlugin;
@@ -60,6 +61,7 @@ impl LaneView {
                 } else {
                     if let Some(color) = match lane_layout.lane_kind {
                         LaneKind::Strings => Some(theme.colors.strings.background),
+                        LaneKind::Drums => Some(theme.colors.drums.background),
                         _ => None,
                     } {
                         ColorBackground::spawn(commands, lane_entity, 0.0, color);
@@ -114,6 +116,12 @@ impl LaneView {
                 }
                 true
             }
+            LaneKind::Drums => {
+                if !settings.hide_drums_lane {
+                    DrumsPlugin::insert_lane_extra(&mut commands.entity(lane_entity), lane)
+                }
+                !settings.hide_drums_lane
+            }
             LaneKind::Shapes => {
                 if !settings.hide_shapes_lane {
                     ShapesPlugin::insert_lane_extra(&mut commands.entity(lane_entity), lane)
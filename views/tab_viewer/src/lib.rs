@@ -22,6 +22,7 @@ pub mod mini;
 pub mod play;
 pub mod tab;
 
+pub mod drums;
 pub mod guitar;
 pub mod lyrics;
 pub mod melody;
@@ -76,6 +77,10 @@ pub mod prelude {
     #[doc(hidden)]
     pub use crate::data::model_entry_data::ModelEntryData;
     #[doc(hidden)]
+    pub use crate::drums::drums_grid::DrumsGrid;
+    #[doc(hidden)]
+    pub use crate::drums::drums_plugin::DrumsPlugin;
+    #[doc(hidden)]
     pub use crate::entry::entry_bundle::EntryBundle;
     #[doc(hidden)]
     pub use crate::entry::entry_playing::EntryPlaying;
@@ -1,6 +1,6 @@
 use edger_bevy::bevy_prelude::*;
-use notation_midi::prelude::{MidiSettings, MidiState};
-use edger_bevy::egui::{CollapsingHeader, Slider, Ui};
+use notation_midi::prelude::{AccompanimentPattern, MidiSettings, MidiState};
+use edger_bevy::egui::{ComboBox, CollapsingHeader, Slider, Ui};
 use float_eq::float_ne;
 
 use crate::prelude::{NotationSettings, PlayControlEvent, Control, EguiControlPanel, NotationState, NotationTheme, Octave};
@@ -30,6 +30,11 @@ impl EguiControlPanel {
                     state.bars_range = None;
                     Control::reload_tab(state, theme);
                 }
+                let notated_form = settings.notated_form;
+                ui.checkbox(&mut settings.notated_form, "Notated Form");
+                if notated_form != settings.notated_form {
+                    Control::reload_tab(state, theme);
+                }
                 let play_title = if midi_state.play_control.play_state.is_playing() {
                     "Pause"
                 } else {
@@ -164,6 +169,58 @@ impl EguiControlPanel {
                             Slider::new(&mut midi_settings.guitar_velocity, 0..=127).text("Guitar"),
                         );
                     });
+                    ui.horizontal(|ui| {
+                        ui.checkbox(&mut midi_settings.bass_mute, "Mute");
+                        ui.add(
+                            Slider::new(&mut midi_settings.bass_velocity, 0..=127).text("Bass"),
+                        );
+                    });
+                    ui.horizontal(|ui| {
+                        ui.checkbox(&mut midi_settings.chord_mute, "Mute");
+                        ui.add(
+                            Slider::new(&mut midi_settings.chord_velocity, 0..=127).text("Chords"),
+                        );
+                    });
+                    ui.horizontal(|ui| {
+                        let chord_pattern = midi_settings.chord_pattern;
+                        ComboBox::from_id_source("chord_pattern")
+                            .selected_text(chord_pattern.to_string())
+                            .show_ui(ui, |ui| {
+                                for pattern in AccompanimentPattern::ALL {
+                                    ui.selectable_value(
+                                        &mut midi_settings.chord_pattern,
+                                        pattern,
+                                        pattern.to_string(),
+                                    );
+                                }
+                            });
+                        if midi_settings.chord_pattern != chord_pattern {
+                            Control::reload_tab(state, theme);
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        ui.checkbox(&mut midi_settings.drums_mute, "Mute");
+                        ui.add(
+                            Slider::new(&mut midi_settings.drums_velocity, 0..=127).text("Drums"),
+                        );
+                    });
+                    ui.horizontal(|ui| {
+                        let strum_speed = midi_settings.strum_speed;
+                        let strum_velocity_curve = midi_settings.strum_velocity_curve;
+                        ui.add(
+                            Slider::new(&mut midi_settings.strum_speed, 0..=50)
+                                .text("Strum (ms)"),
+                        );
+                        ui.add(
+                            Slider::new(&mut midi_settings.strum_velocity_curve, 0.0..=1.0)
+                                .text("Strum Velocity"),
+                        );
+                        if midi_settings.strum_speed != strum_speed
+                            || float_ne!(midi_settings.strum_velocity_curve, strum_velocity_curve, abs <= 0.001)
+                        {
+                            Control::reload_tab(state, theme);
+                        }
+                    });
                     if ui.button("Reset Audio").clicked() {
                         let default = MidiSettings::default();
                         midi_settings.click_mute = default.click_mute;
@@ -172,6 +229,19 @@ impl EguiControlPanel {
                         midi_settings.vocal_velocity = default.vocal_velocity;
                         midi_settings.guitar_mute = default.guitar_mute;
                         midi_settings.guitar_velocity = default.guitar_velocity;
+                        midi_settings.bass_mute = default.bass_mute;
+                        midi_settings.bass_velocity = default.bass_velocity;
+                        midi_settings.chord_mute = default.chord_mute;
+                        midi_settings.chord_velocity = default.chord_velocity;
+                        midi_settings.drums_mute = default.drums_mute;
+                        midi_settings.drums_velocity = default.drums_velocity;
+                        if midi_settings.strum_speed != default.strum_speed
+                            || float_ne!(midi_settings.strum_velocity_curve, default.strum_velocity_curve, abs <= 0.001)
+                        {
+                            midi_settings.strum_speed = default.strum_speed;
+                            midi_settings.strum_velocity_curve = default.strum_velocity_curve;
+                            Control::reload_tab(state, theme);
+                        }
                     }
                 }
             });
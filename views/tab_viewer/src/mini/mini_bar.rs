@@ -114,8 +114,16 @@ impl MiniBar {
                 commands,
                 assets,
                 shape_entity,
-                bar.props.bar_number.to_string().as_str(),
+                bar.calc_bar_number_text().as_str(),
             );
+        } else if let Some(tab) = bar.tab() {
+            let (_, end) = tab.calc_form_labels(bar);
+            if !end.is_empty() {
+                theme
+                    .texts
+                    .mini_map
+                    .spawn_bar_text(commands, assets, shape_entity, end.as_str());
+            }
         }
         bar_entity
     }
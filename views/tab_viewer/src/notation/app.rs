@@ -28,6 +28,7 @@ impl PluginGroup for NotationPlugins {
             .add(MelodyPlugin)
             .add(HarmonyPlugin)
             .add(StringsPlugin)
+            .add(DrumsPlugin)
             .add(ShapesPlugin)
             .add(MiniPlugin)
             .add(TabPlugin)
@@ -158,8 +159,23 @@ impl NotationApp {
                 if let Some(tab_asset) = load_tab(commands, state.tab_path.clone()) {
                     match tab_asset.tab {
                         Ok(tab) => {
+                            let tab = match transpose(&tab, Semitones(state.transpose), state.transpose_strategy) {
+                                Ok(tab) => tab,
+                                Err(err) => {
+                                    println!("\nload_tab(): Transpose Tab Failed: {} {:?}", state.transpose, err);
+                                    state.tab_error = Some(TabError::TransposeFailed(err));
+                                    return;
+                                }
+                            };
                             match Tab::try_parse_arc(tab, settings.add_ready_section, state.bars_range) {
                                 Ok(tab) => {
+                                    state.key_warning = tab.check_meta_key();
+                                    if let Some(candidate) = state.key_warning {
+                                        println!(
+                                            "load_tab(): Key Mismatch: {} {} -> {}",
+                                            tab.meta.key, tab.meta.scale, candidate
+                                        );
+                                    }
                                     state.tab = Some(tab.clone());
                                     if let Ok(mut window) = window_query.get_single_mut() {
                                         let title = format!("{} - {}", NotationApp::TITLE, state.tab_path);
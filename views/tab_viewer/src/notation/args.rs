@@ -2,6 +2,7 @@
 use clap::Parser;
 
 use edger_bevy::bevy_prelude::*;
+use notation_model::prelude::TransposeStrategy;
 
 #[derive(Debug, Resource, Clone)]
 #[cfg_attr(feature = "native", derive(Parser))]
@@ -12,6 +13,18 @@ pub struct NotationArgs {
 
     #[cfg_attr(feature = "native", clap(short, long))]
     pub tab: Vec<String>,
+
+    /// Semitones to transpose the tabs by.
+    #[cfg_attr(feature = "native", clap(long, default_value = "0", allow_hyphen_values = true))]
+    pub transpose: i8,
+
+    /// How fretted tracks are transposed: Auto, Capo or Refret.
+    #[cfg_attr(feature = "native", clap(long, default_value = "Auto", value_parser = ["Auto", "Capo", "Refret"]))]
+    pub transpose_strategy: String,
+
+    /// Write the transposed tabs as RON into this folder and exit, instead of viewing them.
+    #[cfg_attr(feature = "native", clap(long))]
+    pub export: Option<String>,
 }
 
 impl NotationArgs {
@@ -22,9 +35,15 @@ impl NotationArgs {
         return Self::parse_wasm();
         Self {
             lang: "en-US".to_owned(),
-            tab: vec![ "tabs/test.ron".to_owned() ]
+            tab: vec![ "tabs/test.ron".to_owned() ],
+            transpose: 0,
+            transpose_strategy: TransposeStrategy::default().to_ident(),
+            export: None,
         }
     }
+    pub fn transpose_strategy(&self) -> TransposeStrategy {
+        TransposeStrategy::from_ident(&self.transpose_strategy)
+    }
     #[cfg(feature = "native")]
     pub fn parse_native() -> Self {
         use crate::settings::notation_settings::NotationSettings;
@@ -70,6 +89,50 @@ impl NotationArgs {
         Self {
             lang,
             tab,
+            transpose: 0,
+            transpose_strategy: TransposeStrategy::default().to_ident(),
+            export: None,
+        }
+    }
+    /// Tabs not found from the current folder are read from "assets", as the viewer does.
+    #[cfg(feature = "native")]
+    pub fn export_tabs(&self, folder: &str) {
+        use std::path::Path;
+        use notation_model::prelude::{transpose, ProtoTab, Semitones};
+        use ron::ser::{to_string_pretty, PrettyConfig};
+
+        if let Err(err) = std::fs::create_dir_all(folder) {
+            println!("NotationArgs::export_tabs() Create Folder Failed: {} {:?}", folder, err);
+            return;
+        }
+        for tab_path in self.tab.iter() {
+            let mut path = Path::new(tab_path).to_path_buf();
+            if !path.exists() {
+                path = Path::new("assets").join(tab_path);
+            }
+            let tab = match std::fs::read(&path)
+                .map_err(|err| err.to_string())
+                .and_then(|bytes| ron::de::from_bytes::<ProtoTab>(&bytes).map_err(|err| err.to_string()))
+                .and_then(|tab| {
+                    transpose(&tab, Semitones(self.transpose), self.transpose_strategy())
+                        .map_err(|err| err.to_string())
+                }) {
+                Ok(tab) => tab,
+                Err(err) => {
+                    println!("NotationArgs::export_tabs() Failed: {} {}", tab_path, err);
+                    continue;
+                }
+            };
+            let pretty = PrettyConfig::new()
+                .separate_tuple_members(true)
+                .enumerate_arrays(true);
+            let target = Path::new(folder).join(path.file_name().unwrap_or_default());
+            match to_string_pretty(&tab, pretty)
+                .map_err(|err| err.to_string())
+                .and_then(|text| std::fs::write(&target, text).map_err(|err| err.to_string())) {
+                Ok(()) => println!("NotationArgs::export_tabs() {} -> {}", tab_path, target.display()),
+                Err(err) => println!("NotationArgs::export_tabs() Write Failed: {} {}", target.display(), err),
+            }
         }
     }
 }
\ No newline at end of file
@@ -6,6 +6,7 @@ use crate::settings::layout_settings::LayoutMode;
 use crate::prelude::{NotationState, NotationSettings, NotationTheme};
 
 use edger_bevy::prelude::WindowResizedEvent;
+use notation_model::prelude::TransposeStrategy;
 
 pub struct Control();
 
@@ -27,6 +28,16 @@ impl Control {
         state.reload_tab();
         theme._bypass_systems = true;
     }
+    pub fn set_transpose(
+        state: &mut NotationState,
+        theme: &mut NotationTheme,
+        transpose: i8,
+        strategy: TransposeStrategy,
+    ) {
+        state.transpose = transpose;
+        state.transpose_strategy = strategy;
+        Self::reload_tab(state, theme);
+    }
     pub fn toggle_layout_mode(
         state: &mut NotationState,
         settings: &mut NotationSettings,
@@ -5,6 +5,7 @@ use edger_bevy::bevy_egui::EguiContexts;
 use edger_bevy::prelude::AppState;
 use float_eq::float_ne;
 use notation_midi::prelude::{JumpToBarEvent, PlayControlEvent};
+use notation_model::prelude::TransposeStrategy;
 
 use crate::settings::layout_settings::{GridAlignMode, LayoutMode};
 use super::control::Control;
@@ -194,6 +195,12 @@ impl EguiControlPanel {
                     settings.hide_melody_lane = hide_melody_lane;
                     Control::reload_tab(state, theme);
                 }
+                let mut hide_drums_lane = settings.hide_drums_lane;
+                ui.checkbox(&mut hide_drums_lane, "Hide Drums");
+                if settings.hide_drums_lane != hide_drums_lane {
+                    settings.hide_drums_lane = hide_drums_lane;
+                    Control::reload_tab(state, theme);
+                }
                 ui.separator();
                 let show_note_pitch = settings.show_note_pitch;
                 ui.checkbox(
@@ -336,6 +343,13 @@ impl EguiControlPanel {
             }
             ui.separator();
         }
+        if let (Some(tab), Some(candidate)) = (state.tab.as_ref(), state.key_warning.as_ref()) {
+            ui.label(format!(
+                "Key Mismatch: {} {} declared, {} {} detected ({:.2})",
+                tab.meta.key, tab.meta.scale, candidate.key, candidate.scale, candidate.confidence
+            ));
+            ui.separator();
+        }
         ui.horizontal(|ui| {
             if ui.button("Reload Tab").clicked() {
                 state.bars_range = None;
@@ -368,6 +382,27 @@ impl EguiControlPanel {
                 }
             });
         });
+        ui.horizontal(|ui| {
+            ui.label(format!("Transpose: {:+}", state.transpose));
+            if ui.button("-").clicked() {
+                Control::set_transpose(state, theme, state.transpose - 1, state.transpose_strategy);
+            }
+            if ui.button("+").clicked() {
+                Control::set_transpose(state, theme, state.transpose + 1, state.transpose_strategy);
+            }
+            egui::ComboBox::from_id_source("transpose_strategy")
+                .selected_text(state.transpose_strategy.to_string())
+                .show_ui(ui, |ui| {
+                    for strategy in TransposeStrategy::ALL.iter() {
+                        if ui
+                            .selectable_label(*strategy == state.transpose_strategy, strategy.to_string())
+                            .clicked()
+                        {
+                            Control::set_transpose(state, theme, state.transpose, *strategy);
+                        }
+                    }
+                });
+        });
         if args.tab.len() > 1 {
             let width = Self::calc_width(app_state.window_width);
             egui::ComboBox::from_id_source("tab")
@@ -13,6 +13,11 @@ pub struct NotationState {
     pub show_kb: bool,
     pub preset: Option<String>,
     pub tab_error: Option<TabError>,
+    /// Detected key when it disagrees with the tab meta.
+    pub key_warning: Option<KeyCandidate>,
+    /// Semitones the tab is transposed by when loading.
+    pub transpose: i8,
+    pub transpose_strategy: TransposeStrategy,
     pub debug_str: Option<String>,
     pub _despawn_delay_seconds: f32,
     pub _load_tab_delay_seconds: f32,
@@ -34,6 +39,9 @@ impl NotationState {
 
             preset: None,
             tab_error: None,
+            key_warning: None,
+            transpose: 0,
+            transpose_strategy: TransposeStrategy::default(),
             debug_str: None,
             _despawn_delay_seconds: 0.0,
             _load_tab_delay_seconds: 0.0,
@@ -48,6 +56,7 @@ impl NotationState {
     pub fn reload_tab(&mut self) {
         self.tab = None;
         self.tab_error = None;
+        self.key_warning = None;
         self._despawn_delay_seconds = 0.1;
         self._load_tab_delay_seconds = 0.2;
     }
@@ -72,6 +81,10 @@ impl NotationState {
 impl FromWorld for NotationState {
     fn from_world(world: &mut World) -> Self {
         let args = world.get_resource::<NotationArgs>().unwrap();
-        Self::new(args.tab.first().unwrap_or(&"".to_owned()).clone())
+        Self {
+            transpose: args.transpose,
+            transpose_strategy: args.transpose_strategy(),
+            ..Self::new(args.tab.first().unwrap_or(&"".to_owned()).clone())
+        }
     }
 }
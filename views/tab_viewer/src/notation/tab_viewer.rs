@@ -53,6 +53,8 @@ impl Plugin for TabViewerPlugin {
             GuitarView::on_layout_changed,
             GuitarView::update_hand_shape6
                     .in_set(GuitarViewLabel::UpdateHandShapes),
+            GuitarView::update_hand_shape4
+                    .in_set(GuitarViewLabel::UpdateHandShapes),
             GuitarView::update_string_state
                     .in_set(GuitarViewLabel::UpdateStringStates)
                     .after(GuitarViewLabel::UpdateHandShapes),
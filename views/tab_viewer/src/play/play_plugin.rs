@@ -57,7 +57,7 @@ impl PlayPlugin {
     ) {
         let bar_data = BarIndicatorData::new(tab.clone());
         bar_data.create(commands, &theme, entity);
-        let pos_data = PosIndicatorData::new(tab.bar_units());
+        let pos_data = PosIndicatorData::default();
         pos_data.create(commands, &theme, entity);
     }
 }
@@ -1,7 +1,7 @@
 use edger_bevy::bevy_prelude::*;
 use edger_bevy::bevy_prototype_lyon::prelude::*;
 use edger_bevy::prelude::{offscreen, LayoutData, OutlineRectangle, ShapeOp};
-use notation_model::prelude::{BarPosition, Position, TabBarProps, Units};
+use notation_model::prelude::{BarPosition, Position, TabBarProps};
 
 use crate::prelude::NotationTheme;
 
@@ -10,26 +10,30 @@ pub struct PosIndicatorData {
     pub hidden: bool,
     pub bar_props: TabBarProps,
     pub bar_layout: LayoutData,
-    pub bar_units: Units,
     pub bar_position: BarPosition,
 }
 
-impl PosIndicatorData {
-    pub fn new(bar_units: Units) -> Self {
+impl Default for PosIndicatorData {
+    fn default() -> Self {
         PosIndicatorData {
             hidden: false,
             bar_props: TabBarProps::default(),
             bar_layout: LayoutData::ZERO,
-            bar_units,
             bar_position: BarPosition::ZERO,
         }
     }
+}
+
+impl PosIndicatorData {
     pub fn is_synced(&self) -> bool {
         self.bar_position.bar_ordinal == self.bar_props.bar_ordinal
     }
     pub fn offset_x(&self) -> f32 {
         let mut x = self.bar_layout.offset.x;
-        x += self.bar_layout.size.width * self.bar_position.in_bar_pos.0 / self.bar_units.0;
+        if self.bar_position.bar_units.is_positive() {
+            x += self.bar_layout.size.width
+                * (self.bar_position.in_bar_pos / self.bar_position.bar_units).as_f32();
+        }
         x
     }
 }
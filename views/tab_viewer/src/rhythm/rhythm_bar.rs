@@ -75,11 +75,14 @@ impl RhythmBarData {
         entity: Entity,
         tab: &Arc<Tab>,
     ) -> Entity {
-        let signature = tab.signature();
         let bar_props = tab
             .get_bar_of_ordinal(1)
             .map(|x| x.props)
             .unwrap_or_default();
+        let signature = tab
+            .get_bar_of_ordinal(1)
+            .map(|x| x.signature())
+            .unwrap_or(tab.signature());
         let chord = tab.get_bar_of_ordinal(1).and_then(|x| x.get_chord(None));
         let bar_value = RhythmBarValue {
             signature,
@@ -92,7 +95,12 @@ impl RhythmBarData {
             value: bar_value,
         };
         let bar_entity = bar_data.create(commands, theme, entity);
-        let beats = signature.bar_beats;
+        let beats = tab
+            .bars
+            .iter()
+            .map(|x| x.bar_beats())
+            .max()
+            .unwrap_or(signature.bar_beats);
         for index in 0..beats {
             RhythmBeatData::spawn(commands, theme, bar_entity, bar_props, signature, index);
         }
@@ -137,6 +145,7 @@ impl RhythmBarData {
                     || bar_data.value.chord != current_chord
                 {
                     bar_data.bar_props = bar_props;
+                    bar_data.value.signature = bar_props.signature;
                     bar_data.value.chord = current_chord;
                     bar_data.update(&mut commands, &theme, bar_entity);
                 }
@@ -150,11 +159,13 @@ impl RhythmBarData {
             let in_bar_pos = current_position.unwrap().bar.in_bar_pos;
             for (beat_entity, mut beat_data) in beat_query.iter_mut() {
                 beat_data.bar_props = bar_props;
+                beat_data.value.signature = bar_props.signature;
                 beat_data.value.in_bar_pos = in_bar_pos;
                 beat_data.update(&mut commands, &theme, beat_entity);
             }
             for (indicator_entity, mut indicator_data) in indicator_query.iter_mut() {
                 indicator_data.bar_props = bar_props;
+                indicator_data.value.signature = bar_props.signature;
                 indicator_data.value.in_bar_pos = in_bar_pos;
                 indicator_data.update(&mut commands, &theme, indicator_entity);
             }
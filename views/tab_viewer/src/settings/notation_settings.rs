@@ -13,6 +13,8 @@ pub struct NotationSettings {
     pub layout: LayoutSettings,
     pub add_ready_section: bool,
     pub new_row_for_section: bool,
+    /// Show each notated bar once in grid mode, instead of expanding repeats and jumps.
+    pub notated_form: bool,
     pub should_loop: bool,
     pub speed_factor: f32,
     pub hide_bar_number: bool,
@@ -31,6 +33,7 @@ pub struct NotationSettings {
     pub hide_harmony_lane: bool,
     pub hide_lyrics_lane: bool,
     pub hide_melody_lane: bool,
+    pub hide_drums_lane: bool,
     pub override_beat_size: Option<f32>,
     pub override_chord_size: Option<f32>,
     pub override_guitar_width: Option<f32>,
@@ -45,6 +48,7 @@ impl FromWorld for NotationSettings {
             layout: LayoutSettings::default(),
             add_ready_section: false,
             new_row_for_section: false,
+            notated_form: false,
             should_loop: false,
             speed_factor: 1.0,
             hide_bar_number: false,
@@ -60,6 +64,7 @@ impl FromWorld for NotationSettings {
             hide_harmony_lane: false,
             hide_lyrics_lane: false,
             hide_melody_lane: false,
+            hide_drums_lane: false,
             hide_guitar_view: false,
             hide_mini_map: false,
             hide_chords_view: false,
@@ -94,6 +99,7 @@ impl NotationSettings {
         self.hide_harmony_lane = true;
         self.hide_lyrics_lane = true;
         self.hide_melody_lane = true;
+        self.hide_drums_lane = true;
     }
     pub fn hack_for_screenshot(&mut self) {
         self.layout.mode = LayoutMode::Grid;
@@ -25,7 +25,7 @@ macro_rules! impl_shape_diagram {
                 let x = if self.value.bar_size <= 0.0 {
                     offscreen::offset().x
                 } else {
-                    self.value.bar_size / self.bar_props.bar_units.0 * self.entry_props.in_bar_pos.0
+                    self.value.bar_size / self.bar_props.bar_units.as_f32() * self.entry_props.in_bar_pos.as_f32()
                         + theme.shapes.shape_x
                 };
                 StrokePath {
@@ -3,5 +3,6 @@ pub mod strings_grid;
 pub mod strings_plugin;
 
 pub mod pick_bundle;
+pub mod pick_grace;
 pub mod pick_note;
 pub mod pick_systems;
@@ -0,0 +1,60 @@
+use edger_bevy::bevy_prelude::*;
+use edger_bevy::bevy_prototype_lyon::prelude::*;
+use edger_bevy::prelude::{offscreen, OutlineRectangle, ShapeOp};
+use notation_model::prelude::Syllable;
+use notation_midi::prelude::PlayingState;
+
+use crate::prelude::{EntryData, NotationTheme};
+
+/// Ornament notes drawn small in front of the main note, on its string.
+pub type PickGraceData = EntryData<PickGraceValue>;
+
+#[derive(Clone, Debug)]
+pub struct PickGraceValue {
+    pub string: u8,
+    pub syllable: Syllable,
+    pub index: usize,
+    pub count: usize,
+    pub bar_size: f32,
+}
+
+impl PickGraceValue {
+    pub fn new(string: u8, syllable: Syllable, index: usize, count: usize) -> Self {
+        Self {
+            string,
+            syllable,
+            index,
+            count,
+            bar_size: 0.0,
+        }
+    }
+}
+
+impl ShapeOp<NotationTheme, OutlineRectangle> for PickGraceData {
+    fn get_shape(&self, theme: &NotationTheme) -> OutlineRectangle {
+        let sizes = theme.sizes.strings;
+        let width = sizes.grace_note_width;
+        let height = sizes.grace_note_height;
+        let color = theme.colors.of_syllable(self.value.syllable);
+        let outline_color = theme.colors.strings.outline.of_state(&PlayingState::Idle);
+        let outline_width = sizes.note_outline.of_state(&PlayingState::Idle);
+        let offset = if self.value.bar_size <= 0.0 {
+            offscreen::offset()
+        } else {
+            let x = self.value.bar_size / self.bar_props.bar_units.as_f32()
+                * self.entry_props.in_bar_pos.as_f32()
+                - width * (self.value.count - self.value.index) as f32;
+            let y = sizes.calc_string_y(self.value.string);
+            Vec3::new(x, y + height / 2.0, theme.z.pick)
+        };
+        OutlineRectangle {
+            width: width - outline_width,
+            height,
+            origin: shapes::RectangleOrigin::TopLeft,
+            color,
+            outline_width,
+            outline_color,
+            offset,
+        }
+    }
+}
@@ -37,7 +37,7 @@ impl PickNoteData {
     }
     pub fn calc_width_height(&self, theme: &NotationTheme) -> (f32, f32) {
         let width =
-            self.value.bar_size / self.bar_props.bar_units.0 * self.entry_props.tied_units.0;
+            self.value.bar_size / self.bar_props.bar_units.as_f32() * self.entry_props.tied_units.as_f32();
         let mut height = theme.sizes.strings.note_height;
         let outline = self.calc_outline(theme);
         if self.value.playing_state.is_current() {
@@ -71,7 +71,7 @@ impl ShapeOp<NotationTheme, OutlineRectangle> for PickNoteData {
             offscreen::offset()
         } else {
             let x =
-                self.value.bar_size / self.bar_props.bar_units.0 * self.entry_props.in_bar_pos.0;
+                self.value.bar_size / self.bar_props.bar_units.as_f32() * self.entry_props.in_bar_pos.as_f32();
             let y = theme
                 .sizes
                 .strings
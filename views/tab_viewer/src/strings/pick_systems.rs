@@ -1,11 +1,12 @@
 use edger_bevy::bevy_prelude::*;
 
 use edger_bevy::prelude::{text, ShapeOp};
-use notation_model::prelude::LaneEntry;
+use notation_model::prelude::{Entry, LaneEntry};
 
 use crate::prelude::{EntryPlaying, NotationAssets, NotationSettings, NotationTheme, ToneBundle, ToneMode};
-use notation_model::prelude::Pick;
+use notation_model::prelude::{Pick, PickTechnique, Semitones};
 
+use super::pick_grace::{PickGraceData, PickGraceValue};
 use super::pick_note::{PickNoteData, PickNoteValue};
 
 pub fn on_entry_playing_changed(
@@ -63,15 +64,21 @@ macro_rules! impl_pick_system {
             if let Some(bar) = entry.bar() {
                 if let Some((fretboard, shape)) = bar.$get_fretted_shape(entry) {
                     let meta = bar.tab_meta();
+                    let mut main_string = None;
                     for pick_note in pick.get_notes() {
                         if let Some((fret, note)) =
                             fretboard.shape_pick_fret_note(&meta.scale, &meta.key, &shape, pick_note)
                         {
+                            if main_string.map(|(_, x)| Semitones::from(note) > x).unwrap_or(true) {
+                                main_string = Some((pick_note.string, Semitones::from(note)));
+                            }
                             let syllable = bar.calc_syllable(&note.pitch);
                             let data =
                                 PickNoteData::new(entry, PickNoteValue::new(pick_note, syllable));
                             let note_entity = data.create(commands, theme, entity);
+                            let dead_note = pick_note.technique == Some(PickTechnique::DeadNote);
                             if !settings.hide_strings_lane
+                                && !dead_note
                                 && (settings.always_show_fret || pick_note.fret.is_some())
                             {
                                 theme.texts.strings.spawn_fret_text(
@@ -81,6 +88,28 @@ macro_rules! impl_pick_system {
                                     fret,
                                 );
                             }
+                            if let Some(technique) = pick_note.technique {
+                                if !settings.hide_strings_lane {
+                                    theme.texts.strings.spawn_technique_text(
+                                        commands,
+                                        note_entity,
+                                        &assets,
+                                        &technique,
+                                        fret,
+                                    );
+                                }
+                            }
+                        }
+                    }
+                    if let Some((string, _)) = main_string {
+                        if !entry.prev_is_tie() {
+                            let grace_notes = bar.calc_ornament_notes(entry);
+                            for (index, note) in grace_notes.iter().enumerate() {
+                                let syllable = bar.calc_syllable(&note.pitch);
+                                let value =
+                                    PickGraceValue::new(string, syllable, index, grace_notes.len());
+                                PickGraceData::new(entry, value).create(commands, theme, entity);
+                            }
                         }
                     }
                 }
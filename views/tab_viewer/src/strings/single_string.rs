@@ -6,6 +6,7 @@ use crate::prelude::{LaneData, NotationTheme};
 #[derive(Clone, Debug)]
 pub struct SingleStringValue {
     pub string: u8,
+    pub string_num: u8,
     pub bar_size: f32,
 }
 
@@ -17,7 +18,9 @@ impl ShapeOp<NotationTheme, StrokeLine> for SingleStringData {
         StrokeLine {
             from: Vec2::ZERO,
             to: Vec2::new(self.value.bar_size, 0.0),
-            line_width: theme.guitar.get_string_width(self.value.string),
+            line_width: theme
+                .guitar
+                .get_string_width(self.value.string, self.value.string_num),
             color: theme.colors.strings.string.idle,
             offset: Vec3::new(0.0, y, theme.z.string),
         }
@@ -22,6 +22,7 @@ macro_rules! impl_strings_grid {
                 for string in 1..=$strings {
                     let data = SingleStringData::new(lane, SingleStringValue {
                         string: string as u8,
+                        string_num: $strings,
                         bar_size: 0.0,
                     });
                     data.create(commands, theme, entity);
@@ -24,9 +24,16 @@ impl StringsPlugin {
     pub fn insert_lane_extra(commands: &mut EntityCommands, lane: &BarLane) {
         match lane.track.kind {
             TrackKind::Guitar => Self::insert_lane_extra6(commands, lane),
+            TrackKind::Bass => Self::insert_lane_extra4(commands, lane),
             _ => (),
         }
     }
+    pub fn string_num(track_kind: &TrackKind) -> u8 {
+        match track_kind {
+            TrackKind::Bass => 4,
+            _ => 6,
+        }
+    }
 }
 
 macro_rules! impl_strings_plugin {
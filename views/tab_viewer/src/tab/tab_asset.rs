@@ -1,5 +1,6 @@
 use thiserror::Error;
 use notation_model::parse::ParseError;
+use notation_model::prelude::TransposeError;
 use edger_bevy::bevy_prelude::*;
 use edger_bevy::bevy::asset::{Asset, AssetLoader, AsyncReadExt, LoadContext, io::Reader};
 use edger_bevy::bevy::reflect::TypePath;
@@ -23,6 +24,8 @@ pub enum TabError {
     GetTabFailed(String),
     #[error("parse tab failed")]
     ParseFailed(ParseError),
+    #[error("transpose tab failed")]
+    TransposeFailed(TransposeError),
 }
 
 impl From<ProtoTab> for TabAsset {
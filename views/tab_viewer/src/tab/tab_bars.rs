@@ -26,6 +26,10 @@ use super::tab_events::{TabBarsDoLayoutEvent, TabBarsResizedEvent, TabBarsResize
 pub struct TabBars {
     pub tab: Arc<Tab>,
     pub bar_layouts: Arc<Vec<BarLayoutData>>,
+    /// Cell of each of the bars in the notated form, repeated bars share the cell.
+    pub notated_cells: Arc<Vec<usize>>,
+    /// Bar ordinal shown in each of the cells in the notated form.
+    pub notated_bars: Arc<Vec<usize>>,
 }
 impl Display for TabBars {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -34,7 +38,46 @@ impl Display for TabBars {
 }
 impl TabBars {
     pub fn new(tab: Arc<Tab>, bar_layouts: Arc<Vec<BarLayoutData>>) -> Self {
-        Self { tab, bar_layouts }
+        let notated_cells = Arc::new(tab.calc_notated_bar_indices());
+        let notated_bars = Arc::new(
+            tab.notated_bars()
+                .iter()
+                .map(|x| x.props.bar_ordinal)
+                .collect(),
+        );
+        Self {
+            tab,
+            bar_layouts,
+            notated_cells,
+            notated_bars,
+        }
+    }
+    pub fn is_notated<'a>(engine: &NotationLayout<'a>) -> bool {
+        engine.settings.notated_form && engine.settings.layout.mode == LayoutMode::Grid
+    }
+    fn cell_count<'a>(&self, engine: &NotationLayout<'a>) -> usize {
+        if Self::is_notated(engine) {
+            self.notated_bars.len()
+        } else {
+            self.tab.bars.len()
+        }
+    }
+    fn cell_bar_ordinal<'a>(&self, engine: &NotationLayout<'a>, cell: usize) -> Option<usize> {
+        if Self::is_notated(engine) {
+            self.notated_bars.get(cell).cloned()
+        } else {
+            Some(cell)
+        }
+    }
+    /// Repeated bars are hidden under the first one in the notated form.
+    pub fn is_bar_visible<'a>(&self, engine: &NotationLayout<'a>, bar_ordinal: usize) -> bool {
+        !Self::is_notated(engine)
+            || self
+                .notated_cells
+                .get(bar_ordinal)
+                .and_then(|cell| self.notated_bars.get(*cell))
+                .map(|x| *x == bar_ordinal)
+                .unwrap_or(true)
     }
 }
 impl<'a> View<NotationLayout<'a>> for TabBars {
@@ -58,7 +101,7 @@ impl TabBars {
         };
         let grid_data = GridData::new_fixed(
             1,
-            self.tab.bars.len(),
+            self.cell_count(engine),
             size,
             cell_margin,
             LayoutAnchor::TOP_LEFT,
@@ -71,8 +114,12 @@ impl TabBars {
     }
     fn sync_bar_layouts<'a>(&self, engine: &NotationLayout<'a>, begin: usize, count: usize) -> f32 {
         let mut non_ghost_lanes: HashSet<String> = HashSet::new();
+        let bar_layout_of_cell = |cell: usize| {
+            self.cell_bar_ordinal(engine, cell)
+                .and_then(|x| self.bar_layouts.get(x))
+        };
         for offset in 0..count {
-            if let Some(bar_layout) = self.bar_layouts.get(begin + offset) {
+            if let Some(bar_layout) = bar_layout_of_cell(begin + offset) {
                 for lane_layout in bar_layout.lane_layouts.iter() {
                     if engine.settings.layout.video_recording_mode
                         || !lane_layout.is_ghost()
@@ -83,7 +130,7 @@ impl TabBars {
             }
         }
         for offset in 0..count {
-            if let Some(bar_layout) = self.bar_layouts.get(begin + offset) {
+            if let Some(bar_layout) = bar_layout_of_cell(begin + offset) {
                 for lane_layout in bar_layout.lane_layouts.iter() {
                     if lane_layout.is_ghost() {
                         let visible = non_ghost_lanes.contains(&lane_layout.id());
@@ -92,7 +139,7 @@ impl TabBars {
                 }
             }
         }
-        let bar_layout = self.bar_layouts.get(begin).unwrap();
+        let bar_layout = bar_layout_of_cell(begin).unwrap();
         bar_layout.height()
     }
     fn calc_grid_data_normal_grid<'a>(&self, engine: &NotationLayout<'a>, grid_size: LayoutSize, rows: usize, cols: usize, cell_width: f32, cell_margin: LayoutSize) -> GridData {
@@ -116,7 +163,10 @@ impl TabBars {
         let mut sparse_row_sizes = Vec::new();
         let mut begin = 0;
         let mut count = 0;
-        for bar in self.tab.bars.iter() {
+        let cell_bars = (0..self.cell_count(engine))
+            .filter_map(|cell| self.cell_bar_ordinal(engine, cell))
+            .filter_map(|x| self.tab.bars.get(x));
+        for bar in cell_bars {
             if count >= cols || count > 0 && bar.props.bar_index == 0 {
                 sparse_rows += 1;
                 let height = self.sync_bar_layouts(engine, begin, count);
@@ -165,7 +215,7 @@ impl<'a> GridView<NotationLayout<'a>, BarView> for TabBars {
             tab_width - bar_margin * 2.0,
             bar_width_range,
             0.0,
-            self.tab.bars.len(),
+            self.cell_count(engine),
         );
         let cell_margin = engine.theme.sizes.cell_margin(&engine.settings);
         if engine.settings.layout.mode == LayoutMode::Line {
@@ -191,6 +241,14 @@ impl<'a> GridView<NotationLayout<'a>, BarView> for TabBars {
             }
         }
     }
+    fn calc_row_col(&self, engine: &NotationLayout<'a>, grid_data: &GridData, index: usize) -> (usize, usize) {
+        let cell = if Self::is_notated(engine) {
+            self.notated_cells.get(index).cloned().unwrap_or(index)
+        } else {
+            index
+        };
+        grid_data.calc_row_col(cell)
+    }
 }
 
 impl TabBars {
@@ -204,13 +262,19 @@ impl TabBars {
             for ((_k, _i), lane) in bar.lanes.iter() {
                 let lane_id = lane.id();
                 if !lane_layouts.contains_key(&lane_id) {
-                    let height = theme.sizes.calc_lane_height(settings, lane.kind);
+                    let height = theme
+                        .sizes
+                        .calc_lane_height(settings, lane.kind, &lane.track.kind);
                     let margin = theme.sizes.layout.lane_margin;
                     lane_layouts.insert(lane_id, (lane.kind, LaneLayoutData::new(&lane, height, margin)));
                     if lane.kind == LaneKind::Strings && !settings.hide_harmony_lane {
                         let lane_id = lane.kind_id(LaneKind::Harmony);
                         if !lane_layouts.contains_key(&lane_id) {
-                            let height = theme.sizes.calc_lane_height(settings, LaneKind::Harmony);
+                            let height = theme.sizes.calc_lane_height(
+                                settings,
+                                LaneKind::Harmony,
+                                &lane.track.kind,
+                            );
                             let margin = theme.sizes.layout.lane_margin;
                             lane_layouts.insert(lane_id, (lane.kind, LaneLayoutData::new_virtual(&lane, LaneKind::Harmony, height, margin)));
                         }
@@ -319,6 +383,7 @@ impl TabBars {
         settings: Res<NotationSettings>,
         mut layout_query: LayoutQuery,
         cell_query: ViewQuery<BarView>,
+        mut visibility_query: Query<(&BarView, &mut Visibility)>,
         mut tab_resized_evts: EventWriter<TabBarsResizedPreEvent>,
     ) {
         if theme._bypass_systems {
@@ -334,6 +399,13 @@ impl TabBars {
                 evt.entity,
                 evt.layout,
             );
+            for (bar_view, mut visibility) in visibility_query.iter_mut() {
+                *visibility = if evt.view.is_bar_visible(&engine, bar_view.bar_props.bar_ordinal) {
+                    Visibility::Inherited
+                } else {
+                    Visibility::Hidden
+                };
+            }
             tab_resized_evts.send(TabBarsResizedPreEvent(evt.entity));
         }
     }
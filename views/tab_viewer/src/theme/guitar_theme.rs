@@ -63,7 +63,9 @@ impl Default for GuitarTheme {
 }
 
 impl GuitarTheme {
-    pub fn get_string_width(&self, string: u8) -> f32 {
+    /// Fewer strings use the widths of the lower guitar strings.
+    pub fn get_string_width(&self, string: u8, string_num: u8) -> f32 {
+        let string = string + 6 - string_num.min(6);
         let index = if string < 1 {
             0
         } else if string > 6 {
@@ -76,8 +78,9 @@ impl GuitarTheme {
     pub fn calc_scale(&self, guitar_width: f32) -> f32 {
         guitar_width / self.syllable_base_width
     }
-    pub fn calc_string_x(&self, string: u8, guitar_width: f32) -> f32 {
-        -1.0 * (string as f32 - 3.5) * guitar_width * self.string_x_factor
+    pub fn calc_string_x(&self, string: u8, string_num: u8, guitar_width: f32) -> f32 {
+        let center = (string_num as f32 + 1.0) / 2.0;
+        -(string as f32 - center) * guitar_width * self.string_x_factor
     }
     pub fn calc_fret_y(&self, fret: u8, guitar_height: f32) -> f32 {
         let index = if fret as usize >= self.fret_y_factors.len() {
@@ -1,5 +1,5 @@
 use notation_model::prelude::{
-    Chord, IntervalQuality, Octave, Semitones, Signature, Syllable,
+    Chord, DrumPiece, IntervalQuality, Octave, Semitones, Signature, Syllable,
 };
 use notation_midi::prelude::PlayingState;
 
@@ -86,6 +86,7 @@ pub struct ThemeColors {
     pub lyrics: LyricsColors,
     pub section: SectionColors,
     pub strings: StringsColors,
+    pub drums: DrumsColors,
     pub rhythm: RhythmColors,
     pub mini_map: MiniMapColors,
     pub ui: UiColors,
@@ -139,6 +140,9 @@ pub struct BarColors {
     pub beat_color2: Color,
     pub pos_indicator_color: Color,
     pub line_color: Color,
+    pub tuplet_color: Color,
+    pub tempo_curve_color: Color,
+    pub dynamic_curve_color: Color,
 }
 impl Default for BarColors {
     fn default() -> Self {
@@ -153,6 +157,9 @@ impl Default for BarColors {
             beat_color2: ThemeColors::hex_linear("00000000"),
             pos_indicator_color: ThemeColors::hex_linear("00000077"),
             line_color: ThemeColors::hex_linear("79554844"),
+            tuplet_color: ThemeColors::hex_linear("00000066"),
+            tempo_curve_color: ThemeColors::hex_linear("000000AA"),
+            dynamic_curve_color: ThemeColors::hex_linear("000000AA"),
         }
     }
 }
@@ -288,6 +295,36 @@ impl Default for StringsColors {
     }
 }
 
+#[derive(Copy, Clone, PartialEq, Serialize, Deserialize, Debug)]
+pub struct DrumsColors {
+    pub background: Color,
+    pub outline: PlayingColors,
+    pub row: Color,
+    pub cymbal: Color,
+    pub drum: Color,
+}
+impl Default for DrumsColors {
+    fn default() -> Self {
+        Self {
+            background: hex_linear("3B3E5644"),
+            outline: PlayingColors::default(),
+            row: hex_linear("9CA5D344"),
+            cymbal: hex_linear("EECB16"),
+            drum: hex_linear("EF7071"),
+        }
+    }
+}
+impl DrumsColors {
+    pub fn of_piece(&self, piece: DrumPiece) -> Color {
+        match piece {
+            DrumPiece::Crash | DrumPiece::Ride | DrumPiece::OpenHiHat | DrumPiece::HiHat => {
+                self.cymbal
+            }
+            _ => self.drum,
+        }
+    }
+}
+
 #[derive(Copy, Clone, PartialEq, Serialize, Deserialize, Debug)]
 pub struct SectionColors {
     pub sections: [Color; 12],
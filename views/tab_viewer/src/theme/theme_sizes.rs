@@ -1,10 +1,10 @@
 use edger_bevy::prelude::LayoutSize;
-use notation_model::prelude::{LaneKind, Note, Semitones, Tab, TrackKind};
+use notation_model::prelude::{DrumPiece, LaneKind, Note, Semitones, Tab, TrackKind};
 use notation_midi::prelude::PlayingState;
 
 use serde::{Deserialize, Serialize};
 
-use crate::prelude::NotationSettings;
+use crate::prelude::{NotationSettings, StringsPlugin};
 
 #[derive(Copy, Clone, PartialEq, Serialize, Deserialize, Debug)]
 pub struct PlayingSize {
@@ -37,6 +37,7 @@ pub struct ThemeSizes {
     pub harmony: NotesSizes,
     pub lyrics: LyricsSizes,
     pub strings: StringsSizes,
+    pub drums: DrumsSizes,
     pub mini_map: MiniMapSizes,
     pub tab_control: TabControlSizes,
     pub layout: LayoutSizes,
@@ -51,6 +52,7 @@ impl Default for ThemeSizes {
             harmony: NotesSizes::default_harmony(),
             lyrics: Default::default(),
             strings: Default::default(),
+            drums: Default::default(),
             mini_map: Default::default(),
             tab_control: Default::default(),
             layout: Default::default(),
@@ -69,6 +71,17 @@ pub struct BarSizes {
     pub pos_indicator_extra: f32,
     pub grid_line_width: f32,
     pub grid_root_line_width: f32,
+    pub tuplet_y: f32,
+    pub tuplet_height: f32,
+    pub tuplet_line_width: f32,
+    pub tempo_curve_y: f32,
+    pub tempo_curve_text_width: f32,
+    pub tempo_curve_dash: f32,
+    pub tempo_curve_line_width: f32,
+    pub fermata_radius: f32,
+    pub dynamic_curve_y: f32,
+    pub dynamic_curve_height: f32,
+    pub dynamic_curve_line_width: f32,
 }
 impl Default for BarSizes {
     fn default() -> Self {
@@ -82,6 +95,17 @@ impl Default for BarSizes {
             pos_indicator_extra: 8.0,
             grid_line_width: 1.5,
             grid_root_line_width: 1.5,
+            tuplet_y: 8.0,
+            tuplet_height: 4.0,
+            tuplet_line_width: 1.5,
+            tempo_curve_y: 18.0,
+            tempo_curve_text_width: 48.0,
+            tempo_curve_dash: 4.0,
+            tempo_curve_line_width: 1.5,
+            fermata_radius: 7.0,
+            dynamic_curve_y: 18.0,
+            dynamic_curve_height: 8.0,
+            dynamic_curve_line_width: 1.5,
         }
     }
 }
@@ -99,6 +123,8 @@ pub struct ChordSizes {
     pub interval_dot_radius_factor: f32,
     pub interval_dot_big_radius_factor: f32,
     pub interval_dot_outline: f32,
+    /// Dots of 9th, 11th and 13th got thicker outlines than the 2nd, 4th and 6th.
+    pub interval_dot_compound_outline: f32,
     pub interval_dot_offset_2_factor: f32,
     pub interval_dot_offset_3_4_factor: f32,
     pub interval_dot_offset_5_7_factor: f32,
@@ -118,6 +144,7 @@ impl Default for ChordSizes {
             interval_dot_radius_factor: 0.22,
             interval_dot_big_radius_factor: 0.40,
             interval_dot_outline: 0.5,
+            interval_dot_compound_outline: 1.5,
             interval_dot_offset_2_factor: 0.3,
             interval_dot_offset_3_4_factor: 0.4,
             interval_dot_offset_5_7_factor: 0.6,
@@ -129,6 +156,8 @@ impl Default for ChordSizes {
 pub struct NotesSizes {
     pub note_height: f32,
     pub note_outline: PlayingSize,
+    pub grace_note_width: f32,
+    pub grace_note_height: f32,
     pub semitone_height: f32,
     pub lowest: Semitones,
     pub highest: Semitones,
@@ -140,6 +169,8 @@ impl Default for NotesSizes {
         Self {
             note_height: 4.0,
             note_outline: PlayingSize::new(1.0, 1.5, 1.0),
+            grace_note_width: 4.0,
+            grace_note_height: 3.0,
             semitone_height: 2.0,
             lowest: Semitones(i8::MAX),
             highest: Semitones(i8::MIN),
@@ -258,6 +289,8 @@ pub struct StringsSizes {
     pub string_space: f32,
     pub note_height: f32,
     pub note_outline: PlayingSize,
+    pub grace_note_width: f32,
+    pub grace_note_height: f32,
 }
 impl Default for StringsSizes {
     fn default() -> Self {
@@ -265,18 +298,44 @@ impl Default for StringsSizes {
             string_space: 12.0,
             note_height: 6.0,
             note_outline: PlayingSize::new(1.0, 1.5, 1.0),
+            grace_note_width: 5.0,
+            grace_note_height: 4.0,
         }
     }
 }
 impl StringsSizes {
-    pub fn layout_height(&self) -> f32 {
-        self.string_space * 6.0
+    pub fn layout_height(&self, string_num: u8) -> f32 {
+        self.string_space * string_num as f32
     }
     pub fn calc_string_y(&self, string: u8) -> f32 {
         -1.0 * self.string_space * (string as f32 - 0.5)
     }
 }
 
+#[derive(Copy, Clone, PartialEq, Serialize, Deserialize, Debug)]
+pub struct DrumsSizes {
+    pub row_space: f32,
+    pub note_height: f32,
+    pub note_outline: PlayingSize,
+}
+impl Default for DrumsSizes {
+    fn default() -> Self {
+        Self {
+            row_space: 8.0,
+            note_height: 6.0,
+            note_outline: PlayingSize::new(1.0, 1.5, 1.0),
+        }
+    }
+}
+impl DrumsSizes {
+    pub fn layout_height(&self) -> f32 {
+        self.row_space * DrumPiece::ALL.len() as f32
+    }
+    pub fn calc_row_y(&self, row: usize) -> f32 {
+        -self.row_space * (row as f32 + 0.5)
+    }
+}
+
 #[derive(Copy, Clone, PartialEq, Serialize, Deserialize, Debug)]
 pub struct TabControlSizes {
     pub control_width_factor: f32,
@@ -366,7 +425,12 @@ impl ThemeSizes {
         };
         LayoutSize::new(0.0, height)
     }
-    pub fn calc_lane_height(&self, settings: &NotationSettings, lane_kind: LaneKind) -> f32 {
+    pub fn calc_lane_height(
+        &self,
+        settings: &NotationSettings,
+        lane_kind: LaneKind,
+        track_kind: &TrackKind,
+    ) -> f32 {
         match lane_kind {
             LaneKind::Lyrics => {
                 if settings.hide_lyrics_lane {
@@ -393,7 +457,15 @@ impl ThemeSizes {
                 if settings.hide_strings_lane {
                     0.0
                 } else {
-                    self.strings.layout_height()
+                    self.strings
+                        .layout_height(StringsPlugin::string_num(track_kind))
+                }
+            }
+            LaneKind::Drums => {
+                if settings.hide_drums_lane {
+                    0.0
+                } else {
+                    self.drums.layout_height()
                 }
             }
             LaneKind::Shapes => {
@@ -1,5 +1,5 @@
 use edger_bevy::prelude::{text, LayoutData};
-use notation_model::prelude::{Syllable, Scale, Key};
+use notation_model::prelude::{Key, PickTechnique, Scale, Syllable};
 use serde::{Deserialize, Serialize};
 
 use edger_bevy::bevy::{prelude::*, sprite::Anchor};
@@ -40,6 +40,17 @@ pub struct TabTexts {
     pub bar_font_color: Color,
     pub bar_x: f32,
     pub bar_y: f32,
+    pub tuplet_font_size: f32,
+    pub tuplet_font_color: Color,
+    pub tuplet_y: f32,
+    pub meta_font_size: f32,
+    pub meta_font_color: Color,
+    pub meta_x: f32,
+    pub meta_y: f32,
+    pub accent_font_size: f32,
+    pub accent_font_color: Color,
+    pub accent_x: f32,
+    pub accent_y: f32,
 }
 impl Default for TabTexts {
     fn default() -> Self {
@@ -48,6 +59,17 @@ impl Default for TabTexts {
             bar_font_color: ThemeColors::hex_linear("00000066"),
             bar_x: -6.0,
             bar_y: -6.0,
+            tuplet_font_size: 14.0,
+            tuplet_font_color: ThemeColors::hex_linear("00000066"),
+            tuplet_y: 8.0,
+            meta_font_size: 16.0,
+            meta_font_color: ThemeColors::hex_linear("000000AA"),
+            meta_x: 4.0,
+            meta_y: 18.0,
+            accent_font_size: 14.0,
+            accent_font_color: ThemeColors::hex_linear("000000AA"),
+            accent_x: 6.0,
+            accent_y: 8.0,
         }
     }
 }
@@ -73,6 +95,75 @@ impl TabTexts {
             3.0,
         );
     }
+    pub fn spawn_tuplet_text(
+        &self,
+        commands: &mut Commands,
+        assets: &NotationAssets,
+        entity: Entity,
+        text: &str,
+    ) {
+        text::spawn(
+            commands,
+            entity,
+            text,
+            assets.latin_font.clone(),
+            self.tuplet_font_size,
+            self.tuplet_font_color,
+            JustifyText::Center,
+            Anchor::Center,
+            0.0,
+            self.tuplet_y,
+            1.0,
+        );
+    }
+    pub fn spawn_meta_text(
+        &self,
+        commands: &mut Commands,
+        assets: &NotationAssets,
+        entity: Entity,
+        text: &str,
+    ) -> Entity {
+        text::spawn(
+            commands,
+            entity,
+            text,
+            assets.latin_font.clone(),
+            self.meta_font_size,
+            self.meta_font_color,
+            JustifyText::Left,
+            Anchor::CenterLeft,
+            self.meta_x,
+            self.meta_y,
+            3.0,
+        )
+    }
+    pub fn update_meta_x(&self, transform: &mut Transform, x: f32) {
+        transform.translation.x = x + self.meta_x;
+    }
+    pub fn spawn_accent_text(
+        &self,
+        commands: &mut Commands,
+        assets: &NotationAssets,
+        entity: Entity,
+        text: &str,
+    ) -> Entity {
+        text::spawn(
+            commands,
+            entity,
+            text,
+            assets.latin_font.clone(),
+            self.accent_font_size,
+            self.accent_font_color,
+            JustifyText::Center,
+            Anchor::Center,
+            self.accent_x,
+            self.accent_y,
+            3.0,
+        )
+    }
+    pub fn update_accent_x(&self, transform: &mut Transform, x: f32) {
+        transform.translation.x = x + self.accent_x;
+    }
     pub fn update_bar_number_x(&self, transform: &mut Transform, bar_width: f32) {
         transform.translation.x = bar_width + self.bar_x;
     }
@@ -312,6 +403,9 @@ pub struct StringsTexts {
     pub text_z: f32,
     pub fret_font_size: f32,
     pub fret_font_color: Color,
+    pub technique_x: f32,
+    pub technique_y: f32,
+    pub technique_font_size: f32,
 }
 impl Default for StringsTexts {
     fn default() -> Self {
@@ -321,6 +415,9 @@ impl Default for StringsTexts {
             text_z: 1.0,
             fret_font_size: 18.0,
             fret_font_color: super::theme_colors::hex_linear("000000"),
+            technique_x: 16.0,
+            technique_y: 4.0,
+            technique_font_size: 13.0,
         }
     }
 }
@@ -347,6 +444,38 @@ impl StringsTexts {
             self.text_z,
         );
     }
+    /// Dead notes are shown as a cross in place of the fret.
+    pub fn spawn_technique_text(
+        &self,
+        commands: &mut Commands,
+        entity: Entity,
+        assets: &NotationAssets,
+        technique: &PickTechnique,
+        fret: u8,
+    ) {
+        let (text, font_size, x, y) = match technique {
+            PickTechnique::DeadNote => ("x".to_owned(), self.fret_font_size, self.text_x, self.text_y),
+            _ => (
+                technique.to_glyph(fret),
+                self.technique_font_size,
+                self.technique_x,
+                self.technique_y,
+            ),
+        };
+        text::spawn(
+            commands,
+            entity,
+            text.as_str(),
+            assets.latin_font.clone(),
+            font_size,
+            self.fret_font_color,
+            JustifyText::Left,
+            Anchor::Center,
+            x,
+            y,
+            self.text_z,
+        );
+    }
 }
 
 #[derive(Copy, Clone, PartialEq, Serialize, Deserialize, Debug)]
@@ -8,7 +8,11 @@ pub struct ThemeZ {
     pub tone: f32,
     pub word: f32,
     pub pick: f32,
+    pub drum: f32,
     pub bar_separator: f32,
+    pub tuplet: f32,
+    pub tempo_curve: f32,
+    pub dynamic_curve: f32,
     pub bar_indicator: f32,
     pub pos_indicator: f32,
     pub tab_control: f32,
@@ -36,7 +40,11 @@ impl Default for ThemeZ {
             tone: 8.0,
             word: 9.0,
             pick: 10.0,
+            drum: 10.0,
             bar_separator: 2.0,
+            tuplet: 3.0,
+            tempo_curve: 3.0,
+            dynamic_curve: 3.0,
             bar_indicator: 19.0,
             pos_indicator: 20.0,
             tab_control: 20.0,
@@ -1,4 +1,5 @@
 pub mod tone_bundle;
+pub mod tone_grace;
 pub mod tone_mode;
 pub mod tone_note;
 pub mod tone_systems;
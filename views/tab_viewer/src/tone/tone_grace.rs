@@ -0,0 +1,80 @@
+use edger_bevy::bevy_prelude::*;
+use edger_bevy::bevy_prototype_lyon::prelude::*;
+use edger_bevy::prelude::{offscreen, OutlineRectangle, ShapeOp};
+use notation_model::prelude::{Note, Syllable};
+use notation_midi::prelude::PlayingState;
+
+use crate::prelude::{EntryData, NotationTheme};
+use crate::theme::theme_sizes::NotesSizes;
+
+use super::tone_mode::ToneMode;
+
+/// Ornament notes drawn small in front of the main note.
+pub type ToneGraceData = EntryData<ToneGraceValue>;
+
+#[derive(Clone, Debug)]
+pub struct ToneGraceValue {
+    pub note: Note,
+    pub mode: ToneMode,
+    pub index: usize,
+    pub count: usize,
+    pub bar_size: f32,
+}
+
+impl ToneGraceValue {
+    pub fn new(note: Note, mode: ToneMode, index: usize, count: usize) -> Self {
+        Self {
+            note,
+            mode,
+            index,
+            count,
+            bar_size: 0.0,
+        }
+    }
+    pub fn syllable(&self) -> Syllable {
+        self.note.syllable
+    }
+}
+
+impl ToneGraceData {
+    fn get_sizes(&self, theme: &NotationTheme) -> NotesSizes {
+        if self.value.mode.is_melody() {
+            theme.sizes.melody
+        } else {
+            theme.sizes.harmony
+        }
+    }
+}
+
+impl ShapeOp<NotationTheme, OutlineRectangle> for ToneGraceData {
+    fn get_shape(&self, theme: &NotationTheme) -> OutlineRectangle {
+        let sizes = self.get_sizes(theme);
+        let width = sizes.grace_note_width;
+        let height = sizes.grace_note_height;
+        let color = theme.colors.of_syllable(self.value.syllable());
+        let outline_color = theme
+            .colors
+            .syllables
+            .outline
+            .of_state(&PlayingState::Idle);
+        let outline_width = sizes.note_outline.of_state(&PlayingState::Idle);
+        let offset = if self.value.bar_size <= 0.0 {
+            offscreen::offset()
+        } else {
+            let x = self.value.bar_size / self.bar_props.bar_units.as_f32()
+                * self.entry_props.in_bar_pos.as_f32()
+                - width * (self.value.count - self.value.index) as f32;
+            let y = sizes.calc_note_y(self.value.note);
+            Vec3::new(x, y + height / 2.0, theme.z.tone)
+        };
+        OutlineRectangle {
+            width: width - outline_width,
+            height,
+            origin: shapes::RectangleOrigin::TopLeft,
+            color,
+            outline_width,
+            outline_color,
+            offset,
+        }
+    }
+}
@@ -48,7 +48,7 @@ impl ShapeOp<NotationTheme, OutlineRectangle> for ToneNoteData {
             offscreen::offset()
         } else {
             let x =
-                self.value.bar_size / self.bar_props.bar_units.0 * self.entry_props.in_bar_pos.0;
+                self.value.bar_size / self.bar_props.bar_units.as_f32() * self.entry_props.in_bar_pos.as_f32();
             let mut y = self.get_sizes(theme).calc_note_y(self.value.note);
             if self.value.playing_state.is_current() {
                 let outline = self.calc_outline(theme);
@@ -90,7 +90,7 @@ impl ToneNoteData {
     fn calc_width_height(&self, theme: &NotationTheme) -> (f32, f32) {
         let outline = self.calc_outline(theme);
         let width =
-            self.value.bar_size / self.bar_props.bar_units.0 * self.entry_props.tied_units.0;
+            self.value.bar_size / self.bar_props.bar_units.as_f32() * self.entry_props.tied_units.as_f32();
         let mut height = self.get_sizes(theme).note_height;
         if self.value.playing_state.is_current() {
             height += outline;
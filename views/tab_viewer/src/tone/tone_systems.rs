@@ -5,6 +5,7 @@ use notation_model::prelude::{Entry, LaneEntry, Tone};
 
 use crate::prelude::{EntryPlaying, NotationAssets, NotationSettings, NotationTheme};
 
+use super::tone_grace::{ToneGraceData, ToneGraceValue};
 use super::tone_mode::ToneMode;
 use super::tone_note::{ToneNoteData, ToneNoteValue};
 
@@ -47,6 +48,13 @@ pub fn create_tone_notes(
                 }
             }
         }
+        if !entry.prev_is_tie() {
+            let grace_notes = bar.calc_ornament_notes(entry);
+            for (index, note) in grace_notes.iter().enumerate() {
+                let value = ToneGraceValue::new(*note, mode, index, grace_notes.len());
+                ToneGraceData::new(entry, value).create(commands, theme, entity);
+            }
+        }
     }
 }
 
@@ -34,8 +34,8 @@ pub type WordTextData = EntryData<WordTextValue>;
 
 impl ShapeOp<NotationTheme, StrokeLine> for WordTextData {
     fn get_shape(&self, theme: &NotationTheme) -> StrokeLine {
-        let width = self.value.bar_size / self.bar_props.bar_units.0
-            * self.entry_props.tied_units.0
+        let width = self.value.bar_size / self.bar_props.bar_units.as_f32()
+            * self.entry_props.tied_units.as_f32()
             - theme.sizes.lyrics.word_gap;
         let line_width = theme
             .sizes
@@ -46,7 +46,7 @@ impl ShapeOp<NotationTheme, StrokeLine> for WordTextData {
             offscreen::offset()
         } else {
             let x =
-                self.value.bar_size / self.bar_props.bar_units.0 * self.entry_props.in_bar_pos.0;
+                self.value.bar_size / self.bar_props.bar_units.as_f32() * self.entry_props.in_bar_pos.as_f32();
             let y = 0.0;
             Vec3::new(x, y, theme.z.word)
         };